@@ -1,35 +1,61 @@
 // TODO: Make these modules private
 pub mod config;
+mod connectivity;
+pub mod disk_check;
+pub mod disks;
+mod event_commands;
 pub mod farmer;
+pub mod http;
 mod networking;
 pub mod node;
+mod node_snapshot;
+pub mod readiness;
+pub mod stats;
+mod upgrade_checkpoint;
 mod utils;
 
-use crate::backend::config::{Config, ConfigError, RawConfig};
+use crate::backend::config::{Config, ConfigError, RawConfig, RawConfigError};
+use crate::backend::connectivity::NoInternetConnectionError;
+use crate::backend::disk_check::check_disk;
+use crate::backend::event_commands::run_event_command;
 use crate::backend::farmer::maybe_node_client::MaybeNodeRpcClient;
 use crate::backend::farmer::{
-    DiskFarm, Farmer, FarmerAction, FarmerNotification, FarmerOptions, InitialFarmState,
+    DedicatedPieceCache, DiskFarm, FarmPreallocationProgress, Farmer, FarmerAction,
+    FarmerExtraArgs, FarmerNotification, FarmerOptions, InitialFarmState,
+    PlottedSectorsScanProgress, ScanCancelledError, UnsupportedFarmChainError,
 };
 use crate::backend::networking::{create_network, NetworkOptions};
 use crate::backend::node::{
     dsn_bootstrap_nodes, BlockImported, ChainInfo, ChainSpec, ConsensusNode,
-    ConsensusNodeCreationError, SyncState, GENESIS_HASH,
+    ConsensusNodeCreationError, NodeExtraArgs, SpacePledged, SyncState, GENESIS_HASH,
 };
+use crate::backend::stats::{PlottingStats, PlottingStatsFlushGuard};
+use crate::backend::upgrade_checkpoint::UpgradeCheckpoint;
 use backoff::ExponentialBackoff;
 use future::FutureExt;
 use futures::channel::mpsc;
 use futures::{future, select, SinkExt, StreamExt};
 use parking_lot::Mutex;
+use reqwest::Url;
 use sc_subspace_chain_specs::GEMINI_3H_CHAIN_SPEC;
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::NonZeroU32;
 use std::path::{Path, PathBuf};
 use std::pin::pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Weak};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use subspace_archiving::archiver::is_piece_valid;
 use subspace_core_primitives::crypto::kzg::{embedded_kzg_settings, Kzg};
-use subspace_core_primitives::{BlockNumber, Piece, PieceIndex, PublicKey};
+use subspace_core_primitives::{
+    BlockNumber, Piece, PieceIndex, PublicKey, SegmentCommitment, SegmentIndex,
+};
+use subspace_farmer::farm::{SectorPlottingDetails, SectorUpdate};
 use subspace_farmer::farmer_cache::{FarmerCache, FarmerCacheWorker};
+use subspace_farmer::single_disk_farm::piece_cache::DiskPieceCache;
 use subspace_farmer::single_disk_farm::SingleDiskFarm;
 use subspace_farmer::utils::farmer_piece_getter::{
     DsnCacheRetryPolicy, FarmerPieceGetter, WeakFarmerPieceGetter,
@@ -37,10 +63,13 @@ use subspace_farmer::utils::farmer_piece_getter::{
 use subspace_farmer::utils::piece_validator::SegmentCommitmentPieceValidator;
 use subspace_farmer::utils::plotted_pieces::PlottedPieces;
 use subspace_farmer::utils::run_future_in_dedicated_thread;
+use subspace_farmer::NodeClient;
 use subspace_farmer_components::PieceGetter;
 use subspace_networking::libp2p::identity::ed25519::{Keypair, SecretKey};
+use subspace_networking::libp2p::kad::RecordKey;
 use subspace_networking::libp2p::multiaddr::Protocol;
 use subspace_networking::libp2p::Multiaddr;
+use subspace_networking::utils::multihash::ToMultihash;
 use subspace_networking::utils::piece_provider::PieceProvider;
 use subspace_networking::{Node, NodeRunner};
 use subspace_runtime_primitives::Balance;
@@ -50,7 +79,7 @@ use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 use tokio::runtime::Handle;
 use tokio::sync::Semaphore;
-use tracing::{error, info_span, warn, Instrument};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 /// Get piece retry attempts number.
 const PIECE_GETTER_MAX_RETRIES: u16 = 7;
@@ -61,12 +90,179 @@ const PIECE_GETTER_MAX_CONCURRENCY: usize = 512;
 const GET_PIECE_INITIAL_INTERVAL: Duration = Duration::from_secs(5);
 /// Defines max duration between get_piece calls.
 const GET_PIECE_MAX_INTERVAL: Duration = Duration::from_secs(40);
+/// How often piece getter statistics are sent to the frontend
+const PIECE_GETTER_STATS_INTERVAL: Duration = Duration::from_secs(10);
+/// How often plotting statistics are persisted and sent to the frontend
+const PLOTTING_STATS_INTERVAL: Duration = Duration::from_secs(60);
+/// How long to wait on startup for farm directories to appear, for example when external disk
+/// is not mounted yet
+const FARM_DIRECTORY_WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+/// How often to check whether missing farm directories appeared
+const FARM_DIRECTORY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// How many times to retrieve piece that fails KZG commitment verification before giving up
+const PIECE_VERIFICATION_MAX_ATTEMPTS: usize = 3;
+/// How many rounds of random walking to do when re-fetching piece that failed KZG commitment
+/// verification from archival storage
+const PIECE_VERIFICATION_MAX_RANDOM_WALK_ROUNDS: usize = 15;
+
+/// Piece retrieval statistics since application start
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct PieceGetterStats {
+    /// Pieces retrieved from local farmer cache
+    pub cache_hits: u64,
+    /// Pieces that had to be retrieved from the network (or node)
+    pub network_fetches: u64,
+    /// Average time it took to retrieve piece from the network
+    pub average_fetch_latency: Duration,
+    /// Pieces that failed KZG commitment verification and were re-fetched, only when verification
+    /// is enabled in configuration
+    pub verification_failures: u64,
+}
+
+#[derive(Debug, Default)]
+struct PieceGetterStatsCollector {
+    cache_hits: AtomicU64,
+    network_fetches: AtomicU64,
+    network_fetch_time_micros: AtomicU64,
+    verification_failures: AtomicU64,
+}
+
+impl PieceGetterStatsCollector {
+    fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_network_fetch(&self, latency: Duration) {
+        self.network_fetches.fetch_add(1, Ordering::Relaxed);
+        self.network_fetch_time_micros
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn record_verification_failure(&self) {
+        self.verification_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> PieceGetterStats {
+        let network_fetches = self.network_fetches.load(Ordering::Relaxed);
+        let network_fetch_time_micros = self.network_fetch_time_micros.load(Ordering::Relaxed);
+
+        PieceGetterStats {
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            network_fetches,
+            average_fetch_latency: Duration::from_micros(
+                network_fetch_time_micros
+                    .checked_div(network_fetches)
+                    .unwrap_or_default(),
+            ),
+            verification_failures: self.verification_failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Verifies pieces against KZG commitment of the segment they belong to
+#[derive(Clone)]
+struct PieceVerifier {
+    kzg: Kzg,
+    node_client: MaybeNodeRpcClient,
+    segment_commitments: Arc<Mutex<HashMap<SegmentIndex, SegmentCommitment>>>,
+    /// Used to re-fetch pieces that failed verification, bypassing farmer cache that may have
+    /// returned invalid piece in the first place
+    piece_provider: Arc<PieceProvider<SegmentCommitmentPieceValidator<MaybeNodeRpcClient>>>,
+}
+
+impl fmt::Debug for PieceVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PieceVerifier").finish_non_exhaustive()
+    }
+}
+
+impl PieceVerifier {
+    fn new(
+        kzg: Kzg,
+        node_client: MaybeNodeRpcClient,
+        piece_provider: PieceProvider<SegmentCommitmentPieceValidator<MaybeNodeRpcClient>>,
+    ) -> Self {
+        Self {
+            kzg,
+            node_client,
+            segment_commitments: Arc::default(),
+            piece_provider: Arc::new(piece_provider),
+        }
+    }
+
+    /// Re-fetch piece from the network directly, bypassing farmer cache
+    async fn refetch(
+        &self,
+        piece_index: PieceIndex,
+    ) -> Result<Option<Piece>, Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(piece) = self
+            .piece_provider
+            .get_piece_from_dsn_cache(piece_index)
+            .await?
+        {
+            return Ok(Some(piece));
+        }
+
+        Ok(self
+            .piece_provider
+            .get_piece_from_archival_storage(piece_index, PIECE_VERIFICATION_MAX_RANDOM_WALK_ROUNDS)
+            .await)
+    }
+
+    /// Returns piece back together with verification result
+    async fn verify(
+        &self,
+        piece_index: PieceIndex,
+        piece: Piece,
+    ) -> Result<(Piece, bool), Box<dyn Error + Send + Sync + 'static>> {
+        let segment_commitment = self.segment_commitment(piece_index.segment_index()).await?;
+        let kzg = self.kzg.clone();
+
+        // Verification is CPU-intensive, don't block async runtime
+        let result = tokio::task::spawn_blocking(move || {
+            let valid = is_piece_valid(&kzg, &piece, &segment_commitment, piece_index.position());
+            (piece, valid)
+        })
+        .await?;
+
+        Ok(result)
+    }
+
+    async fn segment_commitment(
+        &self,
+        segment_index: SegmentIndex,
+    ) -> Result<SegmentCommitment, Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(segment_commitment) = self.segment_commitments.lock().get(&segment_index) {
+            return Ok(*segment_commitment);
+        }
+
+        let segment_commitment = self
+            .node_client
+            .segment_headers(vec![segment_index])
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or_else(|| format!("Segment header for segment {segment_index} not found"))?
+            .segment_commitment();
+
+        self.segment_commitments
+            .lock()
+            .insert(segment_index, segment_commitment);
+
+        Ok(segment_commitment)
+    }
+}
 
 #[derive(Debug, Clone)]
 struct PieceGetterWrapper {
     farmer_piece_getter:
         FarmerPieceGetter<SegmentCommitmentPieceValidator<MaybeNodeRpcClient>, MaybeNodeRpcClient>,
+    farmer_cache: FarmerCache,
     semaphore: Arc<Semaphore>,
+    stats: Arc<PieceGetterStatsCollector>,
+    /// Verifies pieces used for plotting when enabled in configuration
+    piece_verifier: Option<PieceVerifier>,
 }
 
 #[async_trait::async_trait]
@@ -76,7 +272,14 @@ impl DsnSyncPieceGetter for PieceGetterWrapper {
         piece_index: PieceIndex,
     ) -> Result<Option<Piece>, Box<dyn Error + Send + Sync + 'static>> {
         let _permit = self.semaphore.acquire().await;
-        Ok(self.farmer_piece_getter.get_piece_fast(piece_index).await)
+        if let Some(piece) = self.get_piece_from_cache(piece_index).await {
+            return Ok(Some(piece));
+        }
+
+        let start = Instant::now();
+        let maybe_piece = self.farmer_piece_getter.get_piece_fast(piece_index).await;
+        self.stats.record_network_fetch(start.elapsed());
+        Ok(maybe_piece)
     }
 }
 
@@ -87,7 +290,55 @@ impl PieceGetter for PieceGetterWrapper {
         piece_index: PieceIndex,
     ) -> Result<Option<Piece>, Box<dyn Error + Send + Sync + 'static>> {
         let _permit = self.semaphore.acquire().await;
-        self.farmer_piece_getter.get_piece(piece_index).await
+        let mut attempt = 0;
+
+        loop {
+            let maybe_piece = if attempt == 0
+                && let Some(piece) = self.get_piece_from_cache(piece_index).await
+            {
+                Some(piece)
+            } else {
+                let start = Instant::now();
+                let result = match &self.piece_verifier {
+                    // Farmer piece getter could return the same invalid piece from farmer cache
+                    // again, so verifier re-fetches it from the network directly
+                    Some(piece_verifier) if attempt > 0 => {
+                        piece_verifier.refetch(piece_index).await
+                    }
+                    _ => self.farmer_piece_getter.get_piece(piece_index).await,
+                };
+                self.stats.record_network_fetch(start.elapsed());
+                result?
+            };
+
+            let Some(piece) = maybe_piece else {
+                return Ok(None);
+            };
+            let Some(piece_verifier) = &self.piece_verifier else {
+                return Ok(Some(piece));
+            };
+
+            let (piece, valid) = piece_verifier.verify(piece_index, piece).await?;
+            if valid {
+                return Ok(Some(piece));
+            }
+
+            self.stats.record_verification_failure();
+            attempt += 1;
+
+            if attempt >= PIECE_VERIFICATION_MAX_ATTEMPTS {
+                return Err(format!(
+                    "Piece {piece_index} failed KZG commitment verification {attempt} times"
+                )
+                .into());
+            }
+
+            warn!(
+                %piece_index,
+                %attempt,
+                "Piece failed KZG commitment verification, re-fetching"
+            );
+        }
     }
 }
 
@@ -97,12 +348,29 @@ impl PieceGetterWrapper {
             SegmentCommitmentPieceValidator<MaybeNodeRpcClient>,
             MaybeNodeRpcClient,
         >,
+        farmer_cache: FarmerCache,
+        piece_verifier: Option<PieceVerifier>,
     ) -> Self {
         let semaphore = Arc::new(Semaphore::new(PIECE_GETTER_MAX_CONCURRENCY));
         Self {
             farmer_piece_getter,
+            farmer_cache,
             semaphore,
+            stats: Arc::default(),
+            piece_verifier,
+        }
+    }
+
+    /// Check local farmer cache first, such that cache hits can be distinguished from network
+    /// fetches in statistics. Farmer piece getter checks the same cache again on a miss, which is
+    /// a cheap local lookup, everything it returns after that is counted as a network fetch.
+    async fn get_piece_from_cache(&self, piece_index: PieceIndex) -> Option<Piece> {
+        let key = RecordKey::from(piece_index.to_multihash());
+        let maybe_piece = self.farmer_cache.get_piece(key).await;
+        if maybe_piece.is_some() {
+            self.stats.record_cache_hit();
         }
+        maybe_piece
     }
 
     fn downgrade(&self) -> WeakPieceGetterWrapper {
@@ -147,11 +415,31 @@ pub enum LoadingStep {
     },
     CheckingConfiguration,
     ConfigurationIsValid,
+    /// Farm directory doesn't exist yet, waiting for its disk to be connected or mounted
+    WaitingForFarmDirectory {
+        farm_index: u8,
+        path: PathBuf,
+    },
     DecodingChainSpecification,
     DecodedChainSpecificationSuccessfully,
+    /// Checking that bootstrap nodes are reachable before starting node and farmer
+    CheckingInternetConnection,
     CheckingNodePath,
     CreatingNodePath,
     NodePathReady,
+    /// Downloading node snapshot, only when configured and node doesn't have a database yet
+    DownloadingNodeSnapshot {
+        downloaded: u64,
+        /// Total size of the snapshot if known
+        total: Option<u64>,
+    },
+    VerifyingNodeSnapshot,
+    ImportingNodeSnapshot,
+    NodeSnapshotImported,
+    /// Snapshot import failed, node will sync normally
+    NodeSnapshotImportFailed {
+        error: String,
+    },
     PreparingNetworkingStack,
     ReadingNetworkKeypair,
     GeneratingNetworkKeypair,
@@ -160,8 +448,29 @@ pub enum LoadingStep {
     NetworkingStackCreatedSuccessfully,
     CreatingConsensusNode,
     ConsensusNodeCreatedSuccessfully,
+    /// Benchmarking farm and piece cache directories, only when enabled in configuration
+    CheckingDisks {
+        path: PathBuf,
+    },
     CreatingFarmer,
+    /// Reading sectors that were already plotted before, nothing is being plotted at this point
+    ScanningPlottedSectors {
+        scanned_sectors: u64,
+        total_sectors: u64,
+        /// Farm that is currently being scanned
+        farm_index: u8,
+        farm_scanned_sectors: u64,
+        farm_total_sectors: u64,
+    },
+    /// Allocating disk space for the whole farm before plotting
+    PreallocatingFarm {
+        farm_index: u8,
+        preallocated: u64,
+        total: u64,
+    },
     FarmerCreatedSuccessfully,
+    /// Previous upgrade was interrupted and is being completed
+    ResumingUpgrade,
     WipingFarm {
         farm_index: u8,
         path: PathBuf,
@@ -169,6 +478,12 @@ pub enum LoadingStep {
     WipingNode {
         path: PathBuf,
     },
+    WipingCompleted {
+        /// Number of farm and node directories that were wiped
+        wiped_directories: usize,
+        /// Number of bytes freed on disk
+        freed_bytes: u64,
+    },
 }
 
 #[derive(Debug)]
@@ -180,7 +495,18 @@ enum LoadedConsensusChainNode {
 #[derive(Debug, Clone)]
 pub enum NodeNotification {
     SyncStateUpdate(SyncState),
+    /// Number of peers node is connected to changed
+    ConnectedPeersUpdate(u32),
     BlockImported(BlockImported),
+    /// Reward address balance increased after node is synced, typically because reward was
+    /// received
+    RewardReceived {
+        /// Amount by which balance increased
+        amount: Balance,
+        reward_address_balance: Balance,
+    },
+    /// Periodic estimate of space pledged to the network, only sent once node is synced
+    SpacePledged(SpacePledged),
 }
 
 /// Notification messages send from backend about its operation
@@ -198,13 +524,49 @@ pub enum BackendNotification {
         raw_config: RawConfig,
         compatible_chain: String,
     },
+    /// Previous upgrade was interrupted before farms and node were wiped completely, it needs to
+    /// be completed before application can start
+    InterruptedUpgrade {
+        raw_config: RawConfig,
+    },
+    /// Existing farm belongs to a chain that is not supported by this version of the app
+    UnsupportedFarmChain {
+        raw_config: RawConfig,
+        farm_directory: PathBuf,
+        /// Hex-encoded genesis hash of the chain farm was created for
+        farm_genesis_hash: String,
+        supported_chain: String,
+    },
     NotConfigured,
+    /// None of bootstrap nodes are reachable on startup, loading continues after
+    /// [`BackendAction::RetryInternetConnectionCheck`]
+    NoInternetConnection {
+        error: String,
+    },
+    /// Configuration file is encrypted and passphrase is either missing or wrong, loading continues
+    /// after [`BackendAction::ConfigPassphraseEntered`]
+    ConfigurationPassphraseRequired {
+        wrong_passphrase: bool,
+    },
+    /// Configuration file failed to parse and was moved to backup, application continues as if it
+    /// wasn't configured yet
+    ConfigurationIsCorrupt {
+        backup_path: PathBuf,
+        error: Arc<RawConfigError>,
+    },
     // TODO: Indicate what is invalid so that UI can render it properly
     ConfigurationIsInvalid {
         config: RawConfig,
         error: ConfigError,
     },
     ConfigSaveResult(anyhow::Result<()>),
+    /// Result of [`BackendAction::ClearFarmerCache`]
+    FarmerCacheClearResult(anyhow::Result<()>),
+    /// Application was started in safe mode, only configuration was loaded, neither node nor farmer
+    /// are running
+    SafeMode {
+        raw_config: RawConfig,
+    },
     Running {
         config: Config,
         raw_config: RawConfig,
@@ -212,10 +574,17 @@ pub enum BackendNotification {
         reward_address_balance: Balance,
         initial_farm_states: Vec<InitialFarmState>,
         farm_during_initial_plotting: bool,
+        /// Farming is held back until node is synced
+        farm_only_when_synced: bool,
+        /// Plot cache is disabled on Windows when total pledged space is too large
+        plot_cache_enabled: bool,
         chain_info: ChainInfo,
     },
     Node(NodeNotification),
     Farmer(FarmerNotification),
+    PieceGetterStats(PieceGetterStats),
+    /// All-time plotting statistics
+    PlottingStats(PlottingStats),
     Stopped {
         /// Error in case stopped due to error
         error: Option<anyhow::Error>,
@@ -224,6 +593,54 @@ pub enum BackendNotification {
         /// Error that happened
         error: anyhow::Error,
     },
+    /// Indices of plotted sectors were exported to requested file after startup scan
+    PlotMapExported {
+        path: PathBuf,
+    },
+}
+
+/// Backend notification along with the time it was emitted at
+#[derive(Debug)]
+pub struct TimestampedBackendNotification {
+    /// Time at which notification was emitted by the backend, allows frontend to compute rates
+    /// accurately regardless of delivery delays
+    pub timestamp: Instant,
+    pub notification: BackendNotification,
+}
+
+/// Sender of backend notifications that attaches emission timestamp to each notification
+#[derive(Debug, Clone)]
+pub struct BackendNotificationSender {
+    sender: mpsc::Sender<TimestampedBackendNotification>,
+}
+
+impl BackendNotificationSender {
+    pub fn new(sender: mpsc::Sender<TimestampedBackendNotification>) -> Self {
+        Self { sender }
+    }
+
+    pub async fn send(&mut self, notification: BackendNotification) -> Result<(), mpsc::SendError> {
+        self.sender
+            .send(TimestampedBackendNotification {
+                timestamp: Instant::now(),
+                notification,
+            })
+            .await
+    }
+
+    /// Send notification from synchronous context, blocks current thread if channel is full
+    fn send_blocking(&mut self, notification: BackendNotification) -> Result<(), mpsc::SendError> {
+        self.sender
+            .try_send(TimestampedBackendNotification {
+                timestamp: Instant::now(),
+                notification,
+            })
+            .or_else(|error| {
+                tokio::task::block_in_place(|| {
+                    Handle::current().block_on(self.sender.send(error.into_inner()))
+                })
+            })
+    }
 }
 
 /// Control action messages sent to backend to control its behavior
@@ -231,8 +648,26 @@ pub enum BackendNotification {
 pub enum BackendAction {
     /// Config was created or updated
     NewConfig { raw_config: RawConfig },
+    /// Farm was wiped and needs to be removed from configuration, doesn't require restart, only
+    /// failure is reported with [`BackendNotification::ConfigSaveResult`]
+    RemoveFarm { path: PathBuf },
     /// Farmer action
     Farmer(FarmerAction),
+    /// Collect plotted pieces of farms again, for example after farm files were modified by
+    /// external tools, progress is reported with
+    /// [`FarmerNotification::PlottedSectorsScanProgress`]
+    RescanFarms,
+    /// Passphrase for encrypted configuration was set with
+    /// [`config::encryption::set_config_passphrase()`] in response to
+    /// [`BackendNotification::ConfigurationPassphraseRequired`], configuration can be loaded again
+    ConfigPassphraseEntered,
+    /// Load again in response to [`BackendNotification::NoInternetConnection`], with `skip` set to
+    /// `true` internet connection is not checked anymore
+    RetryInternetConnectionCheck { skip: bool },
+    /// Delete contents of farmer cache (piece caches of farms and dedicated piece cache), such
+    /// that it is rebuilt from scratch on next start, only supported in safe mode when farmer is
+    /// not running, result is reported with [`BackendNotification::FarmerCacheClearResult`]
+    ClearFarmerCache,
 }
 
 struct LoadedBackend {
@@ -241,7 +676,9 @@ struct LoadedBackend {
     config_file_path: PathBuf,
     consensus_node: ConsensusNode,
     farmer: Farmer,
+    node_client: MaybeNodeRpcClient,
     node_runner: NodeRunner<FarmerCache>,
+    piece_getter_stats: Arc<PieceGetterStatsCollector>,
 }
 
 enum BackendLoadingResult {
@@ -250,17 +687,91 @@ enum BackendLoadingResult {
         raw_config: RawConfig,
         compatible_chain: String,
     },
+    InterruptedUpgrade {
+        raw_config: RawConfig,
+    },
+    UnsupportedFarmChain {
+        raw_config: RawConfig,
+        farm_directory: PathBuf,
+        farm_genesis_hash: String,
+        supported_chain: String,
+    },
+    /// Startup was cancelled by user before farmer was created
+    Cancelled,
 }
 
 // NOTE: this is an async function, but it might do blocking operations and should be running on a
 // dedicated CPU core
 pub async fn create(
     mut backend_action_receiver: mpsc::Receiver<BackendAction>,
-    mut notifications_sender: mpsc::Sender<BackendNotification>,
+    mut notifications_sender: BackendNotificationSender,
+    safe_mode: bool,
+    maybe_config_file_path: Option<PathBuf>,
+    export_plot_map: Option<PathBuf>,
+    proxy: Option<Url>,
+    startup_cancelled: Arc<AtomicBool>,
 ) {
+    let maybe_config_file_path = maybe_config_file_path.as_deref();
+
+    if safe_mode {
+        if let Err(error) = run_safe_mode(
+            &mut backend_action_receiver,
+            &mut notifications_sender,
+            maybe_config_file_path,
+        )
+        .await
+        {
+            if let Err(error) = notifications_sender
+                .send(BackendNotification::IrrecoverableError { error })
+                .await
+            {
+                error!(%error, "Failed to send safe mode error notification");
+            }
+        }
+        return;
+    }
+
+    let mut check_internet_connection = true;
     let loading_result = try {
         'load: loop {
-            if let Some(backend_loaded) = load(&mut notifications_sender).await? {
+            let maybe_backend_loaded = match load(
+                &mut notifications_sender,
+                maybe_config_file_path,
+                export_plot_map.as_deref(),
+                proxy.as_ref(),
+                &startup_cancelled,
+                check_internet_connection,
+            )
+            .await
+            {
+                Ok(maybe_backend_loaded) => maybe_backend_loaded,
+                Err(error) => {
+                    if request_config_passphrase(
+                        &error,
+                        &mut backend_action_receiver,
+                        &mut notifications_sender,
+                    )
+                    .await?
+                    {
+                        continue 'load;
+                    }
+
+                    if let Some(skip) = request_internet_connection_retry(
+                        &error,
+                        &mut backend_action_receiver,
+                        &mut notifications_sender,
+                    )
+                    .await?
+                    {
+                        check_internet_connection = !skip;
+                        continue 'load;
+                    }
+
+                    Err(error)?
+                }
+            };
+
+            if let Some(backend_loaded) = maybe_backend_loaded {
                 break backend_loaded;
             }
 
@@ -286,7 +797,13 @@ pub async fn create(
                                 .await?;
                         }
 
-                        let config_file_path = RawConfig::default_path().await?;
+                        // Directories are created before configuration is written, such that
+                        // farm directories of written configuration are known to exist, invalid
+                        // configuration will be reported again during loading
+                        if let Err(error) = create_farm_directories(&raw_config).await {
+                            warn!(%error, "Failed to create farm directories");
+                        }
+                        let config_file_path = RawConfig::path(maybe_config_file_path).await?;
                         raw_config
                             .write_to_path(&config_file_path)
                             .await
@@ -301,12 +818,29 @@ pub async fn create(
                         // Try to load config and start again
                         continue 'load;
                     }
+                    BackendAction::RemoveFarm { .. } => {
+                        warn!("Farm removal is not expected before initialization, ignored");
+                    }
                     BackendAction::Farmer(farmer_action) => {
                         warn!(
                             ?farmer_action,
                             "Farmer action is not expected before initialization, ignored"
                         );
                     }
+                    BackendAction::RescanFarms => {
+                        warn!("Farms rescan is not expected before initialization, ignored");
+                    }
+                    BackendAction::ConfigPassphraseEntered => {
+                        warn!("Configuration passphrase was not requested, ignored");
+                    }
+                    BackendAction::RetryInternetConnectionCheck { .. } => {
+                        warn!("Internet connection check retry was not requested, ignored");
+                    }
+                    BackendAction::ClearFarmerCache => {
+                        warn!(
+                            "Farmer cache clearing is not expected before initialization, ignored"
+                        );
+                    }
                 }
             }
 
@@ -334,6 +868,45 @@ pub async fn create(
             }
             return;
         }
+        Ok(BackendLoadingResult::InterruptedUpgrade { raw_config }) => {
+            if let Err(error) = notifications_sender
+                .send(BackendNotification::InterruptedUpgrade { raw_config })
+                .await
+            {
+                error!(%error, "Failed to send interrupted upgrade notification");
+            }
+            return;
+        }
+        Ok(BackendLoadingResult::UnsupportedFarmChain {
+            raw_config,
+            farm_directory,
+            farm_genesis_hash,
+            supported_chain,
+        }) => {
+            if let Err(error) = notifications_sender
+                .send(BackendNotification::UnsupportedFarmChain {
+                    raw_config,
+                    farm_directory,
+                    farm_genesis_hash,
+                    supported_chain,
+                })
+                .await
+            {
+                error!(%error, "Failed to send unsupported farm chain notification");
+            }
+            return;
+        }
+        Ok(BackendLoadingResult::Cancelled) => {
+            info!("Startup was cancelled");
+
+            if let Err(error) = notifications_sender
+                .send(BackendNotification::Stopped { error: None })
+                .await
+            {
+                error!(%error, "Failed to send stopped notification");
+            }
+            return;
+        }
         Err(error) => {
             if let Err(error) = notifications_sender
                 .send(BackendNotification::IrrecoverableError { error })
@@ -360,22 +933,272 @@ pub async fn create(
     }
 }
 
+/// Only loads configuration and allows to change it, neither node nor farmer are started such that
+/// user can remove or fix problematic farm
+async fn run_safe_mode(
+    backend_action_receiver: &mut mpsc::Receiver<BackendAction>,
+    notifications_sender: &mut BackendNotificationSender,
+    maybe_config_file_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let (config_file_path, maybe_raw_config) = loop {
+        match load_configuration(notifications_sender, maybe_config_file_path).await {
+            Ok(result) => {
+                break result;
+            }
+            Err(error) => {
+                if !request_config_passphrase(&error, backend_action_receiver, notifications_sender)
+                    .await?
+                {
+                    return Err(error);
+                }
+            }
+        }
+    };
+
+    match maybe_raw_config {
+        Some(raw_config) => {
+            notifications_sender
+                .send(BackendNotification::SafeMode { raw_config })
+                .await?;
+        }
+        None => {
+            notifications_sender
+                .send(BackendNotification::NotConfigured)
+                .await?;
+        }
+    }
+
+    while let Some(action) = backend_action_receiver.next().await {
+        match action {
+            BackendAction::NewConfig { raw_config } => {
+                if let Err(error) = Config::try_from_raw_config(&raw_config).await {
+                    notifications_sender
+                        .send(BackendNotification::ConfigurationIsInvalid {
+                            config: raw_config,
+                            error,
+                        })
+                        .await?;
+                    continue;
+                }
+
+                // Configuration is only written once its farm directories exist, otherwise they
+                // would be considered missing disks on next start
+                let result = async {
+                    create_farm_directories(&raw_config).await?;
+                    raw_config
+                        .write_to_path(&config_file_path)
+                        .await
+                        .map_err(|error| {
+                            anyhow::anyhow!(
+                                "Failed to write config to \"{}\": {}",
+                                config_file_path.display(),
+                                error
+                            )
+                        })
+                }
+                .await;
+                notifications_sender
+                    .send(BackendNotification::ConfigSaveResult(result))
+                    .await?;
+            }
+            BackendAction::RemoveFarm { path } => {
+                if let Err(error) = remove_farm_from_config(&config_file_path, &path).await {
+                    notifications_sender
+                        .send(BackendNotification::ConfigSaveResult(Err(error)))
+                        .await?;
+                }
+            }
+            BackendAction::Farmer(farmer_action) => {
+                warn!(
+                    ?farmer_action,
+                    "Farmer action is not expected in safe mode, ignored"
+                );
+            }
+            BackendAction::RescanFarms => {
+                warn!("Farms rescan is not expected in safe mode, ignored");
+            }
+            BackendAction::ConfigPassphraseEntered => {
+                warn!("Configuration passphrase was not requested, ignored");
+            }
+            BackendAction::RetryInternetConnectionCheck { .. } => {
+                warn!("Internet connection check retry was not requested, ignored");
+            }
+            BackendAction::ClearFarmerCache => {
+                let result = clear_farmer_cache(&config_file_path).await;
+                if let Err(error) = &result {
+                    error!(%error, "Failed to clear farmer cache");
+                }
+                notifications_sender
+                    .send(BackendNotification::FarmerCacheClearResult(result))
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// If configuration failed to load because it is encrypted and passphrase is missing or wrong, asks
+/// user for passphrase and waits for it to be entered, returns `false` for unrelated errors
+async fn request_config_passphrase(
+    error: &anyhow::Error,
+    backend_action_receiver: &mut mpsc::Receiver<BackendAction>,
+    notifications_sender: &mut BackendNotificationSender,
+) -> anyhow::Result<bool> {
+    let wrong_passphrase = match error.downcast_ref::<RawConfigError>() {
+        Some(RawConfigError::PassphraseRequired) => false,
+        Some(RawConfigError::FailedToDecrypt) => true,
+        _ => {
+            return Ok(false);
+        }
+    };
+
+    notifications_sender
+        .send(BackendNotification::ConfigurationPassphraseRequired { wrong_passphrase })
+        .await?;
+
+    while let Some(backend_action) = backend_action_receiver.next().await {
+        match backend_action {
+            BackendAction::ConfigPassphraseEntered => {
+                return Ok(true);
+            }
+            backend_action => {
+                warn!(
+                    ?backend_action,
+                    "Action is not expected while waiting for configuration passphrase, ignored"
+                );
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Application is shutting down while waiting for configuration passphrase"
+    ))
+}
+
+/// If loading failed because there is no internet connection, tells user about it and waits for
+/// decision to retry, returns `None` for unrelated errors and `Some(skip)` otherwise, where `skip`
+/// means that internet connection should not be checked again
+async fn request_internet_connection_retry(
+    error: &anyhow::Error,
+    backend_action_receiver: &mut mpsc::Receiver<BackendAction>,
+    notifications_sender: &mut BackendNotificationSender,
+) -> anyhow::Result<Option<bool>> {
+    if !error.is::<NoInternetConnectionError>() {
+        return Ok(None);
+    }
+
+    notifications_sender
+        .send(BackendNotification::NoInternetConnection {
+            error: error.to_string(),
+        })
+        .await?;
+
+    while let Some(backend_action) = backend_action_receiver.next().await {
+        match backend_action {
+            BackendAction::RetryInternetConnectionCheck { skip } => {
+                return Ok(Some(skip));
+            }
+            backend_action => {
+                warn!(
+                    ?backend_action,
+                    "Action is not expected while waiting for internet connection, ignored"
+                );
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Application is shutting down while waiting for internet connection"
+    ))
+}
+
 async fn load(
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    notifications_sender: &mut BackendNotificationSender,
+    maybe_config_file_path: Option<&Path>,
+    export_plot_map: Option<&Path>,
+    proxy: Option<&Url>,
+    startup_cancelled: &Arc<AtomicBool>,
+    check_internet_connection: bool,
 ) -> anyhow::Result<Option<BackendLoadingResult>> {
-    let (config_file_path, Some(raw_config)) = load_configuration(notifications_sender).await?
+    let (config_file_path, Some(raw_config)) =
+        load_configuration(notifications_sender, maybe_config_file_path).await?
     else {
         return Ok(None);
     };
 
+    if !wait_for_farm_directories(&raw_config, notifications_sender, startup_cancelled).await? {
+        return Ok(Some(BackendLoadingResult::Cancelled));
+    }
+
+    // Farms and node might be partially wiped, they must not be used with the old chain
+    let upgrade_checkpoint_path = UpgradeCheckpoint::path(&config_file_path);
+    // Upgrade is only resumed automatically when checkpoint is definitely valid, anything else
+    // requires user to decide, since resuming wipes farms and node without confirmation
+    let maybe_upgrade_checkpoint = UpgradeCheckpoint::read_from_path(&upgrade_checkpoint_path)
+        .await
+        .map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to read upgrade checkpoint {}: {error}. If upgrade was not in progress, \
+                remove this file and restart the application",
+                upgrade_checkpoint_path.display()
+            )
+        })?;
+    if maybe_upgrade_checkpoint.is_some() {
+        info!("Previous upgrade was interrupted, resuming it");
+
+        notifications_sender
+            .send(BackendNotification::Loading {
+                step: LoadingStep::ResumingUpgrade,
+                progress: 0.0,
+            })
+            .await?;
+
+        return Ok(Some(BackendLoadingResult::InterruptedUpgrade {
+            raw_config,
+        }));
+    }
+
     let Some(config) = check_configuration(&raw_config, notifications_sender).await? else {
         return Ok(None);
     };
 
+    let extra_args = raw_config.extra_args();
+    if !extra_args.is_empty() {
+        warn!(
+            node = ?extra_args.node,
+            farmer = ?extra_args.farmer,
+            "Using extra arguments, these are not tested as thoroughly and may break things"
+        );
+    }
+
     let chain_spec = load_chain_specification(notifications_sender).await?;
 
+    if check_internet_connection {
+        notifications_sender
+            .send(BackendNotification::Loading {
+                step: LoadingStep::CheckingInternetConnection,
+                progress: 0.0,
+            })
+            .await?;
+
+        let mut bootstrap_nodes = dsn_bootstrap_nodes(&chain_spec)?;
+        bootstrap_nodes.retain(|address| config.network.ip_version.allows(address));
+        connectivity::check_internet_connection(&bootstrap_nodes).await?;
+    }
+
     preparing_node_path(&config.node_path, notifications_sender).await?;
 
+    if let Some(node_snapshot) = &config.node_snapshot {
+        node_snapshot::import_node_snapshot(
+            &config.node_path,
+            node_snapshot,
+            proxy,
+            notifications_sender,
+        )
+        .await?;
+    }
+
     let (
         maybe_node_client,
         node,
@@ -393,41 +1216,56 @@ async fn load(
     .await?;
 
     let kzg = Kzg::new(embedded_kzg_settings());
-    let piece_provider = PieceProvider::new(
-        node.clone(),
-        Some(SegmentCommitmentPieceValidator::new(
+    let create_piece_provider = || {
+        PieceProvider::new(
             node.clone(),
-            maybe_node_client.clone(),
-            kzg.clone(),
-        )),
-    );
+            Some(SegmentCommitmentPieceValidator::new(
+                node.clone(),
+                maybe_node_client.clone(),
+                kzg.clone(),
+            )),
+        )
+    };
 
-    let piece_getter = PieceGetterWrapper::new(FarmerPieceGetter::new(
-        piece_provider,
-        farmer_cache.clone(),
-        maybe_node_client.clone(),
-        Arc::clone(&plotted_pieces),
-        DsnCacheRetryPolicy {
-            max_retries: PIECE_GETTER_MAX_RETRIES,
-            backoff: ExponentialBackoff {
-                initial_interval: GET_PIECE_INITIAL_INTERVAL,
-                max_interval: GET_PIECE_MAX_INTERVAL,
-                // Try until we get a valid piece
-                max_elapsed_time: None,
-                multiplier: 1.75,
-                ..ExponentialBackoff::default()
+    let piece_getter = PieceGetterWrapper::new(
+        FarmerPieceGetter::new(
+            create_piece_provider(),
+            farmer_cache.clone(),
+            maybe_node_client.clone(),
+            Arc::clone(&plotted_pieces),
+            DsnCacheRetryPolicy {
+                max_retries: PIECE_GETTER_MAX_RETRIES,
+                backoff: ExponentialBackoff {
+                    initial_interval: GET_PIECE_INITIAL_INTERVAL,
+                    max_interval: GET_PIECE_MAX_INTERVAL,
+                    // Try until we get a valid piece
+                    max_elapsed_time: None,
+                    multiplier: 1.75,
+                    ..ExponentialBackoff::default()
+                },
             },
-        },
-    ));
+        ),
+        farmer_cache.clone(),
+        config.farmer.verify_pieces.then(|| {
+            PieceVerifier::new(
+                kzg.clone(),
+                maybe_node_client.clone(),
+                create_piece_provider(),
+            )
+        }),
+    );
+    let piece_getter_stats = Arc::clone(&piece_getter.stats);
 
     let create_consensus_node_fut = create_consensus_node(
         &network_keypair,
         config.node_path.clone(),
         config.network.substrate_port,
+        config.network.ip_version,
         chain_spec,
         Arc::new(piece_getter.clone()),
         node.clone(),
         &maybe_node_client,
+        &config.node_extra_args,
         notifications_sender,
     );
     let consensus_node = match create_consensus_node_fut.await? {
@@ -440,18 +1278,58 @@ async fn load(
         }
     };
 
-    let farmer = create_farmer(
+    if config.farmer.check_disks_on_startup {
+        check_disks(&config, notifications_sender).await?;
+    }
+
+    if config.farmer.farm_only_when_synced {
+        maybe_node_client.farm_only_when_synced();
+    }
+    let node_client = maybe_node_client.clone();
+
+    let create_farmer_fut = create_farmer(
         config.reward_address,
         config.farms.clone(),
+        config.farmer.auto_recover_farms,
+        config.farmer.plotting_rate_limit,
+        config.farmer.disable_plot_cache,
+        config.farmer.preallocate_farms,
+        config.farmer.preallocate_farms_without_zeroing,
+        config.farmer.min_plotting_peers,
+        config.piece_cache.clone(),
+        config.farmer_extra_args.clone(),
         plotted_pieces,
         farmer_cache,
         farmer_cache_worker,
         maybe_node_client,
+        node.clone(),
         kzg,
         piece_getter,
+        export_plot_map.map(Path::to_path_buf),
+        Arc::clone(startup_cancelled),
         notifications_sender,
-    )
-    .await?;
+    );
+    let farmer = match create_farmer_fut.await {
+        Ok(farmer) => farmer,
+        Err(error) => {
+            if error.is::<ScanCancelledError>() {
+                return Ok(Some(BackendLoadingResult::Cancelled));
+            }
+
+            return match error.downcast::<UnsupportedFarmChainError>() {
+                Ok(UnsupportedFarmChainError {
+                    farm_directory,
+                    farm_genesis_hash,
+                }) => Ok(Some(BackendLoadingResult::UnsupportedFarmChain {
+                    raw_config,
+                    farm_directory,
+                    farm_genesis_hash,
+                    supported_chain: consensus_node.chain_info().chain_name.clone(),
+                })),
+                Err(error) => Err(error),
+            };
+        }
+    };
 
     Ok(Some(BackendLoadingResult::Success(LoadedBackend {
         config,
@@ -459,14 +1337,16 @@ async fn load(
         config_file_path,
         consensus_node,
         farmer,
+        node_client,
         node_runner,
+        piece_getter_stats,
     })))
 }
 
 async fn run(
     loaded_backend: LoadedBackend,
     backend_action_receiver: &mut mpsc::Receiver<BackendAction>,
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    notifications_sender: &mut BackendNotificationSender,
 ) -> anyhow::Result<()> {
     let LoadedBackend {
         config,
@@ -474,7 +1354,9 @@ async fn run(
         config_file_path,
         consensus_node,
         farmer,
+        node_client,
         mut node_runner,
+        piece_getter_stats,
     } = loaded_backend;
     let networking_fut = run_future_in_dedicated_thread(
         {
@@ -494,49 +1376,117 @@ async fn run(
             reward_address_balance: consensus_node.account_balance(&config.reward_address),
             initial_farm_states: farmer.initial_farm_states().to_vec(),
             farm_during_initial_plotting: farmer.farm_during_initial_plotting(),
+            farm_only_when_synced: config.farmer.farm_only_when_synced,
+            plot_cache_enabled: farmer.plot_cache_enabled(),
             chain_info: consensus_node.chain_info().clone(),
         })
-        .await?;
+        .await?;
+
+    // Balance increases during sync are historical and are not reported as received rewards
+    let node_synced = Arc::new(AtomicBool::new(false));
+    let _on_sync_state_change_handler_id = consensus_node.on_sync_state_change({
+        let notifications_sender = notifications_sender.clone();
+        let node_synced = Arc::clone(&node_synced);
+
+        Arc::new(move |&sync_state| {
+            node_synced.store(sync_state.is_synced(), Ordering::Relaxed);
+            node_client.set_node_synced(sync_state.is_synced());
+
+            let notification = NodeNotification::SyncStateUpdate(sync_state);
+
+            let mut notifications_sender = notifications_sender.clone();
+
+            if let Err(error) =
+                notifications_sender.send_blocking(BackendNotification::Node(notification))
+            {
+                warn!(%error, "Failed to send sync state backend notification");
+            }
+        })
+    });
+    let _on_connected_peers_change_handler_id = consensus_node.on_connected_peers_change({
+        let notifications_sender = notifications_sender.clone();
 
-    let _on_sync_state_change_handler_id = consensus_node.on_sync_state_change({
+        Arc::new(move |&connected_peers| {
+            let notification = NodeNotification::ConnectedPeersUpdate(connected_peers);
+
+            let mut notifications_sender = notifications_sender.clone();
+
+            if let Err(error) =
+                notifications_sender.send_blocking(BackendNotification::Node(notification))
+            {
+                warn!(%error, "Failed to send connected peers backend notification");
+            }
+        })
+    });
+    let _on_space_pledged_handler_id = consensus_node.on_space_pledged({
         let notifications_sender = notifications_sender.clone();
+        let node_synced = Arc::clone(&node_synced);
 
-        Arc::new(move |&sync_state| {
-            let notification = NodeNotification::SyncStateUpdate(sync_state);
+        Arc::new(move |&space_pledged| {
+            // Solution range of historical blocks doesn't reflect current state of the network
+            if !node_synced.load(Ordering::Relaxed) {
+                return;
+            }
+
+            let notification = NodeNotification::SpacePledged(space_pledged);
 
             let mut notifications_sender = notifications_sender.clone();
 
-            if let Err(error) = notifications_sender
-                .try_send(BackendNotification::Node(notification))
-                .or_else(|error| {
-                    tokio::task::block_in_place(|| {
-                        Handle::current().block_on(notifications_sender.send(error.into_inner()))
-                    })
-                })
+            if let Err(error) =
+                notifications_sender.send_blocking(BackendNotification::Node(notification))
             {
-                warn!(%error, "Failed to send sync state backend notification");
+                warn!(%error, "Failed to send space pledged backend notification");
             }
         })
     });
     let _on_imported_block_handler_id = consensus_node.on_block_imported({
         let notifications_sender = notifications_sender.clone();
-        // let reward_address_storage_key = account_storage_key(&config.reward_address);
+        // `None` until the first block is imported, there is nothing to compare balance to before
+        let last_reward_address_balance = Mutex::new(None::<Balance>);
 
         Arc::new(move |&block_imported| {
             let notification = NodeNotification::BlockImported(block_imported);
 
             let mut notifications_sender = notifications_sender.clone();
 
-            if let Err(error) = notifications_sender
-                .try_send(BackendNotification::Node(notification))
-                .or_else(|error| {
-                    tokio::task::block_in_place(|| {
-                        Handle::current().block_on(notifications_sender.send(error.into_inner()))
-                    })
-                })
+            if let Err(error) =
+                notifications_sender.send_blocking(BackendNotification::Node(notification))
             {
                 warn!(%error, "Failed to send imported block backend notification");
             }
+
+            let reward_address_balance = block_imported.reward_address_balance;
+            let previous_reward_address_balance = last_reward_address_balance
+                .lock()
+                .replace(reward_address_balance);
+
+            if !node_synced.load(Ordering::Relaxed) {
+                return;
+            }
+            // Decrease happens on transfers and chain reorgs, neither is a received reward
+            let Some(amount) = previous_reward_address_balance
+                .and_then(|previous| reward_address_balance.checked_sub(previous))
+                .filter(|&amount| amount > 0)
+            else {
+                return;
+            };
+
+            info!(
+                %amount,
+                %reward_address_balance,
+                "Reward address balance increased"
+            );
+
+            let notification = NodeNotification::RewardReceived {
+                amount,
+                reward_address_balance,
+            };
+
+            if let Err(error) =
+                notifications_sender.send_blocking(BackendNotification::Node(notification))
+            {
+                warn!(%error, "Failed to send reward received backend notification");
+            }
         })
     });
     let _on_farmer_notification_handler_id = farmer.on_notification({
@@ -546,18 +1496,114 @@ async fn run(
             let mut notifications_sender = notifications_sender.clone();
 
             if let Err(error) = notifications_sender
-                .try_send(BackendNotification::Farmer(notification.clone()))
-                .or_else(|error| {
-                    tokio::task::block_in_place(|| {
-                        Handle::current().block_on(notifications_sender.send(error.into_inner()))
-                    })
-                })
+                .send_blocking(BackendNotification::Farmer(notification.clone()))
             {
                 warn!(%error, "Failed to send farmer backend notification");
             }
         })
     });
 
+    let _on_initial_plotting_finished_handler_id =
+        (!config.event_commands.initial_plotting_finished.is_empty()).then(|| {
+            let command = config.event_commands.initial_plotting_finished.clone();
+            let farm_directories = config
+                .farms
+                .iter()
+                .map(|farm| farm.directory.display().to_string())
+                .collect::<Vec<_>>();
+            // Farms that were not fully plotted on start
+            let remaining_initial_sectors = Mutex::new(
+                farmer
+                    .initial_farm_states()
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(farm_index, initial_farm_state)| {
+                        let remaining_sectors = initial_farm_state
+                            .total_sectors_count
+                            .saturating_sub(initial_farm_state.plotted_sectors_count);
+
+                        (remaining_sectors > 0).then_some((farm_index as u8, remaining_sectors))
+                    })
+                    .collect::<HashMap<_, _>>(),
+            );
+            // Notifications are called from farmer threads, command runs on backend runtime
+            let runtime_handle = Handle::current();
+
+            farmer.on_notification(Arc::new(move |notification| {
+                let FarmerNotification::SectorUpdate {
+                    farm_index,
+                    update:
+                        SectorUpdate::Plotting(SectorPlottingDetails::Finished {
+                            old_plotted_sector: None,
+                            ..
+                        }),
+                    ..
+                } = notification
+                else {
+                    return;
+                };
+
+                let remaining_farms = {
+                    let mut remaining_initial_sectors = remaining_initial_sectors.lock();
+                    let Some(remaining_sectors) = remaining_initial_sectors.get_mut(farm_index)
+                    else {
+                        return;
+                    };
+                    *remaining_sectors -= 1;
+                    if *remaining_sectors > 0 {
+                        return;
+                    }
+                    remaining_initial_sectors.remove(farm_index);
+                    remaining_initial_sectors.len()
+                };
+
+                let env = [
+                    ("SPACE_ACRES_FARM_INDEX", farm_index.to_string()),
+                    (
+                        "SPACE_ACRES_FARM_DIRECTORY",
+                        farm_directories
+                            .get(usize::from(*farm_index))
+                            .cloned()
+                            .unwrap_or_default(),
+                    ),
+                    ("SPACE_ACRES_REMAINING_FARMS", remaining_farms.to_string()),
+                ];
+                let command = command.clone();
+                runtime_handle.spawn(async move {
+                    run_event_command(&command, "initial-plotting-finished", &env).await;
+                });
+            }))
+        });
+
+    let stats_file_path = PlottingStats::path(&config_file_path);
+    let initial_plotting_stats = match PlottingStats::read_from_path(&stats_file_path).await {
+        Ok(plotting_stats) => plotting_stats,
+        Err(error) => {
+            warn!(
+                %error,
+                path = %stats_file_path.display(),
+                "Failed to read plotting stats, starting from scratch"
+            );
+            PlottingStats::default()
+        }
+    };
+    notifications_sender
+        .send(BackendNotification::PlottingStats(initial_plotting_stats))
+        .await?;
+    let plotting_stats = Arc::new(Mutex::new(initial_plotting_stats));
+    // Backend is dropped rather than stopped on shutdown, guard flushes statistics either way
+    let _plotting_stats_flush_guard =
+        PlottingStatsFlushGuard::new(Arc::clone(&plotting_stats), stats_file_path.clone());
+    let _on_plotting_stats_handler_id = farmer.on_notification({
+        let plotting_stats = Arc::clone(&plotting_stats);
+
+        Arc::new(move |notification| {
+            if let FarmerNotification::SectorUpdate { update, .. } = notification {
+                plotting_stats.lock().update(update);
+            }
+        })
+    });
+
     let mut farmer_action_sender = farmer.action_sender();
 
     // Order is important here, we want to destroy dependents first and only then corresponding
@@ -579,10 +1625,70 @@ async fn run(
         }
     };
 
+    let piece_getter_stats_fut = {
+        let mut notifications_sender = notifications_sender.clone();
+
+        async move {
+            let mut last_stats = PieceGetterStats::default();
+
+            loop {
+                tokio::time::sleep(PIECE_GETTER_STATS_INTERVAL).await;
+
+                let stats = piece_getter_stats.stats();
+                if stats == last_stats {
+                    continue;
+                }
+                last_stats = stats;
+
+                if let Err(error) = notifications_sender
+                    .send(BackendNotification::PieceGetterStats(stats))
+                    .await
+                {
+                    warn!(%error, "Failed to send piece getter stats notification");
+                }
+            }
+        }
+    };
+
+    let plotting_stats_fut = {
+        let mut notifications_sender = notifications_sender.clone();
+
+        async move {
+            let mut last_plotting_stats = initial_plotting_stats;
+
+            loop {
+                tokio::time::sleep(PLOTTING_STATS_INTERVAL).await;
+
+                let plotting_stats = *plotting_stats.lock();
+                if plotting_stats == last_plotting_stats {
+                    continue;
+                }
+                last_plotting_stats = plotting_stats;
+
+                if let Err(error) = plotting_stats.write_to_path(&stats_file_path).await {
+                    warn!(
+                        %error,
+                        path = %stats_file_path.display(),
+                        "Failed to write plotting stats"
+                    );
+                }
+
+                if let Err(error) = notifications_sender
+                    .send(BackendNotification::PlottingStats(plotting_stats))
+                    .await
+                {
+                    warn!(%error, "Failed to send plotting stats notification");
+                }
+            }
+        }
+    };
+
     let networking_fut = pin!(networking_fut);
     let consensus_node_fut = pin!(consensus_node_fut);
     let farmer_fut = pin!(farmer_fut);
     let process_backend_actions_fut = pin!(process_backend_actions_fut);
+    let piece_getter_stats_fut = pin!(piece_getter_stats_fut);
+    let plotting_stats_fut = pin!(plotting_stats_fut);
 
     let result: anyhow::Result<()> = select! {
         result = networking_fut.fuse() => {
@@ -597,6 +1703,12 @@ async fn run(
         _ = process_backend_actions_fut.fuse() => {
             Ok(())
         }
+        _ = piece_getter_stats_fut.fuse() => {
+            Ok(())
+        }
+        _ = plotting_stats_fut.fuse() => {
+            Ok(())
+        }
     };
 
     notifications_sender
@@ -609,7 +1721,8 @@ async fn run(
 }
 
 async fn load_configuration(
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    notifications_sender: &mut BackendNotificationSender,
+    maybe_config_file_path: Option<&Path>,
 ) -> anyhow::Result<(PathBuf, Option<RawConfig>)> {
     notifications_sender
         .send(BackendNotification::Loading {
@@ -618,7 +1731,7 @@ async fn load_configuration(
         })
         .await?;
 
-    let config_file_path = RawConfig::default_path().await?;
+    let config_file_path = RawConfig::path(maybe_config_file_path).await?;
 
     notifications_sender
         .send(BackendNotification::Loading {
@@ -627,8 +1740,42 @@ async fn load_configuration(
         })
         .await?;
 
-    // TODO: Make configuration errors recoverable
-    let maybe_config = RawConfig::read_from_path(&config_file_path).await?;
+    let maybe_config = match RawConfig::read_from_path(&config_file_path).await {
+        Ok(maybe_config) => maybe_config,
+        Err(
+            error @ (RawConfigError::FailedToDeserialize(_)
+            | RawConfigError::FailedToDeserializeToml(_)),
+        ) => {
+            // Corrupt configuration is moved aside and treated as missing, such that user can
+            // start fresh
+            let backup_path = RawConfig::back_up_corrupt(&config_file_path)
+                .await
+                .map_err(|backup_error| {
+                    anyhow::anyhow!(
+                        "Configuration file \"{}\" is corrupt ({error}) and failed to back it up: \
+                        {backup_error}",
+                        config_file_path.display()
+                    )
+                })?;
+            warn!(
+                %error,
+                backup_path = %backup_path.display(),
+                "Configuration file is corrupt, moved it to backup"
+            );
+
+            notifications_sender
+                .send(BackendNotification::ConfigurationIsCorrupt {
+                    backup_path,
+                    error: Arc::new(error),
+                })
+                .await?;
+
+            None
+        }
+        Err(error) => {
+            return Err(error.into());
+        }
+    };
 
     notifications_sender
         .send(BackendNotification::Loading {
@@ -645,7 +1792,7 @@ async fn load_configuration(
 /// Returns `Ok(None)` if configuration failed validation
 async fn check_configuration(
     config: &RawConfig,
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    notifications_sender: &mut BackendNotificationSender,
 ) -> anyhow::Result<Option<Config>> {
     notifications_sender
         .send(BackendNotification::Loading {
@@ -678,7 +1825,7 @@ async fn check_configuration(
 }
 
 async fn load_chain_specification(
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    notifications_sender: &mut BackendNotificationSender,
 ) -> anyhow::Result<ChainSpec> {
     notifications_sender
         .send(BackendNotification::Loading {
@@ -702,7 +1849,7 @@ async fn load_chain_specification(
 
 async fn preparing_node_path(
     node_path: &Path,
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    notifications_sender: &mut BackendNotificationSender,
 ) -> anyhow::Result<()> {
     notifications_sender
         .send(BackendNotification::Loading {
@@ -748,7 +1895,7 @@ async fn create_networking_stack(
     config: &Config,
     protocol_prefix: String,
     chain_spec: &ChainSpec,
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    notifications_sender: &mut BackendNotificationSender,
 ) -> anyhow::Result<(
     MaybeNodeRpcClient,
     Node,
@@ -765,6 +1912,9 @@ async fn create_networking_stack(
         })
         .await?;
 
+    let ip_version = config.network.ip_version;
+    info!(%ip_version, "Using P2P networking IP version");
+
     let bootstrap_nodes = dsn_bootstrap_nodes(chain_spec)?;
 
     let network_path = config.node_path.join("network");
@@ -865,6 +2015,9 @@ async fn create_networking_stack(
             Multiaddr::from(IpAddr::V6(Ipv6Addr::UNSPECIFIED))
                 .with(Protocol::Tcp(config.network.subspace_port)),
         ],
+        reserved_peers: config.reserved_peers.clone(),
+        blocked_peers: config.blocked_peers.clone(),
+        ip_version,
         ..NetworkOptions::default()
     };
     if config.network.faster_networking {
@@ -910,6 +2063,62 @@ async fn create_networking_stack(
     ))
 }
 
+/// Benchmark farm and piece cache directories, slow disks are reported as warnings in logs, but
+/// don't prevent farmer from starting
+async fn check_disks(
+    config: &Config,
+    notifications_sender: &mut BackendNotificationSender,
+) -> anyhow::Result<()> {
+    let directories = config
+        .farms
+        .iter()
+        .map(|farm| farm.directory.clone())
+        .chain(
+            config
+                .piece_cache
+                .as_ref()
+                .map(|piece_cache| piece_cache.directory.clone()),
+        )
+        .collect::<Vec<_>>();
+    let total_directories = directories.len();
+
+    for (index, directory) in directories.into_iter().enumerate() {
+        notifications_sender
+            .send(BackendNotification::Loading {
+                step: LoadingStep::CheckingDisks {
+                    path: directory.clone(),
+                },
+                progress: index as f32 / total_directories as f32 * 100.0,
+            })
+            .await?;
+
+        let result = tokio::task::spawn_blocking({
+            let directory = directory.clone();
+
+            move || check_disk(&directory)
+        })
+        .await?;
+
+        match result {
+            Ok(result) => {
+                let warnings = result.warnings();
+                if warnings.is_empty() {
+                    info!(path = %directory.display(), ?result, "Disk check passed");
+                } else {
+                    for warning in warnings {
+                        warn!(path = %directory.display(), "Disk check: {warning}");
+                    }
+                }
+            }
+            Err(error) => {
+                warn!(%error, path = %directory.display(), "Disk check failed");
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn create_consensus_node(
     network_keypair: &Keypair,
@@ -919,7 +2128,8 @@ async fn create_consensus_node(
     piece_getter: Arc<dyn DsnSyncPieceGetter + Send + Sync + 'static>,
     node: Node,
     maybe_node_rpc_client: &MaybeNodeRpcClient,
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    extra_args: &NodeExtraArgs,
+    notifications_sender: &mut BackendNotificationSender,
 ) -> anyhow::Result<LoadedConsensusChainNode> {
     notifications_sender
         .send(BackendNotification::Loading {
@@ -936,6 +2146,7 @@ async fn create_consensus_node(
         piece_getter,
         node,
         maybe_node_rpc_client,
+        extra_args,
     );
     let consensus_node = match create_consensus_node_fut.await {
         Ok(consensus_node) => consensus_node,
@@ -961,13 +2172,24 @@ async fn create_consensus_node(
 async fn create_farmer(
     reward_address: PublicKey,
     disk_farms: Vec<DiskFarm>,
+    auto_recover_farms: bool,
+    plotting_rate_limit: Option<NonZeroU32>,
+    disable_plot_cache: bool,
+    preallocate_farms: bool,
+    preallocate_farms_without_zeroing: bool,
+    min_plotting_peers: Option<NonZeroU32>,
+    piece_cache: Option<DedicatedPieceCache>,
+    extra_args: FarmerExtraArgs,
     plotted_pieces: Arc<Mutex<Option<PlottedPieces>>>,
     farmer_cache: FarmerCache,
     farmer_cache_worker: FarmerCacheWorker<MaybeNodeRpcClient>,
     node_client: MaybeNodeRpcClient,
+    dsn_node: Node,
     kzg: Kzg,
     piece_getter: PieceGetterWrapper,
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    export_plot_map: Option<PathBuf>,
+    startup_cancelled: Arc<AtomicBool>,
+    notifications_sender: &mut BackendNotificationSender,
 ) -> anyhow::Result<Farmer> {
     notifications_sender
         .send(BackendNotification::Loading {
@@ -976,19 +2198,90 @@ async fn create_farmer(
         })
         .await?;
 
+    let on_plotted_sectors_scan_progress = Arc::new({
+        let notifications_sender = notifications_sender.clone();
+
+        move |scan_progress: &PlottedSectorsScanProgress| {
+            let mut notifications_sender = notifications_sender.clone();
+            let PlottedSectorsScanProgress {
+                scanned_sectors,
+                total_sectors,
+                farm_index,
+                farm_scanned_sectors,
+                farm_total_sectors,
+            } = *scan_progress;
+
+            if let Err(error) = notifications_sender.send_blocking(BackendNotification::Loading {
+                step: LoadingStep::ScanningPlottedSectors {
+                    scanned_sectors,
+                    total_sectors,
+                    farm_index,
+                    farm_scanned_sectors,
+                    farm_total_sectors,
+                },
+                progress: scanned_sectors as f32 / total_sectors.max(1) as f32 * 100.0,
+            }) {
+                warn!(%error, "Failed to send plotted sectors scan progress notification");
+            }
+        }
+    });
+
+    let on_farm_preallocation_progress = Arc::new({
+        let notifications_sender = notifications_sender.clone();
+
+        move |preallocation_progress: &FarmPreallocationProgress| {
+            let mut notifications_sender = notifications_sender.clone();
+            let FarmPreallocationProgress {
+                farm_index,
+                preallocated,
+                total,
+            } = *preallocation_progress;
+
+            if let Err(error) = notifications_sender.send_blocking(BackendNotification::Loading {
+                step: LoadingStep::PreallocatingFarm {
+                    farm_index,
+                    preallocated,
+                    total,
+                },
+                progress: preallocated as f32 / total.max(1) as f32 * 100.0,
+            }) {
+                warn!(%error, "Failed to send farm pre-allocation progress notification");
+            }
+        }
+    });
+
     let farmer_options = FarmerOptions {
         reward_address,
         disk_farms,
+        auto_recover_farms,
+        plotting_rate_limit,
+        disable_plot_cache,
+        preallocate_farms,
+        preallocate_farms_without_zeroing,
+        min_plotting_peers,
+        piece_cache,
+        extra_args,
         node_client,
+        dsn_node,
         plotted_pieces,
         farmer_cache,
         farmer_cache_worker,
         kzg,
         piece_getter,
+        on_plotted_sectors_scan_progress,
+        startup_cancelled,
+        on_farm_preallocation_progress,
+        export_plot_map: export_plot_map.clone(),
     };
 
     let farmer = farmer::create_farmer(farmer_options).await?;
 
+    if let Some(path) = export_plot_map {
+        notifications_sender
+            .send(BackendNotification::PlotMapExported { path })
+            .await?;
+    }
+
     notifications_sender
         .send(BackendNotification::Loading {
             step: LoadingStep::FarmerCreatedSuccessfully,
@@ -1003,21 +2296,40 @@ async fn process_backend_actions(
     config_file_path: &Path,
     backend_action_receiver: &mut mpsc::Receiver<BackendAction>,
     farmer_action_sender: &mut mpsc::Sender<FarmerAction>,
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    notifications_sender: &mut BackendNotificationSender,
 ) {
     while let Some(action) = backend_action_receiver.next().await {
         match action {
             BackendAction::NewConfig { raw_config } => {
-                let result = raw_config
-                    .write_to_path(config_file_path)
-                    .await
-                    .map_err(|error| {
-                        anyhow::anyhow!(
-                            "Failed to write config to \"{}\": {}",
-                            config_file_path.display(),
-                            error
-                        )
-                    });
+                if let Err(error) = Config::try_from_raw_config(&raw_config).await {
+                    if let Err(error) = notifications_sender
+                        .send(BackendNotification::ConfigurationIsInvalid {
+                            config: raw_config,
+                            error,
+                        })
+                        .await
+                    {
+                        error!(%error, "Failed to send invalid configuration notification");
+                    }
+                    continue;
+                }
+
+                // Configuration is only written once its farm directories exist, otherwise they
+                // would be considered missing disks on next start
+                let result = async {
+                    create_farm_directories(&raw_config).await?;
+                    raw_config
+                        .write_to_path(config_file_path)
+                        .await
+                        .map_err(|error| {
+                            anyhow::anyhow!(
+                                "Failed to write config to \"{}\": {}",
+                                config_file_path.display(),
+                                error
+                            )
+                        })
+                }
+                .await;
                 if let Err(error) = notifications_sender
                     .send(BackendNotification::ConfigSaveResult(result))
                     .await
@@ -1025,22 +2337,237 @@ async fn process_backend_actions(
                     error!(%error, "Failed to send config save result notification");
                 }
             }
+            BackendAction::RemoveFarm { path } => {
+                if let Err(error) = remove_farm_from_config(config_file_path, &path).await
+                    && let Err(error) = notifications_sender
+                        .send(BackendNotification::ConfigSaveResult(Err(error)))
+                        .await
+                {
+                    error!(%error, "Failed to send config save result notification");
+                }
+            }
             BackendAction::Farmer(farmer_action) => {
                 if let Err(error) = farmer_action_sender.send(farmer_action).await {
                     error!(%error, "Failed to forward farmer action");
                 }
             }
+            BackendAction::RescanFarms => {
+                if let Err(error) = farmer_action_sender.send(FarmerAction::RescanFarms).await {
+                    error!(%error, "Failed to forward farms rescan");
+                }
+            }
+            BackendAction::ConfigPassphraseEntered => {
+                warn!("Configuration passphrase was not requested, ignored");
+            }
+            BackendAction::RetryInternetConnectionCheck { .. } => {
+                warn!("Internet connection check retry was not requested, ignored");
+            }
+            BackendAction::ClearFarmerCache => {
+                // Piece caches are in use by running farmer
+                warn!("Farmer cache can only be cleared in safe mode, ignored");
+            }
         }
     }
 }
 
+/// Create directories of farms that don't exist yet when configuration is saved, such that on
+/// startup missing farm directory means that its disk is not available rather than that farm is
+/// new, see [`wait_for_farm_directories()`]
+async fn create_farm_directories(raw_config: &RawConfig) -> anyhow::Result<()> {
+    // Disk of disabled farm might not be connected, directory must not be created in its place
+    for farm in raw_config.farms().iter().filter(|farm| farm.enabled) {
+        if !fs::try_exists(&farm.path).await.unwrap_or(true) {
+            fs::create_dir(&farm.path).await.map_err(|error| {
+                anyhow::anyhow!(
+                    "Failed to create farm directory {}: {error}",
+                    farm.path.display()
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Wait for farm directories that don't exist to appear, for example when external disk is not
+/// mounted yet right after system startup, instead of creating farm directory in the wrong place
+/// (like empty mount point).
+///
+/// Returns `false` if startup was cancelled while waiting.
+async fn wait_for_farm_directories(
+    raw_config: &RawConfig,
+    notifications_sender: &mut BackendNotificationSender,
+    startup_cancelled: &AtomicBool,
+) -> anyhow::Result<bool> {
+    let wait_start = Instant::now();
+
+    // Disabled farms are skipped, their disks don't need to be connected, indices match indices
+    // of farms in farmer
+    for (farm_index, farm) in raw_config
+        .farms()
+        .iter()
+        .filter(|farm| farm.enabled)
+        .enumerate()
+    {
+        let mut logged = false;
+
+        while !fs::try_exists(&farm.path).await.unwrap_or(true) {
+            if startup_cancelled.load(Ordering::Acquire) {
+                return Ok(false);
+            }
+
+            let waited = wait_start.elapsed();
+            if waited >= FARM_DIRECTORY_WAIT_TIMEOUT {
+                return Err(anyhow::anyhow!(
+                    "Farm {farm_index} directory {} is not available after waiting for {} \
+                    seconds, make sure its disk is connected and mounted (or create the \
+                    directory if the farm is new)",
+                    farm.path.display(),
+                    FARM_DIRECTORY_WAIT_TIMEOUT.as_secs()
+                ));
+            }
+
+            if !logged {
+                logged = true;
+                info!(
+                    %farm_index,
+                    path = %farm.path.display(),
+                    "Farm directory doesn't exist, waiting for its disk"
+                );
+            }
+
+            notifications_sender
+                .send(BackendNotification::Loading {
+                    step: LoadingStep::WaitingForFarmDirectory {
+                        farm_index: farm_index as u8,
+                        path: farm.path.clone(),
+                    },
+                    progress: waited.as_secs_f32() / FARM_DIRECTORY_WAIT_TIMEOUT.as_secs_f32()
+                        * 100.0,
+                })
+                .await?;
+
+            tokio::time::sleep(FARM_DIRECTORY_CHECK_INTERVAL).await;
+        }
+
+        if logged {
+            info!(
+                %farm_index,
+                path = %farm.path.display(),
+                "Farm directory is available"
+            );
+        }
+    }
+
+    Ok(true)
+}
+
+/// Remove farm from configuration stored on disk, other changes made in the meantime are preserved
+async fn remove_farm_from_config(config_file_path: &Path, path: &Path) -> anyhow::Result<()> {
+    let mut raw_config = RawConfig::read_from_path(config_file_path)
+        .await
+        .map_err(|error| anyhow::anyhow!("Failed to read configuration: {error}"))?
+        .ok_or_else(|| anyhow::anyhow!("Application is not configured yet"))?;
+    raw_config.remove_farm(path);
+
+    raw_config
+        .write_to_path(config_file_path)
+        .await
+        .map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to write config to \"{}\": {}",
+                config_file_path.display(),
+                error
+            )
+        })
+}
+
+/// Delete piece cache files of all farms and dedicated piece cache, farmer cache is then synced
+/// from scratch on next start
+async fn clear_farmer_cache(config_file_path: &Path) -> anyhow::Result<()> {
+    let raw_config = RawConfig::read_from_path(config_file_path)
+        .await
+        .map_err(|error| anyhow::anyhow!("Failed to read configuration: {error}"))?
+        .ok_or_else(|| anyhow::anyhow!("Application is not configured yet"))?;
+
+    // Disk of disabled farm might not be connected, its piece cache is not used anyway
+    let directories = raw_config
+        .farms()
+        .iter()
+        .filter(|farm| farm.enabled)
+        .map(|farm| farm.path.clone())
+        .chain(
+            raw_config
+                .piece_cache()
+                .map(|piece_cache| piece_cache.path.clone()),
+        )
+        .collect::<Vec<_>>();
+    for directory in directories {
+        tokio::task::spawn_blocking({
+            let directory = directory.clone();
+
+            move || DiskPieceCache::wipe(&directory)
+        })
+        .await?
+        .map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to delete piece cache in {}: {error}",
+                directory.display()
+            )
+        })?;
+    }
+
+    info!("Farmer cache cleared, it will be synced from scratch on next start");
+
+    Ok(())
+}
+
+/// Wipe farms and node for upgrade to a new chain.
+///
+/// Progress is recorded in [`UpgradeCheckpoint`] next to the config file, such that upgrade that
+/// was interrupted or failed is resumed on the next start and directories that were already wiped
+/// are skipped.
 pub async fn wipe(
     raw_config: &RawConfig,
-    notifications_sender: &mut mpsc::Sender<BackendNotification>,
+    config_file_path: &Path,
+    notifications_sender: &mut BackendNotificationSender,
 ) -> anyhow::Result<()> {
+    let mut wiped_directories = 0;
+    let mut failed_directories = 0;
+    let mut freed_bytes = 0;
+
+    let upgrade_checkpoint_path = UpgradeCheckpoint::path(config_file_path);
+    let mut upgrade_checkpoint =
+        match UpgradeCheckpoint::read_from_path(&upgrade_checkpoint_path).await {
+            Ok(maybe_upgrade_checkpoint) => maybe_upgrade_checkpoint.unwrap_or_default(),
+            Err(error) => {
+                warn!(
+                    %error,
+                    path = %upgrade_checkpoint_path.display(),
+                    "Failed to read upgrade checkpoint, wiping everything"
+                );
+                UpgradeCheckpoint::default()
+            }
+        };
+    // Upgrade must be recorded before anything is wiped
+    upgrade_checkpoint
+        .write_to_path(&upgrade_checkpoint_path)
+        .await
+        .map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to write upgrade checkpoint {}: {error}",
+                upgrade_checkpoint_path.display()
+            )
+        })?;
+
     let farms = raw_config.farms();
     for (farm_index, farm) in farms.iter().enumerate() {
         let path = &farm.path;
+        if upgrade_checkpoint.wiped_farms.contains(path) {
+            info!(%farm_index, path = %path.display(), "Farm was already wiped, skipping");
+            continue;
+        }
+
         notifications_sender
             .send(BackendNotification::Loading {
                 step: LoadingStep::WipingFarm {
@@ -1054,12 +2581,40 @@ pub async fn wipe(
         let wipe_fut = tokio::task::spawn_blocking({
             let path = path.to_path_buf();
 
-            move || SingleDiskFarm::wipe(&path)
+            move || {
+                let size_before = directory_size(&path);
+                match SingleDiskFarm::wipe(&path) {
+                    Ok(()) => {}
+                    // Farm doesn't exist or its wiping was interrupted after farm info was removed,
+                    // either way there is nothing left to wipe
+                    Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                        debug!(%error, path = %path.display(), "Nothing to wipe in farm directory");
+                    }
+                    Err(error) => {
+                        return Err(error);
+                    }
+                }
+                let size_after = directory_size(&path);
+
+                Ok::<_, std::io::Error>(size_before.saturating_sub(size_after))
+            }
         });
 
         match wipe_fut.await {
-            Ok(Ok(())) => {}
+            Ok(Ok(farm_freed_bytes)) => {
+                wiped_directories += 1;
+                freed_bytes += farm_freed_bytes;
+
+                upgrade_checkpoint.wiped_farms.push(path.clone());
+                if let Err(error) = upgrade_checkpoint
+                    .write_to_path(&upgrade_checkpoint_path)
+                    .await
+                {
+                    warn!(%error, "Failed to record wiped farm in upgrade checkpoint");
+                }
+            }
             Ok(Err(error)) => {
+                failed_directories += 1;
                 notifications_sender
                     .send(BackendNotification::IrrecoverableError {
                         error: anyhow::anyhow!(
@@ -1070,6 +2625,7 @@ pub async fn wipe(
                     .await?
             }
             Err(error) => {
+                failed_directories += 1;
                 notifications_sender
                     .send(BackendNotification::IrrecoverableError {
                         error: anyhow::anyhow!(
@@ -1082,7 +2638,9 @@ pub async fn wipe(
         }
     }
 
-    {
+    if upgrade_checkpoint.node_wiped {
+        info!("Node was already wiped, skipping");
+    } else {
         let path = &raw_config.node_path();
         notifications_sender
             .send(BackendNotification::Loading {
@@ -1093,12 +2651,22 @@ pub async fn wipe(
             })
             .await?;
 
+        let mut node_wiped = true;
         // TODO: Remove "paritydb" once support for upgrade from Gemini 3g is no longer necessary
         for subdirectory in &["db", "network", "paritydb"] {
             let path = path.join(subdirectory);
 
             if fs::try_exists(&path).await.unwrap_or(true) {
+                let subdirectory_size = tokio::task::spawn_blocking({
+                    let path = path.clone();
+
+                    move || directory_size(&path)
+                })
+                .await
+                .unwrap_or_default();
+
                 if let Err(error) = fs::remove_dir_all(&path).await {
+                    node_wiped = false;
                     notifications_sender
                         .send(BackendNotification::IrrecoverableError {
                             error: anyhow::anyhow!(
@@ -1107,10 +2675,69 @@ pub async fn wipe(
                             ),
                         })
                         .await?;
+                } else {
+                    freed_bytes += subdirectory_size;
                 }
             }
         }
+
+        if node_wiped {
+            wiped_directories += 1;
+
+            upgrade_checkpoint.node_wiped = true;
+            if let Err(error) = upgrade_checkpoint
+                .write_to_path(&upgrade_checkpoint_path)
+                .await
+            {
+                warn!(%error, "Failed to record wiped node in upgrade checkpoint");
+            }
+        } else {
+            failed_directories += 1;
+        }
+    }
+
+    if failed_directories > 0 {
+        // Checkpoint is kept, such that wiping is resumed on the next start
+        return Err(anyhow::anyhow!(
+            "Failed to wipe {failed_directories} directories, upgrade will be resumed after \
+            restart"
+        ));
     }
 
+    UpgradeCheckpoint::remove(&upgrade_checkpoint_path)
+        .await
+        .map_err(|error| {
+            anyhow::anyhow!(
+                "Failed to remove upgrade checkpoint {}: {error}",
+                upgrade_checkpoint_path.display()
+            )
+        })?;
+
+    notifications_sender
+        .send(BackendNotification::Loading {
+            step: LoadingStep::WipingCompleted {
+                wiped_directories,
+                freed_bytes,
+            },
+            progress: 100.0,
+        })
+        .await?;
+
     Ok(())
 }
+
+/// Total size of files in directory (recursively), errors are ignored
+fn directory_size(path: &Path) -> u64 {
+    let Ok(read_dir) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    read_dir
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => directory_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
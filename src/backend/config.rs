@@ -1,17 +1,31 @@
-use crate::backend::farmer::DiskFarm;
+pub mod encryption;
+
+use crate::backend::farmer::{DedicatedPieceCache, DiskFarm, FarmerExtraArgs};
+use crate::backend::node::{NodeExtraArgs, GENESIS_HASH};
 use bytesize::ByteSize;
+use clap::Parser;
 use serde::{Deserialize, Serialize};
-use std::io;
+use std::num::{NonZeroU16, NonZeroU32, NonZeroU8, NonZeroUsize};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::thread::available_parallelism;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fmt, io, iter};
 use subspace_core_primitives::PublicKey;
+use subspace_farmer::single_disk_farm::SingleDiskFarmInfo;
 use subspace_farmer::utils::ss58::{parse_ss58_reward_address, Ss58ParsingError};
+use subspace_networking::libp2p::multiaddr::Protocol;
+use subspace_networking::libp2p::{Multiaddr, PeerId};
 use tokio::fs;
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
+use tracing::warn;
 
 const DEFAULT_SUBSTRATE_PORT: u16 = 30333;
 const DEFAULT_SUBSPACE_PORT: u16 = 30433;
+const CONFIG_FILE_NAME: &str = "config.json";
+/// Farms are identified by `u8` index, so there can't be more of them in a single instance
+pub const MAX_FARMS: usize = u8::MAX as usize + 1;
 
 // TODO: Replace with `DiskFarm`
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +33,113 @@ const DEFAULT_SUBSPACE_PORT: u16 = 30433;
 pub struct Farm {
     pub path: PathBuf,
     pub size: String,
+    /// Number of sectors that can be plotted concurrently in this farm (still within shared limit
+    /// for all farms), only shared limit is used if not specified
+    #[serde(default)]
+    pub plotting_concurrency: Option<NonZeroUsize>,
+    /// Disk temperature (in °C) above which plotting of this farm is paused until disk cools down,
+    /// temperature is not monitored if not specified
+    #[serde(default)]
+    pub max_disk_temperature: Option<NonZeroU8>,
+    /// Maximum number of sectors plotted in this farm even if allocated space allows more, can be
+    /// increased later to resume plotting, all allocated space is used if not specified
+    #[serde(default)]
+    pub max_plotted_sectors: Option<NonZeroU16>,
+    /// Farm is located on network filesystem (NFS, SMB, etc.), disables farm locking and applies
+    /// tunings that tolerate higher latency
+    #[serde(default)]
+    pub network_filesystem: bool,
+    /// NUMA node to pin plotting threads of this farm to (Linux only), CPU cores are grouped by
+    /// L3 cache and shared with other farms if not specified
+    #[serde(default)]
+    pub numa_node: Option<u16>,
+    /// Disabled farm is neither plotted nor farmed (and not even opened), but stays in
+    /// configuration, such that it can be enabled again later
+    #[serde(default = "Farm::default_enabled")]
+    pub enabled: bool,
+}
+
+impl Farm {
+    fn default_enabled() -> bool {
+        true
+    }
+}
+
+/// Dedicated piece cache, for instance on a fast SSD, used instead of piece caches of farms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PieceCache {
+    pub path: PathBuf,
+    pub size: String,
+}
+
+/// Extra command-line-style arguments for node and farmer options that are not exposed in UI.
+///
+/// Only a limited set of options is supported and checked, but even supported options can break
+/// things when used incorrectly.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraArgs {
+    #[serde(default)]
+    pub node: Vec<String>,
+    #[serde(default)]
+    pub farmer: Vec<String>,
+}
+
+impl ExtraArgs {
+    pub fn is_empty(&self) -> bool {
+        self.node.is_empty() && self.farmer.is_empty()
+    }
+}
+
+/// Commands executed on application events for automation (like sending a webhook), not exposed
+/// in UI.
+///
+/// Each command is a program followed by its arguments, it is executed directly (not through a
+/// shell) with details of the event in `SPACE_ACRES_*` environment variables and its output is
+/// written to logs. Commands run with the same privileges as the application itself, so only
+/// programs from trusted locations that can't be modified by other users should be configured
+/// here.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventCommands {
+    /// Executed when a farm finishes initial plotting, disabled if empty
+    #[serde(default)]
+    pub initial_plotting_finished: Vec<String>,
+}
+
+/// Conversion of reward address balance into fiat currency shown next to balance in tokens, not
+/// exposed in UI.
+///
+/// Price is only informational, it is fetched from configured source periodically and is not shown
+/// once it gets outdated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiatConfiguration {
+    /// Currency code shown next to converted balance, like `USD`
+    pub currency: String,
+    /// HTTP(S) URL of JSON API with price of one token in the currency, `{currency}` is replaced
+    /// with lowercase currency code
+    pub price_source: String,
+    /// JSON pointer to the price in response of price source, `{currency}` is replaced with
+    /// lowercase currency code, whole response is expected to be the price if not specified
+    #[serde(default)]
+    pub price_pointer: String,
+}
+
+/// Snapshot used to bootstrap node database instead of syncing it from scratch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSnapshot {
+    /// HTTP(S) URL or local path of zstd-compressed tar archive with `db` directory inside
+    pub source: String,
+    /// Hex-encoded BLAKE3 checksum of the archive, required unless [`Self::skip_checksum`] is set
+    #[serde(default)]
+    pub checksum: Option<String>,
+    /// Import archive without verifying its checksum, only meant for archives that are already
+    /// trusted (like the ones created locally)
+    #[serde(default)]
+    pub skip_checksum: bool,
 }
 
 /// Configuration error
@@ -37,14 +158,77 @@ pub enum RawConfigError {
     /// Failed to deserialize configuration file
     #[error("Failed to deserialize configuration file: {0}")]
     FailedToDeserialize(serde_json::Error),
+    /// Failed to deserialize TOML configuration file
+    #[error("Failed to deserialize TOML configuration file: {0}")]
+    FailedToDeserializeToml(toml::de::Error),
+    /// Configuration file is encrypted, but passphrase was not provided
+    #[error(
+        "Configuration file is encrypted, passphrase is required (can be provided with \
+        {} environment variable)",
+        encryption::CONFIG_PASSPHRASE_ENV
+    )]
+    PassphraseRequired,
+    /// Failed to decrypt configuration file
+    #[error("Failed to decrypt configuration file, passphrase is wrong or file is damaged")]
+    FailedToDecrypt,
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+/// IP versions used for P2P networking of node and DSN
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IpVersion {
+    /// Both IPv4 and IPv6
+    #[default]
+    Dual,
+    /// IPv4 only, for networks with broken IPv6
+    Ipv4,
+    /// IPv6 only, for networks with broken IPv4
+    Ipv6,
+}
+
+impl fmt::Display for IpVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dual => write!(f, "IPv4 and IPv6"),
+            Self::Ipv4 => write!(f, "IPv4 only"),
+            Self::Ipv6 => write!(f, "IPv6 only"),
+        }
+    }
+}
+
+impl IpVersion {
+    /// Whether multiaddr can be listened on or dialed with this IP version.
+    ///
+    /// Takes string representation since node and DSN use different versions of `libp2p`.
+    /// Addresses that don't explicitly use the other IP version (like `/dns/`) are allowed.
+    pub fn allows<A>(self, address: &A) -> bool
+    where
+        A: fmt::Display,
+    {
+        let address = address.to_string();
+        match self {
+            Self::Dual => true,
+            Self::Ipv4 => !(address.starts_with("/ip6/") || address.starts_with("/dns6/")),
+            Self::Ipv6 => !(address.starts_with("/ip4/") || address.starts_with("/dns4/")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConfiguration {
     pub substrate_port: u16,
     pub subspace_port: u16,
     #[serde(default)]
     pub faster_networking: bool,
+    /// Multiaddrs (including `/p2p/<peer ID>` suffix) of DSN peers to maintain a connection to
+    #[serde(default)]
+    pub reserved_peers: Vec<String>,
+    /// Peer IDs of DSN peers that will never be connected to
+    #[serde(default)]
+    pub blocked_peers: Vec<String>,
+    /// Restrict P2P networking to one IP version, both are used by default
+    #[serde(default)]
+    pub ip_version: IpVersion,
 }
 
 impl Default for NetworkConfiguration {
@@ -53,10 +237,160 @@ impl Default for NetworkConfiguration {
             substrate_port: DEFAULT_SUBSTRATE_PORT,
             subspace_port: DEFAULT_SUBSPACE_PORT,
             faster_networking: false,
+            reserved_peers: Vec::new(),
+            blocked_peers: Vec::new(),
+            ip_version: IpVersion::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmerConfiguration {
+    /// Periodically try to re-initialize farm after it errored
+    #[serde(default)]
+    pub auto_recover_farms: bool,
+    /// Maximum number of sectors plotted per minute across all farms, unlimited if not specified
+    #[serde(default)]
+    pub plotting_rate_limit: Option<NonZeroU32>,
+    /// Benchmark farm and piece cache directories on startup and warn about slow disks
+    #[serde(default)]
+    pub check_disks_on_startup: bool,
+    /// Reduce allocated space of farms and piece cache to what is available on disk (with a
+    /// warning) instead of failing when allocated space exceeds it
+    #[serde(default)]
+    pub clamp_allocated_space: bool,
+    /// Verify pieces retrieved for plotting against KZG commitment of their segment, invalid
+    /// pieces are rejected and re-fetched at the cost of extra CPU usage
+    #[serde(default)]
+    pub verify_pieces: bool,
+    /// Don't farm until node is synced to avoid failed audits, plotting continues regardless
+    #[serde(default)]
+    pub farm_only_when_synced: bool,
+    /// Don't use plot cache on any platform, saves space that would otherwise be used for caching
+    /// pieces in not yet plotted part of farms at the cost of fewer pieces being cached locally
+    #[serde(default)]
+    pub disable_plot_cache: bool,
+    /// Allocate disk space for the whole farm before plotting starts to reduce fragmentation, can
+    /// take a long time when file system doesn't support fast allocation
+    #[serde(default)]
+    pub preallocate_farms: bool,
+    /// On Windows, mark pre-allocated space as valid data instead of writing zeroes to it, which
+    /// is much faster, but requires administrator rights and makes previous contents of the disk
+    /// in that area readable through the farm file until plotting overwrites it
+    #[serde(default)]
+    pub preallocate_farms_without_zeroing: bool,
+    /// Don't start plotting until at least this many DSN peers are connected (or timeout elapses)
+    /// to avoid stalling on piece retrieval right after start, disabled if not specified
+    #[serde(default)]
+    pub min_plotting_peers: Option<NonZeroU32>,
+}
+
+/// Application theme
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "camelCase")]
+pub enum Theme {
+    /// Detect theme from system settings
+    #[default]
+    System,
+    /// Always use light theme
+    Light,
+    /// Always use dark theme
+    Dark,
+}
+
+/// OS scheduling priority of the whole application process
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessPriority {
+    /// Default priority
+    #[default]
+    Normal,
+    /// Nice level 10 on Unix, below normal priority class on Windows
+    BelowNormal,
+    /// Nice level 19 on Unix, idle priority class on Windows
+    Idle,
+}
+
+/// Daily window in local time during which non-critical desktop notifications are suppressed
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    /// Hour (0-23) when quiet hours start
+    pub start_hour: u8,
+    /// Hour (0-23) when quiet hours end, can be smaller than start hour for windows that span
+    /// midnight
+    pub end_hour: u8,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            start_hour: 22,
+            end_hour: 7,
         }
     }
 }
 
+impl QuietHours {
+    /// Whether quiet hours are in effect at specified hour, window is empty when start and end
+    /// hours are the same
+    pub fn contains(&self, hour: u8) -> bool {
+        if self.start_hour <= self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiConfiguration {
+    /// Start with minimized window regardless of how application was started
+    #[serde(default)]
+    pub start_minimized: bool,
+    #[serde(default)]
+    pub theme: Theme,
+    /// Restart application automatically once new version is installed
+    #[serde(default)]
+    pub restart_on_update: bool,
+    /// Mask reward address in UI, for example for screen sharing
+    #[serde(default)]
+    pub hide_reward_address: bool,
+    /// Also mask balance when reward address is hidden
+    #[serde(default)]
+    pub hide_balance: bool,
+    /// Use high contrast stylesheet for better readability
+    #[serde(default)]
+    pub high_contrast: bool,
+    /// Show desktop notification when reward address balance increases
+    #[serde(default)]
+    pub reward_notifications: bool,
+    /// Show desktop notification when farm crashes or application stops with an error
+    #[serde(default)]
+    pub error_notifications: bool,
+    /// Suppress non-critical desktop notifications (like received rewards) during these hours,
+    /// error notifications are always shown
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    /// Scheduling priority of the application process, such that desktop stays responsive
+    #[serde(default)]
+    pub process_priority: ProcessPriority,
+    /// Number of worker threads of UI and async runtime, number of available CPU cores is used if
+    /// not specified
+    #[serde(default)]
+    pub runtime_threads: Option<NonZeroUsize>,
+    /// Warn when average auditing or proving time of a farm gets closer than this many
+    /// milliseconds to the deadline, disabled if not specified
+    #[serde(default)]
+    pub farming_deadline_warning_margin: Option<NonZeroU16>,
+    /// Warn when sectors of a farm are being plotted, but plotting made no progress for this many
+    /// minutes while not paused, disabled if not specified
+    #[serde(default)]
+    pub plotting_stall_timeout: Option<NonZeroU16>,
+}
+
 // TODO: This config is not necessarily valid, probably combine with valid config
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "version")]
@@ -69,6 +403,23 @@ pub enum RawConfig {
         farms: Vec<Farm>,
         #[serde(default)]
         network: NetworkConfiguration,
+        #[serde(default)]
+        farmer: FarmerConfiguration,
+        /// UI configuration used to be stored here, it was moved to application settings and is only
+        /// read once to migrate it there
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ui: Option<UiConfiguration>,
+        #[serde(default)]
+        piece_cache: Option<PieceCache>,
+        /// Used once on the first start when node doesn't have a database yet
+        #[serde(default)]
+        node_snapshot: Option<NodeSnapshot>,
+        #[serde(default)]
+        extra_args: ExtraArgs,
+        #[serde(default)]
+        event_commands: EventCommands,
+        #[serde(default)]
+        fiat: Option<FiatConfiguration>,
     },
 }
 
@@ -79,11 +430,23 @@ impl Default for RawConfig {
             node_path: PathBuf::new(),
             farms: Vec::new(),
             network: NetworkConfiguration::default(),
+            farmer: FarmerConfiguration::default(),
+            ui: None,
+            piece_cache: None,
+            node_snapshot: None,
+            extra_args: ExtraArgs::default(),
+            event_commands: EventCommands::default(),
+            fiat: None,
         }
     }
 }
 
 impl RawConfig {
+    /// Path to config file within custom app data directory
+    pub fn path_in_data_dir(data_dir: &Path) -> PathBuf {
+        data_dir.join(CONFIG_FILE_NAME)
+    }
+
     pub async fn default_path() -> Result<PathBuf, RawConfigError> {
         let Some(config_local_dir) = dirs::config_local_dir() else {
             return Err(RawConfigError::FailedToDetermineConfigDirectory);
@@ -91,10 +454,10 @@ impl RawConfig {
 
         let app_config_dir = config_local_dir.join(env!("CARGO_PKG_NAME"));
         let config_file_path = match fs::create_dir(&app_config_dir).await {
-            Ok(()) => app_config_dir.join("config.json"),
+            Ok(()) => app_config_dir.join(CONFIG_FILE_NAME),
             Err(error) => {
                 if error.kind() == io::ErrorKind::AlreadyExists {
-                    app_config_dir.join("config.json")
+                    app_config_dir.join(CONFIG_FILE_NAME)
                 } else {
                     return Err(RawConfigError::FailedToCreateConfigDirectory(error));
                 }
@@ -104,11 +467,36 @@ impl RawConfig {
         Ok(config_file_path)
     }
 
+    /// Path to config file, explicitly provided path takes precedence over the default one
+    pub async fn path(maybe_config_file_path: Option<&Path>) -> Result<PathBuf, RawConfigError> {
+        match maybe_config_file_path {
+            Some(config_file_path) => Ok(config_file_path.to_path_buf()),
+            None => Self::default_path().await,
+        }
+    }
+
     pub async fn read_from_path(config_file_path: &Path) -> Result<Option<Self>, RawConfigError> {
-        match fs::read_to_string(config_file_path).await {
-            Ok(config) => serde_json::from_str::<Self>(&config)
-                .map(Some)
-                .map_err(RawConfigError::FailedToDeserialize),
+        match fs::read(config_file_path).await {
+            Ok(contents) => {
+                let contents = if encryption::is_encrypted(&contents) {
+                    encryption::decrypt(&contents)?
+                } else {
+                    contents
+                };
+                let config = String::from_utf8(contents).map_err(|error| {
+                    RawConfigError::FailedToOpen(io::Error::new(io::ErrorKind::InvalidData, error))
+                })?;
+
+                if is_toml(config_file_path) {
+                    toml::from_str::<Self>(&config)
+                        .map(Some)
+                        .map_err(RawConfigError::FailedToDeserializeToml)
+                } else {
+                    serde_json::from_str::<Self>(&config)
+                        .map(Some)
+                        .map_err(RawConfigError::FailedToDeserialize)
+                }
+            }
             Err(error) => {
                 if error.kind() == io::ErrorKind::NotFound {
                     Ok(None)
@@ -119,7 +507,35 @@ impl RawConfig {
         }
     }
 
+    /// Move corrupt configuration file aside, such that it can be repaired manually and new
+    /// configuration can be created in its place, returns path of the backup
+    pub async fn back_up_corrupt(config_file_path: &Path) -> io::Result<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut backup_file_name = config_file_path
+            .file_name()
+            .unwrap_or_default()
+            .to_os_string();
+        backup_file_name.push(format!(".corrupt-{timestamp}"));
+        let backup_path = config_file_path.with_file_name(backup_file_name);
+
+        fs::rename(config_file_path, &backup_path).await?;
+
+        Ok(backup_path)
+    }
+
     pub async fn write_to_path(&self, config_file_path: &Path) -> io::Result<()> {
+        let config = if is_toml(config_file_path) {
+            toml::to_string_pretty(self)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+        } else {
+            serde_json::to_string_pretty(self).expect("Config serialization is infallible; qed")
+        };
+        // Encrypt before opening the file, such that existing file is not truncated on failure
+        let contents = encryption::maybe_encrypt(config.into_bytes())?;
+
         let mut options = OpenOptions::new();
         options.write(true).truncate(true).create(true);
         #[cfg(unix)]
@@ -127,11 +543,7 @@ impl RawConfig {
         options
             .open(config_file_path)
             .await?
-            .write_all(
-                serde_json::to_string_pretty(self)
-                    .expect("Config serialization is infallible; qed")
-                    .as_bytes(),
-            )
+            .write_all(&contents)
             .await
     }
 
@@ -152,10 +564,60 @@ impl RawConfig {
 
     pub fn network(&self) -> NetworkConfiguration {
         let Self::V0 { network, .. } = self;
-        *network
+        network.clone()
+    }
+
+    pub fn farmer(&self) -> FarmerConfiguration {
+        let Self::V0 { farmer, .. } = self;
+        *farmer
+    }
+
+    /// UI configuration stored by older versions, `None` if configuration was written without it
+    pub fn legacy_ui(&self) -> Option<UiConfiguration> {
+        let Self::V0 { ui, .. } = self;
+        *ui
+    }
+
+    pub fn remove_farm(&mut self, path: &Path) {
+        let Self::V0 { farms, .. } = self;
+        farms.retain(|farm| farm.path != path);
+    }
+
+    pub fn piece_cache(&self) -> Option<&PieceCache> {
+        let Self::V0 { piece_cache, .. } = self;
+        piece_cache.as_ref()
+    }
+
+    pub fn node_snapshot(&self) -> Option<&NodeSnapshot> {
+        let Self::V0 { node_snapshot, .. } = self;
+        node_snapshot.as_ref()
+    }
+
+    pub fn extra_args(&self) -> &ExtraArgs {
+        let Self::V0 { extra_args, .. } = self;
+        extra_args
+    }
+
+    pub fn event_commands(&self) -> &EventCommands {
+        let Self::V0 { event_commands, .. } = self;
+        event_commands
+    }
+
+    pub fn fiat(&self) -> Option<&FiatConfiguration> {
+        let Self::V0 { fiat, .. } = self;
+        fiat.as_ref()
     }
 }
 
+/// Config files with `.toml` extension use TOML format, everything else uses JSON for backwards
+/// compatibility
+fn is_toml(config_file_path: &Path) -> bool {
+    config_file_path
+        .extension()
+        .map(|extension| extension.eq_ignore_ascii_case("toml"))
+        .unwrap_or_default()
+}
+
 /// Valid configuration error
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -174,6 +636,60 @@ pub enum ConfigError {
     /// Invalid size format
     #[error("Invalid size format \"{size}\": {error}")]
     InvalidSizeFormat { size: String, error: String },
+    /// Plotting concurrency exceeds available resources
+    #[error("Plotting concurrency {plotting_concurrency} of farm \"{path}\" exceeds number of available CPU cores {max_plotting_concurrency}")]
+    InvalidPlottingConcurrency {
+        path: String,
+        plotting_concurrency: NonZeroUsize,
+        max_plotting_concurrency: usize,
+    },
+    /// Directory doesn't contain an existing farm
+    #[error("Directory \"{path}\" doesn't contain an existing farm")]
+    FarmNotFound { path: String },
+    /// Existing farm can't be read, likely created by incompatible version of farmer
+    #[error("Farm at \"{path}\" has unsupported format: {error}")]
+    UnsupportedFarmFormat { path: String, error: io::Error },
+    /// Existing farm was created for a different chain
+    #[error("Farm at \"{path}\" belongs to chain with genesis hash 0x{genesis_hash}, but only 0x{supported_genesis_hash} is supported")]
+    IncompatibleFarmChain {
+        path: String,
+        genesis_hash: String,
+        supported_genesis_hash: String,
+    },
+    /// Invalid reserved peer address
+    #[error("Invalid reserved peer \"{address}\": {error}")]
+    InvalidReservedPeer { address: String, error: String },
+    /// Invalid blocked peer ID
+    #[error("Invalid blocked peer \"{peer_id}\": {error}")]
+    InvalidBlockedPeer { peer_id: String, error: String },
+    /// Allocated space exceeds space available on disk
+    #[error("Allocated space {allocated_space} of {kind} \"{path}\" exceeds available disk space {available_space}")]
+    InsufficientDiskSpace {
+        kind: String,
+        path: String,
+        allocated_space: String,
+        available_space: String,
+    },
+    /// Paths are the same or one is nested in another
+    #[error("{first_kind} \"{first_path}\" overlaps with {second_kind} \"{second_path}\", they must be separate directories")]
+    OverlappingPaths {
+        first_kind: String,
+        first_path: String,
+        second_kind: String,
+        second_path: String,
+    },
+    /// All configured farms are disabled
+    #[error("All farms are disabled, at least one farm needs to be enabled")]
+    NoEnabledFarms,
+    /// More farms than supported by a single instance
+    #[error("{count} farms are configured, but at most {MAX_FARMS} are supported, consider running multiple instances on different machines instead")]
+    TooManyFarms { count: usize },
+    /// Node snapshot would be imported without verification
+    #[error("Node snapshot \"{snapshot}\" has no checksum, specify it or explicitly set `skipChecksum` to import unverified snapshot")]
+    NodeSnapshotChecksumMissing { snapshot: String },
+    /// Extra arguments are not supported or have invalid values
+    #[error("Invalid extra {kind} arguments: {error}")]
+    InvalidExtraArgs { kind: String, error: String },
 }
 
 #[derive(Debug, Clone)]
@@ -182,6 +698,16 @@ pub struct Config {
     pub node_path: PathBuf,
     pub farms: Vec<DiskFarm>,
     pub network: NetworkConfiguration,
+    /// Parsed [`NetworkConfiguration::reserved_peers`]
+    pub reserved_peers: Vec<Multiaddr>,
+    /// Parsed [`NetworkConfiguration::blocked_peers`]
+    pub blocked_peers: Vec<PeerId>,
+    pub farmer: FarmerConfiguration,
+    pub piece_cache: Option<DedicatedPieceCache>,
+    pub node_snapshot: Option<NodeSnapshot>,
+    pub node_extra_args: NodeExtraArgs,
+    pub farmer_extra_args: FarmerExtraArgs,
+    pub event_commands: EventCommands,
 }
 
 impl Config {
@@ -200,9 +726,28 @@ impl Config {
         let node_path = raw_config.node_path().clone();
         check_path(&node_path).await?;
 
+        check_farms_count(raw_config.farms())?;
+
+        // Disabled farms are checked too, they will be used again once enabled
+        check_overlapping_paths(
+            &node_path,
+            raw_config.farms(),
+            raw_config
+                .piece_cache()
+                .map(|piece_cache| piece_cache.path.as_path()),
+        )
+        .await?;
+
         let mut farms = Vec::with_capacity(raw_config.farms().len());
+        let max_plotting_concurrency = max_plotting_concurrency();
+        let farmer = raw_config.farmer();
 
         for farm in raw_config.farms() {
+            // Disabled farms are not used at all, so they don't need to be accessible either
+            if !farm.enabled {
+                continue;
+            }
+
             let path = PathBuf::from(&farm.path);
 
             check_path(&path).await?;
@@ -213,10 +758,84 @@ impl Config {
                     error,
                 })?
                 .as_u64();
+            let size =
+                fit_allocated_space("Farm", &path, size, farmer.clamp_allocated_space).await?;
+
+            if let Some(plotting_concurrency) = farm.plotting_concurrency
+                && plotting_concurrency.get() > max_plotting_concurrency
+            {
+                return Err(ConfigError::InvalidPlottingConcurrency {
+                    path: path.display().to_string(),
+                    plotting_concurrency,
+                    max_plotting_concurrency,
+                });
+            }
 
             farms.push(DiskFarm {
                 directory: path,
                 allocated_plotting_space: size,
+                plotting_concurrency: farm.plotting_concurrency,
+                max_disk_temperature: farm.max_disk_temperature,
+                max_plotted_sectors: farm.max_plotted_sectors,
+                network_filesystem: farm.network_filesystem,
+                numa_node: farm.numa_node,
+            });
+        }
+
+        if farms.is_empty() {
+            return Err(ConfigError::NoEnabledFarms);
+        }
+
+        let piece_cache = match raw_config.piece_cache() {
+            Some(piece_cache) => {
+                check_path(&piece_cache.path).await?;
+
+                let size = ByteSize::from_str(&piece_cache.size)
+                    .map_err(|error| ConfigError::InvalidSizeFormat {
+                        size: piece_cache.size.clone(),
+                        error,
+                    })?
+                    .as_u64();
+                let size = fit_allocated_space(
+                    "Piece cache",
+                    &piece_cache.path,
+                    size,
+                    farmer.clamp_allocated_space,
+                )
+                .await?;
+
+                Some(DedicatedPieceCache {
+                    directory: piece_cache.path.clone(),
+                    allocated_space: size,
+                })
+            }
+            None => None,
+        };
+
+        let network = raw_config.network();
+        let reserved_peers = network
+            .reserved_peers
+            .iter()
+            .map(|address| parse_reserved_peer(address))
+            .collect::<Result<Vec<_>, _>>()?;
+        let blocked_peers = network
+            .blocked_peers
+            .iter()
+            .map(|peer_id| {
+                PeerId::from_str(peer_id).map_err(|error| ConfigError::InvalidBlockedPeer {
+                    peer_id: peer_id.clone(),
+                    error: error.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let node_snapshot = raw_config.node_snapshot().cloned();
+        if let Some(node_snapshot) = &node_snapshot
+            && node_snapshot.checksum.is_none()
+            && !node_snapshot.skip_checksum
+        {
+            return Err(ConfigError::NodeSnapshotChecksumMissing {
+                snapshot: node_snapshot.source.clone(),
             });
         }
 
@@ -224,7 +843,240 @@ impl Config {
             reward_address,
             node_path,
             farms,
-            network: raw_config.network(),
+            network,
+            reserved_peers,
+            blocked_peers,
+            farmer,
+            piece_cache,
+            node_snapshot,
+            node_extra_args: parse_extra_args("node", &raw_config.extra_args().node)?,
+            farmer_extra_args: parse_extra_args("farmer", &raw_config.extra_args().farmer)?,
+            event_commands: raw_config.event_commands().clone(),
+        })
+    }
+}
+
+/// Parse extra arguments of node or farmer (`kind`) into corresponding options
+pub fn parse_extra_args<Args>(kind: &str, args: &[String]) -> Result<Args, ConfigError>
+where
+    Args: Parser,
+{
+    Args::try_parse_from(iter::once(kind).chain(args.iter().map(String::as_str))).map_err(|error| {
+        ConfigError::InvalidExtraArgs {
+            kind: kind.to_string(),
+            // Only the first line, the rest is usage information that is not helpful here
+            error: error
+                .to_string()
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim_start_matches("error: ")
+                .to_string(),
+        }
+    })
+}
+
+/// Reads information about existing farm (for example created by CLI farmer) and checks that it
+/// is compatible with this application, such that it can be added to configuration without
+/// replotting
+pub fn import_farm(path: PathBuf) -> Result<Farm, ConfigError> {
+    let farm_info = match SingleDiskFarmInfo::load_from(&path) {
+        Ok(Some(farm_info)) => farm_info,
+        Ok(None) => {
+            return Err(ConfigError::FarmNotFound {
+                path: path.display().to_string(),
+            });
+        }
+        Err(error) => {
+            return Err(ConfigError::UnsupportedFarmFormat {
+                path: path.display().to_string(),
+                error,
+            });
+        }
+    };
+
+    let genesis_hash = hex::encode(farm_info.genesis_hash());
+    if genesis_hash != GENESIS_HASH {
+        return Err(ConfigError::IncompatibleFarmChain {
+            path: path.display().to_string(),
+            genesis_hash,
+            supported_genesis_hash: GENESIS_HASH.to_string(),
+        });
+    }
+
+    Ok(Farm {
+        path,
+        // Exact number of bytes, such that farm doesn't need to be resized
+        size: farm_info.allocated_space().to_string(),
+        plotting_concurrency: None,
+        max_disk_temperature: None,
+        max_plotted_sectors: None,
+        network_filesystem: false,
+        numa_node: None,
+        enabled: true,
+    })
+}
+
+/// Reserved peer must be a valid multiaddr that ends with peer ID, otherwise it is not possible to
+/// know which peer to maintain connection to
+fn parse_reserved_peer(address: &str) -> Result<Multiaddr, ConfigError> {
+    let multiaddr =
+        Multiaddr::from_str(address).map_err(|error| ConfigError::InvalidReservedPeer {
+            address: address.to_string(),
+            error: error.to_string(),
+        })?;
+
+    if !matches!(multiaddr.iter().last(), Some(Protocol::P2p(_))) {
+        return Err(ConfigError::InvalidReservedPeer {
+            address: address.to_string(),
+            error: "peer ID is missing, address must end with /p2p/<peer ID>".to_string(),
+        });
+    }
+
+    Ok(multiaddr)
+}
+
+/// Upper bound of plotting rate limit that can be selected in UI, sectors per minute
+pub const MAX_PLOTTING_RATE_LIMIT: u32 = 1000;
+/// Upper bound of minimum number of DSN peers before plotting that can be selected in UI
+pub const MAX_MIN_PLOTTING_PEERS: u32 = 50;
+
+/// Sectors are plotted using CPU, so there is no point in plotting more sectors concurrently than
+/// there are CPU cores
+pub fn max_plotting_concurrency() -> usize {
+    available_parallelism()
+        .map(|cores| cores.get())
+        .unwrap_or(1)
+}
+
+/// Farm indices are `u8`, so there can't be more than [`MAX_FARMS`] farms in total (disabled
+/// farms are counted too since they can be enabled later)
+fn check_farms_count(farms: &[Farm]) -> Result<(), ConfigError> {
+    if farms.len() > MAX_FARMS {
+        return Err(ConfigError::TooManyFarms { count: farms.len() });
+    }
+
+    Ok(())
+}
+
+/// Check that node and farms don't share directories, which would cause data corruption and
+/// incorrect space accounting
+async fn check_overlapping_paths(
+    node_path: &Path,
+    farms: &[Farm],
+    piece_cache_path: Option<&Path>,
+) -> Result<(), ConfigError> {
+    let mut paths = Vec::with_capacity(farms.len() + 2);
+    paths.push(("Node directory".to_string(), node_path));
+    if let Some(piece_cache_path) = piece_cache_path {
+        paths.push(("Piece cache directory".to_string(), piece_cache_path));
+    }
+    // Farms are numbered among enabled farms only, same as in farmer
+    let mut farm_index = 0_usize;
+    for farm in farms {
+        let kind = if farm.enabled {
+            farm_index += 1;
+            format!("Farm {} directory", farm_index - 1)
+        } else {
+            "Disabled farm directory".to_string()
+        };
+        paths.push((kind, farm.path.as_path()));
+    }
+
+    let mut normalized_paths = Vec::with_capacity(paths.len());
+    for (kind, path) in paths {
+        // Resolve symlinks and relative components when path already exists
+        let normalized_path = fs::canonicalize(path)
+            .await
+            .unwrap_or_else(|_error| path.to_path_buf());
+        normalized_paths.push((kind, path, normalized_path));
+    }
+
+    for (index, (first_kind, first_path, first_normalized_path)) in
+        normalized_paths.iter().enumerate()
+    {
+        for (second_kind, second_path, second_normalized_path) in &normalized_paths[index + 1..] {
+            if first_normalized_path.starts_with(second_normalized_path)
+                || second_normalized_path.starts_with(first_normalized_path)
+            {
+                return Err(ConfigError::OverlappingPaths {
+                    first_kind: first_kind.clone(),
+                    first_path: first_path.display().to_string(),
+                    second_kind: second_kind.clone(),
+                    second_path: second_path.display().to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check allocated space against space available on disk for the directory, returns allocated
+/// space that should be used.
+///
+/// Space already used by files in the directory (like existing farm) counts as available. Depending
+/// on `clamp` allocated space that doesn't fit is either reduced to available space or results in
+/// an error.
+async fn fit_allocated_space(
+    kind: &str,
+    path: &Path,
+    allocated_space: u64,
+    clamp: bool,
+) -> Result<u64, ConfigError> {
+    let path_error = |error| ConfigError::PathError {
+        path: path.display().to_string(),
+        error,
+    };
+
+    // Directory may not exist yet, in which case it will be created in parent directory
+    let (existing_path, used_space) = if fs::try_exists(path).await.map_err(path_error)? {
+        let mut used_space = 0;
+        let mut entries = fs::read_dir(path).await.map_err(path_error)?;
+        while let Some(entry) = entries.next_entry().await.map_err(path_error)? {
+            let metadata = entry.metadata().await.map_err(path_error)?;
+            if metadata.is_file() {
+                used_space += metadata.len();
+            }
+        }
+
+        (path.to_path_buf(), used_space)
+    } else {
+        let Some(parent) = path.parent() else {
+            return Err(ConfigError::InvalidPath {
+                path: path.display().to_string(),
+            });
+        };
+
+        (parent.to_path_buf(), 0)
+    };
+
+    let free_space = tokio::task::spawn_blocking(move || fs4::available_space(existing_path))
+        .await
+        .map_err(|error| path_error(io::Error::other(error)))?
+        .map_err(path_error)?;
+    let available_space = free_space + used_space;
+
+    if allocated_space <= available_space {
+        return Ok(allocated_space);
+    }
+
+    if clamp {
+        warn!(
+            %kind,
+            path = %path.display(),
+            allocated_space = %bytesize::to_string(allocated_space, true),
+            available_space = %bytesize::to_string(available_space, true),
+            "Allocated space exceeds available disk space, reducing to available space"
+        );
+
+        Ok(available_space)
+    } else {
+        Err(ConfigError::InsufficientDiskSpace {
+            kind: kind.to_string(),
+            path: path.display().to_string(),
+            allocated_space: bytesize::to_string(allocated_space, true),
+            available_space: bytesize::to_string(available_space, true),
         })
     }
 }
@@ -261,3 +1113,33 @@ async fn check_path(path: &Path) -> Result<(), ConfigError> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{check_farms_count, ConfigError, Farm};
+    use std::path::PathBuf;
+
+    fn farms(count: usize) -> Vec<Farm> {
+        (0..count)
+            .map(|index| Farm {
+                path: PathBuf::from(format!("farm-{index}")),
+                size: "2 GB".to_string(),
+                plotting_concurrency: None,
+                max_disk_temperature: None,
+                max_plotted_sectors: None,
+                network_filesystem: false,
+                numa_node: None,
+                enabled: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn farms_count_limit() {
+        assert!(check_farms_count(&farms(256)).is_ok());
+        assert!(matches!(
+            check_farms_count(&farms(257)),
+            Err(ConfigError::TooManyFarms { count: 257 })
+        ));
+    }
+}
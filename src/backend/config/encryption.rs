@@ -0,0 +1,99 @@
+//! Optional passphrase-based encryption of configuration file at rest.
+//!
+//! Passphrase is process-wide: once set (from environment variable, startup prompt or
+//! configuration view), encrypted configuration can be read and configuration is written
+//! encrypted.
+
+use crate::backend::config::RawConfigError;
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use parking_lot::{const_mutex, Mutex};
+use rand::Rng;
+use std::io;
+use zeroize::Zeroizing;
+
+/// Environment variable with passphrase for encrypted configuration, for headless use
+pub const CONFIG_PASSPHRASE_ENV: &str = "SPACE_ACRES_CONFIG_PASSPHRASE";
+/// Prefix that distinguishes encrypted configuration file from JSON or TOML one
+const MAGIC: &[u8] = b"space-acres-encrypted-config-v0\n";
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 24;
+const KEY_SIZE: usize = 32;
+
+static CONFIG_PASSPHRASE: Mutex<Option<Zeroizing<String>>> = const_mutex(None);
+
+/// Set passphrase used for reading and writing configuration, `None` means configuration will be
+/// written unencrypted
+pub fn set_config_passphrase(passphrase: Option<String>) {
+    *CONFIG_PASSPHRASE.lock() = passphrase.map(Zeroizing::new);
+}
+
+/// Whether configuration passphrase is set and configuration will be written encrypted
+pub fn config_passphrase_set() -> bool {
+    CONFIG_PASSPHRASE.lock().is_some()
+}
+
+pub(super) fn is_encrypted(contents: &[u8]) -> bool {
+    contents.starts_with(MAGIC)
+}
+
+/// Encrypt configuration if passphrase is set, otherwise return it as is
+pub(super) fn maybe_encrypt(contents: Vec<u8>) -> io::Result<Vec<u8>> {
+    let passphrase = CONFIG_PASSPHRASE.lock().clone();
+    let Some(passphrase) = passphrase else {
+        return Ok(contents);
+    };
+
+    let mut salt = [0; SALT_SIZE];
+    let mut nonce = [0; NONCE_SIZE];
+    rand::thread_rng().fill(&mut salt);
+    rand::thread_rng().fill(&mut nonce);
+
+    let key = derive_key(&passphrase, &salt)
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key.as_slice()))
+        .encrypt(XNonce::from_slice(&nonce), contents.as_slice())
+        .map_err(|_error| {
+            io::Error::new(io::ErrorKind::Other, "Failed to encrypt configuration")
+        })?;
+
+    let mut encrypted = Vec::with_capacity(MAGIC.len() + SALT_SIZE + NONCE_SIZE + ciphertext.len());
+    encrypted.extend_from_slice(MAGIC);
+    encrypted.extend_from_slice(&salt);
+    encrypted.extend_from_slice(&nonce);
+    encrypted.extend_from_slice(&ciphertext);
+
+    Ok(encrypted)
+}
+
+/// Decrypt configuration that was checked with [`is_encrypted()`] to be encrypted
+pub(super) fn decrypt(contents: &[u8]) -> Result<Vec<u8>, RawConfigError> {
+    let passphrase = CONFIG_PASSPHRASE.lock().clone();
+    let Some(passphrase) = passphrase else {
+        return Err(RawConfigError::PassphraseRequired);
+    };
+
+    let contents = &contents[MAGIC.len()..];
+    if contents.len() < SALT_SIZE + NONCE_SIZE {
+        return Err(RawConfigError::FailedToDecrypt);
+    }
+    let (salt, contents) = contents.split_at(SALT_SIZE);
+    let (nonce, ciphertext) = contents.split_at(NONCE_SIZE);
+
+    let key = derive_key(&passphrase, salt).map_err(|_error| RawConfigError::FailedToDecrypt)?;
+    // Authentication failure is the only possible error here, which means wrong passphrase or
+    // modified file
+    XChaCha20Poly1305::new(Key::from_slice(key.as_slice()))
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_error| RawConfigError::FailedToDecrypt)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Zeroizing<[u8; KEY_SIZE]>, String> {
+    let mut key = Zeroizing::new([0; KEY_SIZE]);
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|error| format!("Failed to derive configuration encryption key: {error}"))?;
+
+    Ok(key)
+}
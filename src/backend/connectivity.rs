@@ -0,0 +1,100 @@
+//! Startup check of internet connectivity, such that missing connection can be reported clearly
+//! instead of node and farmer failing to connect to peers indefinitely
+
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use std::collections::HashSet;
+use std::time::Duration;
+use subspace_networking::libp2p::multiaddr::Protocol;
+use subspace_networking::libp2p::Multiaddr;
+use tokio::net::TcpStream;
+use tracing::debug;
+
+/// How long to wait for connection to each bootstrap node
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+/// Limit on number of bootstrap nodes that are tried concurrently
+const MAX_CHECKED_ADDRESSES: usize = 8;
+
+/// None of the bootstrap nodes are reachable, most likely there is no internet connection
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "None of {checked_addresses} bootstrap nodes are reachable, internet connection is required"
+)]
+pub(super) struct NoInternetConnectionError {
+    pub(super) checked_addresses: usize,
+}
+
+/// Check that at least one of bootstrap nodes accepts TCP connection.
+///
+/// Succeeds without checking anything if none of the addresses use TCP.
+pub(super) async fn check_internet_connection(
+    bootstrap_nodes: &[Multiaddr],
+) -> Result<(), NoInternetConnectionError> {
+    let addresses = bootstrap_nodes
+        .iter()
+        .filter_map(tcp_address)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .take(MAX_CHECKED_ADDRESSES)
+        .collect::<Vec<_>>();
+
+    if addresses.is_empty() {
+        return Ok(());
+    }
+
+    let mut connections = addresses
+        .iter()
+        .map(|(host, port)| async move {
+            match tokio::time::timeout(
+                CONNECTION_TIMEOUT,
+                TcpStream::connect((host.as_str(), *port)),
+            )
+            .await
+            {
+                Ok(Ok(_stream)) => true,
+                Ok(Err(error)) => {
+                    debug!(%host, %port, %error, "Failed to connect to bootstrap node");
+                    false
+                }
+                Err(_elapsed) => {
+                    debug!(%host, %port, "Connection to bootstrap node timed out");
+                    false
+                }
+            }
+        })
+        .collect::<FuturesUnordered<_>>();
+
+    while let Some(connected) = connections.next().await {
+        if connected {
+            return Ok(());
+        }
+    }
+
+    Err(NoInternetConnectionError {
+        checked_addresses: addresses.len(),
+    })
+}
+
+/// Host and TCP port of the address, `None` if address doesn't use TCP
+fn tcp_address(address: &Multiaddr) -> Option<(String, u16)> {
+    let mut host = None;
+    for protocol in address.iter() {
+        match protocol {
+            Protocol::Dns(name) | Protocol::Dns4(name) | Protocol::Dns6(name) => {
+                host.replace(name.to_string());
+            }
+            Protocol::Ip4(ip) => {
+                host.replace(ip.to_string());
+            }
+            Protocol::Ip6(ip) => {
+                host.replace(ip.to_string());
+            }
+            Protocol::Tcp(port) => {
+                return host.map(|host| (host, port));
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
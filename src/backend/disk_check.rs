@@ -0,0 +1,247 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::slice;
+use std::time::{Duration, Instant};
+
+const DISK_CHECK_FILE_NAME: &str = "space_acres_disk_check.tmp";
+/// Size of the file used for throughput measurements
+const THROUGHPUT_FILE_SIZE: usize = 64 * 1024 * 1024;
+/// Size of each write/read during throughput measurements
+const THROUGHPUT_BLOCK_SIZE: usize = 1024 * 1024;
+/// Size of each write/read during latency measurements, similar to what farming does
+const LATENCY_BLOCK_SIZE: usize = 4 * 1024;
+/// Number of writes/reads used to compute average latency
+const LATENCY_SAMPLES: usize = 64;
+/// Sequential write throughput below this value (MiB/s) makes plotting slow
+const MIN_WRITE_THROUGHPUT: f64 = 50.0;
+/// Sequential read throughput below this value (MiB/s) makes plotting slow
+const MIN_READ_THROUGHPUT: f64 = 100.0;
+/// Average read latency above this value is likely to cause missed rewards during farming
+const MAX_READ_LATENCY: Duration = Duration::from_millis(10);
+/// Average synchronous write latency above this value usually indicates failing disk
+const MAX_WRITE_LATENCY: Duration = Duration::from_millis(100);
+
+/// Alignment of buffers used for reads that bypass OS page cache
+const UNCACHED_IO_ALIGNMENT: usize = 4096;
+
+/// Results of disk check of a single directory
+#[derive(Debug, Copy, Clone)]
+pub struct DiskCheckResult {
+    /// Sequential write throughput in MiB/s
+    pub write_throughput: f64,
+    /// Sequential read throughput in MiB/s
+    pub read_throughput: f64,
+    /// Average latency of small synchronous write
+    pub write_latency: Duration,
+    /// Average latency of small random read
+    pub read_latency: Duration,
+}
+
+impl DiskCheckResult {
+    /// Human-readable warnings about characteristics that are likely too slow for farming, empty
+    /// if disk looks fine
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if self.write_throughput < MIN_WRITE_THROUGHPUT {
+            warnings.push(format!(
+                "write throughput {:.0} MiB/s is below {MIN_WRITE_THROUGHPUT:.0} MiB/s, plotting \
+                will be slow",
+                self.write_throughput
+            ));
+        }
+        if self.read_throughput < MIN_READ_THROUGHPUT {
+            warnings.push(format!(
+                "read throughput {:.0} MiB/s is below {MIN_READ_THROUGHPUT:.0} MiB/s, plotting \
+                will be slow",
+                self.read_throughput
+            ));
+        }
+        if self.read_latency > MAX_READ_LATENCY {
+            warnings.push(format!(
+                "read latency {:.2?} is above {MAX_READ_LATENCY:?}, rewards may be missed",
+                self.read_latency
+            ));
+        }
+        if self.write_latency > MAX_WRITE_LATENCY {
+            warnings.push(format!(
+                "write latency {:.2?} is above {MAX_WRITE_LATENCY:?}, disk may be failing",
+                self.write_latency
+            ));
+        }
+
+        warnings
+    }
+}
+
+/// Benchmark disk the directory is located on using a temporary file that is removed afterwards.
+///
+/// This is a blocking operation that takes a few seconds. Directory must exist already, it is not
+/// created such that a missing mount point doesn't result in benchmarking a different disk.
+pub fn check_disk(directory: &Path) -> io::Result<DiskCheckResult> {
+    if !directory.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Directory {} doesn't exist", directory.display()),
+        ));
+    }
+    let file_path = directory.join(DISK_CHECK_FILE_NAME);
+
+    let result = measure(&file_path);
+
+    if let Err(error) = fs::remove_file(&file_path)
+        && error.kind() != io::ErrorKind::NotFound
+    {
+        return Err(error);
+    }
+
+    result
+}
+
+fn measure(file_path: &Path) -> io::Result<DiskCheckResult> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(file_path)?;
+
+    // Not zeroes, such that file systems with compression don't skew results
+    let block = (0..THROUGHPUT_BLOCK_SIZE)
+        .map(|index| (index % 251) as u8 + 1)
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    for _ in 0..THROUGHPUT_FILE_SIZE / THROUGHPUT_BLOCK_SIZE {
+        file.write_all(&block)?;
+    }
+    file.sync_all()?;
+    let write_throughput = throughput(start.elapsed());
+
+    let write_latency = {
+        let start = Instant::now();
+        for offset in sample_offsets() {
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(&block[..LATENCY_BLOCK_SIZE])?;
+            file.sync_data()?;
+        }
+        start.elapsed() / LATENCY_SAMPLES as u32
+    };
+
+    // Everything was synced to disk already, reads must not be served from OS page cache
+    drop(file);
+    let mut file = open_uncached(file_path)?;
+    let mut buffer = AlignedBuffer::new(THROUGHPUT_BLOCK_SIZE);
+    let buffer = buffer.as_mut_slice();
+
+    let start = Instant::now();
+    for _ in 0..THROUGHPUT_FILE_SIZE / THROUGHPUT_BLOCK_SIZE {
+        file.read_exact(buffer)?;
+    }
+    let read_throughput = throughput(start.elapsed());
+
+    // The whole file was just read, re-open it such that it is not served from OS page cache again
+    drop(file);
+    let mut file = open_uncached(file_path)?;
+    let read_latency = {
+        let start = Instant::now();
+        for offset in sample_offsets() {
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut buffer[..LATENCY_BLOCK_SIZE])?;
+        }
+        start.elapsed() / LATENCY_SAMPLES as u32
+    };
+
+    Ok(DiskCheckResult {
+        write_throughput,
+        read_throughput,
+        write_latency,
+        read_latency,
+    })
+}
+
+/// Open file for reading such that reads go to disk instead of being served from OS page cache
+#[cfg(target_os = "linux")]
+fn open_uncached(file_path: &Path) -> io::Result<File> {
+    use std::os::fd::AsRawFd;
+
+    let file = File::open(file_path)?;
+    // File contents were synced already, hence clean pages are dropped from page cache
+    let result = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    if result != 0 {
+        return Err(io::Error::from_raw_os_error(result));
+    }
+
+    Ok(file)
+}
+
+/// Open file for reading such that reads go to disk instead of being served from OS page cache
+#[cfg(target_os = "macos")]
+fn open_uncached(file_path: &Path) -> io::Result<File> {
+    use std::os::fd::AsRawFd;
+
+    let file = File::open(file_path)?;
+    if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(file)
+}
+
+/// Open file for reading such that reads go to disk instead of being served from OS page cache.
+///
+/// Offsets, sizes and buffers of all reads must be aligned to disk sector size.
+#[cfg(windows)]
+fn open_uncached(file_path: &Path) -> io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use windows_sys::Win32::Storage::FileSystem::FILE_FLAG_NO_BUFFERING;
+
+    OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_NO_BUFFERING)
+        .open(file_path)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn open_uncached(file_path: &Path) -> io::Result<File> {
+    File::open(file_path)
+}
+
+/// Buffer aligned to [`UNCACHED_IO_ALIGNMENT`] as required for reads that bypass OS page cache
+struct AlignedBuffer(Vec<AlignedBlock>);
+
+#[repr(C, align(4096))]
+#[derive(Copy, Clone)]
+struct AlignedBlock([u8; UNCACHED_IO_ALIGNMENT]);
+
+impl AlignedBuffer {
+    fn new(size: usize) -> Self {
+        Self(vec![
+            AlignedBlock([0; UNCACHED_IO_ALIGNMENT]);
+            size.div_ceil(UNCACHED_IO_ALIGNMENT)
+        ])
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: `AlignedBlock` is a plain byte array without padding
+        unsafe {
+            slice::from_raw_parts_mut(
+                self.0.as_mut_ptr().cast::<u8>(),
+                self.0.len() * UNCACHED_IO_ALIGNMENT,
+            )
+        }
+    }
+}
+
+fn throughput(elapsed: Duration) -> f64 {
+    (THROUGHPUT_FILE_SIZE / 1024 / 1024) as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+}
+
+/// Pseudo-random block-aligned offsets within the file, spread across the whole file
+fn sample_offsets() -> impl Iterator<Item = u64> {
+    let blocks = (THROUGHPUT_FILE_SIZE / LATENCY_BLOCK_SIZE) as u64;
+
+    (0..LATENCY_SAMPLES as u64)
+        .map(move |sample| sample.wrapping_mul(2_654_435_761) % blocks * LATENCY_BLOCK_SIZE as u64)
+}
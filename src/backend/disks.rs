@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+/// Disks with less free space than this are not suggested for farming, 2 GB
+const MIN_FREE_SPACE: u64 = 1000 * 1000 * 1000 * 2;
+/// Name of the farm directory suggested on detected disk
+const FARM_DIRECTORY_NAME: &str = "space-acres-farm";
+/// Mount points that never make sense for farming
+#[cfg(target_os = "linux")]
+const IGNORED_MOUNT_POINT_PREFIXES: &[&str] = &["/boot", "/snap", "/var/lib/docker"];
+
+/// Disk (mounted file system or volume) attached to the machine
+#[derive(Debug, Clone)]
+pub struct DetectedDisk {
+    /// Mount point or volume root
+    pub mount_point: PathBuf,
+    /// Free space available to the current user
+    pub free_space: u64,
+    pub total_space: u64,
+}
+
+impl DetectedDisk {
+    /// Suggested farm directory on this disk: in home directory if it is located on this disk
+    /// (since root of system disk is typically not writable) or in the root of the disk otherwise
+    pub fn suggested_farm_path(&self, disks: &[DetectedDisk]) -> PathBuf {
+        if let Some(home_dir) = dirs::home_dir()
+            && home_dir.starts_with(&self.mount_point)
+            && !disks.iter().any(|disk| {
+                disk.mount_point != self.mount_point
+                    && disk.mount_point.starts_with(&self.mount_point)
+                    && home_dir.starts_with(&disk.mount_point)
+            })
+        {
+            return home_dir.join(FARM_DIRECTORY_NAME);
+        }
+
+        self.mount_point.join(FARM_DIRECTORY_NAME)
+    }
+}
+
+/// Enumerate disks with enough free space for farming, sorted by free space (largest first).
+///
+/// This is a blocking operation.
+pub fn detect_disks() -> Vec<DetectedDisk> {
+    let mut disks = mount_points()
+        .into_iter()
+        .filter_map(|mount_point| {
+            let free_space = fs4::available_space(&mount_point).ok()?;
+            let total_space = fs4::total_space(&mount_point).ok()?;
+
+            (free_space >= MIN_FREE_SPACE).then_some(DetectedDisk {
+                mount_point,
+                free_space,
+                total_space,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    disks.sort_by(|a, b| b.free_space.cmp(&a.free_space));
+
+    disks
+}
+
+#[cfg(target_os = "linux")]
+fn mount_points() -> Vec<PathBuf> {
+    let Ok(mounts) = std::fs::read_to_string("/proc/mounts") else {
+        return Vec::new();
+    };
+
+    let mut seen_devices = std::collections::HashSet::new();
+
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            // Spaces and other special characters are escaped as octal sequences
+            let mount_point = fields.next()?.replace("\\040", " ");
+
+            // Only real block devices, not pseudo file systems or loop devices (like snaps)
+            if !device.starts_with("/dev/") || device.starts_with("/dev/loop") {
+                return None;
+            }
+            if IGNORED_MOUNT_POINT_PREFIXES
+                .iter()
+                .any(|prefix| std::path::Path::new(&mount_point).starts_with(prefix))
+            {
+                return None;
+            }
+            // The same device can be mounted multiple times, for example with bind mounts
+            if !seen_devices.insert(device.to_string()) {
+                return None;
+            }
+
+            Some(PathBuf::from(mount_point))
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn mount_points() -> Vec<PathBuf> {
+    ('A'..='Z')
+        .map(|letter| PathBuf::from(format!("{letter}:\\")))
+        .filter(|volume| volume.exists())
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn mount_points() -> Vec<PathBuf> {
+    let mut mount_points = vec![PathBuf::from("/")];
+
+    // External disks on macOS are mounted under `/Volumes`, system volume is a symlink to `/`
+    if let Ok(read_dir) = std::fs::read_dir("/Volumes") {
+        mount_points.extend(
+            read_dir
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| !path.is_symlink() && path.is_dir()),
+        );
+    }
+
+    mount_points
+}
+
+/// Temperature (in °C) of the physical disk where given path is located, `None` if it can't be
+/// determined (for example with RAID/LVM or when disk doesn't expose temperature sensor).
+///
+/// Only supported on Linux through `hwmon` in sysfs, which covers NVMe and (with `drivetemp`
+/// kernel module) SATA disks.
+///
+/// This is a blocking operation.
+#[cfg(target_os = "linux")]
+pub fn disk_temperature(path: &Path) -> Option<f32> {
+    use std::os::unix::fs::MetadataExt;
+
+    let device = std::fs::metadata(path).ok()?.dev();
+    // Same encoding as `major()` and `minor()` in glibc
+    let major = ((device >> 8) & 0xfff) | ((device >> 32) & !0xfff);
+    let minor = (device & 0xff) | ((device >> 12) & !0xff);
+
+    let mut block_device = std::fs::canonicalize(format!("/sys/dev/block/{major}:{minor}")).ok()?;
+    // Partitions are located inside of the disk they belong to
+    if block_device.join("partition").exists() {
+        block_device.pop();
+    }
+
+    // NVMe controllers have `hwmonN` directly, SCSI/SATA disks have it in `hwmon` subdirectory
+    let device_dir = block_device.join("device");
+    [device_dir.clone(), device_dir.join("hwmon")]
+        .into_iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("hwmon"))
+        .find_map(|entry| {
+            let millidegrees = std::fs::read_to_string(entry.path().join("temp1_input")).ok()?;
+            let millidegrees = millidegrees.trim().parse::<i32>().ok()?;
+
+            Some(millidegrees as f32 / 1000.0)
+        })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn disk_temperature(_path: &Path) -> Option<f32> {
+    None
+}
@@ -0,0 +1,59 @@
+//! Execution of user-configured commands on application events, see
+//! [`EventCommands`](crate::backend::config::EventCommands)
+
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::{error, info, warn};
+
+/// Commands that run longer than this are killed
+const EVENT_COMMAND_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Run `command` (program followed by arguments) for `event` with details in `env`, output of the
+/// command is written to logs.
+///
+/// `env` variables are passed along with `SPACE_ACRES_EVENT` set to `event`.
+pub(super) async fn run_event_command(command: &[String], event: &str, env: &[(&str, String)]) {
+    let Some((program, args)) = command.split_first() else {
+        return;
+    };
+
+    info!(%event, %program, ?args, "Running event command");
+
+    let output_fut = Command::new(program)
+        .args(args)
+        .env("SPACE_ACRES_EVENT", event)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(EVENT_COMMAND_TIMEOUT, output_fut).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(error)) => {
+            error!(%event, %program, %error, "Failed to run event command");
+            return;
+        }
+        Err(_elapsed) => {
+            error!(
+                %event,
+                %program,
+                timeout = ?EVENT_COMMAND_TIMEOUT,
+                "Event command timed out and was killed"
+            );
+            return;
+        }
+    };
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        info!(%event, "Event command output: {line}");
+    }
+    for line in String::from_utf8_lossy(&output.stderr).lines() {
+        warn!(%event, "Event command error output: {line}");
+    }
+
+    if output.status.success() {
+        info!(%event, %program, "Event command finished successfully");
+    } else {
+        warn!(%event, %program, status = %output.status, "Event command failed");
+    }
+}
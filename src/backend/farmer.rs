@@ -1,30 +1,45 @@
 pub(super) mod maybe_node_client;
+pub mod numa;
+mod preallocation;
 
+use crate::backend::disks::disk_temperature;
 use crate::backend::farmer::maybe_node_client::MaybeNodeRpcClient;
+use crate::backend::farmer::numa::numa_node_cpu_cores;
+use crate::backend::farmer::preallocation::preallocate_file;
 use crate::backend::utils::{Handler, HandlerFn};
-use crate::backend::PieceGetterWrapper;
+use crate::backend::{directory_size, PieceGetterWrapper};
 use crate::PosTable;
 use anyhow::anyhow;
 use async_lock::Mutex as AsyncMutex;
+use clap::Parser;
 use event_listener_primitives::HandlerId;
 use futures::channel::{mpsc, oneshot};
-use futures::future::BoxFuture;
+use futures::future::{AbortHandle, Aborted, BoxFuture};
 use futures::stream::{FuturesOrdered, FuturesUnordered};
-use futures::{select, FutureExt, StreamExt, TryStreamExt};
+use futures::{future, select, FutureExt, StreamExt, TryStreamExt};
 use parking_lot::Mutex;
-use std::future::pending;
-use std::num::{NonZeroU8, NonZeroUsize};
+use rand::Rng;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::future::{pending, Future};
+use std::num::{NonZeroU16, NonZeroU32, NonZeroU8, NonZeroUsize};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use std::{fmt, fs};
+use std::time::{Duration, Instant};
+use std::{fmt, fs, iter, mem};
 use subspace_core_primitives::crypto::kzg::Kzg;
-use subspace_core_primitives::{PublicKey, Record, SectorIndex};
+use subspace_core_primitives::{Piece, PieceIndex, PieceOffset, PublicKey, Record, SectorIndex};
 use subspace_erasure_coding::ErasureCoding;
-use subspace_farmer::farm::{Farm, FarmingNotification, SectorPlottingDetails, SectorUpdate};
+use subspace_farmer::farm::{
+    Farm, FarmError, FarmingNotification, PieceCache, PieceReader, PlotCache,
+    SectorExpirationDetails, SectorPlottingDetails, SectorUpdate,
+};
 use subspace_farmer::farmer_cache::{FarmerCache, FarmerCacheWorker};
+use subspace_farmer::single_disk_farm::piece_cache::DiskPieceCache;
 use subspace_farmer::single_disk_farm::{
-    SingleDiskFarm, SingleDiskFarmError, SingleDiskFarmOptions,
+    SingleDiskFarm, SingleDiskFarmError, SingleDiskFarmInfo, SingleDiskFarmOptions,
 };
 use subspace_farmer::utils::plotted_pieces::PlottedPieces;
 use subspace_farmer::utils::{
@@ -33,16 +48,74 @@ use subspace_farmer::utils::{
 };
 use subspace_farmer::NodeClient;
 use subspace_farmer_components::plotting::PlottedSector;
+use subspace_farmer_components::sector::{sector_size, SectorMetadataChecksummed};
+use subspace_farmer_components::PieceGetter;
+use subspace_networking::Node;
 use thread_priority::ThreadPriority;
-use tokio::sync::{watch, Barrier, Semaphore};
-use tracing::{debug, error, info, info_span, Instrument};
+use tokio::sync::{watch, Barrier, OwnedSemaphorePermit, Semaphore};
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 /// Minimal cache percentage, there is no need in setting it higher
 const CACHE_PERCENTAGE: NonZeroU8 = NonZeroU8::MIN;
+/// Fixed space used by the farm regardless of its size (reserved plot metadata, farm info,
+/// identity and known peers), rounded up
+const FARM_FIXED_SPACE_OVERHEAD: u64 = 4 * 1024 * 1024;
 /// NOTE: for large gaps between the plotted part and the end of the file plot cache will result in
 /// very long period of writing zeroes on Windows, see https://stackoverflow.com/q/78058306/3806795
 const MAX_SPACE_PLEDGED_FOR_PLOT_CACHE_ON_WINDOWS: u64 = 7 * 1024 * 1024 * 1024 * 1024;
 const FARM_ERROR_PRINT_INTERVAL: Duration = Duration::from_secs(30);
+/// Interval between reminders about farm error grows up to this value to avoid flooding logs
+const FARM_ERROR_PRINT_MAX_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// Interval between attempts to recover farm after error (if enabled)
+const FARM_RECOVERY_INTERVAL: Duration = Duration::from_secs(60);
+/// Percentage of farm's sectors that need to be about to expire or expired for it to be considered
+/// significant and reported
+const SIGNIFICANT_EXPIRING_SECTORS_PERCENTAGE: usize = 1;
+/// Space that doesn't fit a whole sector is considered significant when it is at least this
+/// fraction of sector size (1/N)
+const SIGNIFICANT_UNUSED_SPACE_SECTOR_FRACTION: u64 = 2;
+/// Window over which plotting rate is measured and limited
+const PLOTTING_RATE_WINDOW: Duration = Duration::from_secs(60);
+/// Number of sectors that can be plotted back-to-back before plotting rate limit starts spacing
+/// them out evenly
+const PLOTTING_RATE_BURST: i64 = 1;
+/// How often disk temperature of farms with temperature limit is checked
+const DISK_TEMPERATURE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Plotting is resumed once disk temperature drops this much (in °C) below the limit, such that
+/// plotting doesn't flip-flop around the limit
+const DISK_TEMPERATURE_HYSTERESIS: f32 = 5.0;
+/// Name of the file within farm directory where sectors are plotted
+const PLOT_FILE_NAME: &str = "plot.bin";
+/// Directory within farm directory where plotting self-test farm is created, such that the same
+/// disk is tested
+const PLOTTING_SELF_TEST_DIRECTORY: &str = "space-acres-plotting-self-test";
+/// Plotting self-test fails if a sector is not plotted within this time
+const PLOTTING_SELF_TEST_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+/// Number of random pieces read back from sector plotted during self-test
+const PLOTTING_SELF_TEST_READ_PIECES: usize = 10;
+/// Number of random pieces read from each farm during piece read benchmark
+const READ_BENCHMARK_PIECES: usize = 100;
+/// Reading a piece reads and decodes a whole record, which is much more than proving reads from
+/// disk, farms with slower average piece reads are still very likely to miss rewards
+const READ_BENCHMARK_MAX_AVERAGE_READ_TIME: Duration = Duration::from_secs(1);
+/// Plotting concurrency of farms on network filesystems that don't have explicit limit, such that
+/// high latency of each write doesn't cause many sectors to pile up in memory
+const NETWORK_FILESYSTEM_PLOTTING_CONCURRENCY: NonZeroUsize = NonZeroUsize::MIN;
+/// Network shares may take a while to be re-mounted after connectivity issues, so recovery of farms
+/// on network filesystems is attempted less frequently
+const NETWORK_FILESYSTEM_FARM_RECOVERY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Timeouts applied to farms on network filesystems (like plotting stall timeout) are multiplied by
+/// this, since every read and write takes longer
+pub const NETWORK_FILESYSTEM_TIMEOUT_MULTIPLIER: u32 = 4;
+/// Interval at which permits of shared downloading semaphore that are no longer needed by farms
+/// with own plotting concurrency are released
+const SHARED_DOWNLOADING_PERMITS_RELEASE_INTERVAL: Duration = Duration::from_secs(1);
+/// How often number of connected DSN peers is checked while waiting for enough peers to start
+/// plotting
+const PLOTTING_PEERS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Plotting starts after this time even if there are fewer DSN peers than configured
+const PLOTTING_PEERS_TIMEOUT: Duration = Duration::from_secs(10 * 60);
 
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub struct InitialFarmState {
@@ -50,6 +123,26 @@ pub struct InitialFarmState {
     pub plotted_sectors_count: SectorIndex,
 }
 
+/// Progress of scanning sectors that were already plotted before farmer was started
+#[derive(Debug, Copy, Clone)]
+pub struct PlottedSectorsScanProgress {
+    pub scanned_sectors: u64,
+    pub total_sectors: u64,
+    /// Farm that is currently being scanned
+    pub farm_index: u8,
+    pub farm_scanned_sectors: u64,
+    pub farm_total_sectors: u64,
+}
+
+/// Progress of allocating disk space for the whole farm before plotting
+#[derive(Debug, Copy, Clone)]
+pub struct FarmPreallocationProgress {
+    pub farm_index: u8,
+    /// Bytes allocated so far
+    pub preallocated: u64,
+    pub total: u64,
+}
+
 #[derive(Debug, Clone)]
 pub enum FarmerNotification {
     SectorUpdate {
@@ -64,17 +157,154 @@ pub enum FarmerNotification {
     FarmerCacheSyncProgress {
         /// Progress so far in %
         progress: f32,
+        /// Share of piece cache capacity in % that was already stored when sync started, such that
+        /// only remaining pieces need to be downloaded (`None` if caches were empty)
+        resumed_from: Option<f32>,
+    },
+    /// Significant number of sectors is about to expire or already expired and will need to be
+    /// replotted, sent again every time these numbers change while still significant and once more
+    /// with zero counts after they are no longer significant
+    ExpiringSectors {
+        farm_index: u8,
+        about_to_expire_sectors_count: SectorIndex,
+        expired_sectors_count: SectorIndex,
     },
     FarmError {
         farm_index: u8,
         error: Arc<anyhow::Error>,
     },
+    /// Attempting to recover farm after error
+    FarmRecoveryAttempt {
+        farm_index: u8,
+        /// Attempt number, starting with `1`
+        attempt: usize,
+    },
+    /// Farm was recovered after error and is running again
+    FarmRecovered { farm_index: u8 },
+    /// Plotting rate across all farms, sent every time it changes
+    PlottingRate {
+        /// Number of sectors plotted within the last minute
+        sectors_per_minute: u32,
+        /// Maximum number of sectors allowed to be plotted per minute, unlimited if not specified
+        limit: Option<NonZeroU32>,
+    },
+    /// Connection to the node was lost and farmer is trying to reconnect (`true`) or connection
+    /// was restored (`false`)
+    NodeReconnecting(bool),
+    /// Progress of scanning plotted sectors after farms rescan was requested
+    PlottedSectorsScanProgress(PlottedSectorsScanProgress),
+    /// Farms rescan finished and rescanned farms are running again, farms that failed to open are
+    /// reported with [`FarmerNotification::FarmError`] instead
+    FarmsRescanned {
+        /// Farms that were stopped for rescan
+        farm_indices: Vec<u8>,
+        /// Error if plotted pieces were not collected completely
+        error: Option<Arc<anyhow::Error>>,
+    },
+    /// Farm was stopped and its files are being deleted
+    FarmWiping { farm_index: u8 },
+    /// Farm files were deleted
+    FarmWiped {
+        farm_index: u8,
+        directory: PathBuf,
+        /// Number of bytes freed on disk
+        freed_bytes: u64,
+    },
+    FarmWipeFailed {
+        farm_index: u8,
+        error: Arc<anyhow::Error>,
+    },
+    /// Plotting of the farm was paused because disk temperature exceeded the limit (`paused` is
+    /// `true`) or resumed after disk cooled down (`paused` is `false`)
+    DiskTemperaturePause {
+        farm_index: u8,
+        /// Disk temperature in °C
+        temperature: f32,
+        paused: bool,
+    },
+    /// Piece read benchmark of the farm finished
+    FarmReadBenchmark {
+        farm_index: u8,
+        result: Result<ReadBenchmarkResult, Arc<anyhow::Error>>,
+    },
+    /// Plotting self-test next to the farm finished
+    PlottingSelfTest {
+        farm_index: u8,
+        result: Result<PlottingSelfTestResult, Arc<anyhow::Error>>,
+    },
+    /// Occupancy of farmer cache was collected
+    FarmerCacheOccupancy {
+        result: Result<FarmerCacheOccupancy, Arc<anyhow::Error>>,
+    },
+    /// Plotting start is held until enough DSN peers are connected, sent every time number of
+    /// connected peers changes and once more with `waiting: false` when plotting is allowed to start
+    WaitingForPeers {
+        connected: u32,
+        required: u32,
+        waiting: bool,
+    },
+}
+
+/// How many pieces are stored in piece caches that back farmer cache
+#[derive(Debug, Default, Copy, Clone)]
+pub struct FarmerCacheOccupancy {
+    /// Number of pieces stored
+    pub stored_pieces: u64,
+    /// Total number of pieces that can be stored
+    pub capacity: u64,
+}
+
+/// Result of plotting a single sector into temporary farm and reading it back
+#[derive(Debug, Copy, Clone)]
+pub struct PlottingSelfTestResult {
+    /// Time it took to download, encode and write a sector
+    pub plotting_time: Duration,
+    /// Average time to read a piece back from plotted sector
+    pub average_read_time: Duration,
+}
+
+/// Result of farm piece read benchmark.
+///
+/// Pieces are read and decoded from plotted sectors the same way as when serving them to the
+/// network, this is not the same as raw sector chunk reads done during proving.
+#[derive(Debug, Copy, Clone)]
+pub struct ReadBenchmarkResult {
+    /// Number of pieces read
+    pub pieces_read: usize,
+    pub average_read_time: Duration,
+    pub max_read_time: Duration,
+    /// Piece read throughput in bytes per second
+    pub throughput: u64,
+}
+
+impl ReadBenchmarkResult {
+    /// Whether farm is likely too slow to produce proofs before deadline
+    pub fn too_slow(&self) -> bool {
+        self.average_read_time > READ_BENCHMARK_MAX_AVERAGE_READ_TIME
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum FarmerAction {
     /// Pause (or resume) plotting
     PausePlotting(bool),
+    /// Change maximum number of sectors plotted per minute, `None` removes the limit
+    SetPlottingRateLimit(Option<NonZeroU32>),
+    /// Re-open running farms and collect plotted pieces from them again
+    RescanFarms,
+    /// Stop farm and delete its files, other farms continue running
+    WipeFarm { farm_index: u8 },
+    /// Measure how fast random pieces can be read and decoded from running farms, results are
+    /// reported with
+    /// [`FarmerNotification::FarmReadBenchmark`]
+    BenchmarkFarms,
+    /// Plot a single sector into temporary farm next to each running farm (one farm at a time) and
+    /// read it back to check that plotting works end-to-end, results are reported with
+    /// [`FarmerNotification::PlottingSelfTest`]
+    TestPlotting,
+    /// Count pieces stored in farmer cache, results are reported with
+    /// [`FarmerNotification::FarmerCacheOccupancy`]
+    ReportFarmerCacheOccupancy,
 }
 
 type Notifications = Handler<FarmerNotification>;
@@ -84,6 +314,7 @@ pub(super) struct Farmer {
     farmer_cache_worker_fut: BoxFuture<'static, ()>,
     initial_farm_states: Vec<InitialFarmState>,
     farm_during_initial_plotting: bool,
+    plot_cache_enabled: bool,
     notifications: Arc<Notifications>,
     action_sender: mpsc::Sender<FarmerAction>,
 }
@@ -95,6 +326,7 @@ impl Farmer {
             farmer_cache_worker_fut,
             initial_farm_states,
             farm_during_initial_plotting: _,
+            plot_cache_enabled: _,
             notifications,
             action_sender,
         } = self;
@@ -147,6 +379,12 @@ impl Farmer {
         self.farm_during_initial_plotting
     }
 
+    /// Plot cache is disabled on Windows when total pledged space exceeds
+    /// [`MAX_SPACE_PLEDGED_FOR_PLOT_CACHE_ON_WINDOWS`] or when explicitly disabled in configuration
+    pub(super) fn plot_cache_enabled(&self) -> bool {
+        self.plot_cache_enabled
+    }
+
     pub(super) fn action_sender(&self) -> mpsc::Sender<FarmerAction> {
         self.action_sender.clone()
     }
@@ -156,281 +394,1617 @@ impl Farmer {
     }
 }
 
+#[derive(Debug, Default)]
+struct ExpiringSectors {
+    about_to_expire: HashSet<SectorIndex>,
+    expired: HashSet<SectorIndex>,
+    significant: bool,
+}
+
+impl ExpiringSectors {
+    /// Update internal state with sector update, returns `true` if notification needs to be sent
+    fn update(
+        &mut self,
+        sector_index: SectorIndex,
+        sector_update: &SectorUpdate,
+        total_sectors_count: SectorIndex,
+    ) -> bool {
+        let changed = match sector_update {
+            SectorUpdate::Expiration(SectorExpirationDetails::AboutToExpire) => {
+                self.about_to_expire.insert(sector_index)
+            }
+            SectorUpdate::Expiration(SectorExpirationDetails::Expired) => {
+                let removed = self.about_to_expire.remove(&sector_index);
+                self.expired.insert(sector_index) || removed
+            }
+            SectorUpdate::Plotting(SectorPlottingDetails::Finished { .. }) => {
+                let removed = self.about_to_expire.remove(&sector_index);
+                self.expired.remove(&sector_index) || removed
+            }
+            _ => false,
+        };
+
+        if !changed {
+            return false;
+        }
+
+        let was_significant = self.significant;
+        let expiring_sectors_count = self.about_to_expire.len() + self.expired.len();
+        self.significant = expiring_sectors_count > 0
+            && expiring_sectors_count * 100
+                >= usize::from(total_sectors_count) * SIGNIFICANT_EXPIRING_SECTORS_PERCENTAGE;
+
+        self.significant || was_significant
+    }
+
+    /// Returns numbers of about to expire and expired sectors or zeroes if they are not significant
+    fn significant_counts(&self) -> (SectorIndex, SectorIndex) {
+        if self.significant {
+            (
+                self.about_to_expire.len() as SectorIndex,
+                self.expired.len() as SectorIndex,
+            )
+        } else {
+            (0, 0)
+        }
+    }
+}
+
 impl fmt::Debug for Farmer {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Farmer").finish_non_exhaustive()
     }
 }
 
-fn should_farm_during_initial_plotting() -> bool {
-    let total_cpu_cores = all_cpu_cores()
-        .iter()
-        .flat_map(|set| set.cpu_cores())
-        .count();
-    total_cpu_cores > 8
-}
+/// Subscribe to farm events in order to forward them as notifications and keep plotted pieces up to
+/// date
+fn subscribe_to_farm_events(
+    farm: &dyn Farm,
+    farm_index: u8,
+    notifications: &Arc<Notifications>,
+    plotted_pieces: Arc<Mutex<Option<PlottedPieces>>>,
+) {
+    let span = info_span!("farm", farm_index);
+    let total_sectors_count = farm.total_sectors_count();
+
+    farm.on_sector_update(Arc::new({
+        let notifications = Arc::clone(notifications);
+
+        move |(sector_index, sector_update)| {
+            notifications.call_simple(&FarmerNotification::SectorUpdate {
+                farm_index,
+                sector_index: *sector_index,
+                update: sector_update.clone(),
+            });
+        }
+    }))
+    .detach();
+    farm.on_sector_update(Arc::new({
+        let notifications = Arc::clone(notifications);
+        let expiring_sectors = Mutex::new(ExpiringSectors::default());
+
+        move |(sector_index, sector_update)| {
+            let notification = {
+                let mut expiring_sectors = expiring_sectors.lock();
+
+                if !expiring_sectors.update(*sector_index, sector_update, total_sectors_count) {
+                    return;
+                }
 
-#[derive(Debug, Clone)]
-pub struct DiskFarm {
-    pub directory: PathBuf,
-    pub allocated_plotting_space: u64,
+                let (about_to_expire_sectors_count, expired_sectors_count) =
+                    expiring_sectors.significant_counts();
+
+                FarmerNotification::ExpiringSectors {
+                    farm_index,
+                    about_to_expire_sectors_count,
+                    expired_sectors_count,
+                }
+            };
+
+            notifications.call_simple(&notification);
+        }
+    }))
+    .detach();
+    farm.on_farming_notification(Arc::new({
+        let notifications = Arc::clone(notifications);
+
+        move |notification| {
+            notifications.call_simple(&FarmerNotification::FarmingNotification {
+                farm_index,
+                notification: notification.clone(),
+            });
+        }
+    }))
+    .detach();
+
+    // Collect newly plotted pieces
+    let on_plotted_sector_callback =
+        move |plotted_sector: &PlottedSector, maybe_old_plotted_sector: &Option<PlottedSector>| {
+            let _span_guard = span.enter();
+
+            {
+                let mut plotted_pieces = plotted_pieces.lock();
+                let plotted_pieces = plotted_pieces
+                    .as_mut()
+                    .expect("Initial value is populated before farms start; qed");
+
+                if let Some(old_plotted_sector) = &maybe_old_plotted_sector {
+                    plotted_pieces.delete_sector(farm_index, old_plotted_sector);
+                }
+                plotted_pieces.add_sector(farm_index, plotted_sector);
+            }
+        };
+    farm.on_sector_update(Arc::new(move |(_sector_index, sector_state)| {
+        if let SectorUpdate::Plotting(SectorPlottingDetails::Finished {
+            plotted_sector,
+            old_plotted_sector,
+            ..
+        }) = sector_state
+        {
+            on_plotted_sector_callback(plotted_sector, old_plotted_sector);
+        }
+    }))
+    .detach();
 }
 
-/// Arguments for farmer
-#[derive(Debug)]
-pub(super) struct FarmerOptions {
-    pub(super) reward_address: PublicKey,
-    pub(super) disk_farms: Vec<DiskFarm>,
-    pub(super) node_client: MaybeNodeRpcClient,
-    pub(super) piece_getter: PieceGetterWrapper,
-    pub(super) plotted_pieces: Arc<Mutex<Option<PlottedPieces>>>,
-    pub(super) farmer_cache: FarmerCache,
-    pub(super) farmer_cache_worker: FarmerCacheWorker<MaybeNodeRpcClient>,
-    pub(super) kzg: Kzg,
+/// Indices of plotted sectors of each farm
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlotMap {
+    farms: BTreeMap<u8, Vec<SectorIndex>>,
 }
 
-pub(super) async fn create_farmer(farmer_options: FarmerOptions) -> anyhow::Result<Farmer> {
-    let span = info_span!("Farmer");
-    let _enter = span.enter();
+/// Collect plotted pieces from provided farms, sectors are only read here, nothing is plotted or
+/// replotted. Returns indices of sectors that were read successfully.
+///
+/// Scan is aborted with [`ScanCancelledError`] as soon as `cancelled` is set.
+async fn collect_plotted_pieces(
+    farms: &[(u8, &dyn Farm)],
+    plotted_pieces: &mut PlottedPieces,
+    on_scan_progress: &(dyn Fn(&PlottedSectorsScanProgress) + Send + Sync),
+    cancelled: &AtomicBool,
+) -> anyhow::Result<PlotMap> {
+    let mut plot_map = PlotMap::default();
+    let mut scan_progress = PlottedSectorsScanProgress {
+        scanned_sectors: 0,
+        total_sectors: 0,
+        farm_index: 0,
+        farm_scanned_sectors: 0,
+        farm_total_sectors: 0,
+    };
+    let mut farm_total_sectors = Vec::with_capacity(farms.len());
+    for (farm_index, farm) in farms {
+        let plotted_sectors_count = farm.plotted_sectors_count().await.map_err(|error| {
+            anyhow!("Failed to get plotted sectors count from from index {farm_index}: {error}")
+        })?;
+        scan_progress.total_sectors += u64::from(plotted_sectors_count);
+        farm_total_sectors.push(u64::from(plotted_sectors_count));
+    }
 
-    let FarmerOptions {
-        reward_address,
-        disk_farms,
-        node_client,
-        piece_getter,
-        plotted_pieces,
-        farmer_cache,
-        farmer_cache_worker,
-        kzg,
-    } = farmer_options;
+    for (&(farm_index, farm), farm_total_sectors) in farms.iter().zip(farm_total_sectors) {
+        scan_progress.farm_index = farm_index;
+        scan_progress.farm_scanned_sectors = 0;
+        scan_progress.farm_total_sectors = farm_total_sectors;
+        on_scan_progress(&scan_progress);
+
+        for (sector_index, mut plotted_sectors) in
+            (0 as SectorIndex..).zip(farm.plotted_sectors().await)
+        {
+            while let Some(plotted_sector_result) = plotted_sectors.next().await {
+                if cancelled.load(Ordering::Relaxed) {
+                    return Err(anyhow::Error::new(ScanCancelledError));
+                }
 
-    if disk_farms.is_empty() {
-        return Err(anyhow!("There must be at least one disk farm provided"));
-    }
+                match plotted_sector_result {
+                    Ok(plotted_sector) => {
+                        plotted_pieces.add_sector(farm_index, &plotted_sector);
+                        plot_map
+                            .farms
+                            .entry(farm_index)
+                            .or_default()
+                            .push(plotted_sector.sector_index);
+                    }
+                    Err(error) => {
+                        error!(
+                            %error,
+                            %farm_index,
+                            %sector_index,
+                            "Failed reading plotted sector, skipping"
+                        );
+                    }
+                }
 
-    for farm in &disk_farms {
-        if !farm.directory.exists() {
-            if let Err(error) = fs::create_dir(&farm.directory) {
-                return Err(anyhow!(
-                    "Directory {} doesn't exist and can't be created: {}",
-                    farm.directory.display(),
-                    error
-                ));
+                scan_progress.scanned_sectors += 1;
+                scan_progress.farm_scanned_sectors += 1;
+                // Report every whole percent to avoid flooding with notifications
+                if scan_progress.scanned_sectors * 100 / scan_progress.total_sectors.max(1)
+                    != (scan_progress.scanned_sectors - 1) * 100
+                        / scan_progress.total_sectors.max(1)
+                {
+                    on_scan_progress(&scan_progress);
+                }
             }
         }
     }
 
-    let plot_cache = !cfg!(windows)
-        || disk_farms
-            .iter()
-            .map(|farm| farm.allocated_plotting_space)
-            .sum::<u64>()
-            <= MAX_SPACE_PLEDGED_FOR_PLOT_CACHE_ON_WINDOWS;
+    Ok(plot_map)
+}
 
-    let farmer_app_info = node_client
-        .farmer_app_info()
-        .await
-        .map_err(|error| anyhow::anyhow!(error))?;
+/// Piece reader of the farm that can be replaced once farm is opened again (after recovery or
+/// rescan), such that plotted pieces read from the farm instance that is actually running
+#[derive(Clone)]
+struct ReplaceablePieceReader(Arc<Mutex<Arc<dyn PieceReader>>>);
 
-    let erasure_coding = ErasureCoding::new(
-        NonZeroUsize::new(Record::NUM_S_BUCKETS.next_power_of_two().ilog2() as usize)
-            .expect("Not zero; qed"),
+impl fmt::Debug for ReplaceablePieceReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplaceablePieceReader")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl PieceReader for ReplaceablePieceReader {
+    async fn read_piece(
+        &self,
+        sector_index: SectorIndex,
+        piece_offset: PieceOffset,
+    ) -> Result<Option<Piece>, FarmError> {
+        let piece_reader = self.get();
+        piece_reader.read_piece(sector_index, piece_offset).await
+    }
+}
+
+impl ReplaceablePieceReader {
+    fn new(piece_reader: Arc<dyn PieceReader>) -> Self {
+        Self(Arc::new(Mutex::new(piece_reader)))
+    }
+
+    fn get(&self) -> Arc<dyn PieceReader> {
+        Arc::clone(&self.0.lock())
+    }
+
+    fn replace(&self, piece_reader: Arc<dyn PieceReader>) {
+        *self.0.lock() = piece_reader;
+    }
+}
+
+/// Plotted pieces that read from replaceable piece readers of farms
+fn new_plotted_pieces(piece_readers: &[ReplaceablePieceReader]) -> PlottedPieces {
+    PlottedPieces::new(
+        piece_readers
+            .iter()
+            .map(|piece_reader| Arc::new(piece_reader.clone()) as Arc<dyn PieceReader>)
+            .collect(),
     )
-    .map_err(|error| anyhow::anyhow!(error))?;
+}
 
-    let farmer_cache_worker_fut = Box::pin(
-        farmer_cache_worker
-            .run(piece_getter.downgrade())
-            .in_current_span(),
-    );
+/// Farms opened again and scanned after rescan was requested
+struct RescannedFarms {
+    farms: Vec<(u8, Box<dyn Farm>)>,
+    /// Farms that failed to open again
+    failed_farms: Vec<(u8, anyhow::Error)>,
+    plotted_pieces: PlottedPieces,
+    scan_result: anyhow::Result<()>,
+}
 
-    let farm_during_initial_plotting = should_farm_during_initial_plotting();
-    let mut plotting_thread_pool_core_indices = thread_pool_core_indices(None, None);
-    let mut replotting_thread_pool_core_indices = {
-        let mut replotting_thread_pool_core_indices = thread_pool_core_indices(None, None);
-        // The default behavior is to use all CPU cores, but for replotting we just want half
-        replotting_thread_pool_core_indices
-            .iter_mut()
-            .for_each(|set| set.truncate(set.cpu_cores().len() / 2));
-        replotting_thread_pool_core_indices
-    };
+/// Start farm, its abort handle is stored in `farm_abort_handles` such that it can be stopped for
+/// rescan later
+fn run_farm(
+    farm_index: u8,
+    farm: Box<dyn Farm>,
+    farm_abort_handles: &mut HashMap<u8, AbortHandle>,
+) -> BoxFuture<'static, (u8, Result<anyhow::Result<()>, Aborted>)> {
+    let (farm_fut, abort_handle) = future::abortable(farm.run());
+    farm_abort_handles.insert(farm_index, abort_handle);
+
+    farm_fut.map(move |result| (farm_index, result)).boxed()
+}
 
-    if plotting_thread_pool_core_indices.len() > 1 {
-        info!(
-            l3_cache_groups = %plotting_thread_pool_core_indices.len(),
-            "Multiple L3 cache groups detected"
-        );
+/// Remove sectors of the farm from plotted pieces, such that pieces are no longer read from it
+async fn forget_plotted_sectors(
+    farm: &dyn Farm,
+    farm_index: u8,
+    plotted_pieces: &Mutex<Option<PlottedPieces>>,
+) -> anyhow::Result<()> {
+    let mut plotted_sectors = farm
+        .plotted_sectors()
+        .await
+        .map_err(|error| anyhow!("Failed to get plotted sectors of farm {farm_index}: {error}"))?;
 
-        if plotting_thread_pool_core_indices.len() > disk_farms.len() {
-            plotting_thread_pool_core_indices =
-                CpuCoreSet::regroup(&plotting_thread_pool_core_indices, disk_farms.len());
-            replotting_thread_pool_core_indices =
-                CpuCoreSet::regroup(&replotting_thread_pool_core_indices, disk_farms.len());
+    while let Some(plotted_sector_result) = plotted_sectors.next().await {
+        let plotted_sector = plotted_sector_result.map_err(|error| {
+            anyhow!("Failed to read plotted sector of farm {farm_index}: {error}")
+        })?;
 
-            info!(
-                farms_count = %disk_farms.len(),
-                "Regrouped CPU cores to match number of farms, more farms may leverage CPU more efficiently"
-            );
+        if let Some(plotted_pieces) = plotted_pieces.lock().as_mut() {
+            plotted_pieces.delete_sector(farm_index, &plotted_sector);
         }
     }
 
-    let plotting_thread_pools_count = plotting_thread_pool_core_indices.len();
-
-    let downloading_semaphore =
-        Arc::new(Semaphore::new(plotting_thread_pool_core_indices.len() + 1));
+    Ok(())
+}
 
-    let record_encoding_concurrency = {
-        let cpu_cores = plotting_thread_pool_core_indices
-            .first()
-            .expect("Guaranteed to have some CPU cores; qed");
+/// Delete files of the farm that was already stopped, returns number of bytes freed
+fn wipe_farm(farm_index: u8, directory: PathBuf) -> BoxFuture<'static, (u8, anyhow::Result<u64>)> {
+    async move {
+        let wipe_fut = tokio::task::spawn_blocking(move || {
+            let size_before = directory_size(&directory);
+            SingleDiskFarm::wipe(&directory)?;
+            let size_after = directory_size(&directory);
+
+            Ok::<_, std::io::Error>(size_before.saturating_sub(size_after))
+        });
+
+        let result = match wipe_fut.await {
+            Ok(Ok(freed_bytes)) => Ok(freed_bytes),
+            Ok(Err(error)) => Err(error.into()),
+            Err(error) => Err(error.into()),
+        };
 
-        NonZeroUsize::new((cpu_cores.cpu_cores().len() / 2).min(8))
-            .expect("Guaranteed to have some CPU cores; qed")
-    };
+        (farm_index, result)
+    }
+    .boxed()
+}
 
-    let plotting_thread_pool_manager = create_plotting_thread_pool_manager(
-        plotting_thread_pool_core_indices
-            .into_iter()
-            .zip(replotting_thread_pool_core_indices),
-        Some(ThreadPriority::Min),
-    )?;
+/// Read random pieces from plotted sectors of the farm one by one and measure piece read latency
+/// and throughput
+fn benchmark_farm(
+    farm_index: u8,
+    piece_reader: Arc<dyn PieceReader>,
+    plotted_sectors_count: SectorIndex,
+    pieces_in_sector: u16,
+) -> BoxFuture<'static, (u8, anyhow::Result<ReadBenchmarkResult>)> {
+    async move {
+        if plotted_sectors_count == 0 || pieces_in_sector == 0 {
+            return (
+                farm_index,
+                Err(anyhow!(
+                    "Farm doesn't have plotted sectors to benchmark yet"
+                )),
+            );
+        }
 
-    let (farms, plotting_delay_senders) = {
-        let global_mutex = Arc::default();
-        let info_mutex = &AsyncMutex::new(());
-        let faster_read_sector_record_chunks_mode_barrier =
-            Arc::new(Barrier::new(disk_farms.len()));
-        let faster_read_sector_record_chunks_mode_concurrency = Arc::new(Semaphore::new(1));
-        let (plotting_delay_senders, plotting_delay_receivers) = (0..disk_farms.len())
-            .map(|_| oneshot::channel())
-            .unzip::<_, _, Vec<_>, Vec<_>>();
+        let pieces = {
+            let mut rng = rand::thread_rng();
 
-        let mut farms = Vec::with_capacity(disk_farms.len());
-        let mut farms_stream = disk_farms
-            .into_iter()
-            .zip(plotting_delay_receivers)
-            .enumerate()
-            .map(|(farm_index, (disk_farm, plotting_delay_receiver))| {
-                let node_client = node_client.clone();
-                let farmer_app_info = farmer_app_info.clone();
-                let max_pieces_in_sector = farmer_app_info.protocol_info.max_pieces_in_sector;
-                let kzg = kzg.clone();
-                let erasure_coding = erasure_coding.clone();
-                let piece_getter = piece_getter.clone();
-                let downloading_semaphore = Arc::clone(&downloading_semaphore);
-                let plotting_thread_pool_manager = plotting_thread_pool_manager.clone();
-                let global_mutex = Arc::clone(&global_mutex);
-                let faster_read_sector_record_chunks_mode_barrier =
-                    Arc::clone(&faster_read_sector_record_chunks_mode_barrier);
-                let faster_read_sector_record_chunks_mode_concurrency =
-                    Arc::clone(&faster_read_sector_record_chunks_mode_concurrency);
+            (0..READ_BENCHMARK_PIECES)
+                .map(|_| {
+                    (
+                        rng.gen_range(0..plotted_sectors_count),
+                        PieceOffset::from(rng.gen_range(0..pieces_in_sector)),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
 
-                async move {
-                    let farm_fut = SingleDiskFarm::new::<_, _, PosTable>(
-                        SingleDiskFarmOptions {
-                            directory: disk_farm.directory.clone(),
-                            farmer_app_info,
-                            allocated_space: disk_farm.allocated_plotting_space,
-                            max_pieces_in_sector,
-                            node_client,
-                            reward_address,
-                            kzg,
-                            erasure_coding,
-                            piece_getter,
-                            cache_percentage: CACHE_PERCENTAGE,
-                            downloading_semaphore,
-                            record_encoding_concurrency,
-                            farm_during_initial_plotting,
-                            farming_thread_pool_size: recommended_number_of_farming_threads(),
-                            plotting_thread_pool_manager,
-                            plotting_delay: Some(plotting_delay_receiver),
-                            global_mutex,
-                            disable_farm_locking: false,
-                            faster_read_sector_record_chunks_mode_barrier,
-                            faster_read_sector_record_chunks_mode_concurrency,
-                        },
+        let mut max_read_time = Duration::ZERO;
+        let benchmark_start = Instant::now();
+        for (sector_index, piece_offset) in pieces {
+            let read_start = Instant::now();
+            match piece_reader.read_piece(sector_index, piece_offset).await {
+                Ok(Some(_piece)) => {}
+                Ok(None) => {
+                    return (
                         farm_index,
+                        Err(anyhow!(
+                            "Piece at offset {piece_offset} of sector {sector_index} is missing"
+                        )),
                     );
-
-                    let farm = match farm_fut.await {
-                        Ok(farm) => farm,
-                        Err(SingleDiskFarmError::InsufficientAllocatedSpace {
-                            min_space,
-                            allocated_space,
-                        }) => {
-                            return (
-                                farm_index,
-                                Err(anyhow::anyhow!(
-                                    "Allocated space {} ({}) is not enough, minimum is ~{} (~{}, \
-                                    {} bytes to be exact)",
-                                    bytesize::to_string(allocated_space, true),
-                                    bytesize::to_string(allocated_space, false),
-                                    bytesize::to_string(min_space, true),
-                                    bytesize::to_string(min_space, false),
-                                    min_space
-                                )),
-                            );
-                        }
-                        Err(error) => {
-                            return (farm_index, Err(error.into()));
-                        }
-                    };
-
-                    let _info_guard = info_mutex.lock().await;
-
-                    let info = farm.info();
-                    info!("Farm {farm_index}:");
-                    info!("  ID: {}", info.id());
-                    info!("  Genesis hash: 0x{}", hex::encode(info.genesis_hash()));
-                    info!("  Public key: 0x{}", hex::encode(info.public_key()));
-                    info!(
-                        "  Allocated space: {} ({})",
-                        bytesize::to_string(info.allocated_space(), true),
-                        bytesize::to_string(info.allocated_space(), false)
+                }
+                Err(error) => {
+                    return (
+                        farm_index,
+                        Err(anyhow!(
+                            "Failed to read piece at offset {piece_offset} of sector \
+                            {sector_index}: {error}"
+                        )),
                     );
-                    info!("  Directory: {}", disk_farm.directory.display());
-
-                    (farm_index, Ok(Box::new(farm) as Box<dyn Farm>))
                 }
-                .instrument(info_span!("", %farm_index))
-            })
-            .collect::<FuturesUnordered<_>>();
+            }
+            max_read_time = max_read_time.max(read_start.elapsed());
+        }
+        let benchmark_time = benchmark_start.elapsed();
+
+        let result = ReadBenchmarkResult {
+            pieces_read: READ_BENCHMARK_PIECES,
+            average_read_time: benchmark_time / READ_BENCHMARK_PIECES as u32,
+            max_read_time,
+            throughput: (READ_BENCHMARK_PIECES * Piece::SIZE) as u64 * 1_000
+                / (benchmark_time.as_millis() as u64).max(1),
+        };
 
-        while let Some((farm_index, farm)) = farms_stream.next().await {
-            if let Err(error) = &farm {
-                let span = info_span!("", %farm_index);
-                let _span_guard = span.enter();
+        (farm_index, Ok(result))
+    }
+    .boxed()
+}
 
-                error!(%error, "Single disk creation failed");
+/// Count pieces stored in piece caches, this reads contents of every cache and can take a while for
+/// large caches
+fn farmer_cache_occupancy(
+    piece_caches: Vec<Arc<dyn PieceCache>>,
+) -> BoxFuture<'static, anyhow::Result<FarmerCacheOccupancy>> {
+    async move {
+        let mut occupancy = FarmerCacheOccupancy::default();
+
+        for piece_cache in piece_caches {
+            occupancy.capacity += u64::from(piece_cache.max_num_elements());
+
+            let mut contents = piece_cache
+                .contents()
+                .await
+                .map_err(|error| anyhow!("Failed to read piece cache contents: {error}"))?;
+            while let Some(result) = contents.next().await {
+                let (_offset, maybe_piece_index) = result
+                    .map_err(|error| anyhow!("Failed to read piece cache contents: {error}"))?;
+                if maybe_piece_index.is_some() {
+                    occupancy.stored_pieces += 1;
+                }
             }
-            farms.push((farm_index, farm?));
         }
 
-        // Restore order after unordered initialization
-        farms.sort_unstable_by_key(|(farm_index, _farm)| *farm_index);
+        Ok(occupancy)
+    }
+    .boxed()
+}
 
-        let farms = farms
-            .into_iter()
-            .map(|(_farm_index, farm)| farm)
-            .collect::<Vec<_>>();
+/// Plot a single sector into temporary farm in `directory` using the same pipeline as regular
+/// farms (download, encode, write) and read some of its pieces back. Temporary farm is deleted
+/// afterwards regardless of the result.
+fn test_plotting<FarmFut>(
+    farm_index: u8,
+    directory: PathBuf,
+    farm_fut: FarmFut,
+) -> BoxFuture<'static, (u8, anyhow::Result<PlottingSelfTestResult>)>
+where
+    FarmFut: Future<Output = anyhow::Result<SingleDiskFarm>> + Send + 'static,
+{
+    async move {
+        // Leftovers of previous self-test that was interrupted
+        let _ = tokio::task::spawn_blocking({
+            let directory = directory.clone();
+
+            move || fs::remove_dir_all(directory)
+        })
+        .await;
 
-        (farms, plotting_delay_senders)
-    };
+        let result = async {
+            let plotting_start = Instant::now();
+            let farm = Box::new(farm_fut.await?) as Box<dyn Farm>;
+            let pieces_in_sector = farm.info().pieces_in_sector();
+            let piece_reader = farm.piece_reader();
+
+            let (plotted_sender, plotted_receiver) = oneshot::channel();
+            let plotted_sender = Mutex::new(Some(plotted_sender));
+            let _handler_id =
+                farm.on_sector_update(Arc::new(move |(sector_index, sector_update)| {
+                    if let SectorUpdate::Plotting(SectorPlottingDetails::Finished { .. }) =
+                        sector_update
+                        && let Some(plotted_sender) = plotted_sender.lock().take()
+                    {
+                        let _ = plotted_sender.send(*sector_index);
+                    }
+                }));
+
+            let test_fut = async move {
+                let plotted = tokio::time::timeout(PLOTTING_SELF_TEST_TIMEOUT, plotted_receiver);
+                let Ok(plotted) = plotted.await else {
+                    return Err(anyhow!(
+                        "Sector was not plotted within {} minutes, plotting may be paused, stuck \
+                        on piece retrieval or very slow",
+                        PLOTTING_SELF_TEST_TIMEOUT.as_secs() / 60
+                    ));
+                };
+                let sector_index =
+                    plotted.map_err(|_error| anyhow!("Farm stopped before sector was plotted"))?;
+                let plotting_time = plotting_start.elapsed();
 
-    {
-        let handler_id = Arc::new(Mutex::new(None));
-        // Wait for piece cache to read already cached contents before starting plotting to improve
-        // cache hit ratio
-        handler_id
-            .lock()
-            .replace(farmer_cache.on_sync_progress(Arc::new({
+                let piece_offsets = {
+                    let mut rng = rand::thread_rng();
+
+                    (0..PLOTTING_SELF_TEST_READ_PIECES)
+                        .map(|_| PieceOffset::from(rng.gen_range(0..pieces_in_sector)))
+                        .collect::<Vec<_>>()
+                };
+
+                let read_start = Instant::now();
+                for piece_offset in piece_offsets {
+                    match piece_reader.read_piece(sector_index, piece_offset).await {
+                        Ok(Some(_piece)) => {}
+                        Ok(None) => {
+                            return Err(anyhow!(
+                                "Piece at offset {piece_offset} of plotted sector is missing"
+                            ));
+                        }
+                        Err(error) => {
+                            return Err(anyhow!(
+                                "Failed to read piece at offset {piece_offset} of plotted \
+                                sector: {error}"
+                            ));
+                        }
+                    }
+                }
+
+                Ok(PlottingSelfTestResult {
+                    plotting_time,
+                    average_read_time: read_start.elapsed() / PLOTTING_SELF_TEST_READ_PIECES as u32,
+                })
+            };
+
+            // Farm needs to run for plotting and reading to happen, it stops once test is done
+            select! {
+                result = farm.run().fuse() => {
+                    result?;
+                    Err(anyhow!("Farm exited before self-test finished"))
+                }
+                result = test_fut.fuse() => result,
+            }
+        }
+        .await;
+
+        let wipe_result = tokio::task::spawn_blocking({
+            let directory = directory.clone();
+
+            move || fs::remove_dir_all(directory)
+        })
+        .await;
+        if !matches!(wipe_result, Ok(Ok(()))) {
+            warn!(
+                directory = %directory.display(),
+                "Failed to delete plotting self-test farm, it can be deleted manually"
+            );
+        }
+
+        (farm_index, result)
+    }
+    .boxed()
+}
+
+/// Permits of shared downloading semaphore held on behalf of the farm with own plotting
+/// concurrency, such that sectors downloaded by such farm count towards the shared limit as well.
+///
+/// Farm takes permit of its own semaphore for every sector it downloads, the same number of
+/// permits is acquired from shared semaphore before pieces are retrieved.
+#[derive(Debug, Clone)]
+struct SharedDownloadingPermits {
+    farm_semaphore: Arc<Semaphore>,
+    farm_concurrency: usize,
+    shared_semaphore: Arc<Semaphore>,
+    shared_concurrency: usize,
+    /// Number of held permits and permit itself
+    permit: Arc<AsyncMutex<Option<(usize, OwnedSemaphorePermit)>>>,
+}
+
+impl SharedDownloadingPermits {
+    fn new(
+        farm_semaphore: Arc<Semaphore>,
+        farm_concurrency: usize,
+        shared_semaphore: Arc<Semaphore>,
+        shared_concurrency: usize,
+    ) -> Self {
+        Self {
+            farm_semaphore,
+            farm_concurrency,
+            shared_semaphore,
+            shared_concurrency,
+            permit: Arc::default(),
+        }
+    }
+
+    /// Number of shared permits needed for sectors that farm is downloading right now
+    fn needed_permits(&self) -> usize {
+        self.farm_concurrency
+            .saturating_sub(self.farm_semaphore.available_permits())
+            .min(self.shared_concurrency)
+    }
+
+    /// Wait until enough shared permits are held for sectors that farm is downloading
+    async fn acquire(&self) {
+        let mut permit = self.permit.lock().await;
+        let needed_permits = self.needed_permits();
+        let held_permits = permit.as_ref().map_or(0, |(permits, _)| *permits);
+        if held_permits >= needed_permits {
+            return;
+        }
+
+        // Held permits are released before waiting for all of them at once, otherwise farms
+        // waiting for additional permits while holding some could block each other forever
+        permit.take();
+        let shared_permit = Arc::clone(&self.shared_semaphore)
+            .acquire_many_owned(needed_permits as u32)
+            .await
+            .expect("Shared downloading semaphore is never closed; qed");
+        *permit = Some((needed_permits, shared_permit));
+    }
+
+    /// Release shared permits that are no longer needed after farm finished downloading sectors
+    async fn release_unused(&self) {
+        let mut permit = self.permit.lock().await;
+        let needed_permits = self.needed_permits();
+        let held_permits = permit.as_ref().map_or(0, |(permits, _)| *permits);
+        if held_permits <= needed_permits {
+            return;
+        }
+
+        permit.take();
+        if needed_permits > 0 {
+            // Permits might have been taken by other farms in the meantime, in which case they
+            // will be acquired again on next piece retrieval
+            *permit = Arc::clone(&self.shared_semaphore)
+                .try_acquire_many_owned(needed_permits as u32)
+                .ok()
+                .map(|shared_permit| (needed_permits, shared_permit));
+        }
+    }
+}
+
+/// Piece getter of the farm that holds piece retrieval while plotting of the farm is held back by
+/// per-farm conditions (like disk temperature) or while farm with own plotting concurrency waits
+/// for permits of downloading semaphore that is shared with other farms
+#[derive(Debug, Clone)]
+struct FarmPieceGetter {
+    piece_getter: PieceGetterWrapper,
+    plotting_held: Option<watch::Receiver<bool>>,
+    shared_downloading_permits: Option<SharedDownloadingPermits>,
+}
+
+#[async_trait::async_trait]
+impl PieceGetter for FarmPieceGetter {
+    async fn get_piece(
+        &self,
+        piece_index: PieceIndex,
+    ) -> Result<Option<Piece>, Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(plotting_held) = &self.plotting_held {
+            // Error means monitor exited, in which case nothing holds plotting anymore
+            let _ = plotting_held.clone().wait_for(|held| !held).await;
+        }
+        if let Some(shared_downloading_permits) = &self.shared_downloading_permits {
+            shared_downloading_permits.acquire().await;
+        }
+
+        self.piece_getter.get_piece(piece_index).await
+    }
+}
+
+/// Periodically checks temperature of the disk where farm is located and holds piece retrieval of
+/// the farm's [`FarmPieceGetter`] while disk is too hot, which pauses plotting of this farm only.
+/// Sectors that are already downloaded are encoded and written, sector that is being downloaded
+/// waits for disk to cool down.
+async fn monitor_disk_temperature(
+    farm_index: u8,
+    directory: PathBuf,
+    max_disk_temperature: NonZeroU8,
+    plotting_held_sender: watch::Sender<bool>,
+    notifications: Arc<Notifications>,
+) {
+    let max_disk_temperature = f32::from(max_disk_temperature.get());
+    let mut temperature_available = false;
+    let mut check_interval = tokio::time::interval(DISK_TEMPERATURE_CHECK_INTERVAL);
+    check_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        check_interval.tick().await;
+
+        let temperature = {
+            let directory = directory.clone();
+
+            match tokio::task::spawn_blocking(move || disk_temperature(&directory)).await {
+                Ok(Some(temperature)) => {
+                    temperature_available = true;
+                    temperature
+                }
+                Ok(None) => {
+                    if !temperature_available {
+                        warn!(
+                            %farm_index,
+                            "Disk temperature is not available, plotting will not be paused when \
+                            disk is too hot"
+                        );
+                        return;
+                    }
+                    // Temporary failure, keep current state
+                    continue;
+                }
+                Err(error) => {
+                    error!(%farm_index, %error, "Failed to read disk temperature");
+                    continue;
+                }
+            }
+        };
+
+        let plotting_held = *plotting_held_sender.borrow();
+        if !plotting_held && temperature > max_disk_temperature {
+            warn!(
+                %farm_index,
+                %temperature,
+                %max_disk_temperature,
+                "Disk is too hot, pausing plotting until it cools down"
+            );
+            plotting_held_sender.send_replace(true);
+            notifications.call_simple(&FarmerNotification::DiskTemperaturePause {
+                farm_index,
+                temperature,
+                paused: true,
+            });
+        } else if plotting_held && temperature <= max_disk_temperature - DISK_TEMPERATURE_HYSTERESIS
+        {
+            info!(%farm_index, %temperature, "Disk cooled down, resuming plotting");
+            plotting_held_sender.send_replace(false);
+            notifications.call_simple(&FarmerNotification::DiskTemperaturePause {
+                farm_index,
+                temperature,
+                paused: false,
+            });
+        }
+    }
+}
+
+/// Allocated space of the farm reduced such that no more than `max_plotted_sectors` are plotted,
+/// returns `None` if allocated space doesn't need to be capped.
+///
+/// Only growth beyond space already allocated by existing farm is capped, since farm would be
+/// shrunk and already plotted sectors lost otherwise.
+async fn capped_allocated_space(disk_farm: &DiskFarm, max_pieces_in_sector: u16) -> Option<u64> {
+    let max_plotted_sectors = disk_farm.max_plotted_sectors?;
+
+    // Each sector occupies space in the plot and its metadata, piece cache takes
+    // `CACHE_PERCENTAGE` of allocated space that remains after fixed overhead of the farm
+    let single_sector_size =
+        (sector_size(max_pieces_in_sector) + SectorMetadataChecksummed::encoded_size()) as u64;
+    let capped_allocated_space = FARM_FIXED_SPACE_OVERHEAD
+        + (u64::from(max_plotted_sectors.get()) * single_sector_size * 100)
+            .div_ceil(100 - u64::from(CACHE_PERCENTAGE.get()))
+        // Compensate rounding down when farm calculates the number of sectors
+        + 100;
+    if capped_allocated_space >= disk_farm.allocated_plotting_space {
+        return None;
+    }
+
+    let directory = disk_farm.directory.clone();
+    let existing_allocated_space = match tokio::task::spawn_blocking(move || {
+        SingleDiskFarmInfo::load_from(&directory)
+    })
+    .await
+    {
+        Ok(Ok(maybe_farm_info)) => maybe_farm_info
+            .map(|farm_info| farm_info.allocated_space())
+            .unwrap_or_default(),
+        Ok(Err(error)) => {
+            warn!(
+                %error,
+                directory = %disk_farm.directory.display(),
+                "Failed to read farm info, ignoring it"
+            );
+            0
+        }
+        Err(error) => {
+            warn!(
+                %error,
+                directory = %disk_farm.directory.display(),
+                "Failed to spawn task to read farm info, ignoring it"
+            );
+            0
+        }
+    };
+
+    (existing_allocated_space < capped_allocated_space).then_some(capped_allocated_space)
+}
+
+/// Backing caches for farmer cache without caches of wiped farms, dedicated piece cache is always
+/// used
+fn backing_caches(
+    piece_caches: &[Arc<dyn PieceCache>],
+    plot_caches: &[Arc<dyn PlotCache>],
+    dedicated_piece_cache: bool,
+    wiped_farms: &HashSet<u8>,
+) -> (Vec<Arc<dyn PieceCache>>, Vec<Arc<dyn PlotCache>>) {
+    let is_wiped = |farm_index: usize| {
+        u8::try_from(farm_index).is_ok_and(|farm_index| wiped_farms.contains(&farm_index))
+    };
+
+    let piece_caches = if dedicated_piece_cache {
+        piece_caches.to_vec()
+    } else {
+        piece_caches
+            .iter()
+            .enumerate()
+            .filter(|(farm_index, _piece_cache)| !is_wiped(*farm_index))
+            .map(|(_farm_index, piece_cache)| Arc::clone(piece_cache))
+            .collect()
+    };
+    let plot_caches = plot_caches
+        .iter()
+        .enumerate()
+        .filter(|(farm_index, _plot_cache)| !is_wiped(*farm_index))
+        .map(|(_farm_index, plot_cache)| Arc::clone(plot_cache))
+        .collect();
+
+    (piece_caches, plot_caches)
+}
+
+fn should_farm_during_initial_plotting() -> bool {
+    let total_cpu_cores = all_cpu_cores()
+        .iter()
+        .flat_map(|set| set.cpu_cores())
+        .count();
+    total_cpu_cores > 8
+}
+
+#[derive(Debug, Clone)]
+pub struct DiskFarm {
+    pub directory: PathBuf,
+    pub allocated_plotting_space: u64,
+    /// Number of sectors that can be plotted concurrently, shared limit is used if not specified
+    pub plotting_concurrency: Option<NonZeroUsize>,
+    /// Disk temperature (in °C) above which plotting is paused until disk cools down
+    pub max_disk_temperature: Option<NonZeroU8>,
+    /// Maximum number of sectors plotted even if allocated space allows more
+    pub max_plotted_sectors: Option<NonZeroU16>,
+    /// Farm is located on network filesystem (NFS, SMB, etc.), locking is disabled and tunings
+    /// that tolerate higher latency are applied
+    pub network_filesystem: bool,
+    /// NUMA node to pin plotting threads of the farm to, CPU cores are grouped by L3 cache and
+    /// shared with other farms if not specified
+    pub numa_node: Option<u16>,
+}
+
+impl DiskFarm {
+    /// Own plotting concurrency of the farm, taking network filesystem mode into account, shared
+    /// limit is used if `None`
+    pub fn plotting_concurrency(&self) -> Option<NonZeroUsize> {
+        self.plotting_concurrency.or(self
+            .network_filesystem
+            .then_some(NETWORK_FILESYSTEM_PLOTTING_CONCURRENCY))
+    }
+}
+
+/// Dedicated piece cache that is used instead of piece caches of farms
+#[derive(Debug, Clone)]
+pub struct DedicatedPieceCache {
+    pub directory: PathBuf,
+    pub allocated_space: u64,
+}
+
+/// Extra farmer arguments for options that are not exposed in UI, names match those of Subspace
+/// farmer CLI
+#[derive(Debug, Clone, Parser)]
+#[command(disable_help_flag = true, disable_version_flag = true)]
+pub struct FarmerExtraArgs {
+    /// Whether to farm while initial plotting is in progress, determined automatically by default
+    #[arg(long)]
+    farm_during_initial_plotting: Option<bool>,
+    /// Size of thread pool used for farming in each farm
+    #[arg(long)]
+    farming_thread_pool_size: Option<NonZeroUsize>,
+    /// Number of sectors downloaded concurrently across farms without own plotting concurrency
+    #[arg(long)]
+    sector_downloading_concurrency: Option<NonZeroUsize>,
+    /// Number of records encoded concurrently in each sector
+    #[arg(long)]
+    record_encoding_concurrency: Option<NonZeroUsize>,
+}
+
+/// CPU and concurrency allocation derived from configuration and hardware, shared between farmer
+/// creation and `--explain-resources`
+#[derive(Debug)]
+pub struct FarmerResources {
+    /// Whether plot cache is used
+    pub plot_cache: bool,
+    pub farm_during_initial_plotting: bool,
+    /// CPU cores of each plotting thread pool
+    pub plotting_thread_pool_core_indices: Vec<CpuCoreSet>,
+    /// CPU cores of each replotting thread pool
+    pub replotting_thread_pool_core_indices: Vec<CpuCoreSet>,
+    /// Number of sectors downloaded concurrently across all farms, farms with own plotting
+    /// concurrency are limited by both
+    pub sector_downloading_concurrency: usize,
+    /// Number of records encoded concurrently in each sector
+    pub record_encoding_concurrency: NonZeroUsize,
+    pub farming_thread_pool_size: usize,
+}
+
+impl FarmerResources {
+    /// Derive resources the same way farmer does on startup, without creating farms
+    pub fn derive(
+        disk_farms: &[DiskFarm],
+        extra_args: &FarmerExtraArgs,
+        disable_plot_cache: bool,
+    ) -> Self {
+        let plot_cache = !cfg!(windows)
+            || disk_farms
+                .iter()
+                .map(|farm| farm.allocated_plotting_space)
+                .sum::<u64>()
+                <= MAX_SPACE_PLEDGED_FOR_PLOT_CACHE_ON_WINDOWS;
+        if disable_plot_cache {
+            info!("Plot cache is disabled in configuration");
+        } else if !plot_cache {
+            info!(
+                limit = %bytesize::to_string(MAX_SPACE_PLEDGED_FOR_PLOT_CACHE_ON_WINDOWS, true),
+                "Plot cache is disabled because total pledged space exceeds the limit on Windows, \
+                otherwise it would cause very long periods of writing zeroes to disk"
+            );
+        }
+        let plot_cache = plot_cache && !disable_plot_cache;
+
+        let farm_during_initial_plotting = extra_args
+            .farm_during_initial_plotting
+            .unwrap_or_else(should_farm_during_initial_plotting);
+        let mut plotting_thread_pool_core_indices = thread_pool_core_indices(None, None);
+        let mut replotting_thread_pool_core_indices = {
+            let mut replotting_thread_pool_core_indices = thread_pool_core_indices(None, None);
+            // The default behavior is to use all CPU cores, but for replotting we just want half
+            replotting_thread_pool_core_indices
+                .iter_mut()
+                .for_each(|set| set.truncate(set.cpu_cores().len() / 2));
+            replotting_thread_pool_core_indices
+        };
+
+        if plotting_thread_pool_core_indices.len() > 1 {
+            info!(
+                l3_cache_groups = %plotting_thread_pool_core_indices.len(),
+                "Multiple L3 cache groups detected"
+            );
+
+            if plotting_thread_pool_core_indices.len() > disk_farms.len() {
+                plotting_thread_pool_core_indices =
+                    CpuCoreSet::regroup(&plotting_thread_pool_core_indices, disk_farms.len());
+                replotting_thread_pool_core_indices =
+                    CpuCoreSet::regroup(&replotting_thread_pool_core_indices, disk_farms.len());
+
+                info!(
+                    farms_count = %disk_farms.len(),
+                    "Regrouped CPU cores to match number of farms, more farms may leverage CPU \
+                    more efficiently"
+                );
+            }
+        }
+
+        let sector_downloading_concurrency = extra_args
+            .sector_downloading_concurrency
+            .map(NonZeroUsize::get)
+            .unwrap_or(plotting_thread_pool_core_indices.len() + 1);
+
+        let record_encoding_concurrency =
+            extra_args.record_encoding_concurrency.unwrap_or_else(|| {
+                let cpu_cores = plotting_thread_pool_core_indices
+                    .first()
+                    .expect("Guaranteed to have some CPU cores; qed");
+
+                NonZeroUsize::new((cpu_cores.cpu_cores().len() / 2).min(8))
+                    .expect("Guaranteed to have some CPU cores; qed")
+            });
+        let farming_thread_pool_size = extra_args
+            .farming_thread_pool_size
+            .map(NonZeroUsize::get)
+            .unwrap_or_else(recommended_number_of_farming_threads);
+
+        Self {
+            plot_cache,
+            farm_during_initial_plotting,
+            plotting_thread_pool_core_indices,
+            replotting_thread_pool_core_indices,
+            sector_downloading_concurrency,
+            record_encoding_concurrency,
+            farming_thread_pool_size,
+        }
+    }
+
+    /// Upper bound of number of sectors kept in memory at the same time during plotting: sectors
+    /// being downloaded plus sectors being encoded
+    pub fn max_sectors_in_memory(&self, disk_farms: &[DiskFarm]) -> usize {
+        // Farms with own plotting concurrency are nested under shared downloading limit
+        let downloading_concurrency = disk_farms
+            .iter()
+            .map(|disk_farm| {
+                disk_farm
+                    .plotting_concurrency()
+                    .map_or(self.sector_downloading_concurrency, NonZeroUsize::get)
+            })
+            .sum::<usize>()
+            .min(self.sector_downloading_concurrency);
+
+        downloading_concurrency + self.plotting_thread_pool_core_indices.len()
+    }
+}
+
+/// Scan of already plotted sectors was cancelled by user
+#[derive(Debug, thiserror::Error)]
+#[error("Scan of already plotted sectors was cancelled")]
+pub(super) struct ScanCancelledError;
+
+/// Farm was created for a different chain than the one supported by this version of the app
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Farm at {} belongs to chain with genesis hash 0x{farm_genesis_hash}, which is not supported",
+    farm_directory.display()
+)]
+pub(super) struct UnsupportedFarmChainError {
+    pub(super) farm_directory: PathBuf,
+    /// Hex-encoded genesis hash of the chain farm was created for
+    pub(super) farm_genesis_hash: String,
+}
+
+/// Arguments for farmer
+pub(super) struct FarmerOptions {
+    pub(super) reward_address: PublicKey,
+    pub(super) disk_farms: Vec<DiskFarm>,
+    /// Periodically try to re-initialize farm after it errored
+    pub(super) auto_recover_farms: bool,
+    /// Maximum number of sectors plotted per minute across all farms, unlimited if not specified
+    pub(super) plotting_rate_limit: Option<NonZeroU32>,
+    /// Don't use plot cache regardless of platform
+    pub(super) disable_plot_cache: bool,
+    /// Allocate disk space for the whole farm before plotting starts
+    pub(super) preallocate_farms: bool,
+    /// Skip writing zeroes during pre-allocation on Windows, see [`preallocate_file`]
+    pub(super) preallocate_farms_without_zeroing: bool,
+    /// Don't start plotting until at least this many DSN peers are connected or
+    /// [`PLOTTING_PEERS_TIMEOUT`] elapses
+    pub(super) min_plotting_peers: Option<NonZeroU32>,
+    /// Dedicated piece cache, piece caches of farms are used if not specified
+    pub(super) piece_cache: Option<DedicatedPieceCache>,
+    pub(super) extra_args: FarmerExtraArgs,
+    pub(super) node_client: MaybeNodeRpcClient,
+    pub(super) dsn_node: Node,
+    pub(super) piece_getter: PieceGetterWrapper,
+    pub(super) plotted_pieces: Arc<Mutex<Option<PlottedPieces>>>,
+    pub(super) farmer_cache: FarmerCache,
+    pub(super) farmer_cache_worker: FarmerCacheWorker<MaybeNodeRpcClient>,
+    pub(super) kzg: Kzg,
+    /// Called as already plotted sectors are being scanned on startup
+    pub(super) on_plotted_sectors_scan_progress: HandlerFn<PlottedSectorsScanProgress>,
+    /// Once set, startup scan of already plotted sectors is aborted and farmer creation fails with
+    /// [`ScanCancelledError`]
+    pub(super) startup_cancelled: Arc<AtomicBool>,
+    /// Called as disk space for farms is being allocated on startup (if enabled)
+    pub(super) on_farm_preallocation_progress: HandlerFn<FarmPreallocationProgress>,
+    /// Write indices of plotted sectors of each farm as JSON to this file after startup scan
+    pub(super) export_plot_map: Option<PathBuf>,
+}
+
+fn open_dedicated_piece_cache(
+    DedicatedPieceCache {
+        directory,
+        allocated_space,
+    }: DedicatedPieceCache,
+) -> anyhow::Result<Arc<dyn PieceCache>> {
+    if !directory.exists() {
+        if let Err(error) = fs::create_dir(&directory) {
+            return Err(anyhow!(
+                "Piece cache directory {} doesn't exist and can't be created: {}",
+                directory.display(),
+                error
+            ));
+        }
+    }
+
+    let capacity = allocated_space / u64::from(DiskPieceCache::element_size());
+    let piece_cache = DiskPieceCache::open(&directory, capacity as usize).map_err(|error| {
+        anyhow!(
+            "Failed to open piece cache in {}: {}",
+            directory.display(),
+            error
+        )
+    })?;
+
+    info!(
+        directory = %directory.display(),
+        allocated_space = %bytesize::to_string(allocated_space, true),
+        %capacity,
+        "Using dedicated piece cache"
+    );
+
+    Ok(Arc::new(piece_cache))
+}
+
+/// Warn if significant part of allocated space can't be used because it doesn't fit a whole sector
+fn warn_about_unused_space(
+    allocated_space: u64,
+    total_sectors_count: SectorIndex,
+    sector_size: u64,
+) {
+    // Approximation, farm also stores some metadata, but it is small compared to sector size
+    let plotting_space_percentage = 100 - u64::from(CACHE_PERCENTAGE.get());
+    let plotting_space = allocated_space / 100 * plotting_space_percentage;
+    let unused_space =
+        plotting_space.saturating_sub(u64::from(total_sectors_count) * sector_size) % sector_size;
+
+    if unused_space < sector_size / SIGNIFICANT_UNUSED_SPACE_SECTOR_FRACTION {
+        return;
+    }
+
+    let smaller_allocated_space = allocated_space - unused_space * 100 / plotting_space_percentage;
+    let larger_allocated_space =
+        allocated_space + (sector_size - unused_space) * 100 / plotting_space_percentage;
+    warn!(
+        "  Allocated space is not aligned to sector size ({}), ~{} will not be used, consider \
+        changing allocated space to ~{} to not waste space or to ~{} to fit one more sector",
+        bytesize::to_string(sector_size, true),
+        bytesize::to_string(unused_space, true),
+        bytesize::to_string(smaller_allocated_space, true),
+        bytesize::to_string(larger_allocated_space, true),
+    );
+}
+
+/// Wait until at least `min_peers` DSN peers are connected or [`PLOTTING_PEERS_TIMEOUT`] elapses,
+/// progress is reported with [`FarmerNotification::WaitingForPeers`]
+async fn wait_for_plotting_peers(
+    dsn_node: &Node,
+    min_peers: NonZeroU32,
+    notifications: &Notifications,
+) {
+    let required = min_peers.get();
+    let start = Instant::now();
+    let mut last_connected = None;
+
+    info!(%required, "Waiting for DSN peers before starting plotting");
+
+    let connected = loop {
+        let connected = match dsn_node.connected_peers().await {
+            Ok(connected_peers) => u32::try_from(connected_peers.len()).unwrap_or(u32::MAX),
+            Err(error) => {
+                warn!(%error, "Failed to get connected DSN peers, starting plotting");
+                break last_connected.unwrap_or_default();
+            }
+        };
+
+        if last_connected != Some(connected) {
+            last_connected.replace(connected);
+            notifications.call_simple(&FarmerNotification::WaitingForPeers {
+                connected,
+                required,
+                waiting: true,
+            });
+        }
+
+        if connected >= required {
+            info!(%connected, "Enough DSN peers connected, starting plotting");
+            break connected;
+        }
+
+        if start.elapsed() >= PLOTTING_PEERS_TIMEOUT {
+            warn!(
+                %connected,
+                %required,
+                "Timed out waiting for DSN peers, starting plotting anyway"
+            );
+            break connected;
+        }
+
+        tokio::time::sleep(PLOTTING_PEERS_CHECK_INTERVAL).await;
+    };
+
+    notifications.call_simple(&FarmerNotification::WaitingForPeers {
+        connected,
+        required,
+        waiting: false,
+    });
+}
+
+pub(super) async fn create_farmer(farmer_options: FarmerOptions) -> anyhow::Result<Farmer> {
+    let span = info_span!("Farmer");
+    let _enter = span.enter();
+
+    let FarmerOptions {
+        reward_address,
+        disk_farms,
+        auto_recover_farms,
+        plotting_rate_limit,
+        disable_plot_cache,
+        preallocate_farms,
+        preallocate_farms_without_zeroing,
+        min_plotting_peers,
+        piece_cache,
+        extra_args,
+        node_client,
+        dsn_node,
+        piece_getter,
+        plotted_pieces,
+        farmer_cache,
+        farmer_cache_worker,
+        kzg,
+        on_plotted_sectors_scan_progress,
+        startup_cancelled,
+        on_farm_preallocation_progress,
+        export_plot_map,
+    } = farmer_options;
+
+    if disk_farms.is_empty() {
+        return Err(anyhow!("There must be at least one disk farm provided"));
+    }
+
+    for farm in &disk_farms {
+        if !farm.directory.exists() {
+            if let Err(error) = fs::create_dir(&farm.directory) {
+                return Err(anyhow!(
+                    "Directory {} doesn't exist and can't be created: {}",
+                    farm.directory.display(),
+                    error
+                ));
+            }
+        }
+    }
+
+    let dedicated_piece_cache = match piece_cache {
+        Some(piece_cache) => Some(open_dedicated_piece_cache(piece_cache)?),
+        None => None,
+    };
+
+    let FarmerResources {
+        plot_cache,
+        farm_during_initial_plotting,
+        plotting_thread_pool_core_indices,
+        replotting_thread_pool_core_indices,
+        sector_downloading_concurrency,
+        record_encoding_concurrency,
+        farming_thread_pool_size,
+    } = FarmerResources::derive(&disk_farms, &extra_args, disable_plot_cache);
+
+    let farmer_app_info = node_client
+        .farmer_app_info()
+        .await
+        .map_err(|error| anyhow::anyhow!(error))?;
+
+    let erasure_coding = ErasureCoding::new(
+        NonZeroUsize::new(Record::NUM_S_BUCKETS.next_power_of_two().ilog2() as usize)
+            .expect("Not zero; qed"),
+    )
+    .map_err(|error| anyhow::anyhow!(error))?;
+
+    let farmer_cache_worker_fut = Box::pin(
+        farmer_cache_worker
+            .run(piece_getter.downgrade())
+            .in_current_span(),
+    );
+
+    let plotting_thread_pools_count = plotting_thread_pool_core_indices.len();
+
+    let downloading_semaphore = Arc::new(Semaphore::new(sector_downloading_concurrency));
+    // Farms with own plotting concurrency get their own semaphore, but still hold permits of the
+    // shared one for sectors they download
+    let (farm_downloading_semaphores, farm_shared_downloading_permits) = disk_farms
+        .iter()
+        .map(|disk_farm| match disk_farm.plotting_concurrency() {
+            Some(plotting_concurrency) => {
+                let farm_semaphore = Arc::new(Semaphore::new(plotting_concurrency.get()));
+                let shared_downloading_permits = SharedDownloadingPermits::new(
+                    Arc::clone(&farm_semaphore),
+                    plotting_concurrency.get(),
+                    Arc::clone(&downloading_semaphore),
+                    sector_downloading_concurrency,
+                );
+                (farm_semaphore, Some(shared_downloading_permits))
+            }
+            None => (Arc::clone(&downloading_semaphore), None),
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    // Farms with disk temperature monitoring can have their plotting held while their disk is too
+    // hot
+    let (plotting_held_senders, farm_plotting_held) = disk_farms
+        .iter()
+        .map(|disk_farm| {
+            disk_farm
+                .max_disk_temperature
+                .map(|_max_disk_temperature| {
+                    let (plotting_held_sender, plotting_held_receiver) = watch::channel(false);
+                    (Some(plotting_held_sender), Some(plotting_held_receiver))
+                })
+                .unwrap_or_default()
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    let sector_size = sector_size(farmer_app_info.protocol_info.max_pieces_in_sector) as u64;
+
+    let plotting_thread_pool_manager = create_plotting_thread_pool_manager(
+        plotting_thread_pool_core_indices
+            .into_iter()
+            .zip(replotting_thread_pool_core_indices),
+        Some(ThreadPriority::Min),
+    )?;
+    // Farms pinned to the same NUMA node share thread pools that only use CPU cores of that node
+    let mut numa_plotting_thread_pool_managers = HashMap::new();
+    for numa_node in disk_farms
+        .iter()
+        .filter_map(|disk_farm| disk_farm.numa_node)
+    {
+        if numa_plotting_thread_pool_managers.contains_key(&numa_node) {
+            continue;
+        }
+
+        match numa_node_cpu_cores(numa_node) {
+            Ok(cpu_cores) => {
+                info!(
+                    %numa_node,
+                    cpu_cores = %cpu_cores.cpu_cores().len(),
+                    "Plotting of some farms is pinned to NUMA node"
+                );
+                let mut replotting_cpu_cores = cpu_cores.clone();
+                replotting_cpu_cores.truncate((cpu_cores.cpu_cores().len() / 2).max(1));
+                numa_plotting_thread_pool_managers.insert(
+                    numa_node,
+                    create_plotting_thread_pool_manager(
+                        iter::once((cpu_cores, replotting_cpu_cores)),
+                        Some(ThreadPriority::Min),
+                    )?,
+                );
+            }
+            Err(error) => {
+                warn!(
+                    %numa_node,
+                    %error,
+                    "Failed to get CPU cores of NUMA node, farms pinned to it will use default \
+                    thread pools"
+                );
+            }
+        }
+    }
+
+    let create_farm = {
+        let plotting_thread_pool_manager = plotting_thread_pool_manager.clone();
+        let numa_plotting_thread_pool_managers = numa_plotting_thread_pool_managers.clone();
+        let global_mutex = Arc::default();
+        let faster_read_sector_record_chunks_mode_concurrency = Arc::new(Semaphore::new(1));
+        let shared_downloading_semaphore = Arc::clone(&downloading_semaphore);
+        let farm_shared_downloading_permits = farm_shared_downloading_permits.clone();
+
+        move |farm_index: usize,
+              disk_farm: DiskFarm,
+              plotting_delay: Option<oneshot::Receiver<()>>,
+              faster_read_sector_record_chunks_mode_barrier: Arc<Barrier>| {
+            let node_client = node_client.clone();
+            let farmer_app_info = farmer_app_info.clone();
+            let max_pieces_in_sector = farmer_app_info.protocol_info.max_pieces_in_sector;
+            let kzg = kzg.clone();
+            let erasure_coding = erasure_coding.clone();
+            let piece_getter = FarmPieceGetter {
+                piece_getter: piece_getter.clone(),
+                // Plotting self-test farm isn't monitored
+                plotting_held: farm_plotting_held.get(farm_index).cloned().flatten(),
+                shared_downloading_permits: farm_shared_downloading_permits
+                    .get(farm_index)
+                    .cloned()
+                    .flatten(),
+            };
+            // Plotting self-test farm doesn't have its own semaphore
+            let downloading_semaphore = farm_downloading_semaphores
+                .get(farm_index)
+                .map(Arc::clone)
+                .unwrap_or_else(|| Arc::clone(&shared_downloading_semaphore));
+            let plotting_thread_pool_manager = disk_farm
+                .numa_node
+                .and_then(|numa_node| numa_plotting_thread_pool_managers.get(&numa_node))
+                .unwrap_or(&plotting_thread_pool_manager)
+                .clone();
+            let global_mutex = Arc::clone(&global_mutex);
+            let faster_read_sector_record_chunks_mode_concurrency =
+                Arc::clone(&faster_read_sector_record_chunks_mode_concurrency);
+
+            async move {
+                let allocated_space =
+                    match capped_allocated_space(&disk_farm, max_pieces_in_sector).await {
+                        Some(allocated_space) => {
+                            info!(
+                                %farm_index,
+                                max_plotted_sectors = ?disk_farm.max_plotted_sectors,
+                                allocated_space = %bytesize::to_string(allocated_space, true),
+                                "Number of plotted sectors is capped, not all allocated space is \
+                                used yet"
+                            );
+                            allocated_space
+                        }
+                        None => disk_farm.allocated_plotting_space,
+                    };
+                if disk_farm.network_filesystem {
+                    warn!(
+                        %farm_index,
+                        directory = %disk_farm.directory.display(),
+                        "Farm is in network filesystem mode, locking is disabled and farm must \
+                        not be used by more than one farmer at a time, farming on network \
+                        filesystems is less reliable and may miss rewards due to latency"
+                    );
+                }
+
+                let farm_fut = SingleDiskFarm::new::<_, _, PosTable>(
+                    SingleDiskFarmOptions {
+                        directory: disk_farm.directory.clone(),
+                        farmer_app_info,
+                        allocated_space,
+                        max_pieces_in_sector,
+                        node_client,
+                        reward_address,
+                        kzg,
+                        erasure_coding,
+                        piece_getter,
+                        cache_percentage: CACHE_PERCENTAGE,
+                        downloading_semaphore,
+                        record_encoding_concurrency,
+                        farm_during_initial_plotting,
+                        farming_thread_pool_size,
+                        plotting_thread_pool_manager,
+                        plotting_delay,
+                        global_mutex,
+                        disable_farm_locking: disk_farm.network_filesystem,
+                        faster_read_sector_record_chunks_mode_barrier,
+                        faster_read_sector_record_chunks_mode_concurrency,
+                    },
+                    farm_index,
+                );
+
+                match farm_fut.await {
+                    Ok(farm) => Ok(farm),
+                    Err(SingleDiskFarmError::InsufficientAllocatedSpace {
+                        min_space,
+                        allocated_space,
+                    }) => Err(anyhow::anyhow!(
+                        "Allocated space {} ({}) is not enough, minimum is ~{} (~{}, {} bytes to \
+                        be exact)",
+                        bytesize::to_string(allocated_space, true),
+                        bytesize::to_string(allocated_space, false),
+                        bytesize::to_string(min_space, true),
+                        bytesize::to_string(min_space, false),
+                        min_space
+                    )),
+                    Err(SingleDiskFarmError::WrongChain { wrong_chain, .. }) => {
+                        Err(anyhow::Error::new(UnsupportedFarmChainError {
+                            farm_directory: disk_farm.directory.clone(),
+                            farm_genesis_hash: wrong_chain,
+                        }))
+                    }
+                    Err(error) => Err(error.into()),
+                }
+            }
+        }
+    };
+
+    let (farms, plotting_delay_senders) = {
+        let info_mutex = &AsyncMutex::new(());
+        let faster_read_sector_record_chunks_mode_barrier =
+            Arc::new(Barrier::new(disk_farms.len()));
+        let (plotting_delay_senders, plotting_delay_receivers) = (0..disk_farms.len())
+            .map(|_| oneshot::channel())
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        let mut farms = Vec::with_capacity(disk_farms.len());
+        let mut farms_stream = disk_farms
+            .iter()
+            .cloned()
+            .zip(plotting_delay_receivers)
+            .enumerate()
+            .map(|(farm_index, (disk_farm, plotting_delay_receiver))| {
+                let farm_fut = create_farm(
+                    farm_index,
+                    disk_farm.clone(),
+                    Some(plotting_delay_receiver),
+                    Arc::clone(&faster_read_sector_record_chunks_mode_barrier),
+                );
+
+                async move {
+                    let farm = match farm_fut.await {
+                        Ok(farm) => farm,
+                        Err(error) => {
+                            return (farm_index, Err(error));
+                        }
+                    };
+
+                    let _info_guard = info_mutex.lock().await;
+
+                    let info = farm.info();
+                    info!("Farm {farm_index}:");
+                    info!("  ID: {}", info.id());
+                    info!("  Genesis hash: 0x{}", hex::encode(info.genesis_hash()));
+                    info!("  Public key: 0x{}", hex::encode(info.public_key()));
+                    info!(
+                        "  Allocated space: {} ({})",
+                        bytesize::to_string(info.allocated_space(), true),
+                        bytesize::to_string(info.allocated_space(), false)
+                    );
+                    info!("  Directory: {}", disk_farm.directory.display());
+                    // Allocated space is intentionally not aligned when number of sectors is capped
+                    if disk_farm.max_plotted_sectors.is_none() {
+                        warn_about_unused_space(
+                            info.allocated_space(),
+                            farm.total_sectors_count(),
+                            sector_size,
+                        );
+                    }
+
+                    (farm_index, Ok(Box::new(farm) as Box<dyn Farm>))
+                }
+                // Recorded as a number such that `--debug-farm` log filter directive matches it
+                .instrument(info_span!("", farm_index))
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        while let Some((farm_index, farm)) = farms_stream.next().await {
+            if let Err(error) = &farm {
+                let span = info_span!("", farm_index);
+                let _span_guard = span.enter();
+
+                error!(%error, "Single disk creation failed");
+            }
+            farms.push((farm_index, farm?));
+        }
+
+        // Restore order after unordered initialization
+        farms.sort_unstable_by_key(|(farm_index, _farm)| *farm_index);
+
+        // Copying farm directory results in farms with identical IDs and identities, which leads to
+        // undefined behavior, refuse to start instead
+        let mut farm_ids = HashMap::with_capacity(farms.len());
+        for (farm_index, farm) in &farms {
+            if let Some(existing_farm_index) = farm_ids.insert(*farm.info().id(), *farm_index) {
+                return Err(anyhow!(
+                    "Farms at {} and {} have the same ID {}, likely because one of them was \
+                    copied from the other, remove one of them and try again",
+                    disk_farms[existing_farm_index].directory.display(),
+                    disk_farms[*farm_index].directory.display(),
+                    farm.info().id(),
+                ));
+            }
+        }
+
+        let farms = farms
+            .into_iter()
+            .map(|(_farm_index, farm)| farm)
+            .collect::<Vec<_>>();
+
+        (farms, plotting_delay_senders)
+    };
+
+    if preallocate_farms {
+        for ((farm_index, farm), disk_farm) in (0_u8..).zip(&farms).zip(&disk_farms) {
+            let plot_file = disk_farm.directory.join(PLOT_FILE_NAME);
+            let total = u64::from(farm.total_sectors_count()) * sector_size;
+
+            info!(
+                %farm_index,
+                size = %bytesize::to_string(total, true),
+                "Pre-allocating farm (this may take some time)..."
+            );
+
+            let on_progress = Arc::clone(&on_farm_preallocation_progress);
+            let result = tokio::task::spawn_blocking(move || {
+                preallocate_file(
+                    &plot_file,
+                    total,
+                    preallocate_farms_without_zeroing,
+                    &mut |preallocated| {
+                        on_progress(&FarmPreallocationProgress {
+                            farm_index,
+                            preallocated,
+                            total,
+                        });
+                    },
+                )
+            })
+            .await?;
+
+            // Farm works fine without it, so this is not fatal
+            if let Err(error) = result {
+                warn!(%farm_index, %error, "Failed to pre-allocate farm, continuing without it");
+            }
+        }
+    }
+
+    let (farmer_cache_sync_started_sender, farmer_cache_sync_started_receiver) =
+        oneshot::channel::<()>();
+    {
+        let handler_id = Arc::new(Mutex::new(None));
+        // Wait for piece cache to read already cached contents before starting plotting to improve
+        // cache hit ratio
+        handler_id
+            .lock()
+            .replace(farmer_cache.on_sync_progress(Arc::new({
                 let handler_id = Arc::clone(&handler_id);
-                let plotting_delay_senders = Mutex::new(plotting_delay_senders);
+                let farmer_cache_sync_started_sender =
+                    Mutex::new(Some(farmer_cache_sync_started_sender));
 
                 move |_progress| {
-                    for plotting_delay_sender in plotting_delay_senders.lock().drain(..) {
+                    if let Some(farmer_cache_sync_started_sender) =
+                        farmer_cache_sync_started_sender.lock().take()
+                    {
                         // Doesn't matter if receiver is gone
-                        let _ = plotting_delay_sender.send(());
+                        let _ = farmer_cache_sync_started_sender.send(());
                     }
 
                     // Unsubscribe from this event
@@ -438,56 +2012,106 @@ pub(super) async fn create_farmer(farmer_options: FarmerOptions) -> anyhow::Resu
                 }
             })));
     }
+    // Stored such that backing caches can be updated in case farm is recovered after error
+    let mut piece_caches = match &dedicated_piece_cache {
+        Some(dedicated_piece_cache) => vec![Arc::clone(dedicated_piece_cache)],
+        None => farms
+            .iter()
+            .map(|farm| farm.piece_cache())
+            .collect::<Vec<_>>(),
+    };
+    let mut plot_caches = if plot_cache {
+        farms.iter().map(|farm| farm.plot_cache()).collect()
+    } else {
+        Vec::new()
+    };
+    // Farmer cache keeps pieces that are already stored in backing caches and only downloads
+    // missing ones, so sync interrupted by restart resumes from what was stored before
+    let piece_cache_sync_resumed_from = match farmer_cache_occupancy(piece_caches.clone()).await {
+        Ok(occupancy) => (occupancy.stored_pieces > 0 && occupancy.capacity > 0)
+            .then(|| occupancy.stored_pieces as f32 / occupancy.capacity as f32 * 100.0),
+        Err(error) => {
+            warn!(%error, "Failed to check piece cache contents before sync");
+            None
+        }
+    };
     farmer_cache
-        .replace_backing_caches(
-            farms.iter().map(|farm| farm.piece_cache()).collect(),
-            if plot_cache {
-                farms.iter().map(|farm| farm.plot_cache()).collect()
-            } else {
-                Vec::new()
-            },
-        )
+        .replace_backing_caches(piece_caches.clone(), plot_caches.clone())
         .await;
 
-    // Store piece readers so we can reference them later
-    let piece_readers = farms
+    // Store piece readers so we can reference them later
+    let piece_readers = farms
+        .iter()
+        .map(|farm| ReplaceablePieceReader::new(farm.piece_reader()))
+        .collect::<Vec<_>>();
+
+    let initial_farm_states = farms
+        .iter()
+        .enumerate()
+        .map(|(farm_index, farm)| async move {
+            anyhow::Ok(InitialFarmState {
+                total_sectors_count: farm.total_sectors_count(),
+                plotted_sectors_count: farm.plotted_sectors_count().await.map_err(|error| {
+                    anyhow!(
+                        "Failed to get plotted sectors count from from index {farm_index}: \
+                        {error}"
+                    )
+                })?,
+            })
+        })
+        .collect::<FuturesOrdered<_>>()
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    // Sectors that were plotted before start are used for read benchmark, replotting doesn't
+    // change this
+    let benchmark_sectors = farms
         .iter()
-        .map(|farm| farm.piece_reader())
+        .zip(&initial_farm_states)
+        .map(|(farm, initial_farm_state)| {
+            (
+                initial_farm_state.plotted_sectors_count,
+                farm.info().pieces_in_sector(),
+            )
+        })
         .collect::<Vec<_>>();
 
     info!("Collecting already plotted pieces (this will take some time)...");
 
-    // Collect already plotted pieces
     {
-        let mut future_plotted_pieces = PlottedPieces::new(piece_readers);
+        let farms = farms
+            .iter()
+            .enumerate()
+            .map(|(farm_index, farm)| {
+                let farm_index = farm_index.try_into().map_err(|_error| {
+                    anyhow!(
+                        "More than 256 plots are not supported, consider running multiple farmer \
+                        instances"
+                    )
+                })?;
 
-        for (farm_index, farm) in farms.iter().enumerate() {
-            let farm_index = farm_index.try_into().map_err(|_error| {
-                anyhow!(
-                    "More than 256 plots are not supported, consider running multiple farmer \
-                    instances"
-                )
+                anyhow::Ok((farm_index, farm.as_ref()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut future_plotted_pieces = new_plotted_pieces(&piece_readers);
+
+        let plot_map = collect_plotted_pieces(
+            &farms,
+            &mut future_plotted_pieces,
+            on_plotted_sectors_scan_progress.as_ref(),
+            &startup_cancelled,
+        )
+        .await?;
+
+        if let Some(path) = &export_plot_map {
+            let plot_map =
+                serde_json::to_vec_pretty(&plot_map).expect("Plot map is always serializable; qed");
+            // Exported before farms start, such that it reflects plotted sectors at this point
+            tokio::fs::write(path, plot_map).await.map_err(|error| {
+                anyhow!("Failed to export plot map to {}: {error}", path.display())
             })?;
 
-            for (sector_index, mut plotted_sectors) in
-                (0 as SectorIndex..).zip(farm.plotted_sectors().await)
-            {
-                while let Some(plotted_sector_result) = plotted_sectors.next().await {
-                    match plotted_sector_result {
-                        Ok(plotted_sector) => {
-                            future_plotted_pieces.add_sector(farm_index, &plotted_sector);
-                        }
-                        Err(error) => {
-                            error!(
-                                %error,
-                                %farm_index,
-                                %sector_index,
-                                "Failed reading plotted sector on startup, skipping"
-                            );
-                        }
-                    }
-                }
-            }
+            info!(path = %path.display(), "Exported plot map");
         }
 
         plotted_pieces.lock().replace(future_plotted_pieces);
@@ -504,29 +2128,23 @@ pub(super) async fn create_farmer(farmer_options: FarmerOptions) -> anyhow::Resu
             move |progress| {
                 notifications.call_simple(&FarmerNotification::FarmerCacheSyncProgress {
                     progress: *progress,
+                    resumed_from: piece_cache_sync_resumed_from,
                 });
             }
         }))
         .detach();
 
-    let initial_farm_states = farms
-        .iter()
-        .enumerate()
-        .map(|(farm_index, farm)| async move {
-            anyhow::Ok(InitialFarmState {
-                total_sectors_count: farm.total_sectors_count(),
-                plotted_sectors_count: farm.plotted_sectors_count().await.map_err(|error| {
-                    anyhow!(
-                        "Failed to get plotted sectors count from from index {farm_index}: \
-                        {error}"
-                    )
-                })?,
-            })
-        })
-        .collect::<FuturesOrdered<_>>()
-        .try_collect::<Vec<_>>()
-        .await?;
+    node_client
+        .on_reconnecting(Arc::new({
+            let notifications = Arc::clone(&notifications);
+
+            move |&reconnecting| {
+                notifications.call_simple(&FarmerNotification::NodeReconnecting(reconnecting));
+            }
+        }))
+        .detach();
 
+    let mut farm_abort_handles = HashMap::new();
     let mut farms_stream = farms
         .into_iter()
         .enumerate()
@@ -534,92 +2152,242 @@ pub(super) async fn create_farmer(farmer_options: FarmerOptions) -> anyhow::Resu
             let farm_index = u8::try_from(farm_index).expect(
                 "More than 256 plots are not supported, this is checked above already; qed",
             );
-            let plotted_pieces = Arc::clone(&plotted_pieces);
-            let span = info_span!("farm", %farm_index);
-
-            farm.on_sector_update(Arc::new({
-                let notifications = Arc::clone(&notifications);
-
-                move |(sector_index, sector_update)| {
-                    notifications.call_simple(&FarmerNotification::SectorUpdate {
-                        farm_index,
-                        sector_index: *sector_index,
-                        update: sector_update.clone(),
-                    });
-                }
-            }))
-            .detach();
-            farm.on_farming_notification(Arc::new({
-                let notifications = Arc::clone(&notifications);
-
-                move |notification| {
-                    notifications.call_simple(&FarmerNotification::FarmingNotification {
-                        farm_index,
-                        notification: notification.clone(),
-                    });
-                }
-            }))
-            .detach();
-
-            // Collect newly plotted pieces
-            let on_plotted_sector_callback =
-                move |plotted_sector: &PlottedSector,
-                      maybe_old_plotted_sector: &Option<PlottedSector>| {
-                    let _span_guard = span.enter();
 
-                    {
-                        let mut plotted_pieces = plotted_pieces.lock();
-                        let plotted_pieces = plotted_pieces
-                            .as_mut()
-                            .expect("Initial value was populated above; qed");
-
-                        if let Some(old_plotted_sector) = &maybe_old_plotted_sector {
-                            plotted_pieces.delete_sector(farm_index, old_plotted_sector);
-                        }
-                        plotted_pieces.add_sector(farm_index, plotted_sector);
-                    }
-                };
-            farm.on_sector_update(Arc::new(move |(_sector_index, sector_state)| {
-                if let SectorUpdate::Plotting(SectorPlottingDetails::Finished {
-                    plotted_sector,
-                    old_plotted_sector,
-                    ..
-                }) = sector_state
-                {
-                    on_plotted_sector_callback(plotted_sector, old_plotted_sector);
-                }
-            }))
-            .detach();
+            subscribe_to_farm_events(
+                farm.as_ref(),
+                farm_index,
+                &notifications,
+                Arc::clone(&plotted_pieces),
+            );
 
-            farm.run().map(move |result| (farm_index, result))
+            run_farm(farm_index, farm, &mut farm_abort_handles)
         })
         .collect::<FuturesUnordered<_>>();
 
-    // Drop original instance such that the only remaining instances are in `SingleDiskFarm`
-    // event handlers
-    drop(plotted_pieces);
-
     let (action_sender, mut action_receiver) = mpsc::channel(1);
+    let (rescan_farms_sender, mut rescan_farms_receiver) = mpsc::channel(1);
+    let (wipe_farm_sender, mut wipe_farm_receiver) = mpsc::unbounded();
+    let (benchmark_farms_sender, mut benchmark_farms_receiver) = mpsc::channel(1);
+    let (test_plotting_sender, mut test_plotting_receiver) = mpsc::channel(1);
+    let (farmer_cache_occupancy_sender, mut farmer_cache_occupancy_receiver) = mpsc::channel(1);
     let (pause_plotting_sender, mut pause_plotting_receiver) = watch::channel(false);
+    let (plotting_rate_limit_sender, mut plotting_rate_limit_receiver) =
+        watch::channel(plotting_rate_limit);
+    let (plotting_rate_limited_sender, mut plotting_rate_limited_receiver) = watch::channel(false);
 
     let pause_plotting_actions_fut = async move {
-        let mut thread_pools = Vec::with_capacity(plotting_thread_pools_count);
+        // Each NUMA node has a single pair of thread pools
+        let plotting_thread_pool_managers =
+            iter::once((plotting_thread_pool_manager, plotting_thread_pools_count))
+                .chain(
+                    numa_plotting_thread_pool_managers
+                        .into_values()
+                        .map(|plotting_thread_pool_manager| (plotting_thread_pool_manager, 1)),
+                )
+                .collect::<Vec<_>>();
+        let mut thread_pools = plotting_thread_pool_managers
+            .iter()
+            .map(|(_plotting_thread_pool_manager, thread_pools_count)| {
+                Vec::with_capacity(*thread_pools_count)
+            })
+            .collect::<Vec<_>>();
 
         loop {
-            if *pause_plotting_receiver.borrow_and_update() {
+            // Both need to be marked as seen, hence no short-circuiting here
+            let plotting_paused = *pause_plotting_receiver.borrow_and_update();
+            let plotting_rate_limited = *plotting_rate_limited_receiver.borrow_and_update();
+
+            if plotting_paused || plotting_rate_limited {
                 // Collect all managers so that plotting will be effectively paused
-                if thread_pools.len() < plotting_thread_pools_count {
+                if let Some(((plotting_thread_pool_manager, _), thread_pools)) =
+                    plotting_thread_pool_managers
+                        .iter()
+                        .zip(&mut thread_pools)
+                        .find(|((_, thread_pools_count), thread_pools)| {
+                            thread_pools.len() < *thread_pools_count
+                        })
+                {
                     thread_pools.push(plotting_thread_pool_manager.get_thread_pools().await);
                     // Allow to un-pause plotting quickly if user requests it
                     continue;
                 }
             } else {
-                // Returns all thread pools back to the manager
-                thread_pools.clear();
+                // Returns all thread pools back to the managers
+                thread_pools.iter_mut().for_each(Vec::clear);
+            }
+
+            select! {
+                result = pause_plotting_receiver.changed().fuse() => {
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                result = plotting_rate_limited_receiver.changed().fuse() => {
+                    if result.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    };
+
+    let (sector_plotted_sender, mut sector_plotted_receiver) = mpsc::unbounded();
+    let sector_plotted_handler_id = notifications.add(Arc::new(move |notification| {
+        if let FarmerNotification::SectorUpdate {
+            update: SectorUpdate::Plotting(SectorPlottingDetails::Finished { .. }),
+            ..
+        } = notification
+        {
+            // Doesn't matter if receiver is gone
+            let _ = sector_plotted_sender.unbounded_send(Instant::now());
+        }
+    }));
+
+    // Token bucket that is refilled with one sector every `PLOTTING_RATE_WINDOW / limit`, plotting
+    // is paused while bucket is empty, such that sectors are spaced evenly rather than plotted in
+    // bursts. Sectors that are being encoded at the moment plotting is paused are not interrupted
+    // and are subtracted from the bucket once plotted, which delays further plotting accordingly.
+    let plotting_rate_fut = {
+        let notifications = Arc::clone(&notifications);
+
+        async move {
+            let _sector_plotted_handler_id = sector_plotted_handler_id;
+            // Times at which sectors were plotted within the last window (for reporting), oldest
+            // first
+            let mut sector_plotted_times = VecDeque::<Instant>::new();
+            let mut last_plotting_rate = None;
+            let mut tokens = PLOTTING_RATE_BURST;
+            let mut last_refill = Instant::now();
+
+            loop {
+                let now = Instant::now();
+                while sector_plotted_times
+                    .front()
+                    .is_some_and(|sector_plotted_time| {
+                        now.duration_since(*sector_plotted_time) >= PLOTTING_RATE_WINDOW
+                    })
+                {
+                    sector_plotted_times.pop_front();
+                }
+
+                let limit = *plotting_rate_limit_receiver.borrow_and_update();
+                let refill_interval = limit.map(|limit| PLOTTING_RATE_WINDOW / limit.get());
+                if let Some(refill_interval) = refill_interval {
+                    while tokens < PLOTTING_RATE_BURST
+                        && now.duration_since(last_refill) >= refill_interval
+                    {
+                        tokens += 1;
+                        last_refill += refill_interval;
+                    }
+                }
+                if tokens >= PLOTTING_RATE_BURST {
+                    // Full bucket is not refilled, refill interval starts once token is consumed
+                    last_refill = now;
+                }
+
+                let sectors_per_minute = sector_plotted_times.len() as u32;
+                plotting_rate_limited_sender.send_replace(limit.is_some() && tokens <= 0);
+
+                if last_plotting_rate != Some((sectors_per_minute, limit)) {
+                    last_plotting_rate.replace((sectors_per_minute, limit));
+                    notifications.call_simple(&FarmerNotification::PlottingRate {
+                        sectors_per_minute,
+                        limit,
+                    });
+                }
+
+                let window_expiration = sector_plotted_times
+                    .front()
+                    .map(|sector_plotted_time| *sector_plotted_time + PLOTTING_RATE_WINDOW);
+                let next_refill = refill_interval
+                    .filter(|_| tokens < PLOTTING_RATE_BURST)
+                    .map(|refill_interval| last_refill + refill_interval);
+                let next_wakeup = window_expiration.into_iter().chain(next_refill).min();
+
+                select! {
+                    maybe_sector_plotted_time = sector_plotted_receiver.next() => {
+                        let Some(sector_plotted_time) = maybe_sector_plotted_time else {
+                            break;
+                        };
+                        sector_plotted_times.push_back(sector_plotted_time);
+                        if limit.is_some() {
+                            tokens -= 1;
+                        }
+                    }
+                    result = plotting_rate_limit_receiver.changed().fuse() => {
+                        if result.is_err() {
+                            break;
+                        }
+                        // Sectors plotted under previous limit don't delay plotting under new one
+                        tokens = tokens.max(0);
+                    }
+                    _ = async {
+                        match next_wakeup {
+                            Some(next_wakeup) => {
+                                tokio::time::sleep_until(next_wakeup.into()).await;
+                            }
+                            None => {
+                                pending::<()>().await;
+                            }
+                        }
+                    }.fuse() => {
+                        // Oldest sector falls out of the window or bucket is refilled
+                    }
+                }
+            }
+        }
+    };
+
+    let disk_temperature_fut = {
+        let mut monitors = disk_farms
+            .iter()
+            .zip(plotting_held_senders)
+            .enumerate()
+            .filter_map(|(farm_index, (disk_farm, plotting_held_sender))| {
+                let farm_index = u8::try_from(farm_index).expect(
+                    "More than 256 plots are not supported, this is checked above already; qed",
+                );
+
+                Some(monitor_disk_temperature(
+                    farm_index,
+                    disk_farm.directory.clone(),
+                    disk_farm.max_disk_temperature?,
+                    plotting_held_sender?,
+                    Arc::clone(&notifications),
+                ))
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        async move {
+            while monitors.next().await.is_some() {
+                // Monitor exits when temperature is not available
+            }
+
+            pending::<()>().await;
+        }
+    };
+
+    let shared_downloading_permits_fut = {
+        let farm_shared_downloading_permits = farm_shared_downloading_permits
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        async move {
+            if farm_shared_downloading_permits.is_empty() {
+                pending::<()>().await;
             }
 
-            if pause_plotting_receiver.changed().await.is_err() {
-                break;
+            let mut release_interval =
+                tokio::time::interval(SHARED_DOWNLOADING_PERMITS_RELEASE_INTERVAL);
+            release_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            loop {
+                release_interval.tick().await;
+
+                for shared_downloading_permits in &farm_shared_downloading_permits {
+                    shared_downloading_permits.release_unused().await;
+                }
             }
         }
     };
@@ -632,48 +2400,670 @@ pub(super) async fn create_farmer(farmer_options: FarmerOptions) -> anyhow::Resu
                         debug!(%error, "Failed to forward pause plotting");
                     }
                 }
+                FarmerAction::SetPlottingRateLimit(plotting_rate_limit) => {
+                    if let Err(error) = plotting_rate_limit_sender.send(plotting_rate_limit) {
+                        debug!(%error, "Failed to forward plotting rate limit");
+                    }
+                }
+                FarmerAction::RescanFarms => {
+                    // Rescan that is already pending will cover this request as well
+                    let _ = rescan_farms_sender.clone().try_send(());
+                }
+                FarmerAction::WipeFarm { farm_index } => {
+                    if let Err(error) = wipe_farm_sender.unbounded_send(farm_index) {
+                        debug!(%error, "Failed to forward farm wipe");
+                    }
+                }
+                FarmerAction::BenchmarkFarms => {
+                    // Benchmark that is already pending will cover this request as well
+                    let _ = benchmark_farms_sender.clone().try_send(());
+                }
+                FarmerAction::TestPlotting => {
+                    // Self-test that is already pending will cover this request as well
+                    let _ = test_plotting_sender.clone().try_send(());
+                }
+                FarmerAction::ReportFarmerCacheOccupancy => {
+                    // Report that is already pending will cover this request as well
+                    let _ = farmer_cache_occupancy_sender.clone().try_send(());
+                }
             }
         }
         anyhow::Ok(())
     };
 
-    let mut farm_errors = Vec::new();
+    let mut farm_errors = HashMap::new();
+    let mut farm_recoveries = FuturesUnordered::new();
+    let mut farm_recovery_abort_handles = HashMap::<u8, AbortHandle>::new();
+    // Farms that were requested to be wiped, they are never started again
+    let mut wiped_farms = HashSet::new();
+    let mut farm_wipes = FuturesUnordered::new();
+    // Farms stopped for rescan that were not dropped yet, rescan starts once all of them are
+    let mut farms_stopping_for_rescan = HashSet::new();
+    // Farms that are being rescanned, until rescan result is applied
+    let mut farms_to_rescan = Vec::<u8>::new();
+    let mut farm_rescans = FuturesUnordered::new();
+    let mut farm_benchmarks = FuturesUnordered::new();
+    let mut plotting_self_tests = FuturesUnordered::new();
+    // Farms that are waiting for their plotting self-test, tests run one at a time to not compete
+    // with each other for plotting resources
+    let mut plotting_self_test_queue = VecDeque::<u8>::new();
+    let mut farmer_cache_occupancy_reports = FuturesUnordered::new();
+
+    let start_plotting_self_test = {
+        let create_farm = create_farm.clone();
+        let farm_directories = disk_farms
+            .iter()
+            .map(|disk_farm| disk_farm.directory.clone())
+            .collect::<Vec<_>>();
+
+        move |farm_index: u8| {
+            let directory =
+                farm_directories[usize::from(farm_index)].join(PLOTTING_SELF_TEST_DIRECTORY);
+            info!(
+                %farm_index,
+                directory = %directory.display(),
+                "Starting plotting self-test"
+            );
+
+            let farm_fut = create_farm(
+                // Index that doesn't clash with any of the configured farms
+                farm_directories.len(),
+                DiskFarm {
+                    directory: directory.clone(),
+                    // Capped to a single sector by `max_plotted_sectors`
+                    allocated_plotting_space: sector_size * 2,
+                    plotting_concurrency: None,
+                    max_disk_temperature: None,
+                    max_plotted_sectors: Some(NonZeroU16::MIN),
+                    network_filesystem: false,
+                    numa_node: None,
+                },
+                None,
+                Arc::new(Barrier::new(1)),
+            );
+            test_plotting(farm_index, directory, farm_fut)
+        }
+    };
+
+    // Farm is stopped before wiping, so it is opened once more to remove its sectors from plotted
+    // pieces before files are deleted
+    let forget_and_wipe_farm = {
+        let create_farm = create_farm.clone();
+        let plotted_pieces = Arc::clone(&plotted_pieces);
+
+        move |farm_index: u8, disk_farm: DiskFarm| {
+            let create_farm = create_farm.clone();
+            let plotted_pieces = Arc::clone(&plotted_pieces);
+
+            async move {
+                let directory = disk_farm.directory.clone();
+                let farm_fut = create_farm(
+                    usize::from(farm_index),
+                    disk_farm,
+                    None,
+                    Arc::new(Barrier::new(1)),
+                );
+                let result = match farm_fut.await {
+                    Ok(farm) => {
+                        let farm = Box::new(farm) as Box<dyn Farm>;
+                        forget_plotted_sectors(farm.as_ref(), farm_index, &plotted_pieces).await
+                    }
+                    Err(error) => Err(error),
+                };
+                if let Err(error) = result {
+                    warn!(
+                        %farm_index,
+                        %error,
+                        "Failed to remove farm from plotted pieces, reading its pieces will fail \
+                        until restart"
+                    );
+                }
+
+                wipe_farm(farm_index, directory).await
+            }
+            .boxed()
+        }
+    };
 
     let farms_fut = {
         let notifications = Arc::clone(&notifications);
 
         async move {
-            while let Some((farm_index, result)) = farms_stream.next().await {
-                match result {
-                    Ok(()) => {
-                        info!(%farm_index, "Farm exited successfully");
-                    }
-                    Err(error) => {
+            loop {
+                select! {
+                    (farm_index, result) = farms_stream.select_next_some() => {
+                        farm_abort_handles.remove(&farm_index);
+                        if farms_stopping_for_rescan.remove(&farm_index)
+                            && farms_stopping_for_rescan.is_empty()
+                        {
+                            // All farms are dropped at this point and can be opened again, farms
+                            // wiped in the meantime are not
+                            let farms_to_open = farms_to_rescan
+                                .iter()
+                                .filter(|farm_index| !wiped_farms.contains(*farm_index))
+                                .map(|&farm_index| {
+                                    (farm_index, disk_farms[usize::from(farm_index)].clone())
+                                })
+                                .collect::<Vec<_>>();
+                            let create_farm = create_farm.clone();
+                            let piece_readers = piece_readers.clone();
+                            let notifications = Arc::clone(&notifications);
+
+                            farm_rescans.push(async move {
+                                let mut farms = Vec::with_capacity(farms_to_open.len());
+                                let mut failed_farms = Vec::new();
+                                for (farm_index, disk_farm) in farms_to_open {
+                                    let farm_fut = create_farm(
+                                        usize::from(farm_index),
+                                        disk_farm,
+                                        None,
+                                        Arc::new(Barrier::new(1)),
+                                    );
+
+                                    match farm_fut.await {
+                                        Ok(farm) => {
+                                            farms.push((farm_index, Box::new(farm) as Box<dyn Farm>));
+                                        }
+                                        Err(error) => {
+                                            failed_farms.push((farm_index, error));
+                                        }
+                                    }
+                                }
+
+                                // Previous farm instances are already dropped, so readers can be
+                                // replaced right away
+                                for (farm_index, farm) in &farms {
+                                    piece_readers[usize::from(*farm_index)]
+                                        .replace(farm.piece_reader());
+                                }
+                                let mut plotted_pieces = new_plotted_pieces(&piece_readers);
+                                let scan_result = collect_plotted_pieces(
+                                    &farms
+                                        .iter()
+                                        .map(|(farm_index, farm)| (*farm_index, farm.as_ref()))
+                                        .collect::<Vec<_>>(),
+                                    &mut plotted_pieces,
+                                    &|scan_progress| {
+                                        notifications.call_simple(
+                                            &FarmerNotification::PlottedSectorsScanProgress(
+                                                *scan_progress,
+                                            ),
+                                        );
+                                    },
+                                    // Rescan is not cancellable, farms need to be started again
+                                    // anyway
+                                    &AtomicBool::new(false),
+                                )
+                                .await;
+
+                                RescannedFarms {
+                                    farms,
+                                    failed_farms,
+                                    plotted_pieces,
+                                    scan_result: scan_result.map(|_plot_map| ()),
+                                }
+                            }.boxed());
+                        }
+                        if wiped_farms.contains(&farm_index) {
+                            // Farm instance is dropped at this point, so its files can be deleted
+                            farm_wipes.push(forget_and_wipe_farm(
+                                farm_index,
+                                disk_farms[usize::from(farm_index)].clone(),
+                            ));
+                            continue;
+                        }
+                        let Ok(result) = result else {
+                            debug!(%farm_index, "Farm stopped for rescan");
+                            continue;
+                        };
+                        let error = match result {
+                            Ok(()) => {
+                                info!(%farm_index, "Farm exited successfully");
+                                continue;
+                            }
+                            Err(error) => error,
+                        };
+
                         error!(%farm_index, %error, "Farm exited with error");
 
                         let error = Arc::new(error);
 
-                        farm_errors.push(AsyncJoinOnDrop::new(
-                            tokio::spawn({
-                                let error = Arc::clone(&error);
+                        farm_errors.insert(
+                            farm_index,
+                            AsyncJoinOnDrop::new(
+                                tokio::spawn({
+                                    let error = Arc::clone(&error);
+
+                                    async move {
+                                        let mut print_interval = FARM_ERROR_PRINT_INTERVAL;
+                                        let mut repeated = 0_usize;
+
+                                        loop {
+                                            tokio::time::sleep(print_interval).await;
+                                            repeated += 1;
+                                            print_interval = (print_interval * 2)
+                                                .min(FARM_ERROR_PRINT_MAX_INTERVAL);
+
+                                            error!(
+                                                %farm_index,
+                                                %error,
+                                                %repeated,
+                                                "Farm errored and stopped"
+                                            );
+                                        }
+                                    }
+                                }),
+                                true,
+                            ),
+                        );
 
-                                async move {
-                                    loop {
-                                        tokio::time::sleep(FARM_ERROR_PRINT_INTERVAL).await;
+                        notifications
+                            .call_simple(&FarmerNotification::FarmError { farm_index, error });
 
-                                        error!(
-                                            %farm_index,
-                                            %error,
-                                            "Farm errored and stopped"
-                                        );
+                        if auto_recover_farms {
+                            let create_farm = create_farm.clone();
+                            let disk_farm = disk_farms[usize::from(farm_index)].clone();
+                            let notifications = Arc::clone(&notifications);
+
+                            let (farm_recovery_fut, abort_handle) = future::abortable(async move {
+                                let mut attempt = 0;
+                                let recovery_interval = if disk_farm.network_filesystem {
+                                    NETWORK_FILESYSTEM_FARM_RECOVERY_INTERVAL
+                                } else {
+                                    FARM_RECOVERY_INTERVAL
+                                };
+
+                                loop {
+                                    tokio::time::sleep(recovery_interval).await;
+                                    attempt += 1;
+
+                                    info!(%farm_index, %attempt, "Attempting to recover farm");
+                                    notifications.call_simple(
+                                        &FarmerNotification::FarmRecoveryAttempt {
+                                            farm_index,
+                                            attempt,
+                                        },
+                                    );
+
+                                    // Recovered farm is initialized on its own, so it must not
+                                    // wait for other farms
+                                    let farm_fut = create_farm(
+                                        usize::from(farm_index),
+                                        disk_farm.clone(),
+                                        None,
+                                        Arc::new(Barrier::new(1)),
+                                    );
+
+                                    match farm_fut.await {
+                                        Ok(farm) => {
+                                            break (farm_index, Box::new(farm) as Box<dyn Farm>);
+                                        }
+                                        Err(error) => {
+                                            error!(
+                                                %farm_index,
+                                                %attempt,
+                                                %error,
+                                                "Farm recovery attempt failed"
+                                            );
+                                        }
                                     }
                                 }
-                            }),
-                            true,
-                        ));
+                            });
+                            farm_recovery_abort_handles.insert(farm_index, abort_handle);
+                            farm_recoveries.push(farm_recovery_fut);
+                        }
+                    }
+                    result = farm_recoveries.select_next_some() => {
+                        let Ok((farm_index, farm)) = result else {
+                            // Recovery was cancelled because farm is being wiped
+                            continue;
+                        };
+                        farm_recovery_abort_handles.remove(&farm_index);
+                        info!(%farm_index, "Farm recovered successfully");
+
+                        let plotted_pieces = Arc::clone(&plotted_pieces);
+
+                        // Pieces are read from recovered farm instance from now on
+                        piece_readers[usize::from(farm_index)].replace(farm.piece_reader());
+                        // Sectors of the farm instance that errored are still in plotted pieces,
+                        // they are replaced with sectors of recovered farm instance
+                        for mut plotted_sectors in farm.plotted_sectors().await {
+                            while let Some(plotted_sector_result) = plotted_sectors.next().await {
+                                if let Ok(plotted_sector) = plotted_sector_result
+                                    && let Some(plotted_pieces) = plotted_pieces.lock().as_mut()
+                                {
+                                    plotted_pieces.delete_sector(farm_index, &plotted_sector);
+                                    plotted_pieces.add_sector(farm_index, &plotted_sector);
+                                }
+                            }
+                        }
 
-                        notifications
-                            .call_simple(&FarmerNotification::FarmError { farm_index, error });
+                        if dedicated_piece_cache.is_none() {
+                            piece_caches[usize::from(farm_index)] = farm.piece_cache();
+                        }
+                        if plot_cache {
+                            plot_caches[usize::from(farm_index)] = farm.plot_cache();
+                        }
+                        let (active_piece_caches, active_plot_caches) = backing_caches(
+                            &piece_caches,
+                            &plot_caches,
+                            dedicated_piece_cache.is_some(),
+                            &wiped_farms,
+                        );
+                        farmer_cache
+                            .replace_backing_caches(active_piece_caches, active_plot_caches)
+                            .await;
+
+                        subscribe_to_farm_events(
+                            farm.as_ref(),
+                            farm_index,
+                            &notifications,
+                            plotted_pieces,
+                        );
+
+                        farm_errors.remove(&farm_index);
+                        notifications.call_simple(&FarmerNotification::FarmRecovered { farm_index });
+
+                        farms_stream.push(run_farm(farm_index, farm, &mut farm_abort_handles));
+                    }
+                    () = rescan_farms_receiver.select_next_some() => {
+                        if !farms_to_rescan.is_empty() {
+                            debug!(?farms_to_rescan, "Farms rescan is already in progress");
+                            continue;
+                        }
+
+                        // Plotted sectors of running farm can't be accessed, so farms are stopped
+                        // and opened again, farms that errored are left alone
+                        let mut farm_indices =
+                            farm_abort_handles.keys().copied().collect::<Vec<_>>();
+                        farm_indices.sort_unstable();
+                        if farm_indices.is_empty() {
+                            notifications.call_simple(&FarmerNotification::FarmsRescanned {
+                                farm_indices,
+                                error: None,
+                            });
+                            continue;
+                        }
+                        info!(?farm_indices, "Rescanning farms");
+
+                        for abort_handle in farm_abort_handles.values() {
+                            abort_handle.abort();
+                        }
+                        farm_abort_handles.clear();
+                        // Farms are opened again in a separate future once all of them are dropped
+                        farms_stopping_for_rescan.extend(farm_indices.iter().copied());
+                        farms_to_rescan = farm_indices;
+                    }
+                    rescanned_farms = farm_rescans.select_next_some() => {
+                        let RescannedFarms {
+                            farms,
+                            failed_farms,
+                            plotted_pieces: future_plotted_pieces,
+                            scan_result,
+                        } = rescanned_farms;
+
+                        for (farm_index, error) in failed_farms {
+                            // Handled the same way as farm that exited with error
+                            farms_stream.push(future::ready((farm_index, Ok(Err(error)))).boxed());
+                        }
+
+                        for (farm_index, farm) in &farms {
+                            if dedicated_piece_cache.is_none() {
+                                piece_caches[usize::from(*farm_index)] = farm.piece_cache();
+                            }
+                            if plot_cache {
+                                plot_caches[usize::from(*farm_index)] = farm.plot_cache();
+                            }
+                        }
+                        let (active_piece_caches, active_plot_caches) = backing_caches(
+                            &piece_caches,
+                            &plot_caches,
+                            dedicated_piece_cache.is_some(),
+                            &wiped_farms,
+                        );
+                        farmer_cache
+                            .replace_backing_caches(active_piece_caches, active_plot_caches)
+                            .await;
+
+                        let error = match scan_result {
+                            Ok(()) => {
+                                info!("Finished rescanning farms successfully");
+                                None
+                            }
+                            Err(error) => {
+                                error!(
+                                    %error,
+                                    "Failed to rescan farms, plotted pieces may be incomplete"
+                                );
+                                Some(Arc::new(error))
+                            }
+                        };
+                        plotted_pieces.lock().replace(future_plotted_pieces);
+
+                        for (farm_index, farm) in farms {
+                            if wiped_farms.contains(&farm_index) {
+                                // Wiped while rescan was in progress, files are deleted once farm
+                                // is dropped
+                                drop(farm);
+                                farm_wipes.push(forget_and_wipe_farm(
+                                    farm_index,
+                                    disk_farms[usize::from(farm_index)].clone(),
+                                ));
+                                continue;
+                            }
+
+                            subscribe_to_farm_events(
+                                farm.as_ref(),
+                                farm_index,
+                                &notifications,
+                                Arc::clone(&plotted_pieces),
+                            );
+
+                            farms_stream.push(run_farm(farm_index, farm, &mut farm_abort_handles));
+                        }
+
+                        notifications.call_simple(&FarmerNotification::FarmsRescanned {
+                            farm_indices: mem::take(&mut farms_to_rescan),
+                            error,
+                        });
+                    }
+                    farm_index = wipe_farm_receiver.select_next_some() => {
+                        if usize::from(farm_index) >= disk_farms.len()
+                            || !wiped_farms.insert(farm_index)
+                        {
+                            continue;
+                        }
+
+                        info!(%farm_index, "Wiping farm");
+                        notifications.call_simple(&FarmerNotification::FarmWiping { farm_index });
+
+                        // Farm is no longer used for caching and can't be recovered anymore
+                        let (active_piece_caches, active_plot_caches) = backing_caches(
+                            &piece_caches,
+                            &plot_caches,
+                            dedicated_piece_cache.is_some(),
+                            &wiped_farms,
+                        );
+                        farmer_cache
+                            .replace_backing_caches(active_piece_caches, active_plot_caches)
+                            .await;
+                        farm_errors.remove(&farm_index);
+                        if let Some(abort_handle) =
+                            farm_recovery_abort_handles.remove(&farm_index)
+                        {
+                            abort_handle.abort();
+                        }
+
+                        // Sectors of the farm are removed from plotted pieces before files are
+                        // deleted
+                        match farm_abort_handles.remove(&farm_index) {
+                            Some(abort_handle) => {
+                                // Files are deleted once farm is stopped
+                                abort_handle.abort();
+                            }
+                            None if farms_stopping_for_rescan.contains(&farm_index) => {
+                                // Files are deleted once farm stopped for rescan is dropped
+                            }
+                            None if farms_to_rescan.contains(&farm_index)
+                                && !farm_rescans.is_empty() =>
+                            {
+                                // Files are deleted once rescan finishes and farm is dropped
+                            }
+                            None => {
+                                farm_wipes.push(forget_and_wipe_farm(
+                                    farm_index,
+                                    disk_farms[usize::from(farm_index)].clone(),
+                                ));
+                            }
+                        }
+                    }
+                    (farm_index, result) = farm_wipes.select_next_some() => {
+                        match result {
+                            Ok(freed_bytes) => {
+                                info!(
+                                    %farm_index,
+                                    freed = %bytesize::to_string(freed_bytes, true),
+                                    "Farm wiped successfully"
+                                );
+                                notifications.call_simple(&FarmerNotification::FarmWiped {
+                                    farm_index,
+                                    directory: disk_farms[usize::from(farm_index)]
+                                        .directory
+                                        .clone(),
+                                    freed_bytes,
+                                });
+                            }
+                            Err(error) => {
+                                error!(%farm_index, %error, "Failed to wipe farm");
+                                notifications.call_simple(&FarmerNotification::FarmWipeFailed {
+                                    farm_index,
+                                    error: Arc::new(error),
+                                });
+                            }
+                        }
+                    }
+                    () = benchmark_farms_receiver.select_next_some() => {
+                        if !farm_benchmarks.is_empty() {
+                            info!("Farms piece read benchmark is already in progress");
+                            continue;
+                        }
+
+                        // Only running farms are benchmarked
+                        let mut farm_indices =
+                            farm_abort_handles.keys().copied().collect::<Vec<_>>();
+                        farm_indices.sort_unstable();
+                        info!(?farm_indices, "Benchmarking farms piece reads");
+
+                        for farm_index in farm_indices {
+                            let (plotted_sectors_count, pieces_in_sector) =
+                                benchmark_sectors[usize::from(farm_index)];
+                            farm_benchmarks.push(benchmark_farm(
+                                farm_index,
+                                piece_readers[usize::from(farm_index)].get(),
+                                plotted_sectors_count,
+                                pieces_in_sector,
+                            ));
+                        }
+                    }
+                    (farm_index, result) = farm_benchmarks.select_next_some() => {
+                        match &result {
+                            Ok(result) => {
+                                if result.too_slow() {
+                                    warn!(
+                                        %farm_index,
+                                        ?result,
+                                        "Farm piece read benchmark finished, farm is likely too \
+                                        slow to produce proofs in time"
+                                    );
+                                } else {
+                                    info!(%farm_index, ?result, "Farm piece read benchmark finished");
+                                }
+                            }
+                            Err(error) => {
+                                error!(%farm_index, %error, "Farm piece read benchmark failed");
+                            }
+                        }
+                        notifications.call_simple(&FarmerNotification::FarmReadBenchmark {
+                            farm_index,
+                            result: result.map_err(Arc::new),
+                        });
+                    }
+                    () = test_plotting_receiver.select_next_some() => {
+                        if !plotting_self_tests.is_empty() {
+                            info!("Plotting self-test is already in progress");
+                            continue;
+                        }
+
+                        // Only disks of running farms are tested
+                        let mut farm_indices =
+                            farm_abort_handles.keys().copied().collect::<Vec<_>>();
+                        farm_indices.sort_unstable();
+                        plotting_self_test_queue.extend(farm_indices);
+
+                        if let Some(farm_index) = plotting_self_test_queue.pop_front() {
+                            plotting_self_tests.push(start_plotting_self_test(farm_index));
+                        }
+                    }
+                    (farm_index, result) = plotting_self_tests.select_next_some() => {
+                        match &result {
+                            Ok(result) => {
+                                info!(
+                                    %farm_index,
+                                    ?result,
+                                    "Plotting self-test finished successfully"
+                                );
+                            }
+                            Err(error) => {
+                                error!(%farm_index, %error, "Plotting self-test failed");
+                            }
+                        }
+                        notifications.call_simple(&FarmerNotification::PlottingSelfTest {
+                            farm_index,
+                            result: result.map_err(Arc::new),
+                        });
+
+                        while let Some(farm_index) = plotting_self_test_queue.pop_front() {
+                            // Farm might have been wiped in the meantime
+                            if !wiped_farms.contains(&farm_index) {
+                                plotting_self_tests.push(start_plotting_self_test(farm_index));
+                                break;
+                            }
+                        }
+                    }
+                    () = farmer_cache_occupancy_receiver.select_next_some() => {
+                        if !farmer_cache_occupancy_reports.is_empty() {
+                            info!("Farmer cache occupancy is already being collected");
+                            continue;
+                        }
+
+                        let (active_piece_caches, _active_plot_caches) = backing_caches(
+                            &piece_caches,
+                            &plot_caches,
+                            dedicated_piece_cache.is_some(),
+                            &wiped_farms,
+                        );
+                        farmer_cache_occupancy_reports
+                            .push(farmer_cache_occupancy(active_piece_caches));
+                    }
+                    result = farmer_cache_occupancy_reports.select_next_some() => {
+                        match &result {
+                            Ok(occupancy) => {
+                                info!(
+                                    stored_pieces = %occupancy.stored_pieces,
+                                    capacity = %occupancy.capacity,
+                                    "Farmer cache occupancy"
+                                );
+                            }
+                            Err(error) => {
+                                warn!(%error, "Failed to collect farmer cache occupancy");
+                            }
+                        }
+                        notifications.call_simple(&FarmerNotification::FarmerCacheOccupancy {
+                            result: result.map_err(Arc::new),
+                        });
+                    }
+                    complete => {
+                        break;
                     }
                 }
             }
@@ -682,12 +3072,45 @@ pub(super) async fn create_farmer(farmer_options: FarmerOptions) -> anyhow::Resu
         }
     };
 
+    let plotting_start_fut = {
+        let notifications = Arc::clone(&notifications);
+
+        async move {
+            let wait_for_peers_fut = async {
+                if let Some(min_plotting_peers) = min_plotting_peers {
+                    wait_for_plotting_peers(&dsn_node, min_plotting_peers, &notifications).await;
+                }
+            };
+            // Doesn't matter if sender is gone, plotting will start anyway
+            let _ = future::join(farmer_cache_sync_started_receiver, wait_for_peers_fut).await;
+
+            for plotting_delay_sender in plotting_delay_senders {
+                // Doesn't matter if receiver is gone
+                let _ = plotting_delay_sender.send(());
+            }
+
+            pending::<()>().await;
+        }
+    };
+
     let farmer_fut = Box::pin(
         async move {
             select! {
+                _ = plotting_start_fut.fuse() => {
+                    Ok(())
+                }
                 _ = pause_plotting_actions_fut.fuse() => {
                     Ok(())
                 }
+                _ = plotting_rate_fut.fuse() => {
+                    Ok(())
+                }
+                _ = disk_temperature_fut.fuse() => {
+                    Ok(())
+                }
+                _ = shared_downloading_permits_fut.fuse() => {
+                    Ok(())
+                }
                 _ = process_actions_fut.fuse() => {
                     Ok(())
                 }
@@ -704,6 +3127,7 @@ pub(super) async fn create_farmer(farmer_options: FarmerOptions) -> anyhow::Resu
         farmer_cache_worker_fut,
         initial_farm_states,
         farm_during_initial_plotting,
+        plot_cache_enabled: plot_cache,
         notifications,
         action_sender,
     })
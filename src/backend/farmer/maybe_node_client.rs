@@ -1,66 +1,114 @@
+use crate::backend::utils::{Handler, HandlerFn};
 use arc_swap::ArcSwapOption;
-use futures::Stream;
+use event_listener_primitives::HandlerId;
+use futures::future::BoxFuture;
+use futures::{future, stream, FutureExt, Stream, StreamExt};
+use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use subspace_core_primitives::SegmentHeader;
 use subspace_farmer::node_client::{Error, NodeClientExt};
 use subspace_farmer::{NodeClient, NodeRpcClient};
 use subspace_rpc_primitives::{
     FarmerAppInfo, RewardSignatureResponse, RewardSigningInfo, SlotInfo, SolutionResponse,
 };
+use tracing::{debug, info, warn};
+
+/// For how long to keep trying to reconnect to the node before surfacing an error
+const NODE_RECONNECT_GRACE_PERIOD: Duration = Duration::from_secs(60);
+/// Interval between attempts to reconnect to the node
+const NODE_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+type SubscribeFn<T> = Arc<
+    dyn Fn(
+            Arc<NodeRpcClient>,
+        )
+            -> BoxFuture<'static, Result<Pin<Box<dyn Stream<Item = T> + Send + 'static>>, Error>>
+        + Send
+        + Sync,
+>;
+
+#[derive(Debug, Default)]
+struct Inner {
+    client: ArcSwapOption<NodeRpcClient>,
+    url: ArcSwapOption<String>,
+    reconnecting: AtomicBool,
+    reconnecting_handlers: Handler<bool>,
+    /// Slot info is not delivered to farms until node is synced
+    farm_only_when_synced: AtomicBool,
+    node_synced: AtomicBool,
+}
 
 // TODO: Replace RPC client with a client that can work with node directly
-/// Wrapper node client that allows injecting real inner node RPC client after construction
+/// Wrapper node client that allows injecting real inner node RPC client after construction.
+///
+/// Short node outages are tolerated: failed requests are retried after reconnecting to the node
+/// for up to [`NODE_RECONNECT_GRACE_PERIOD`] and subscriptions are re-established once the node
+/// is back.
 #[derive(Debug, Clone, Default)]
 pub(in super::super) struct MaybeNodeRpcClient {
-    inner: Arc<ArcSwapOption<NodeRpcClient>>,
+    inner: Arc<Inner>,
 }
 
 #[async_trait::async_trait]
 impl NodeClient for MaybeNodeRpcClient {
     async fn farmer_app_info(&self) -> Result<FarmerAppInfo, Error> {
-        match &*self.inner.load() {
-            Some(inner) => inner.farmer_app_info().await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.with_reconnect(|inner| async move { inner.farmer_app_info().await })
+            .await
     }
 
     async fn subscribe_slot_info(
         &self,
     ) -> Result<Pin<Box<dyn Stream<Item = SlotInfo> + Send + 'static>>, Error> {
-        match &*self.inner.load() {
-            Some(inner) => inner.subscribe_slot_info().await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        let slot_info_stream = self
+            .resubscribing(Arc::new(|inner| {
+                async move { inner.subscribe_slot_info().await }.boxed()
+            }))
+            .await?;
+
+        let client = self.clone();
+        Ok(Box::pin(slot_info_stream.filter(move |slot_info| {
+            let farm = !client.inner.farm_only_when_synced.load(Ordering::Relaxed)
+                || client.inner.node_synced.load(Ordering::Relaxed);
+            if !farm {
+                debug!(slot = %slot_info.slot_number, "Skipping farming, node is not synced yet");
+            }
+
+            future::ready(farm)
+        })))
     }
 
     async fn submit_solution_response(
         &self,
         solution_response: SolutionResponse,
     ) -> Result<(), Error> {
-        match &*self.inner.load() {
-            Some(inner) => inner.submit_solution_response(solution_response).await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.with_reconnect(|inner| {
+            let solution_response = solution_response.clone();
+            async move { inner.submit_solution_response(solution_response).await }
+        })
+        .await
     }
 
     async fn subscribe_reward_signing(
         &self,
     ) -> Result<Pin<Box<dyn Stream<Item = RewardSigningInfo> + Send + 'static>>, Error> {
-        match &*self.inner.load() {
-            Some(inner) => inner.subscribe_reward_signing().await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.resubscribing(Arc::new(|inner| {
+            async move { inner.subscribe_reward_signing().await }.boxed()
+        }))
+        .await
     }
 
     async fn submit_reward_signature(
         &self,
         reward_signature: RewardSignatureResponse,
     ) -> Result<(), Error> {
-        match &*self.inner.load() {
-            Some(inner) => inner.submit_reward_signature(reward_signature).await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.with_reconnect(|inner| {
+            let reward_signature = reward_signature.clone();
+            async move { inner.submit_reward_signature(reward_signature).await }
+        })
+        .await
     }
 
     async fn subscribe_archived_segment_headers(
@@ -69,59 +117,174 @@ impl NodeClient for MaybeNodeRpcClient {
         Pin<Box<dyn Stream<Item = subspace_core_primitives::SegmentHeader> + Send + 'static>>,
         Error,
     > {
-        match &*self.inner.load() {
-            Some(inner) => inner.subscribe_archived_segment_headers().await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.resubscribing(Arc::new(|inner| {
+            async move { inner.subscribe_archived_segment_headers().await }.boxed()
+        }))
+        .await
     }
 
     async fn segment_headers(
         &self,
         segment_indexes: Vec<subspace_core_primitives::SegmentIndex>,
     ) -> Result<Vec<Option<subspace_core_primitives::SegmentHeader>>, Error> {
-        match &*self.inner.load() {
-            Some(inner) => inner.segment_headers(segment_indexes).await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.with_reconnect(|inner| {
+            let segment_indexes = segment_indexes.clone();
+            async move { inner.segment_headers(segment_indexes).await }
+        })
+        .await
     }
 
     async fn piece(
         &self,
         piece_index: subspace_core_primitives::PieceIndex,
     ) -> Result<Option<subspace_core_primitives::Piece>, Error> {
-        match &*self.inner.load() {
-            Some(inner) => inner.piece(piece_index).await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.with_reconnect(|inner| async move { inner.piece(piece_index).await })
+            .await
     }
 
     async fn acknowledge_archived_segment_header(
         &self,
         segment_index: subspace_core_primitives::SegmentIndex,
     ) -> Result<(), Error> {
-        match &*self.inner.load() {
-            Some(inner) => {
-                inner
-                    .acknowledge_archived_segment_header(segment_index)
-                    .await
-            }
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.with_reconnect(|inner| async move {
+            inner
+                .acknowledge_archived_segment_header(segment_index)
+                .await
+        })
+        .await
     }
 }
 
 #[async_trait::async_trait]
 impl NodeClientExt for MaybeNodeRpcClient {
     async fn last_segment_headers(&self, limit: u64) -> Result<Vec<Option<SegmentHeader>>, Error> {
-        match &*self.inner.load() {
-            Some(inner) => inner.last_segment_headers(limit).await,
-            None => Err("Inner node client not injected yet".into()),
-        }
+        self.with_reconnect(|inner| async move { inner.last_segment_headers(limit).await })
+            .await
     }
 }
 
 impl MaybeNodeRpcClient {
-    pub(in super::super) fn inject(&self, inner: NodeRpcClient) {
-        self.inner.store(Some(Arc::new(inner)))
+    /// Inject connected client, `url` is used to reconnect if connection is lost later
+    pub(in super::super) fn inject(&self, inner: NodeRpcClient, url: String) {
+        self.inner.url.store(Some(Arc::new(url)));
+        self.inner.client.store(Some(Arc::new(inner)))
+    }
+
+    /// Don't farm (plotting is not affected) until node is reported as synced with
+    /// [`Self::set_node_synced()`]
+    pub(in super::super) fn farm_only_when_synced(&self) {
+        self.inner
+            .farm_only_when_synced
+            .store(true, Ordering::Relaxed);
+    }
+
+    /// Update node sync status, used with [`Self::farm_only_when_synced()`]
+    pub(in super::super) fn set_node_synced(&self, node_synced: bool) {
+        self.inner.node_synced.store(node_synced, Ordering::Relaxed);
+    }
+
+    /// Called with `true` when connection to the node is lost and reconnection is in progress and
+    /// with `false` once connection is restored
+    pub(in super::super) fn on_reconnecting(&self, callback: HandlerFn<bool>) -> HandlerId {
+        self.inner.reconnecting_handlers.add(callback)
+    }
+
+    /// Run request against inner client, reconnecting and retrying it if the node is temporarily
+    /// unavailable
+    async fn with_reconnect<T, F, Fut>(&self, request: F) -> Result<T, Error>
+    where
+        F: Fn(Arc<NodeRpcClient>) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let Some(client) = self.inner.client.load_full() else {
+            return Err("Inner node client not injected yet".into());
+        };
+
+        let error = match request(Arc::clone(&client)).await {
+            Ok(result) => {
+                return Ok(result);
+            }
+            Err(error) => error,
+        };
+
+        // Error may be unrelated to connection, in which case existing connection still works and
+        // there is nothing to retry
+        if client.farmer_app_info().await.is_ok() {
+            return Err(error);
+        }
+
+        match self.reconnect().await {
+            Some(client) => request(client).await,
+            None => Err(error),
+        }
+    }
+
+    /// Subscribe using inner client and transparently re-subscribe if subscription ends because
+    /// the node went away
+    async fn resubscribing<T>(
+        &self,
+        subscribe: SubscribeFn<T>,
+    ) -> Result<Pin<Box<dyn Stream<Item = T> + Send + 'static>>, Error>
+    where
+        T: Send + 'static,
+    {
+        let subscription = self.with_reconnect(|inner| subscribe(inner)).await?;
+
+        Ok(Box::pin(stream::unfold(
+            (self.clone(), subscribe, subscription),
+            |(client, subscribe, mut subscription)| async move {
+                loop {
+                    if let Some(item) = subscription.next().await {
+                        return Some((item, (client, subscribe, subscription)));
+                    }
+
+                    let Some(inner) = client.reconnect().await else {
+                        warn!("Node didn't come back in time, subscription ended");
+                        return None;
+                    };
+                    subscription = match subscribe(inner).await {
+                        Ok(subscription) => subscription,
+                        Err(error) => {
+                            warn!(%error, "Failed to re-subscribe after reconnecting to node");
+                            return None;
+                        }
+                    };
+                }
+            },
+        )))
+    }
+
+    /// Reconnect to the node, retrying for up to [`NODE_RECONNECT_GRACE_PERIOD`], returns new
+    /// client on success
+    async fn reconnect(&self) -> Option<Arc<NodeRpcClient>> {
+        let url = self.inner.url.load_full()?;
+        let start = Instant::now();
+
+        loop {
+            match NodeRpcClient::new(&url).await {
+                Ok(client) => {
+                    let client = Arc::new(client);
+                    self.inner.client.store(Some(Arc::clone(&client)));
+                    if self.inner.reconnecting.swap(false, Ordering::AcqRel) {
+                        info!("Reconnected to node");
+                        self.inner.reconnecting_handlers.call_simple(&false);
+                    }
+
+                    return Some(client);
+                }
+                Err(error) => {
+                    if !self.inner.reconnecting.swap(true, Ordering::AcqRel) {
+                        warn!(%error, "Connection to node lost, reconnecting");
+                        self.inner.reconnecting_handlers.call_simple(&true);
+                    }
+
+                    if start.elapsed() >= NODE_RECONNECT_GRACE_PERIOD {
+                        return None;
+                    }
+                }
+            }
+
+            tokio::time::sleep(NODE_RECONNECT_INTERVAL).await;
+        }
     }
 }
@@ -0,0 +1,70 @@
+//! NUMA node detection for pinning plotting of individual farms to specific NUMA nodes
+
+use std::io;
+#[cfg(target_os = "linux")]
+use subspace_farmer::utils::parse_cpu_cores_sets;
+use subspace_farmer::utils::CpuCoreSet;
+
+/// Number of NUMA nodes in the system, `1` if it can't be determined or system isn't NUMA
+pub fn numa_node_count() -> u16 {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_dir("/sys/devices/system/node")
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| {
+                        entry.file_name().to_str().is_some_and(|file_name| {
+                            file_name
+                                .strip_prefix("node")
+                                .is_some_and(|node| node.parse::<u16>().is_ok())
+                        })
+                    })
+                    .count() as u16
+            })
+            .unwrap_or_default()
+            .max(1)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        1
+    }
+}
+
+/// CPU cores of NUMA node as a single set.
+///
+/// Threads pinned to these cores allocate memory from the same NUMA node due to first-touch
+/// allocation policy of the OS, so memory of pinned thread pools is local to the node as well.
+pub(super) fn numa_node_cpu_cores(numa_node: u16) -> io::Result<CpuCoreSet> {
+    #[cfg(target_os = "linux")]
+    {
+        let cpu_list =
+            std::fs::read_to_string(format!("/sys/devices/system/node/node{numa_node}/cpulist"))?;
+        // Format of `cpulist` is `0-15,32-47`, which is a single set for the parser
+        let cpu_list = cpu_list.trim();
+        if cpu_list.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("NUMA node {numa_node} has no CPU cores"),
+            ));
+        }
+
+        parse_cpu_cores_sets(cpu_list)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("NUMA node {numa_node} has no CPU cores"),
+                )
+            })
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Pinning to NUMA node {numa_node} is only supported on Linux"),
+        ))
+    }
+}
@@ -0,0 +1,200 @@
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+/// Progress is reported after each step of this size
+const PREALLOCATION_PROGRESS_STEP: u64 = 1024 * 1024 * 1024;
+/// Size of the buffer used when space has to be allocated by writing zeroes
+#[cfg(not(target_os = "linux"))]
+const ZEROES_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+/// Allocate disk space for the whole file up front, such that file system can place it
+/// contiguously instead of growing it piece by piece during plotting. `on_progress` is called with
+/// number of bytes allocated so far.
+///
+/// Contents of the file that were already written are never modified.
+///
+/// This is a blocking operation that can take a long time on platforms where space can only be
+/// allocated by writing zeroes.
+///
+/// With `without_zeroing` on Windows the whole file is marked as valid data with
+/// `SetFileValidData` instead of writing zeroes (if process has administrator rights). This is a
+/// trade-off: allocation is nearly instant, but whatever was stored on disk in that area before
+/// becomes readable through the file until plotting overwrites it. Ignored on other platforms.
+pub(super) fn preallocate_file(
+    path: &Path,
+    size: u64,
+    without_zeroing: bool,
+    on_progress: &mut dyn FnMut(u64),
+) -> io::Result<()> {
+    let file = OpenOptions::new().read(true).write(true).open(path)?;
+
+    allocate(&file, size, without_zeroing, on_progress)?;
+    file.sync_all()
+}
+
+#[cfg(target_os = "linux")]
+fn allocate(
+    file: &File,
+    size: u64,
+    _without_zeroing: bool,
+    on_progress: &mut dyn FnMut(u64),
+) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let mut offset = 0;
+    while offset < size {
+        let len = (size - offset).min(PREALLOCATION_PROGRESS_STEP);
+        // SAFETY: File descriptor is valid for the lifetime of `file`, mode `0` only allocates
+        // blocks that are not allocated yet and never changes existing contents
+        let result = unsafe {
+            libc::fallocate(
+                file.as_raw_fd(),
+                0,
+                offset as libc::off_t,
+                len as libc::off_t,
+            )
+        };
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        offset += len;
+        on_progress(offset);
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn allocate(
+    file: &File,
+    size: u64,
+    without_zeroing: bool,
+    on_progress: &mut dyn FnMut(u64),
+) -> io::Result<()> {
+    use std::os::windows::io::AsRawHandle;
+    use tracing::debug;
+    use windows_sys::Win32::Storage::FileSystem::SetFileValidData;
+
+    let current_size = file.metadata()?.len();
+    if current_size < size {
+        file.set_len(size)?;
+    }
+
+    // Marking the whole file as valid data avoids writing zeroes, which otherwise happens on the
+    // first write far past already written data, but exposes previous contents of the disk and
+    // requires a privilege that is only available to administrators, so user has to opt-in
+    if without_zeroing && enable_manage_volume_privilege() {
+        // SAFETY: Handle is valid for the lifetime of `file`
+        if unsafe { SetFileValidData(file.as_raw_handle() as _, size as i64) } != 0 {
+            on_progress(size);
+            return Ok(());
+        }
+
+        debug!(
+            error = %io::Error::last_os_error(),
+            "Failed to set valid data length, writing zeroes instead"
+        );
+    }
+
+    write_zeroes(file, current_size, size, on_progress)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn allocate(
+    file: &File,
+    size: u64,
+    _without_zeroing: bool,
+    on_progress: &mut dyn FnMut(u64),
+) -> io::Result<()> {
+    let current_size = file.metadata()?.len();
+
+    write_zeroes(file, current_size, size, on_progress)
+}
+
+/// Write zeroes in `from..to` range, only used past the end of already written data
+#[cfg(not(target_os = "linux"))]
+fn write_zeroes(
+    file: &File,
+    from: u64,
+    to: u64,
+    on_progress: &mut dyn FnMut(u64),
+) -> io::Result<()> {
+    use std::io::{Seek, SeekFrom, Write};
+
+    let zeroes = vec![0u8; ZEROES_BUFFER_SIZE];
+    let mut file = file;
+    file.seek(SeekFrom::Start(from))?;
+
+    on_progress(from);
+    let mut offset = from;
+    while offset < to {
+        let len = (to - offset).min(ZEROES_BUFFER_SIZE as u64) as usize;
+        file.write_all(&zeroes[..len])?;
+
+        let previous_offset = offset;
+        offset += len as u64;
+        if offset / PREALLOCATION_PROGRESS_STEP != previous_offset / PREALLOCATION_PROGRESS_STEP
+            || offset == to
+        {
+            on_progress(offset);
+        }
+    }
+
+    Ok(())
+}
+
+/// Enable `SeManageVolumePrivilege` for the current process, which is necessary for
+/// `SetFileValidData`, returns `false` if it is not available
+#[cfg(windows)]
+fn enable_manage_volume_privilege() -> bool {
+    use std::{iter, mem, ptr};
+    use windows_sys::Win32::Foundation::{CloseHandle, GetLastError, ERROR_SUCCESS, HANDLE};
+    use windows_sys::Win32::Security::{
+        AdjustTokenPrivileges, LookupPrivilegeValueW, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_ENABLED,
+        TOKEN_ADJUST_PRIVILEGES, TOKEN_PRIVILEGES,
+    };
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    let privilege_name = "SeManageVolumePrivilege"
+        .encode_utf16()
+        .chain(iter::once(0))
+        .collect::<Vec<u16>>();
+
+    // SAFETY: All pointers are valid for the duration of the calls and token handle is closed
+    // before returning
+    unsafe {
+        let mut token: HANDLE = 0;
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_ADJUST_PRIVILEGES, &mut token) == 0 {
+            return false;
+        }
+
+        let mut token_privileges = TOKEN_PRIVILEGES {
+            PrivilegeCount: 1,
+            Privileges: [LUID_AND_ATTRIBUTES {
+                Luid: mem::zeroed(),
+                Attributes: SE_PRIVILEGE_ENABLED,
+            }],
+        };
+        let enabled = LookupPrivilegeValueW(
+            ptr::null(),
+            privilege_name.as_ptr(),
+            &mut token_privileges.Privileges[0].Luid,
+        ) != 0
+            && AdjustTokenPrivileges(
+                token,
+                0,
+                &token_privileges,
+                0,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            ) != 0
+            // Succeeds even if privilege is not assigned, in which case last error is set
+            && GetLastError() == ERROR_SUCCESS;
+
+        CloseHandle(token);
+
+        enabled
+    }
+}
@@ -0,0 +1,21 @@
+use reqwest::{Client, Proxy, Url};
+
+/// Schemes of proxies supported by HTTP client
+pub const SUPPORTED_PROXY_SCHEMES: &[&str] = &["http", "https", "socks5", "socks5h"];
+
+/// HTTP client for requests made by the application, all requests go through proxy if specified
+pub fn http_client(proxy: Option<&Url>) -> Result<Client, reqwest::Error> {
+    let mut client_builder = Client::builder();
+    if let Some(proxy) = proxy {
+        client_builder = client_builder.proxy(Proxy::all(proxy.clone())?);
+    }
+    client_builder.build()
+}
+
+/// Proxy URL without password, such that it can be logged and displayed
+pub fn redacted_proxy(proxy: &Url) -> Url {
+    let mut proxy = proxy.clone();
+    // Can only fail for URLs that can't have credentials in the first place
+    let _ = proxy.set_password(None);
+    proxy
+}
@@ -1,3 +1,5 @@
+use crate::backend::config::IpVersion;
+use event_listener_primitives::HandlerId;
 use parking_lot::Mutex;
 use std::collections::HashSet;
 use std::path::Path;
@@ -9,13 +11,14 @@ use subspace_farmer::KNOWN_PEERS_CACHE_SIZE;
 use subspace_networking::libp2p::identity::ed25519::Keypair;
 use subspace_networking::libp2p::kad::RecordKey;
 use subspace_networking::libp2p::multiaddr::Protocol;
-use subspace_networking::libp2p::Multiaddr;
+use subspace_networking::libp2p::{Multiaddr, PeerId};
 use subspace_networking::utils::multihash::ToMultihash;
 use subspace_networking::utils::strip_peer_id;
 use subspace_networking::{
-    construct, Config, KademliaMode, KnownPeersManager, KnownPeersManagerConfig, Node, NodeRunner,
-    PieceByIndexRequest, PieceByIndexRequestHandler, PieceByIndexResponse,
-    SegmentHeaderBySegmentIndexesRequestHandler, SegmentHeaderRequest, SegmentHeaderResponse,
+    construct, Config, KademliaMode, KnownPeersManager, KnownPeersManagerConfig,
+    KnownPeersRegistry, Node, NodeRunner, PeerAddressRemovedEvent, PieceByIndexRequest,
+    PieceByIndexRequestHandler, PieceByIndexResponse, SegmentHeaderBySegmentIndexesRequestHandler,
+    SegmentHeaderRequest, SegmentHeaderResponse,
 };
 use subspace_rpc_primitives::MAX_SEGMENT_HEADERS_PER_REQUEST;
 use tracing::{debug, error, info, info_span, Instrument};
@@ -40,6 +43,8 @@ pub struct NetworkOptions {
     pub enable_private_ips: bool,
     /// Multiaddrs of reserved nodes to maintain a connection to, multiple are supported
     pub reserved_peers: Vec<Multiaddr>,
+    /// Peers that will never be connected to
+    pub blocked_peers: Vec<PeerId>,
     /// Defines max established incoming connection limit
     pub in_connections: u32,
     /// Defines max established outgoing swarm connection limit
@@ -50,6 +55,8 @@ pub struct NetworkOptions {
     pub pending_out_connections: u32,
     /// Known external addresses
     pub external_addresses: Vec<Multiaddr>,
+    /// IP versions allowed for listening, dialing and storing learned addresses of peers
+    pub ip_version: IpVersion,
 }
 
 impl Default for NetworkOptions {
@@ -60,30 +67,96 @@ impl Default for NetworkOptions {
             listen_on: vec![],
             enable_private_ips: false,
             reserved_peers: Vec::new(),
+            blocked_peers: Vec::new(),
             in_connections: 300,
             out_connections: 100,
             pending_in_connections: 100,
             pending_out_connections: 100,
             external_addresses: Vec::new(),
+            ip_version: IpVersion::default(),
         }
     }
 }
 
+/// Registry of known peers that drops addresses not allowed by IP version, such that addresses
+/// learned from other peers are neither persisted nor dialed
+struct IpVersionKnownPeers<R> {
+    inner: R,
+    ip_version: IpVersion,
+}
+
+impl<R> IpVersionKnownPeers<R> {
+    fn filter_addresses(&self, mut addresses: Vec<Multiaddr>) -> Vec<Multiaddr> {
+        addresses.retain(|address| self.ip_version.allows(address));
+        addresses
+    }
+}
+
+#[async_trait::async_trait]
+impl<R> KnownPeersRegistry for IpVersionKnownPeers<R>
+where
+    R: KnownPeersRegistry,
+{
+    async fn add_known_peer(&mut self, peer_id: PeerId, addresses: Vec<Multiaddr>) {
+        let addresses = self.filter_addresses(addresses);
+        if !addresses.is_empty() {
+            self.inner.add_known_peer(peer_id, addresses).await;
+        }
+    }
+
+    async fn remove_known_peer_addresses(&mut self, peer_id: PeerId, addresses: Vec<Multiaddr>) {
+        self.inner
+            .remove_known_peer_addresses(peer_id, addresses)
+            .await;
+    }
+
+    fn remove_all_known_peer_addresses(&mut self, peer_id: PeerId) {
+        self.inner.remove_all_known_peer_addresses(peer_id);
+    }
+
+    async fn all_known_peers(&mut self) -> Vec<(PeerId, Vec<Multiaddr>)> {
+        // Addresses persisted before IP version was restricted are skipped
+        self.inner
+            .all_known_peers()
+            .await
+            .into_iter()
+            .filter_map(|(peer_id, addresses)| {
+                let addresses = self.filter_addresses(addresses);
+
+                (!addresses.is_empty()).then_some((peer_id, addresses))
+            })
+            .collect()
+    }
+
+    async fn run(&mut self) {
+        self.inner.run().await;
+    }
+
+    fn on_unreachable_address(
+        &mut self,
+        handler: Arc<dyn Fn(&PeerAddressRemovedEvent) + Send + Sync + 'static>,
+    ) -> Option<HandlerId> {
+        self.inner.on_unreachable_address(handler)
+    }
+}
+
 #[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn create_network<NC>(
     protocol_prefix: String,
     base_path: &Path,
     NetworkOptions {
         keypair,
-        listen_on,
-        bootstrap_nodes,
+        mut listen_on,
+        mut bootstrap_nodes,
         enable_private_ips,
-        reserved_peers,
+        mut reserved_peers,
+        blocked_peers,
         in_connections,
         out_connections,
         pending_in_connections,
         pending_out_connections,
-        external_addresses,
+        mut external_addresses,
+        ip_version,
     }: NetworkOptions,
     weak_plotted_pieces: Weak<Mutex<Option<PlottedPieces>>>,
     node_client: NC,
@@ -95,16 +168,27 @@ where
     let span = info_span!("Network");
     let _enter = span.enter();
 
+    listen_on.retain(|address| ip_version.allows(address));
+    bootstrap_nodes.retain(|address| ip_version.allows(address));
+    reserved_peers.retain(|address| ip_version.allows(address));
+    external_addresses.retain(|address| ip_version.allows(address));
+
     let networking_parameters_registry = KnownPeersManager::new(KnownPeersManagerConfig {
         path: Some(base_path.join("known_addresses.bin").into_boxed_path()),
         ignore_peer_list: strip_peer_id(bootstrap_nodes.clone())
             .into_iter()
             .map(|(peer_id, _)| peer_id)
+            .chain(blocked_peers.iter().copied())
             .collect::<HashSet<_>>(),
         cache_size: KNOWN_PEERS_CACHE_SIZE,
         ..Default::default()
     })
-    .map(Box::new)?;
+    .map(|known_peers_manager| {
+        Box::new(IpVersionKnownPeers {
+            inner: known_peers_manager,
+            ip_version,
+        })
+    })?;
 
     let default_config = Config::new(protocol_prefix, keypair.into(), farmer_cache.clone(), None);
     let config = Config {
@@ -241,6 +325,20 @@ where
             }))
             .detach();
 
+            // Commands are only processed once node runner is running, hence background task
+            tokio::spawn({
+                let node = node.clone();
+
+                async move {
+                    for peer_id in blocked_peers {
+                        if let Err(error) = node.ban_peer(peer_id).await {
+                            error!(%error, %peer_id, "Failed to block peer");
+                        }
+                    }
+                }
+                .in_current_span()
+            });
+
             // Consider returning HandlerId instead of each `detach()` calls for other usages.
             (node, node_runner)
         })
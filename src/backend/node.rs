@@ -1,9 +1,11 @@
 mod utils;
 
+use crate::backend::config::IpVersion;
 use crate::backend::farmer::maybe_node_client::MaybeNodeRpcClient;
 use crate::backend::node::utils::account_storage_key;
 use crate::backend::utils::{Handler, HandlerFn};
 use crate::PosTable;
+use clap::Parser;
 use event_listener_primitives::HandlerId;
 use frame_system::AccountInfo;
 use futures::{select, FutureExt, StreamExt};
@@ -16,9 +18,12 @@ use sc_client_db::PruningMode;
 use sc_consensus_slots::SlotProportion;
 use sc_informant::OutputFormat;
 use sc_network::config::{Ed25519Secret, NodeKeyConfig, NonReservedPeerMode, SetConfig};
+use sc_network_sync::warp::WarpSyncPhase;
 use sc_service::{BlocksPruning, Configuration, GenericChainSpec};
 use sc_storage_monitor::{StorageMonitorParams, StorageMonitorService};
 use serde_json::Value;
+use sp_api::ProvideRuntimeApi;
+use sp_consensus_subspace::SubspaceApi;
 use sp_core::crypto::Ss58AddressFormat;
 use sp_core::storage::StorageKey;
 use sp_core::H256;
@@ -29,8 +34,9 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use subspace_core_primitives::{BlockNumber, PublicKey};
+use subspace_core_primitives::{BlockNumber, PublicKey, Record, SolutionRange};
 use subspace_farmer::NodeRpcClient;
+use subspace_farmer_components::sector::sector_size;
 use subspace_networking::libp2p::identity::ed25519::Keypair;
 use subspace_networking::libp2p::Multiaddr;
 use subspace_networking::Node;
@@ -44,12 +50,14 @@ use subspace_service::sync_from_dsn::DsnSyncPieceGetter;
 use subspace_service::{FullClient, NewFull};
 use tokio::fs;
 use tokio::time::MissedTickBehavior;
-use tracing::error;
+use tracing::{debug, error, warn};
 
 pub(super) const GENESIS_HASH: &str =
     "0c121c75f4ef450f40619e1fca9d1e8e7fbabc42c895bc4790801e85d5a91c34";
 pub(super) const RPC_PORT: u16 = 19944;
 const SYNC_STATUS_EVENT_INTERVAL: Duration = Duration::from_secs(5);
+/// Solution range only changes once per era, so there is no need to estimate space pledged often
+const SPACE_PLEDGED_EVENT_INTERVAL: Duration = Duration::from_secs(60);
 
 /// The maximum number of characters for a node name.
 const NODE_NAME_MAX_LENGTH: usize = 64;
@@ -83,6 +91,27 @@ pub struct ChainInfo {
 pub enum SyncKind {
     Dsn,
     Regular,
+    /// Warp sync downloads state of a recent block instead of importing all blocks one by one, so
+    /// best block number jumps rather than increasing steadily
+    Warp(WarpSyncStage),
+}
+
+/// Stage of warp sync
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WarpSyncStage {
+    /// Looking for peers and downloading proofs to find target block
+    Preparing,
+    /// Downloading state of the target block
+    DownloadingState {
+        /// Progress in %
+        percentage: u32,
+        /// Bytes downloaded so far
+        downloaded: u64,
+    },
+    /// Importing downloaded state
+    ImportingState,
+    /// Downloading blocks preceding target block in the background
+    DownloadingBlocks,
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
@@ -108,10 +137,21 @@ pub struct BlockImported {
     pub reward_address_balance: Balance,
 }
 
+/// Space pledged to the network, estimated from the current solution range
+#[derive(Debug, Copy, Clone)]
+pub struct SpacePledged {
+    /// Total space pledged to the network in bytes
+    pub total_space_pledged: u64,
+    /// Size of one sector in bytes, such that space pledged by local farms can be compared to total
+    pub sector_size: u64,
+}
+
 #[derive(Default, Debug)]
 struct Handlers {
     sync_state_change: Handler<SyncState>,
+    connected_peers_change: Handler<u32>,
     block_imported: Handler<BlockImported>,
+    space_pledged: Handler<SpacePledged>,
 }
 
 pub(super) struct ConsensusNode {
@@ -181,12 +221,54 @@ impl ConsensusNode {
             self.handlers
                 .sync_state_change
                 .call_simple(&last_sync_state);
+            let mut last_connected_peers = 0;
+            self.handlers
+                .connected_peers_change
+                .call_simple(&last_connected_peers);
 
             loop {
                 sync_status_interval.tick().await;
 
                 if let Ok(sync_status) = self.full_node.sync_service.status().await {
-                    let sync_state = if sync_status.state.is_major_syncing() {
+                    let connected_peers = sync_status.num_connected_peers;
+                    if connected_peers != last_connected_peers {
+                        self.handlers
+                            .connected_peers_change
+                            .call_simple(&connected_peers);
+
+                        last_connected_peers = connected_peers;
+                    }
+
+                    let warp_sync_stage = if let Some(state_sync) = &sync_status.state_sync {
+                        Some(WarpSyncStage::DownloadingState {
+                            percentage: state_sync.percentage,
+                            downloaded: state_sync.size,
+                        })
+                    } else if let Some(warp_sync) = &sync_status.warp_sync {
+                        match warp_sync.phase {
+                            WarpSyncPhase::DownloadingState => {
+                                Some(WarpSyncStage::DownloadingState {
+                                    percentage: 0,
+                                    downloaded: warp_sync.total_bytes,
+                                })
+                            }
+                            WarpSyncPhase::ImportingState => Some(WarpSyncStage::ImportingState),
+                            WarpSyncPhase::DownloadingBlocks(_) => {
+                                Some(WarpSyncStage::DownloadingBlocks)
+                            }
+                            WarpSyncPhase::Complete => None,
+                            _ => Some(WarpSyncStage::Preparing),
+                        }
+                    } else {
+                        None
+                    };
+
+                    let sync_state = if let Some(warp_sync_stage) = warp_sync_stage {
+                        SyncState::Syncing {
+                            kind: SyncKind::Warp(warp_sync_stage),
+                            target: sync_status.best_seen_block.unwrap_or_default(),
+                        }
+                    } else if sync_status.state.is_major_syncing() {
                         SyncState::Syncing {
                             kind: if self.pause_sync.load(Ordering::Acquire) {
                                 // We are pausing Substrate's sync during sync from DNS
@@ -211,6 +293,19 @@ impl ConsensusNode {
             }
         };
 
+        let space_pledged_notifications_fut = async {
+            let mut space_pledged_interval = tokio::time::interval(SPACE_PLEDGED_EVENT_INTERVAL);
+            space_pledged_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            loop {
+                space_pledged_interval.tick().await;
+
+                if let Some(space_pledged) = get_space_pledged(&self.full_node.client) {
+                    self.handlers.space_pledged.call_simple(&space_pledged);
+                }
+            }
+        };
+
         let task_manager = self.full_node.task_manager.future();
 
         select! {
@@ -220,6 +315,9 @@ impl ConsensusNode {
             _ = sync_status_notifications_fut.fuse() => {
                 // Nothing else to do
             }
+            _ = space_pledged_notifications_fut.fuse() => {
+                // Nothing else to do
+            }
         }
 
         Ok(())
@@ -248,9 +346,74 @@ impl ConsensusNode {
         self.handlers.sync_state_change.add(callback)
     }
 
+    /// Number of peers node is connected to, only called when it changes
+    pub(super) fn on_connected_peers_change(&self, callback: HandlerFn<u32>) -> HandlerId {
+        self.handlers.connected_peers_change.add(callback)
+    }
+
     pub(super) fn on_block_imported(&self, callback: HandlerFn<BlockImported>) -> HandlerId {
         self.handlers.block_imported.add(callback)
     }
+
+    /// Periodic estimate of space pledged to the network, only accurate once node is synced
+    pub(super) fn on_space_pledged(&self, callback: HandlerFn<SpacePledged>) -> HandlerId {
+        self.handlers.space_pledged.add(callback)
+    }
+}
+
+/// Called periodically and failures are expected while runtime state is not available yet (for
+/// example during sync), hence logged at debug level only
+fn get_space_pledged(client: &FullClient<RuntimeApi>) -> Option<SpacePledged> {
+    let best_hash = client.info().best_hash;
+    let runtime_api = client.runtime_api();
+
+    let solution_range = match runtime_api.solution_ranges(best_hash) {
+        Ok(solution_ranges) => solution_ranges.current,
+        Err(error) => {
+            debug!(%error, "Failed to query solution ranges");
+            return None;
+        }
+    };
+    let slot_probability = match runtime_api.chain_constants(best_hash) {
+        Ok(chain_constants) => chain_constants.slot_probability(),
+        Err(error) => {
+            debug!(%error, "Failed to query chain constants");
+            return None;
+        }
+    };
+    let max_pieces_in_sector = match runtime_api.max_pieces_in_sector(best_hash) {
+        Ok(max_pieces_in_sector) => max_pieces_in_sector,
+        Err(error) => {
+            debug!(%error, "Failed to query max pieces in sector");
+            return None;
+        }
+    };
+
+    let sector_size = sector_size(max_pieces_in_sector) as u64;
+    let sectors = solution_range_to_sectors(solution_range, slot_probability, max_pieces_in_sector);
+
+    Some(SpacePledged {
+        total_space_pledged: sectors.saturating_mul(sector_size),
+        sector_size,
+    })
+}
+
+/// Number of sectors pledged to the network that corresponds to given solution range, inverse of
+/// how consensus adjusts solution range to keep expected number of solutions per slot equal to
+/// slot probability
+fn solution_range_to_sectors(
+    solution_range: SolutionRange,
+    slot_probability: (u64, u64),
+    max_pieces_in_sector: u16,
+) -> u64 {
+    let solution_range_per_sector = SolutionRange::MAX
+        // Account for slot probability
+        / slot_probability.1 * slot_probability.0
+        // Take sector size and probability of hitting occupied s-bucket in sector into account
+        / (u64::from(max_pieces_in_sector) * Record::NUM_CHUNKS as u64
+            / Record::NUM_S_BUCKETS as u64);
+
+    solution_range_per_sector / solution_range.max(1)
 }
 
 fn get_total_account_balance(
@@ -351,13 +514,59 @@ pub(super) fn generate_node_name() -> String {
     }
 }
 
+/// Extra node arguments for options that are not exposed in UI, names match those of Substrate
+/// node CLI
+#[derive(Debug, Clone, Parser)]
+#[command(disable_help_flag = true, disable_version_flag = true)]
+pub struct NodeExtraArgs {
+    /// Number of inbound peers to accept
+    #[arg(long)]
+    in_peers: Option<u32>,
+    /// Number of outbound peers to maintain
+    #[arg(long)]
+    out_peers: Option<u32>,
+    /// Allow connecting to private IPv4/IPv6 addresses
+    #[arg(long)]
+    allow_private_ips: bool,
+    /// Public address that other nodes will use to connect to this node
+    #[arg(long)]
+    public_addr: Vec<sc_network::Multiaddr>,
+    /// Address to expose Prometheus metrics on
+    #[arg(long)]
+    prometheus_listen_on: Option<SocketAddr>,
+    /// Node name reported to telemetry
+    #[arg(long)]
+    name: Option<String>,
+}
+
 fn create_consensus_chain_config(
     keypair: &Keypair,
     base_path: PathBuf,
     substrate_port: u16,
+    ip_version: IpVersion,
     chain_spec: ChainSpec,
+    extra_args: &NodeExtraArgs,
 ) -> Configuration {
     let telemetry_endpoints = chain_spec.0.telemetry_endpoints().clone();
+    let mut listen_on = vec![
+        sc_network::Multiaddr::from(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+            .with(sc_network::multiaddr::Protocol::Tcp(substrate_port)),
+        sc_network::Multiaddr::from(IpAddr::V6(Ipv6Addr::UNSPECIFIED))
+            .with(sc_network::multiaddr::Protocol::Tcp(substrate_port)),
+    ];
+    listen_on.retain(|address| ip_version.allows(address));
+    // Substrate networking doesn't allow filtering addresses learned from other peers, but peers
+    // will not be able to connect over IP version that isn't listened on
+    let mut bootstrap_nodes = chain_spec.0.boot_nodes().to_vec();
+    bootstrap_nodes.retain(|address| ip_version.allows(address));
+    let mut public_addresses = extra_args.public_addr.clone();
+    public_addresses.retain(|address| {
+        let allowed = ip_version.allows(address);
+        if !allowed {
+            warn!(%address, %ip_version, "Public address is not allowed by IP version, ignoring");
+        }
+        allowed
+    });
 
     let consensus_chain_config = SubstrateConfiguration {
         impl_name: env!("CARGO_PKG_NAME").to_string(),
@@ -366,14 +575,9 @@ fn create_consensus_chain_config(
         base_path,
         transaction_pool: Default::default(),
         network: SubstrateNetworkConfiguration {
-            listen_on: vec![
-                sc_network::Multiaddr::from(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
-                    .with(sc_network::multiaddr::Protocol::Tcp(substrate_port)),
-                sc_network::Multiaddr::from(IpAddr::V6(Ipv6Addr::UNSPECIFIED))
-                    .with(sc_network::multiaddr::Protocol::Tcp(substrate_port)),
-            ],
-            public_addresses: Vec::new(),
-            bootstrap_nodes: chain_spec.0.boot_nodes().to_vec(),
+            listen_on,
+            public_addresses,
+            bootstrap_nodes,
             node_key: NodeKeyConfig::Ed25519(Ed25519Secret::Input(
                 libp2p_identity_substate::ed25519::SecretKey::try_from_bytes(
                     keypair.secret().as_ref().to_vec(),
@@ -382,14 +586,14 @@ fn create_consensus_chain_config(
             )),
             default_peers_set: SetConfig {
                 // Substrate's default
-                in_peers: 8,
+                in_peers: extra_args.in_peers.unwrap_or(8),
                 // Substrate's default
-                out_peers: 32,
+                out_peers: extra_args.out_peers.unwrap_or(32),
                 reserved_nodes: Vec::new(),
                 non_reserved_mode: NonReservedPeerMode::Accept,
             },
-            node_name: generate_node_name(),
-            allow_private_ips: false,
+            node_name: extra_args.name.clone().unwrap_or_else(generate_node_name),
+            allow_private_ips: extra_args.allow_private_ips,
             force_synced: false,
         },
         state_pruning: PruningMode::ArchiveCanonical,
@@ -410,7 +614,7 @@ fn create_consensus_chain_config(
             // Substrate's default
             max_subscriptions_per_connection: 1024,
         },
-        prometheus_listen_on: None,
+        prometheus_listen_on: extra_args.prometheus_listen_on,
         telemetry_endpoints,
         force_authoring: false,
         chain_spec: Box::new(chain_spec.0),
@@ -426,15 +630,18 @@ pub(super) async fn create_consensus_node(
     keypair: &Keypair,
     base_path: PathBuf,
     substrate_port: u16,
+    ip_version: IpVersion,
     chain_spec: ChainSpec,
     piece_getter: Arc<dyn DsnSyncPieceGetter + Send + Sync + 'static>,
     node: Node,
     maybe_node_rpc_client: &MaybeNodeRpcClient,
+    extra_args: &NodeExtraArgs,
 ) -> Result<ConsensusNode, ConsensusNodeCreationError> {
     set_default_ss58_version(&chain_spec);
 
     let pot_external_entropy = pot_external_entropy(&chain_spec)?;
-    let dsn_bootstrap_nodes = dsn_bootstrap_nodes(&chain_spec)?;
+    let mut dsn_bootstrap_nodes = dsn_bootstrap_nodes(&chain_spec)?;
+    dsn_bootstrap_nodes.retain(|address| ip_version.allows(address));
 
     let chain_info = ChainInfo {
         chain_name: chain_spec.0.name().to_string(),
@@ -448,8 +655,14 @@ pub(super) async fn create_consensus_node(
             .to_string(),
     };
 
-    let consensus_chain_config =
-        create_consensus_chain_config(keypair, base_path.clone(), substrate_port, chain_spec);
+    let consensus_chain_config = create_consensus_chain_config(
+        keypair,
+        base_path.clone(),
+        substrate_port,
+        ip_version,
+        chain_spec,
+        extra_args,
+    );
     let pause_sync = Arc::clone(&consensus_chain_config.network.pause_sync);
 
     let consensus_node = {
@@ -521,17 +734,16 @@ pub(super) async fn create_consensus_node(
         sc_service::Error::Other(format!("Failed to start storage monitor: {error:?}"))
     })?;
 
-    let node_client = NodeRpcClient::new(&format!("ws://127.0.0.1:{RPC_PORT}"))
-        .await
-        .map_err(|error| {
-            sc_service::Error::Application(
-                format!("Failed to connect to internal node RPC: {error}").into(),
-            )
-        })?;
+    let node_rpc_url = format!("ws://127.0.0.1:{RPC_PORT}");
+    let node_client = NodeRpcClient::new(&node_rpc_url).await.map_err(|error| {
+        sc_service::Error::Application(
+            format!("Failed to connect to internal node RPC: {error}").into(),
+        )
+    })?;
 
     // Inject working node client into wrapper we have created before such that networking can
     // respond to incoming requests properly
-    maybe_node_rpc_client.inject(node_client);
+    maybe_node_rpc_client.inject(node_client, node_rpc_url);
 
     Ok(ConsensusNode::new(consensus_node, pause_sync, chain_info))
 }
@@ -0,0 +1,265 @@
+use crate::backend::config::NodeSnapshot;
+use crate::backend::http::http_client;
+use crate::backend::{BackendNotification, BackendNotificationSender, LoadingStep};
+use futures::channel::mpsc;
+use reqwest::Url;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+
+/// Snapshot archive is downloaded into this file in node directory before being imported
+const DOWNLOADED_SNAPSHOT_FILE_NAME: &str = "snapshot.tar.zst.tmp";
+/// Snapshot archive is unpacked into this directory in node directory before being moved in place
+const SNAPSHOT_IMPORT_DIRECTORY_NAME: &str = "snapshot-import.tmp";
+/// Directory with node database, both in node directory and in snapshot archive
+const DATABASE_DIRECTORY_NAME: &str = "db";
+/// How often to send download progress notifications
+const DOWNLOAD_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, thiserror::Error)]
+enum NodeSnapshotError {
+    #[error("Failed to download snapshot: {0}")]
+    Download(#[from] reqwest::Error),
+    #[error("Snapshot I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Snapshot checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+    #[error("Snapshot doesn't contain node database directory \"{DATABASE_DIRECTORY_NAME}\"")]
+    MissingDatabase,
+    #[error("Notification channel closed")]
+    Notification(#[from] mpsc::SendError),
+}
+
+/// Bootstrap node database from snapshot in case node doesn't have a database yet.
+///
+/// Snapshot is a zstd-compressed tar archive with `db` directory inside, it is downloaded (or read
+/// from local file), verified against checksum unless explicitly skipped and unpacked into node
+/// directory. Any failure is logged and reported as [`LoadingStep::NodeSnapshotImportFailed`],
+/// after which node will sync normally, only error returned is when notification can't be sent.
+pub(super) async fn import_node_snapshot(
+    node_path: &Path,
+    node_snapshot: &NodeSnapshot,
+    proxy: Option<&Url>,
+    notifications_sender: &mut BackendNotificationSender,
+) -> Result<(), mpsc::SendError> {
+    if fs::try_exists(node_path.join(DATABASE_DIRECTORY_NAME))
+        .await
+        .unwrap_or(true)
+    {
+        info!("Node database already exists, skipping snapshot import");
+        return Ok(());
+    }
+
+    let downloaded_snapshot_path = node_path.join(DOWNLOADED_SNAPSHOT_FILE_NAME);
+    let import_directory = node_path.join(SNAPSHOT_IMPORT_DIRECTORY_NAME);
+
+    let result = import(
+        node_path,
+        node_snapshot,
+        &downloaded_snapshot_path,
+        &import_directory,
+        proxy,
+        notifications_sender,
+    )
+    .await;
+
+    if let Err(error) = fs::remove_file(&downloaded_snapshot_path).await
+        && error.kind() != io::ErrorKind::NotFound
+    {
+        warn!(
+            %error,
+            path = %downloaded_snapshot_path.display(),
+            "Failed to remove downloaded snapshot"
+        );
+    }
+    if let Err(error) = fs::remove_dir_all(&import_directory).await
+        && error.kind() != io::ErrorKind::NotFound
+    {
+        warn!(
+            %error,
+            path = %import_directory.display(),
+            "Failed to remove snapshot import directory"
+        );
+    }
+
+    match result {
+        Ok(()) => {
+            info!(source = %node_snapshot.source, "Node snapshot imported successfully");
+
+            notifications_sender
+                .send(BackendNotification::Loading {
+                    step: LoadingStep::NodeSnapshotImported,
+                    progress: 0.0,
+                })
+                .await
+        }
+        Err(NodeSnapshotError::Notification(error)) => Err(error),
+        Err(error) => {
+            warn!(
+                %error,
+                source = %node_snapshot.source,
+                "Failed to import node snapshot, node will sync normally"
+            );
+
+            notifications_sender
+                .send(BackendNotification::Loading {
+                    step: LoadingStep::NodeSnapshotImportFailed {
+                        error: error.to_string(),
+                    },
+                    progress: 0.0,
+                })
+                .await
+        }
+    }
+}
+
+async fn import(
+    node_path: &Path,
+    node_snapshot: &NodeSnapshot,
+    downloaded_snapshot_path: &Path,
+    import_directory: &Path,
+    proxy: Option<&Url>,
+    notifications_sender: &mut BackendNotificationSender,
+) -> Result<(), NodeSnapshotError> {
+    let snapshot_path = if node_snapshot.source.starts_with("http://")
+        || node_snapshot.source.starts_with("https://")
+    {
+        download(
+            &node_snapshot.source,
+            downloaded_snapshot_path,
+            proxy,
+            notifications_sender,
+        )
+        .await?;
+
+        downloaded_snapshot_path.to_path_buf()
+    } else {
+        PathBuf::from(&node_snapshot.source)
+    };
+
+    // Configuration validation ensures checksum is only missing when verification was explicitly
+    // skipped
+    if let Some(expected_checksum) = &node_snapshot.checksum {
+        notifications_sender
+            .send(BackendNotification::Loading {
+                step: LoadingStep::VerifyingNodeSnapshot,
+                progress: 0.0,
+            })
+            .await?;
+
+        let actual_checksum = tokio::task::spawn_blocking({
+            let snapshot_path = snapshot_path.clone();
+
+            move || checksum(&snapshot_path)
+        })
+        .await
+        .map_err(io::Error::other)??;
+
+        if !actual_checksum.eq_ignore_ascii_case(expected_checksum.trim()) {
+            return Err(NodeSnapshotError::ChecksumMismatch {
+                expected: expected_checksum.clone(),
+                actual: actual_checksum,
+            });
+        }
+    } else {
+        warn!(
+            source = %node_snapshot.source,
+            "Importing node snapshot without checksum verification as configured"
+        );
+    }
+
+    notifications_sender
+        .send(BackendNotification::Loading {
+            step: LoadingStep::ImportingNodeSnapshot,
+            progress: 0.0,
+        })
+        .await?;
+
+    tokio::task::spawn_blocking({
+        let import_directory = import_directory.to_path_buf();
+
+        move || unpack(&snapshot_path, &import_directory)
+    })
+    .await
+    .map_err(io::Error::other)??;
+
+    let imported_database = import_directory.join(DATABASE_DIRECTORY_NAME);
+    if !fs::try_exists(&imported_database).await? {
+        return Err(NodeSnapshotError::MissingDatabase);
+    }
+
+    // Rename is atomic, such that node never sees partially imported database
+    fs::rename(imported_database, node_path.join(DATABASE_DIRECTORY_NAME)).await?;
+
+    Ok(())
+}
+
+async fn download(
+    url: &str,
+    destination: &Path,
+    proxy: Option<&Url>,
+    notifications_sender: &mut BackendNotificationSender,
+) -> Result<(), NodeSnapshotError> {
+    let mut response = http_client(proxy)?
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?;
+    let total = response.content_length();
+    let mut file = fs::File::create(destination).await?;
+    let mut downloaded = 0;
+    let mut last_progress_notification = None::<Instant>;
+
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+
+        if last_progress_notification
+            .map(|instant| instant.elapsed() >= DOWNLOAD_PROGRESS_INTERVAL)
+            .unwrap_or(true)
+        {
+            last_progress_notification.replace(Instant::now());
+
+            notifications_sender
+                .send(BackendNotification::Loading {
+                    step: LoadingStep::DownloadingNodeSnapshot { downloaded, total },
+                    progress: total
+                        .map(|total| downloaded as f32 / total.max(1) as f32 * 100.0)
+                        .unwrap_or_default(),
+                })
+                .await?;
+        }
+    }
+
+    file.sync_all().await?;
+
+    Ok(())
+}
+
+/// Hex-encoded BLAKE3 hash of the file
+fn checksum(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0; 1024 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+fn unpack(snapshot_path: &Path, import_directory: &Path) -> io::Result<()> {
+    let decoder = zstd::stream::read::Decoder::new(File::open(snapshot_path)?)?;
+    let mut archive = tar::Archive::new(decoder);
+
+    archive.unpack(import_directory)
+}
@@ -0,0 +1,69 @@
+//! Minimal HTTP readiness probe for orchestration systems like Kubernetes or Docker
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info};
+
+/// How long to wait for request before responding anyway
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// Only the beginning of the request is read, its contents don't matter
+const REQUEST_BUFFER_SIZE: usize = 1024;
+
+/// Respond to every HTTP request on `address` with `200 OK` when `ready` is `true` and with
+/// `503 Service Unavailable` otherwise, regardless of path or method.
+///
+/// Only returns on failure to bind to `address`.
+pub async fn serve_readiness_endpoint(
+    address: SocketAddr,
+    ready: Arc<AtomicBool>,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(address).await?;
+    info!(%address, "Readiness endpoint started");
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _peer_address)) => stream,
+            Err(error) => {
+                debug!(%error, "Failed to accept readiness endpoint connection");
+                continue;
+            }
+        };
+
+        let ready = ready.load(Ordering::Acquire);
+        tokio::spawn(async move {
+            if let Err(error) = respond(stream, ready).await {
+                debug!(%error, "Failed to respond to readiness request");
+            }
+        });
+    }
+}
+
+async fn respond(mut stream: TcpStream, ready: bool) -> io::Result<()> {
+    // Request needs to be read before responding, otherwise some clients will see connection
+    // reset instead of response
+    let mut request = [0; REQUEST_BUFFER_SIZE];
+    let _ = tokio::time::timeout(REQUEST_READ_TIMEOUT, stream.read(&mut request)).await;
+
+    let (status, body) = if ready {
+        ("200 OK", "ready\n")
+    } else {
+        ("503 Service Unavailable", "not ready\n")
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+        Content-Type: text/plain\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\
+        \r\n\
+        {body}",
+        body.len()
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
@@ -0,0 +1,111 @@
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use std::{fs as std_fs, io};
+use subspace_farmer::farm::{SectorPlottingDetails, SectorUpdate};
+use tokio::fs;
+use tracing::warn;
+
+const PLOTTING_STATS_FILE_SUFFIX: &str = ".plotting_stats.json";
+
+/// All-time plotting statistics, persisted across restarts
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlottingStats {
+    /// Total number of sectors plotted, including replotted sectors
+    pub sectors_plotted: u64,
+    /// Number of sectors that were replotted
+    pub sectors_replotted: u64,
+    /// Cumulative time spent plotting sectors
+    pub plotting_time: Duration,
+}
+
+impl PlottingStats {
+    /// Statistics are stored next to the config file and named after it, such that instances with
+    /// different config files have separate statistics even in the same directory
+    pub fn path(config_file_path: &Path) -> PathBuf {
+        let mut file_name = config_file_path
+            .file_stem()
+            .map(|file_stem| file_stem.to_os_string())
+            .unwrap_or_default();
+        file_name.push(PLOTTING_STATS_FILE_SUFFIX);
+
+        config_file_path.with_file_name(file_name)
+    }
+
+    /// Read statistics from file, returns default statistics if file doesn't exist yet
+    pub async fn read_from_path(stats_file_path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(stats_file_path).await {
+            Ok(stats) => serde_json::from_str(&stats)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) => {
+                if error.kind() == io::ErrorKind::NotFound {
+                    Ok(Self::default())
+                } else {
+                    Err(error)
+                }
+            }
+        }
+    }
+
+    pub async fn write_to_path(&self, stats_file_path: &Path) -> io::Result<()> {
+        fs::write(stats_file_path, self.to_json()).await
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Stats serialization is infallible; qed")
+    }
+
+    /// Update statistics with sector update, returns `true` if statistics changed
+    pub fn update(&mut self, sector_update: &SectorUpdate) -> bool {
+        let SectorUpdate::Plotting(SectorPlottingDetails::Finished {
+            old_plotted_sector,
+            time,
+            ..
+        }) = sector_update
+        else {
+            return false;
+        };
+
+        self.sectors_plotted += 1;
+        if old_plotted_sector.is_some() {
+            self.sectors_replotted += 1;
+        }
+        self.plotting_time += *time;
+
+        true
+    }
+}
+
+/// Writes plotting statistics to file when dropped, such that sectors plotted since last periodic
+/// write are not lost when backend is shut down
+#[derive(Debug)]
+pub struct PlottingStatsFlushGuard {
+    plotting_stats: Arc<Mutex<PlottingStats>>,
+    stats_file_path: PathBuf,
+}
+
+impl PlottingStatsFlushGuard {
+    pub fn new(plotting_stats: Arc<Mutex<PlottingStats>>, stats_file_path: PathBuf) -> Self {
+        Self {
+            plotting_stats,
+            stats_file_path,
+        }
+    }
+}
+
+impl Drop for PlottingStatsFlushGuard {
+    fn drop(&mut self) {
+        let plotting_stats = *self.plotting_stats.lock();
+        // Async runtime might be shutting down already, hence blocking write
+        if let Err(error) = std_fs::write(&self.stats_file_path, plotting_stats.to_json()) {
+            warn!(
+                %error,
+                path = %self.stats_file_path.display(),
+                "Failed to write plotting stats on shutdown"
+            );
+        }
+    }
+}
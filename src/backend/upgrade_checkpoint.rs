@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Suffix appended to config file stem to get checkpoint file name
+const UPGRADE_CHECKPOINT_FILE_SUFFIX: &str = ".upgrade.json";
+
+/// Progress of wiping farms and node during upgrade to a new chain persisted across restarts, such
+/// that interrupted upgrade is completed on the next start instead of starting with partially wiped
+/// farms and node
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct UpgradeCheckpoint {
+    /// Farms that were already wiped
+    #[serde(default)]
+    pub(super) wiped_farms: Vec<PathBuf>,
+    /// Node was already wiped
+    #[serde(default)]
+    pub(super) node_wiped: bool,
+}
+
+impl UpgradeCheckpoint {
+    /// Checkpoint is stored next to the config file and named after it, such that instances with
+    /// different config files in the same directory have separate checkpoints
+    pub(super) fn path(config_file_path: &Path) -> PathBuf {
+        let mut file_name = config_file_path
+            .file_stem()
+            .map(|file_stem| file_stem.to_os_string())
+            .unwrap_or_default();
+        file_name.push(UPGRADE_CHECKPOINT_FILE_SUFFIX);
+
+        config_file_path.with_file_name(file_name)
+    }
+
+    /// Read checkpoint from file, returns `None` if upgrade is not in progress
+    pub(super) async fn read_from_path(checkpoint_file_path: &Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(checkpoint_file_path).await {
+            Ok(checkpoint) => serde_json::from_str::<Self>(&checkpoint)
+                .map(Some)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) => {
+                if error.kind() == io::ErrorKind::NotFound {
+                    Ok(None)
+                } else {
+                    Err(error)
+                }
+            }
+        }
+    }
+
+    /// Remove checkpoint once upgrade is completed
+    pub(super) async fn remove(checkpoint_file_path: &Path) -> io::Result<()> {
+        match fs::remove_file(checkpoint_file_path).await {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Write checkpoint atomically, such that it is never partially written if application is
+    /// killed in the middle of the upgrade
+    pub(super) async fn write_to_path(&self, checkpoint_file_path: &Path) -> io::Result<()> {
+        let tmp_file_path = checkpoint_file_path.with_extension("json.tmp");
+        fs::write(
+            &tmp_file_path,
+            serde_json::to_string_pretty(self)
+                .expect("Checkpoint serialization is infallible; qed"),
+        )
+        .await?;
+        fs::rename(&tmp_file_path, checkpoint_file_path).await
+    }
+}
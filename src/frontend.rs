@@ -1,4 +1,7 @@
+pub mod app_settings;
 pub mod configuration;
 pub mod loading;
 pub mod new_version;
 pub mod running;
+pub mod statistics;
+pub mod translations;
@@ -0,0 +1,86 @@
+//! Runtime preferences of the application (as opposed to farming configuration), stored in
+//! application data directory separately from configuration file.
+//!
+//! Settings that only affect how UI looks or behaves belong here rather than in [`RawConfig`], such
+//! that changing them doesn't require backend to write configuration and they are available before
+//! configuration is loaded (or decrypted).
+
+use crate::backend::config::{RawConfig, UiConfiguration};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use tracing::warn;
+
+const APP_SETTINGS_FILE_NAME: &str = "settings.json";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    /// Show details of each farm on running screen
+    #[serde(default)]
+    pub farm_details: bool,
+    /// Preferences edited in configuration view (theme, notifications, privacy, etc.)
+    #[serde(default)]
+    pub ui: UiConfiguration,
+    /// UI configuration was moved from configuration file already
+    #[serde(default)]
+    pub ui_migrated: bool,
+}
+
+impl AppSettings {
+    /// Path to settings file in application data directory, instances supervised together have
+    /// separate settings, such that they don't overwrite each other's settings and each one
+    /// migrates UI configuration from its own configuration file
+    pub fn path(app_data_dir: &Path, instance_tag: Option<&str>) -> PathBuf {
+        match instance_tag {
+            Some(instance_tag) => app_data_dir.join(format!("settings-{instance_tag}.json")),
+            None => app_data_dir.join(APP_SETTINGS_FILE_NAME),
+        }
+    }
+
+    /// Read settings from file, defaults are used if settings were not saved yet or can't be read
+    pub fn load(path: &Path) -> Self {
+        let contents = match fs::read(path) {
+            Ok(contents) => contents,
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    warn!(%error, path = %path.display(), "Failed to read app settings");
+                }
+                return Self::default();
+            }
+        };
+
+        match serde_json::from_slice(&contents) {
+            Ok(app_settings) => app_settings,
+            Err(error) => {
+                warn!(%error, path = %path.display(), "Failed to parse app settings, using defaults");
+                Self::default()
+            }
+        }
+    }
+
+    /// Move UI configuration stored in configuration file by older versions into settings, only
+    /// happens once, returns `true` if settings were changed and need to be saved
+    pub fn migrate_legacy_ui(&mut self, maybe_raw_config: Option<&RawConfig>) -> bool {
+        if self.ui_migrated {
+            return false;
+        }
+
+        if let Some(ui) = maybe_raw_config.and_then(RawConfig::legacy_ui) {
+            self.ui = ui;
+        }
+        self.ui_migrated = true;
+
+        true
+    }
+
+    /// Write settings to file, existing settings are replaced atomically
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        let contents =
+            serde_json::to_vec_pretty(self).expect("Settings serialization is infallible; qed");
+
+        fs::write(&tmp_path, contents)?;
+        fs::rename(&tmp_path, path)
+    }
+}
@@ -1,38 +1,175 @@
 mod farm;
 
-use crate::backend::config::{NetworkConfiguration, RawConfig};
+use crate::backend::config::encryption::{config_passphrase_set, set_config_passphrase};
+use crate::backend::config::{
+    import_farm, parse_extra_args, ConfigError, EventCommands, ExtraArgs, Farm,
+    FarmerConfiguration, FiatConfiguration, IpVersion, NetworkConfiguration, NodeSnapshot,
+    PieceCache, ProcessPriority, RawConfig, Theme, UiConfiguration, MAX_FARMS,
+    MAX_MIN_PLOTTING_PEERS, MAX_PLOTTING_RATE_LIMIT,
+};
+use crate::backend::disks::{detect_disks, DetectedDisk};
+use crate::backend::farmer::FarmerExtraArgs;
+use crate::backend::node::NodeExtraArgs;
 use crate::frontend::configuration::farm::{
-    FarmWidget, FarmWidgetInit, FarmWidgetInput, FarmWidgetOutput,
+    suggested_farm_size, FarmWidget, FarmWidgetInit, FarmWidgetInput, FarmWidgetOutput,
 };
+use bytesize::ByteSize;
 use gtk::prelude::*;
-use relm4::factory::FactoryVecDeque;
+use relm4::factory::{FactoryVecDeque, FactoryVecDequeGuard};
 use relm4::prelude::*;
 use relm4_components::open_dialog::{
     OpenDialog, OpenDialogMsg, OpenDialogResponse, OpenDialogSettings,
 };
 use relm4_icons::icon_name;
+use std::mem;
+use std::num::{NonZeroU16, NonZeroU32, NonZeroUsize};
 use std::ops::Deref;
 use std::path::PathBuf;
+use std::str::FromStr;
+use subspace_core_primitives::PublicKey;
 use subspace_farmer::utils::ss58::parse_ss58_reward_address;
 use tracing::{debug, warn};
+use zeroize::Zeroizing;
+
+/// Signing context used by Substrate wallets for sr25519 signatures
+const SUBSTRATE_SIGNING_CONTEXT: &[u8] = b"substrate";
+/// Node needs at least this much space, used for warning in setup wizard
+const MIN_NODE_FREE_SPACE: ByteSize = ByteSize::gib(100);
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum DirectoryKind {
     NodePath,
     FarmPath(DynamicIndex),
+    /// Existing farm to be imported
+    ImportFarm,
+    /// Dedicated piece cache directory
+    PieceCache,
+}
+
+/// Step of the setup wizard shown on the first run
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum WizardStep {
+    RewardAddress,
+    NodePath,
+    Farms,
+}
+
+impl WizardStep {
+    fn number(&self) -> usize {
+        match self {
+            Self::RewardAddress => 1,
+            Self::NodePath => 2,
+            Self::Farms => 3,
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Self::RewardAddress => "Rewards address",
+            Self::NodePath => "Node storage",
+            Self::Farms => "Farms",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::RewardAddress => {
+                "Farming rewards are paid to this address. You don't need to create an account \
+                on-chain, just create an address in a Substrate wallet like SubWallet or \
+                polkadot{.js} extension and paste it below (address for any Substrate chain in SS58 \
+                format works)."
+            }
+            Self::NodePath => {
+                "Node stores blockchain data and is needed to farm. Select a directory on a disk \
+                with at least 100 GiB of free space, good quality SSD recommended."
+            }
+            Self::Farms => {
+                "Farms are where plots are stored, the more space is pledged, the more rewards \
+                can be expected. Any SSD works, high endurance not necessary. Once directory is \
+                selected, free space on corresponding disk is detected and most of it is \
+                suggested as farm size."
+            }
+        }
+    }
+
+    fn previous(&self) -> Option<Self> {
+        match self {
+            Self::RewardAddress => None,
+            Self::NodePath => Some(Self::RewardAddress),
+            Self::Farms => Some(Self::NodePath),
+        }
+    }
+
+    fn next(&self) -> Option<Self> {
+        match self {
+            Self::RewardAddress => Some(Self::NodePath),
+            Self::NodePath => Some(Self::Farms),
+            Self::Farms => None,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum ConfigurationInput {
+    /// Start guided setup wizard instead of showing the whole configuration form at once
+    StartWizard,
+    WizardNext,
+    WizardBack,
+    /// Leave setup wizard and show the whole configuration form
+    SkipWizard,
     AddFarm,
+    ImportFarm,
+    DetectDisks,
+    /// Add farm on previously detected disk with specified index
+    AddDetectedDisk(usize),
     RewardAddressChanged(String),
+    VerifyRewardAddressOwnershipChanged(bool),
+    RewardAddressSignatureChanged(String),
     OpenDirectory(DirectoryKind),
     DirectorySelected(PathBuf),
     SubstratePortChanged(u16),
     SubspacePortChanged(u16),
     FasterNetworkingChanged(bool),
+    IpVersionChanged(IpVersion),
+    AutoRecoverFarmsChanged(bool),
+    CheckDisksOnStartupChanged(bool),
+    ClampAllocatedSpaceChanged(bool),
+    VerifyPiecesChanged(bool),
+    FarmOnlyWhenSyncedChanged(bool),
+    DisablePlotCacheChanged(bool),
+    PreallocateFarmsChanged(bool),
+    PreallocateFarmsWithoutZeroingChanged(bool),
+    PlottingRateLimitChanged(u32),
+    MinPlottingPeersChanged(u32),
+    PieceCacheSizeChanged(String),
+    /// Stop using dedicated piece cache, piece caches of farms will be used instead
+    ClearPieceCache,
+    StartMinimizedChanged(bool),
+    TestPlottingAfterStartChanged(bool),
+    ThemeChanged(Theme),
+    HighContrastChanged(bool),
+    ProcessPriorityChanged(ProcessPriority),
+    RuntimeThreadsChanged(usize),
+    RestartOnUpdateChanged(bool),
+    HideRewardAddressChanged(bool),
+    HideBalanceChanged(bool),
+    RewardNotificationsChanged(bool),
+    ErrorNotificationsChanged(bool),
+    QuietHoursChanged(bool),
+    QuietHoursStartChanged(u8),
+    QuietHoursEndChanged(u8),
+    FarmingDeadlineWarningMarginChanged(u16),
+    PlottingStallTimeoutChanged(u16),
+    EncryptConfigChanged(bool),
+    ConfigPassphraseChanged(String),
+    NodeExtraArgsChanged(String),
+    FarmerExtraArgsChanged(String),
+    MoveUp(DynamicIndex),
+    MoveDown(DynamicIndex),
     Delete(DynamicIndex),
     Reconfigure(RawConfig),
+    /// UI configuration stored in application settings, edited together with configuration
+    UiConfiguration(UiConfiguration),
     Start,
     Back,
     Cancel,
@@ -42,12 +179,33 @@ pub enum ConfigurationInput {
 
 #[derive(Debug)]
 pub enum ConfigurationOutput {
+    /// UI configuration is saved to application settings rather than configuration file, sent
+    /// before [`Self::StartWithNewConfig`] and [`Self::ConfigUpdate`]
+    UiConfigUpdate(UiConfiguration),
+    /// Plotting self-test was requested to run once farmer is started, sent before
+    /// [`Self::StartWithNewConfig`]
+    TestPlottingAfterStart,
     StartWithNewConfig(RawConfig),
     ConfigUpdate(RawConfig),
     Back,
     Close,
 }
 
+#[derive(Debug)]
+pub enum ConfigurationCommandOutput {
+    FarmImported(Result<Farm, ConfigError>),
+    DisksDetected(Vec<DetectedDisk>),
+    NodePathFreeSpace {
+        path: PathBuf,
+        result: Result<ByteSize, String>,
+    },
+    FarmFreeSpace {
+        index: DynamicIndex,
+        path: PathBuf,
+        result: Result<ByteSize, String>,
+    },
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum MaybeValid<T> {
     Unknown(T),
@@ -98,6 +256,11 @@ struct NetworkConfigurationWrapper {
     substrate_port: MaybeValid<u16>,
     subspace_port: MaybeValid<u16>,
     faster_networking: bool,
+    ip_version: IpVersion,
+    /// Not editable in UI yet, but needs to be preserved on reconfiguration
+    reserved_peers: Vec<String>,
+    /// Not editable in UI yet, but needs to be preserved on reconfiguration
+    blocked_peers: Vec<String>,
 }
 
 impl Default for NetworkConfigurationWrapper {
@@ -113,6 +276,9 @@ impl From<NetworkConfiguration> for NetworkConfigurationWrapper {
             substrate_port: MaybeValid::Unknown(config.substrate_port),
             subspace_port: MaybeValid::Unknown(config.subspace_port),
             faster_networking: config.faster_networking,
+            ip_version: config.ip_version,
+            reserved_peers: config.reserved_peers,
+            blocked_peers: config.blocked_peers,
         }
     }
 }
@@ -120,12 +286,52 @@ impl From<NetworkConfiguration> for NetworkConfigurationWrapper {
 #[derive(Debug)]
 pub struct ConfigurationView {
     reward_address: MaybeValid<String>,
+    /// Require signature of [`Self::reward_address_challenge`] by the reward address before it is
+    /// accepted
+    verify_reward_address_ownership: bool,
+    reward_address_challenge: String,
+    /// Hex-encoded signature of the challenge, valid only if it matches reward address
+    reward_address_signature: MaybeValid<String>,
     node_path: MaybeValid<PathBuf>,
     farms: FactoryVecDeque<FarmWidget>,
     network_configuration: NetworkConfigurationWrapper,
+    farmer_configuration: FarmerConfiguration,
+    ui_configuration: UiConfiguration,
+    /// Dedicated piece cache directory, empty if piece caches of farms are used
+    piece_cache_path: MaybeValid<PathBuf>,
+    piece_cache_size: MaybeValid<String>,
+    /// Not editable in UI yet, but needs to be preserved on reconfiguration
+    node_snapshot: Option<NodeSnapshot>,
+    /// Not editable in UI, but needs to be preserved on reconfiguration
+    event_commands: EventCommands,
+    /// Not editable in UI, but needs to be preserved on reconfiguration
+    fiat: Option<FiatConfiguration>,
+    /// Whitespace-separated extra node arguments
+    node_extra_args: MaybeValid<String>,
+    /// Whitespace-separated extra farmer arguments
+    farmer_extra_args: MaybeValid<String>,
+    node_extra_args_error: Option<String>,
+    farmer_extra_args_error: Option<String>,
     pending_directory_selection: Option<DirectoryKind>,
     open_dialog: Controller<OpenDialog>,
     reconfiguration: bool,
+    /// Current step of the setup wizard, `None` when the whole configuration form is shown
+    wizard_step: Option<WizardStep>,
+    /// Free space on the disk where node path is located or error if it failed to be checked
+    node_path_free_space: Option<Result<ByteSize, String>>,
+    /// Error that happened during the last farm import attempt
+    farm_import_error: Option<String>,
+    /// Disks detected on request, `None` if detection wasn't requested or is in progress
+    detected_disks: Option<Vec<DetectedDisk>>,
+    detecting_disks: bool,
+    /// Buttons for adding farms on detected disks, rebuilt whenever detected disks change
+    detected_disks_list: gtk::Box,
+    /// Write configuration file encrypted with a passphrase
+    encrypt_config: bool,
+    /// New passphrase for configuration encryption, empty to keep the current one
+    config_passphrase: Zeroizing<String>,
+    /// Run plotting self-test next to each farm once farmer is started
+    test_plotting_after_start: bool,
 }
 
 #[relm4::component(pub)]
@@ -133,7 +339,7 @@ impl Component for ConfigurationView {
     type Init = gtk::Window;
     type Input = ConfigurationInput;
     type Output = ConfigurationOutput;
-    type CommandOutput = ();
+    type CommandOutput = ConfigurationCommandOutput;
 
     view! {
         #[root]
@@ -147,11 +353,58 @@ impl Component for ConfigurationView {
                     set_orientation: gtk::Orientation::Vertical,
                     set_spacing: 20,
 
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 10,
+                        #[watch]
+                        set_visible: model.wizard_step.is_some(),
+
+                        gtk::Label {
+                            add_css_class: "heading",
+                            set_halign: gtk::Align::Start,
+                            #[watch]
+                            set_label: &model.wizard_step.map(|step| {
+                                format!("Step {} of 3: {}", step.number(), step.title())
+                            })
+                            .unwrap_or_default(),
+                        },
+
+                        gtk::Label {
+                            set_halign: gtk::Align::Start,
+                            #[watch]
+                            set_label: model.wizard_step
+                                .map(|step| step.description())
+                                .unwrap_or_default(),
+                            set_wrap: true,
+                        },
+
+                        gtk::Box {
+                            set_spacing: 10,
+                            #[watch]
+                            set_visible: model.wizard_step == Some(WizardStep::RewardAddress),
+
+                            gtk::LinkButton {
+                                set_label: "SubWallet",
+                                set_uri: "https://www.subwallet.app/",
+                            },
+
+                            gtk::LinkButton {
+                                set_label: "polkadot{.js} extension",
+                                set_uri: "https://polkadot.js.org/extension/",
+                            },
+                        },
+                    },
+
                     gtk::ListBox {
                         gtk::ListBoxRow {
                             set_activatable: false,
                             set_margin_bottom: 10,
                             set_selectable: false,
+                            #[watch]
+                            set_visible: matches!(
+                                model.wizard_step,
+                                None | Some(WizardStep::NodePath)
+                            ),
 
                             gtk::Box {
                                 set_orientation: gtk::Orientation::Vertical,
@@ -200,11 +453,53 @@ impl Component for ConfigurationView {
                                         set_label: "Select",
                                     },
                                 },
+
+                                gtk::Label {
+                                    #[watch]
+                                    set_css_classes: match &model.node_path_free_space {
+                                        Some(Ok(free_space)) if *free_space < MIN_NODE_FREE_SPACE => {
+                                            &["warning-label"]
+                                        }
+                                        Some(Err(_)) => &["error-label"],
+                                        _ => &["dim-label"],
+                                    },
+                                    set_halign: gtk::Align::Start,
+                                    #[watch]
+                                    set_label: &match &model.node_path_free_space {
+                                        Some(Ok(free_space)) if *free_space < MIN_NODE_FREE_SPACE => {
+                                            format!(
+                                                "Only {} of free space available, at least {} is \
+                                                recommended",
+                                                free_space.to_string_as(true),
+                                                MIN_NODE_FREE_SPACE.to_string_as(true),
+                                            )
+                                        }
+                                        Some(Ok(free_space)) => {
+                                            format!(
+                                                "{} of free space available",
+                                                free_space.to_string_as(true),
+                                            )
+                                        }
+                                        Some(Err(error)) => {
+                                            format!("Failed to check free space: {error}")
+                                        }
+                                        None => String::new(),
+                                    },
+                                    #[watch]
+                                    set_visible: model.wizard_step.is_some()
+                                        && model.node_path_free_space.is_some(),
+                                    set_wrap: true,
+                                },
                             },
                         },
                         gtk::ListBoxRow {
                             set_activatable: false,
                             set_selectable: false,
+                            #[watch]
+                            set_visible: matches!(
+                                model.wizard_step,
+                                None | Some(WizardStep::RewardAddress)
+                            ),
 
                             gtk::Box {
                                 set_orientation: gtk::Orientation::Vertical,
@@ -239,12 +534,77 @@ impl Component for ConfigurationView {
                                     set_secondary_icon_sensitive: false,
                                     #[track = "model.reward_address.unknown()"]
                                     set_text: &model.reward_address,
+                                    #[watch]
+                                    set_visibility: !model.ui_configuration.hide_reward_address,
                                     set_tooltip_markup: Some(
                                         "Use Subwallet or polkadot{.js} extension or any other \
                                         Substrate wallet to create it first (address for any Substrate \
                                         chain in SS58 format works)"
                                     ),
                                 },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Verify ownership:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::VerifyRewardAddressOwnershipChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.verify_reward_address_ownership,
+                                        set_tooltip:
+                                            "Prove that you control rewards address by signing a message with your wallet, protects against typos in the address that would make rewards unrecoverable",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_orientation: gtk::Orientation::Vertical,
+                                    set_spacing: 10,
+                                    #[watch]
+                                    set_visible: model.verify_reward_address_ownership,
+
+                                    gtk::Label {
+                                        set_halign: gtk::Align::Start,
+                                        set_label: "Sign the following message with the wallet that holds rewards address \
+                                            (for example using \"Sign message\" in polkadot{.js} extension) and paste \
+                                            resulting signature below:",
+                                        set_wrap: true,
+                                    },
+
+                                    gtk::Label {
+                                        add_css_class: "monospace",
+                                        set_halign: gtk::Align::Start,
+                                        set_label: &model.reward_address_challenge,
+                                        set_selectable: true,
+                                        set_wrap: true,
+                                    },
+
+                                    gtk::Entry {
+                                        connect_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::RewardAddressSignatureChanged(
+                                                entry.text().into()
+                                            ));
+                                        },
+                                        set_placeholder_text: Some("0x..."),
+                                        #[watch]
+                                        set_secondary_icon_name: model.reward_address_signature.icon(),
+                                        set_secondary_icon_activatable: false,
+                                        set_secondary_icon_sensitive: false,
+                                        #[watch]
+                                        set_tooltip: if model.reward_address_signature.valid() {
+                                            "Signature is valid, rewards address is confirmed"
+                                        } else {
+                                            "Signature must be created by rewards address entered above"
+                                        },
+                                    },
+                                },
                             },
                         },
                     },
@@ -253,10 +613,37 @@ impl Component for ConfigurationView {
                     //  unfortunately render before other fields
                     #[local_ref]
                     configuration_list_box -> gtk::ListBox {
+                        #[watch]
+                        set_visible: matches!(model.wizard_step, None | Some(WizardStep::Farms)),
+                    },
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 10,
+                        #[watch]
+                        set_visible: matches!(model.wizard_step, None | Some(WizardStep::Farms))
+                            && (model.detecting_disks || model.detected_disks.is_some()),
+
+                        gtk::Label {
+                            add_css_class: "heading",
+                            set_halign: gtk::Align::Start,
+                            #[watch]
+                            set_label: if model.detecting_disks {
+                                "Detecting disks..."
+                            } else if model.detected_disks.as_ref().is_some_and(Vec::is_empty) {
+                                "No disks with enough free space found"
+                            } else {
+                                "Detected disks, click to add farm"
+                            },
+                        },
+
+                        model.detected_disks_list.clone(),
                     },
 
                     gtk::Expander {
                         set_label: Some("Advanced configuration"),
+                        #[watch]
+                        set_visible: model.wizard_step.is_none(),
 
                         gtk::Box {
                             set_orientation: gtk::Orientation::Vertical,
@@ -357,149 +744,1204 @@ impl Component for ConfigurationView {
                                                 "By default networking is optimized for consumer routers, but if you have more powerful setup, faster networking may improve sync speed and other processes",
                                         },
                                     },
-                                },
-                            },
-                        },
-                    },
 
-                    gtk::Box {
-                        gtk::Box {
-                            set_halign: gtk::Align::Start,
-                            set_hexpand: true,
-                            set_spacing: 10,
-
-                            gtk::Button {
-                                connect_clicked => ConfigurationInput::AddFarm,
+                                    gtk::Box {
+                                        set_spacing: 10,
 
-                                gtk::Label {
-                                    set_label: "Add farm",
-                                    set_margin_all: 10,
+                                        gtk::Label {
+                                            set_label: "IP version:"
+                                        },
+                                        gtk::DropDown::from_strings(&["IPv4 and IPv6", "IPv4 only", "IPv6 only"]) {
+                                            connect_selected_notify[sender] => move |drop_down| {
+                                                let ip_version = match drop_down.selected() {
+                                                    1 => IpVersion::Ipv4,
+                                                    2 => IpVersion::Ipv6,
+                                                    _ => IpVersion::Dual,
+                                                };
+                                                sender.input(ConfigurationInput::IpVersionChanged(ip_version));
+                                            },
+                                            #[watch]
+                                            set_selected: match model.network_configuration.ip_version {
+                                                IpVersion::Dual => 0,
+                                                IpVersion::Ipv4 => 1,
+                                                IpVersion::Ipv6 => 2,
+                                            },
+                                            set_tooltip:
+                                                "Restrict node and DSN networking to one IP version if the other one is broken on your network and causes long connection timeouts",
+                                        },
+                                    },
                                 },
                             },
-                        },
 
-                        if model.reconfiguration {
                             gtk::Box {
-                                set_halign: gtk::Align::End,
+                                set_orientation: gtk::Orientation::Vertical,
                                 set_spacing: 10,
 
-                                gtk::Button {
-                                    connect_clicked => ConfigurationInput::Cancel,
+                                gtk::Label {
+                                    add_css_class: "heading",
+                                    set_halign: gtk::Align::Start,
+                                    set_label: "Farmer",
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
 
                                     gtk::Label {
-                                        set_label: "Cancel",
-                                        set_margin_all: 10,
+                                        set_label: "Recover farms after errors:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::AutoRecoverFarmsChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.farmer_configuration.auto_recover_farms,
+                                        set_tooltip:
+                                            "Periodically try to re-initialize farm that stopped due to an error (for example brief disk disconnection) and resume farming",
                                     },
                                 },
 
-                                gtk::Button {
-                                    add_css_class: "suggested-action",
-                                    connect_clicked => ConfigurationInput::Save,
-                                    #[watch]
-                                    set_sensitive: model.reward_address.valid()
-                                        && model.node_path.valid()
-                                        && !model.farms.is_empty()
-                                        && model.farms.iter().all(FarmWidget::valid),
+                                gtk::Box {
+                                    set_spacing: 10,
 
                                     gtk::Label {
-                                        set_label: "Save",
-                                        set_margin_all: 10,
+                                        set_label: "Check disks on startup:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::CheckDisksOnStartupChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.farmer_configuration.check_disks_on_startup,
+                                        set_tooltip:
+                                            "Benchmark farm and piece cache directories on startup and warn in logs about disks that are likely too slow for farming, takes a few seconds per directory",
                                     },
                                 },
-                            }
-                        } else {
-                            gtk::Box {
-                                set_halign: gtk::Align::End,
-                                set_spacing: 10,
 
-                                gtk::Button {
-                                    connect_clicked => ConfigurationInput::Back,
+                                gtk::Box {
+                                    set_spacing: 10,
 
                                     gtk::Label {
-                                        set_label: "Back",
-                                        set_margin_all: 10,
+                                        set_label: "Verify pieces:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::VerifyPiecesChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.farmer_configuration.verify_pieces,
+                                        set_tooltip:
+                                            "Verify pieces retrieved for plotting against KZG commitment of their segment and re-fetch invalid pieces, increases CPU usage during plotting",
                                     },
                                 },
 
-                                gtk::Button {
-                                    add_css_class: "suggested-action",
-                                    connect_clicked => ConfigurationInput::Start,
-                                    #[watch]
-                                    set_sensitive:
-                                        model.reward_address.valid()
-                                            && model.node_path.valid()
-                                            && !model.farms.is_empty()
-                                            && model.farms.iter().all(FarmWidget::valid),
+                                gtk::Box {
+                                    set_spacing: 10,
 
                                     gtk::Label {
-                                        set_label: "Start",
-                                        set_margin_all: 10,
+                                        set_label: "Farm only when node is synced:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::FarmOnlyWhenSyncedChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.farmer_configuration.farm_only_when_synced,
+                                        set_tooltip:
+                                            "Don't attempt farming until node is fully synced to avoid failed audits, plotting continues at full speed regardless",
                                     },
                                 },
-                            }
-                        },
-                    },
-                },
-            },
-        }
-    }
 
-    fn init(
-        parent_root: Self::Init,
-        root: Self::Root,
-        sender: ComponentSender<Self>,
-    ) -> ComponentParts<Self> {
-        let open_dialog = OpenDialog::builder()
-            .transient_for_native(&parent_root)
-            .launch(OpenDialogSettings {
-                folder_mode: true,
-                accept_label: "Select".to_string(),
-                ..OpenDialogSettings::default()
-            })
-            .forward(sender.input_sender(), |response| match response {
-                OpenDialogResponse::Accept(path) => ConfigurationInput::DirectorySelected(path),
-                OpenDialogResponse::Cancel => ConfigurationInput::Ignore,
-            });
+                                gtk::Box {
+                                    set_spacing: 10,
 
-        let mut farms = FactoryVecDeque::builder()
-            .launch(gtk::ListBox::new())
-            .forward(sender.input_sender(), |output| match output {
-                FarmWidgetOutput::OpenDirectory(index) => {
-                    ConfigurationInput::OpenDirectory(DirectoryKind::FarmPath(index))
-                }
-                FarmWidgetOutput::ValidityUpdate => ConfigurationInput::Ignore,
-                FarmWidgetOutput::Delete(index) => ConfigurationInput::Delete(index),
-            });
+                                    gtk::Label {
+                                        set_label: "Disable plot cache:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::DisablePlotCacheChanged(
+                                                state
+                                            ));
 
-        farms.guard().push_back(FarmWidgetInit::default());
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.farmer_configuration.disable_plot_cache,
+                                        set_tooltip:
+                                            "Don't cache pieces in not yet plotted part of farms, avoids writing cached pieces to disk that will be overwritten by plotting later at the cost of fewer pieces being available locally for the network and for plotting",
+                                    },
+                                },
 
-        let model = Self {
-            reward_address: Default::default(),
-            node_path: Default::default(),
-            farms,
-            network_configuration: Default::default(),
-            pending_directory_selection: Default::default(),
-            open_dialog,
-            reconfiguration: false,
-        };
+                                gtk::Box {
+                                    set_spacing: 10,
 
-        let configuration_list_box = model.farms.widget();
-        let widgets = view_output!();
+                                    gtk::Label {
+                                        set_label: "Pre-allocate farms:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::PreallocateFarmsChanged(
+                                                state
+                                            ));
 
-        ComponentParts { model, widgets }
-    }
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.farmer_configuration.preallocate_farms,
+                                        set_tooltip:
+                                            "Allocate disk space for the whole farm on startup before plotting, reduces fragmentation and improves proving performance on file systems that fragment. Fast on Linux, but on Windows and macOS zeroes are written to the whole unplotted part of each farm, which can take hours for large farms on the first start",
+                                    },
+                                },
 
-    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
-        self.process_input(input, sender);
-    }
-}
+                                gtk::Box {
+                                    set_spacing: 10,
+                                    set_visible: cfg!(windows),
 
-impl ConfigurationView {
-    fn process_input(&mut self, input: ConfigurationInput, sender: ComponentSender<Self>) {
+                                    gtk::Label {
+                                        set_label: "Pre-allocate farms without zeroing:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::PreallocateFarmsWithoutZeroingChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.farmer_configuration.preallocate_farms_without_zeroing,
+                                        #[watch]
+                                        set_sensitive: model.farmer_configuration.preallocate_farms,
+                                        set_tooltip:
+                                            "Mark pre-allocated space as valid data instead of writing zeroes to it, which is nearly instant, but only works when application runs with administrator rights. Whatever was stored on disk in that area before (including deleted files) becomes readable through the farm file until plotting overwrites it, only enable this on disks that don't contain sensitive deleted data",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Reduce allocated space to available:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::ClampAllocatedSpaceChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.farmer_configuration.clamp_allocated_space,
+                                        set_tooltip:
+                                            "When allocated space of a farm or piece cache exceeds available disk space, use available space instead (with a warning in logs) rather than refusing to start",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Plotting rate limit (sectors/minute):"
+                                    },
+                                    gtk::SpinButton {
+                                        connect_value_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::PlottingRateLimitChanged(
+                                                entry.value().round() as u32
+                                            ));
+                                        },
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            0.0,
+                                            0.0,
+                                            MAX_PLOTTING_RATE_LIMIT as f64,
+                                            1.0,
+                                            0.0,
+                                            0.0,
+                                        ),
+                                        set_tooltip: "Maximum number of sectors plotted per minute across all farms (for example to keep temperatures under control), 0 for unlimited",
+                                        #[watch]
+                                        set_value: model.farmer_configuration.plotting_rate_limit.map(NonZeroU32::get).unwrap_or_default() as f64,
+                                        set_width_chars: 3,
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Minimum peers before plotting:"
+                                    },
+                                    gtk::SpinButton {
+                                        connect_value_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::MinPlottingPeersChanged(
+                                                entry.value().round() as u32
+                                            ));
+                                        },
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            0.0,
+                                            0.0,
+                                            MAX_MIN_PLOTTING_PEERS as f64,
+                                            1.0,
+                                            0.0,
+                                            0.0,
+                                        ),
+                                        set_tooltip: "Don't start plotting until at least this many peers are connected to avoid stalling on piece retrieval right after start, plotting starts anyway after 10 minutes, 0 to not wait",
+                                        #[watch]
+                                        set_value: model.farmer_configuration.min_plotting_peers.map(NonZeroU32::get).unwrap_or_default() as f64,
+                                        set_width_chars: 3,
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Dedicated piece cache:"
+                                    },
+
+                                    gtk::Box {
+                                        add_css_class: "linked",
+                                        set_hexpand: true,
+
+                                        gtk::Entry {
+                                            set_can_focus: false,
+                                            set_editable: false,
+                                            set_hexpand: true,
+                                            set_placeholder_text: Some("Stored in farms"),
+                                            set_primary_icon_name: Some(icon_name::SSD),
+                                            set_primary_icon_activatable: false,
+                                            set_primary_icon_sensitive: false,
+                                            #[watch]
+                                            set_text: model.piece_cache_path.display().to_string().as_str(),
+                                            set_tooltip_markup: Some(
+                                                "Directory for piece cache that is used instead of piece caches \
+                                                of farms, for example on a small fast SSD, such that its size \
+                                                doesn't depend on the number and size of farms"
+                                            ),
+                                        },
+
+                                        gtk::Button {
+                                            connect_clicked => ConfigurationInput::OpenDirectory(
+                                                DirectoryKind::PieceCache
+                                            ),
+                                            set_label: "Select",
+                                        },
+
+                                        gtk::Button {
+                                            connect_clicked => ConfigurationInput::ClearPieceCache,
+                                            set_label: "Clear",
+                                            #[watch]
+                                            set_sensitive: !model.piece_cache_path.as_os_str().is_empty(),
+                                            set_tooltip: "Use piece caches of farms instead",
+                                        },
+                                    },
+
+                                    gtk::Entry {
+                                        connect_activate[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::PieceCacheSizeChanged(
+                                                entry.text().into()
+                                            ));
+                                        },
+                                        connect_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::PieceCacheSizeChanged(
+                                                entry.text().into()
+                                            ));
+                                        },
+                                        set_placeholder_text: Some("50GB, 100GiB, etc."),
+                                        set_primary_icon_name: Some(icon_name::SIZE_HORIZONTALLY),
+                                        set_primary_icon_activatable: false,
+                                        set_primary_icon_sensitive: false,
+                                        #[watch]
+                                        set_secondary_icon_name: model.piece_cache_size.icon(),
+                                        set_secondary_icon_activatable: false,
+                                        set_secondary_icon_sensitive: false,
+                                        #[watch]
+                                        set_sensitive: !model.piece_cache_path.as_os_str().is_empty(),
+                                        #[track = "model.piece_cache_size.unknown()"]
+                                        set_text: model.piece_cache_size.as_str(),
+                                        set_tooltip_markup: Some(
+                                            "Size of dedicated piece cache in whichever units you prefer"
+                                        ),
+                                        set_width_chars: 12,
+                                    },
+                                },
+                            },
+
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_spacing: 10,
+
+                                gtk::Label {
+                                    add_css_class: "heading",
+                                    set_halign: gtk::Align::Start,
+                                    set_label: "Extra arguments",
+                                },
+
+                                gtk::Label {
+                                    add_css_class: "warning-label",
+                                    set_halign: gtk::Align::Start,
+                                    set_label: "Only a limited set of options is supported, but even supported options may break things, use with care",
+                                    set_wrap: true,
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Node:"
+                                    },
+                                    gtk::Entry {
+                                        connect_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::NodeExtraArgsChanged(
+                                                entry.text().into()
+                                            ));
+                                        },
+                                        set_hexpand: true,
+                                        set_placeholder_text: Some("--in-peers 16 --out-peers 64"),
+                                        #[watch]
+                                        set_secondary_icon_name: model.node_extra_args.icon(),
+                                        set_secondary_icon_activatable: false,
+                                        set_secondary_icon_sensitive: false,
+                                        #[track = "model.node_extra_args.unknown()"]
+                                        set_text: &model.node_extra_args,
+                                        set_tooltip:
+                                            "Supported: --in-peers, --out-peers, --allow-private-ips, --public-addr, --prometheus-listen-on, --name",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Farmer:"
+                                    },
+                                    gtk::Entry {
+                                        connect_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::FarmerExtraArgsChanged(
+                                                entry.text().into()
+                                            ));
+                                        },
+                                        set_hexpand: true,
+                                        set_placeholder_text: Some("--farming-thread-pool-size 8"),
+                                        #[watch]
+                                        set_secondary_icon_name: model.farmer_extra_args.icon(),
+                                        set_secondary_icon_activatable: false,
+                                        set_secondary_icon_sensitive: false,
+                                        #[track = "model.farmer_extra_args.unknown()"]
+                                        set_text: &model.farmer_extra_args,
+                                        set_tooltip:
+                                            "Supported: --farm-during-initial-plotting, --farming-thread-pool-size, --sector-downloading-concurrency, --record-encoding-concurrency",
+                                    },
+                                },
+
+                                gtk::Label {
+                                    add_css_class: "error-label",
+                                    set_halign: gtk::Align::Start,
+                                    #[watch]
+                                    set_label: model.node_extra_args_error
+                                        .as_deref()
+                                        .or(model.farmer_extra_args_error.as_deref())
+                                        .unwrap_or_default(),
+                                    #[watch]
+                                    set_visible: model.node_extra_args_error.is_some()
+                                        || model.farmer_extra_args_error.is_some(),
+                                    set_wrap: true,
+                                },
+                            },
+
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_spacing: 10,
+
+                                gtk::Label {
+                                    add_css_class: "heading",
+                                    set_halign: gtk::Align::Start,
+                                    set_label: "Application",
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Start minimized:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::StartMinimizedChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.ui_configuration.start_minimized,
+                                        set_tooltip:
+                                            "Minimize window on start even when application was started manually rather than automatically on login",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Theme:"
+                                    },
+                                    gtk::DropDown::from_strings(&["System", "Light", "Dark"]) {
+                                        connect_selected_notify[sender] => move |drop_down| {
+                                            let theme = match drop_down.selected() {
+                                                1 => Theme::Light,
+                                                2 => Theme::Dark,
+                                                _ => Theme::System,
+                                            };
+                                            sender.input(ConfigurationInput::ThemeChanged(theme));
+                                        },
+                                        #[watch]
+                                        set_selected: match model.ui_configuration.theme {
+                                            Theme::System => 0,
+                                            Theme::Light => 1,
+                                            Theme::Dark => 2,
+                                        },
+                                        set_tooltip: "Theme takes effect after restart",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Encrypt configuration:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::EncryptConfigChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.encrypt_config,
+                                        set_tooltip: "Encrypt configuration file (reward address, paths, etc.) with a passphrase, which will need to be entered on every start or provided with SPACE_ACRES_CONFIG_PASSPHRASE environment variable",
+                                    },
+                                    gtk::PasswordEntry {
+                                        connect_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::ConfigPassphraseChanged(
+                                                entry.text().into()
+                                            ));
+                                        },
+                                        set_hexpand: true,
+                                        set_show_peek_icon: true,
+                                        set_tooltip: "New passphrase, leave empty to keep the current one",
+                                        #[watch]
+                                        set_visible: model.encrypt_config,
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Process priority:"
+                                    },
+                                    gtk::DropDown::from_strings(&["Normal", "Below normal", "Idle"]) {
+                                        connect_selected_notify[sender] => move |drop_down| {
+                                            let process_priority = match drop_down.selected() {
+                                                1 => ProcessPriority::BelowNormal,
+                                                2 => ProcessPriority::Idle,
+                                                _ => ProcessPriority::Normal,
+                                            };
+                                            sender.input(ConfigurationInput::ProcessPriorityChanged(
+                                                process_priority
+                                            ));
+                                        },
+                                        #[watch]
+                                        set_selected: match model.ui_configuration.process_priority {
+                                            ProcessPriority::Normal => 0,
+                                            ProcessPriority::BelowNormal => 1,
+                                            ProcessPriority::Idle => 2,
+                                        },
+                                        set_tooltip: "Lower OS scheduling priority of the whole application keeps desktop \
+                                            responsive at the cost of slower plotting when computer is busy with \
+                                            other tasks, takes effect after restart",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Runtime threads:"
+                                    },
+                                    gtk::SpinButton {
+                                        connect_value_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::RuntimeThreadsChanged(
+                                                entry.value().round() as usize
+                                            ));
+                                        },
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            0.0,
+                                            0.0,
+                                            1_024.0,
+                                            1.0,
+                                            0.0,
+                                            0.0,
+                                        ),
+                                        set_tooltip: "Number of worker threads of UI and async runtime (networking, node and farmer coordination), \
+                                            plotting and farming use their own threads, 0 to use number of CPU cores, takes effect after restart",
+                                        #[watch]
+                                        set_value: model.ui_configuration.runtime_threads.map(NonZeroUsize::get).unwrap_or_default() as f64,
+                                        set_width_chars: 4,
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "High contrast:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::HighContrastChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.ui_configuration.high_contrast,
+                                        set_tooltip:
+                                            "Use more contrasting colors and make dimmed text fully opaque for better readability",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Restart after update:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::RestartOnUpdateChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.ui_configuration.restart_on_update,
+                                        set_tooltip:
+                                            "Restart application automatically once new version is installed, this will interrupt plotting",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Hide reward address:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::HideRewardAddressChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.ui_configuration.hide_reward_address,
+                                        set_tooltip:
+                                            "Mask reward address in the UI, for example during screen sharing, can also be toggled on the main screen",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Hide balance together with reward address:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::HideBalanceChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.ui_configuration.hide_balance,
+                                        set_tooltip:
+                                            "Also mask reward address balance whenever reward address is hidden",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Notify about received rewards:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::RewardNotificationsChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.ui_configuration.reward_notifications,
+                                        set_tooltip:
+                                            "Show desktop notification when reward address balance increases, received rewards are always shown in the status bar",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Notify about errors:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::ErrorNotificationsChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.ui_configuration.error_notifications,
+                                        set_tooltip:
+                                            "Show desktop notification when a farm crashes or application stops with an error, these are shown during quiet hours too",
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Quiet hours for notifications:"
+                                    },
+                                    gtk::Switch {
+                                        connect_state_set[sender] => move |_switch, state| {
+                                            sender.input(ConfigurationInput::QuietHoursChanged(
+                                                state
+                                            ));
+
+                                            gtk::glib::Propagation::Proceed
+                                        },
+                                        #[watch]
+                                        set_active: model.ui_configuration.quiet_hours.is_some(),
+                                        set_tooltip:
+                                            "Don't show reward notifications during these hours (local time), error notifications are still shown",
+                                    },
+                                    gtk::Label {
+                                        set_label: "from"
+                                    },
+                                    gtk::SpinButton {
+                                        connect_value_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::QuietHoursStartChanged(
+                                                entry.value().round() as u8
+                                            ));
+                                        },
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            0.0,
+                                            0.0,
+                                            23.0,
+                                            1.0,
+                                            0.0,
+                                            0.0,
+                                        ),
+                                        set_tooltip: "Hour when quiet hours start",
+                                        #[watch]
+                                        set_sensitive: model.ui_configuration.quiet_hours.is_some(),
+                                        #[watch]
+                                        set_value: model.ui_configuration.quiet_hours.unwrap_or_default().start_hour as f64,
+                                        set_width_chars: 2,
+                                    },
+                                    gtk::Label {
+                                        set_label: "to"
+                                    },
+                                    gtk::SpinButton {
+                                        connect_value_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::QuietHoursEndChanged(
+                                                entry.value().round() as u8
+                                            ));
+                                        },
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            0.0,
+                                            0.0,
+                                            23.0,
+                                            1.0,
+                                            0.0,
+                                            0.0,
+                                        ),
+                                        set_tooltip: "Hour when quiet hours end",
+                                        #[watch]
+                                        set_sensitive: model.ui_configuration.quiet_hours.is_some(),
+                                        #[watch]
+                                        set_value: model.ui_configuration.quiet_hours.unwrap_or_default().end_hour as f64,
+                                        set_width_chars: 2,
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Farming deadline warning margin (ms):"
+                                    },
+                                    gtk::SpinButton {
+                                        connect_value_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::FarmingDeadlineWarningMarginChanged(
+                                                entry.value().round() as u16
+                                            ));
+                                        },
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            0.0,
+                                            0.0,
+                                            1_000.0,
+                                            50.0,
+                                            0.0,
+                                            0.0,
+                                        ),
+                                        set_tooltip: "Warn in status bar when average auditing or proving time of a farm gets closer than this to the deadline, which usually means disk is too slow, 0 to disable",
+                                        #[watch]
+                                        set_value: model.ui_configuration.farming_deadline_warning_margin.map(NonZeroU16::get).unwrap_or_default() as f64,
+                                        set_width_chars: 4,
+                                    },
+                                },
+
+                                gtk::Box {
+                                    set_spacing: 10,
+
+                                    gtk::Label {
+                                        set_label: "Plotting stall warning timeout (minutes):"
+                                    },
+                                    gtk::SpinButton {
+                                        connect_value_changed[sender] => move |entry| {
+                                            sender.input(ConfigurationInput::PlottingStallTimeoutChanged(
+                                                entry.value().round() as u16
+                                            ));
+                                        },
+                                        set_adjustment: &gtk::Adjustment::new(
+                                            0.0,
+                                            0.0,
+                                            1_440.0,
+                                            5.0,
+                                            0.0,
+                                            0.0,
+                                        ),
+                                        set_tooltip: "Warn in status bar when sectors of a farm are being plotted, but there was no plotting progress for this long (for example stuck on piece retrieval), paused plotting is not considered stalled, 0 to disable",
+                                        #[watch]
+                                        set_value: model.ui_configuration.plotting_stall_timeout.map(NonZeroU16::get).unwrap_or_default() as f64,
+                                        set_width_chars: 4,
+                                    },
+                                },
+                            },
+                        },
+                    },
+
+                    gtk::Box {
+                        gtk::Box {
+                            set_halign: gtk::Align::Start,
+                            set_hexpand: true,
+                            set_spacing: 10,
+                            #[watch]
+                            set_visible: matches!(model.wizard_step, None | Some(WizardStep::Farms)),
+
+                            gtk::Button {
+                                connect_clicked => ConfigurationInput::AddFarm,
+                                #[watch]
+                                set_sensitive: model.farms.len() < MAX_FARMS,
+
+                                gtk::Label {
+                                    set_label: "Add farm",
+                                    set_margin_all: 10,
+                                },
+                            },
+
+                            gtk::Button {
+                                connect_clicked => ConfigurationInput::ImportFarm,
+                                #[watch]
+                                set_sensitive: model.farms.len() < MAX_FARMS,
+                                set_tooltip: "Add farm that was already created elsewhere, for example by CLI farmer, without replotting it",
+
+                                gtk::Label {
+                                    set_label: "Import existing farm",
+                                    set_margin_all: 10,
+                                },
+                            },
+
+                            gtk::Button {
+                                connect_clicked => ConfigurationInput::DetectDisks,
+                                #[watch]
+                                set_sensitive: !model.detecting_disks,
+                                set_tooltip: "Find attached disks with free space and suggest farms on them",
+
+                                gtk::Label {
+                                    set_label: "Detect disks",
+                                    set_margin_all: 10,
+                                },
+                            },
+
+                            gtk::CheckButton {
+                                connect_toggled[sender] => move |check_button| {
+                                    sender.input(ConfigurationInput::TestPlottingAfterStartChanged(
+                                        check_button.is_active()
+                                    ));
+                                },
+                                set_label: Some("Test plotting after start"),
+                                set_tooltip: "Once started, plot a single sector into a temporary directory inside of each farm and read it back to check that plotting works on its disk end-to-end, results are shown in the event log",
+                                // Reconfiguration requires restart, so there is nothing to hook into
+                                #[watch]
+                                set_visible: !model.reconfiguration,
+                                #[watch]
+                                set_active: model.test_plotting_after_start,
+                            },
+
+                            gtk::Label {
+                                add_css_class: "error-label",
+                                #[watch]
+                                set_label: model.farm_import_error.as_deref().unwrap_or_default(),
+                                #[watch]
+                                set_visible: model.farm_import_error.is_some(),
+                                set_wrap: true,
+                            },
+                        },
+
+                        if model.reconfiguration {
+                            gtk::Box {
+                                set_halign: gtk::Align::End,
+                                set_spacing: 10,
+
+                                gtk::Button {
+                                    connect_clicked => ConfigurationInput::Cancel,
+
+                                    gtk::Label {
+                                        set_label: "Cancel",
+                                        set_margin_all: 10,
+                                    },
+                                },
+
+                                gtk::Button {
+                                    add_css_class: "suggested-action",
+                                    connect_clicked => ConfigurationInput::Save,
+                                    #[watch]
+                                    set_sensitive: model.reward_address_accepted()
+                                        && model.node_path.valid()
+                                        && model.farms.iter().any(FarmWidget::enabled)
+                                        && model.farms.iter().all(FarmWidget::valid)
+                                        && model.piece_cache_valid()
+                                        && model.extra_args_valid()
+                                        && model.config_encryption_valid(),
+
+                                    gtk::Label {
+                                        set_label: "Save",
+                                        set_margin_all: 10,
+                                    },
+                                },
+                            }
+                        } else if model.wizard_step.is_some() {
+                            gtk::Box {
+                                set_halign: gtk::Align::End,
+                                set_hexpand: true,
+                                set_spacing: 10,
+
+                                gtk::Button {
+                                    connect_clicked => ConfigurationInput::SkipWizard,
+                                    set_tooltip: "Show all configuration options at once",
+
+                                    gtk::Label {
+                                        set_label: "Skip wizard",
+                                        set_margin_all: 10,
+                                    },
+                                },
+
+                                gtk::Button {
+                                    connect_clicked => ConfigurationInput::WizardBack,
+
+                                    gtk::Label {
+                                        set_label: "Back",
+                                        set_margin_all: 10,
+                                    },
+                                },
+
+                                if model.wizard_step.and_then(|step| step.next()).is_some() {
+                                    gtk::Button {
+                                        add_css_class: "suggested-action",
+                                        connect_clicked => ConfigurationInput::WizardNext,
+                                        #[watch]
+                                        set_sensitive: match model.wizard_step {
+                                            Some(WizardStep::RewardAddress) => model.reward_address_accepted(),
+                                            Some(WizardStep::NodePath) => model.node_path.valid(),
+                                            Some(WizardStep::Farms) | None => false,
+                                        },
+
+                                        gtk::Label {
+                                            set_label: "Next",
+                                            set_margin_all: 10,
+                                        },
+                                    }
+                                } else {
+                                    gtk::Button {
+                                        add_css_class: "suggested-action",
+                                        connect_clicked => ConfigurationInput::Start,
+                                        #[watch]
+                                        set_sensitive:
+                                            model.reward_address_accepted()
+                                                && model.node_path.valid()
+                                                && model.farms.iter().any(FarmWidget::enabled)
+                                                && model.farms.iter().all(FarmWidget::valid)
+                                                && model.piece_cache_valid()
+                                                && model.extra_args_valid()
+                                                && model.config_encryption_valid(),
+
+                                        gtk::Label {
+                                            set_label: "Start",
+                                            set_margin_all: 10,
+                                        },
+                                    }
+                                },
+                            }
+                        } else {
+                            gtk::Box {
+                                set_halign: gtk::Align::End,
+                                set_spacing: 10,
+
+                                gtk::Button {
+                                    connect_clicked => ConfigurationInput::Back,
+
+                                    gtk::Label {
+                                        set_label: "Back",
+                                        set_margin_all: 10,
+                                    },
+                                },
+
+                                gtk::Button {
+                                    add_css_class: "suggested-action",
+                                    connect_clicked => ConfigurationInput::Start,
+                                    #[watch]
+                                    set_sensitive:
+                                        model.reward_address_accepted()
+                                            && model.node_path.valid()
+                                            && model.farms.iter().any(FarmWidget::enabled)
+                                            && model.farms.iter().all(FarmWidget::valid)
+                                            && model.piece_cache_valid()
+                                            && model.extra_args_valid()
+                                            && model.config_encryption_valid(),
+
+                                    gtk::Label {
+                                        set_label: "Start",
+                                        set_margin_all: 10,
+                                    },
+                                },
+                            }
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        parent_root: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let open_dialog = OpenDialog::builder()
+            .transient_for_native(&parent_root)
+            .launch(OpenDialogSettings {
+                folder_mode: true,
+                accept_label: "Select".to_string(),
+                ..OpenDialogSettings::default()
+            })
+            .forward(sender.input_sender(), |response| match response {
+                OpenDialogResponse::Accept(path) => ConfigurationInput::DirectorySelected(path),
+                OpenDialogResponse::Cancel => ConfigurationInput::Ignore,
+            });
+
+        let mut farms = FactoryVecDeque::builder()
+            .launch(gtk::ListBox::new())
+            .forward(sender.input_sender(), |output| match output {
+                FarmWidgetOutput::OpenDirectory(index) => {
+                    ConfigurationInput::OpenDirectory(DirectoryKind::FarmPath(index))
+                }
+                FarmWidgetOutput::ValidityUpdate => ConfigurationInput::Ignore,
+                FarmWidgetOutput::MoveUp(index) => ConfigurationInput::MoveUp(index),
+                FarmWidgetOutput::MoveDown(index) => ConfigurationInput::MoveDown(index),
+                FarmWidgetOutput::Delete(index) => ConfigurationInput::Delete(index),
+            });
+
+        farms.guard().push_back(FarmWidgetInit::default());
+
+        let model = Self {
+            reward_address: Default::default(),
+            verify_reward_address_ownership: false,
+            reward_address_challenge: format!(
+                "Space Acres rewards address ownership: {}",
+                hex::encode(rand::random::<[u8; 16]>())
+            ),
+            reward_address_signature: Default::default(),
+            node_path: Default::default(),
+            farms,
+            network_configuration: Default::default(),
+            farmer_configuration: Default::default(),
+            ui_configuration: Default::default(),
+            piece_cache_path: Default::default(),
+            piece_cache_size: Default::default(),
+            node_snapshot: None,
+            event_commands: Default::default(),
+            fiat: None,
+            node_extra_args: Default::default(),
+            farmer_extra_args: Default::default(),
+            node_extra_args_error: None,
+            farmer_extra_args_error: None,
+            pending_directory_selection: Default::default(),
+            open_dialog,
+            reconfiguration: false,
+            wizard_step: None,
+            node_path_free_space: None,
+            farm_import_error: None,
+            detected_disks: None,
+            detecting_disks: false,
+            detected_disks_list: gtk::Box::new(gtk::Orientation::Vertical, 5),
+            encrypt_config: config_passphrase_set(),
+            config_passphrase: Zeroizing::default(),
+            test_plotting_after_start: false,
+        };
+
+        let configuration_list_box = model.farms.widget();
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        self.process_input(input, sender);
+        self.update_farm_indices();
+    }
+
+    fn update_cmd(
+        &mut self,
+        input: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        self.process_command(input, sender);
+        self.update_farm_indices();
+    }
+}
+
+impl ConfigurationView {
+    fn process_input(&mut self, input: ConfigurationInput, sender: ComponentSender<Self>) {
         match input {
+            ConfigurationInput::StartWizard => {
+                self.wizard_step = Some(WizardStep::RewardAddress);
+            }
+            ConfigurationInput::WizardNext => {
+                self.wizard_step = self.wizard_step.and_then(|step| step.next());
+            }
+            ConfigurationInput::WizardBack => match self.wizard_step {
+                Some(step) => match step.previous() {
+                    Some(previous_step) => {
+                        self.wizard_step.replace(previous_step);
+                    }
+                    None => {
+                        if sender.output(ConfigurationOutput::Back).is_err() {
+                            debug!("Failed to send ConfigurationOutput::Back");
+                        }
+                    }
+                },
+                None => {
+                    warn!("Wizard back requested, but wizard is not active");
+                }
+            },
+            ConfigurationInput::SkipWizard => {
+                self.wizard_step.take();
+            }
             ConfigurationInput::AddFarm => {
                 self.farms.guard().push_back(FarmWidgetInit::default());
             }
+            ConfigurationInput::ImportFarm => {
+                self.farm_import_error.take();
+                self.pending_directory_selection
+                    .replace(DirectoryKind::ImportFarm);
+                self.open_dialog.emit(OpenDialogMsg::Open);
+            }
+            ConfigurationInput::DetectDisks => {
+                self.detecting_disks = true;
+                self.detected_disks.take();
+                self.update_detected_disks_list(&sender);
+
+                sender.oneshot_command(async move {
+                    ConfigurationCommandOutput::DisksDetected(
+                        tokio::task::spawn_blocking(detect_disks)
+                            .await
+                            .unwrap_or_default(),
+                    )
+                });
+            }
+            ConfigurationInput::AddDetectedDisk(index) => {
+                let Some(detected_disks) = &mut self.detected_disks else {
+                    return;
+                };
+                if index >= detected_disks.len() || self.farms.len() >= MAX_FARMS {
+                    return;
+                }
+                let path = detected_disks[index].suggested_farm_path(detected_disks);
+                let disk = detected_disks.remove(index);
+
+                if self.farms.iter().any(|farm| farm.farm().path == path) {
+                    self.update_detected_disks_list(&sender);
+                    return;
+                }
+
+                {
+                    let mut farms = self.farms.guard();
+                    // Replace the only empty farm that is present by default instead of adding
+                    // another one
+                    if farms.len() == 1
+                        && farms
+                            .get(0)
+                            .is_some_and(|farm| farm.farm().path.as_os_str().is_empty())
+                    {
+                        farms.clear();
+                    }
+                    farms.push_back(FarmWidgetInit {
+                        path: MaybeValid::Valid(path),
+                        // `Unknown` is a hack to make it actually render the first time
+                        size: MaybeValid::Unknown(suggested_farm_size(disk.free_space)),
+                        plotting_concurrency: None,
+                        max_disk_temperature: None,
+                        max_plotted_sectors: None,
+                        network_filesystem: false,
+                        numa_node: None,
+                        enabled: true,
+                    });
+                }
+                self.update_detected_disks_list(&sender);
+            }
             ConfigurationInput::OpenDirectory(directory_kind) => {
                 self.pending_directory_selection.replace(directory_kind);
                 self.open_dialog.emit(OpenDialogMsg::Open);
@@ -507,13 +1949,44 @@ impl ConfigurationView {
             ConfigurationInput::DirectorySelected(path) => {
                 match self.pending_directory_selection.take() {
                     Some(DirectoryKind::NodePath) => {
-                        self.node_path = MaybeValid::Valid(path);
+                        self.node_path = MaybeValid::Valid(path.clone());
+                        self.node_path_free_space.take();
+
+                        sender.oneshot_command(async move {
+                            let result = free_space(path.clone()).await;
+                            ConfigurationCommandOutput::NodePathFreeSpace { path, result }
+                        });
                     }
                     Some(DirectoryKind::FarmPath(index)) => {
                         self.farms.send(
                             index.current_index(),
-                            FarmWidgetInput::DirectorySelected(path),
+                            FarmWidgetInput::DirectorySelected(path.clone()),
                         );
+
+                        sender.oneshot_command(async move {
+                            let result = free_space(path.clone()).await;
+                            ConfigurationCommandOutput::FarmFreeSpace {
+                                index,
+                                path,
+                                result,
+                            }
+                        });
+                    }
+                    Some(DirectoryKind::PieceCache) => {
+                        self.piece_cache_path = MaybeValid::Valid(path);
+                    }
+                    Some(DirectoryKind::ImportFarm) => {
+                        if self.farms.iter().any(|farm| farm.farm().path == path) {
+                            self.farm_import_error.replace(format!(
+                                "Farm at \"{}\" is already in configuration",
+                                path.display()
+                            ));
+                            return;
+                        }
+
+                        sender.oneshot_command(async move {
+                            ConfigurationCommandOutput::FarmImported(import_farm(path))
+                        });
                     }
                     None => {
                         warn!(
@@ -532,13 +2005,170 @@ impl ConfigurationView {
             ConfigurationInput::FasterNetworkingChanged(faster_networking) => {
                 self.network_configuration.faster_networking = faster_networking;
             }
+            ConfigurationInput::IpVersionChanged(ip_version) => {
+                self.network_configuration.ip_version = ip_version;
+            }
+            ConfigurationInput::AutoRecoverFarmsChanged(auto_recover_farms) => {
+                self.farmer_configuration.auto_recover_farms = auto_recover_farms;
+            }
+            ConfigurationInput::CheckDisksOnStartupChanged(check_disks_on_startup) => {
+                self.farmer_configuration.check_disks_on_startup = check_disks_on_startup;
+            }
+            ConfigurationInput::ClampAllocatedSpaceChanged(clamp_allocated_space) => {
+                self.farmer_configuration.clamp_allocated_space = clamp_allocated_space;
+            }
+            ConfigurationInput::VerifyPiecesChanged(verify_pieces) => {
+                self.farmer_configuration.verify_pieces = verify_pieces;
+            }
+            ConfigurationInput::FarmOnlyWhenSyncedChanged(farm_only_when_synced) => {
+                self.farmer_configuration.farm_only_when_synced = farm_only_when_synced;
+            }
+            ConfigurationInput::DisablePlotCacheChanged(disable_plot_cache) => {
+                self.farmer_configuration.disable_plot_cache = disable_plot_cache;
+            }
+            ConfigurationInput::PreallocateFarmsChanged(preallocate_farms) => {
+                self.farmer_configuration.preallocate_farms = preallocate_farms;
+            }
+            ConfigurationInput::PreallocateFarmsWithoutZeroingChanged(
+                preallocate_farms_without_zeroing,
+            ) => {
+                self.farmer_configuration.preallocate_farms_without_zeroing =
+                    preallocate_farms_without_zeroing;
+            }
+            ConfigurationInput::PlottingRateLimitChanged(plotting_rate_limit) => {
+                self.farmer_configuration.plotting_rate_limit =
+                    NonZeroU32::new(plotting_rate_limit);
+            }
+            ConfigurationInput::MinPlottingPeersChanged(min_plotting_peers) => {
+                self.farmer_configuration.min_plotting_peers = NonZeroU32::new(min_plotting_peers);
+            }
+            ConfigurationInput::PieceCacheSizeChanged(size) => {
+                self.piece_cache_size = if ByteSize::from_str(&size)
+                    .map(|size| size.as_u64() > 0)
+                    .unwrap_or_default()
+                {
+                    MaybeValid::Valid(size)
+                } else {
+                    MaybeValid::Invalid(size)
+                };
+            }
+            ConfigurationInput::ClearPieceCache => {
+                self.piece_cache_path = MaybeValid::default();
+                self.piece_cache_size = MaybeValid::default();
+            }
+            ConfigurationInput::StartMinimizedChanged(start_minimized) => {
+                self.ui_configuration.start_minimized = start_minimized;
+            }
+            ConfigurationInput::TestPlottingAfterStartChanged(test_plotting_after_start) => {
+                self.test_plotting_after_start = test_plotting_after_start;
+            }
+            ConfigurationInput::ThemeChanged(theme) => {
+                self.ui_configuration.theme = theme;
+            }
+            ConfigurationInput::HighContrastChanged(high_contrast) => {
+                self.ui_configuration.high_contrast = high_contrast;
+            }
+            ConfigurationInput::ProcessPriorityChanged(process_priority) => {
+                self.ui_configuration.process_priority = process_priority;
+            }
+            ConfigurationInput::RuntimeThreadsChanged(runtime_threads) => {
+                self.ui_configuration.runtime_threads = NonZeroUsize::new(runtime_threads);
+            }
+            ConfigurationInput::RestartOnUpdateChanged(restart_on_update) => {
+                self.ui_configuration.restart_on_update = restart_on_update;
+            }
+            ConfigurationInput::HideRewardAddressChanged(hide_reward_address) => {
+                self.ui_configuration.hide_reward_address = hide_reward_address;
+            }
+            ConfigurationInput::HideBalanceChanged(hide_balance) => {
+                self.ui_configuration.hide_balance = hide_balance;
+            }
+            ConfigurationInput::RewardNotificationsChanged(reward_notifications) => {
+                self.ui_configuration.reward_notifications = reward_notifications;
+            }
+            ConfigurationInput::ErrorNotificationsChanged(error_notifications) => {
+                self.ui_configuration.error_notifications = error_notifications;
+            }
+            ConfigurationInput::QuietHoursChanged(quiet_hours) => {
+                self.ui_configuration.quiet_hours =
+                    quiet_hours.then(|| self.ui_configuration.quiet_hours.unwrap_or_default());
+            }
+            ConfigurationInput::QuietHoursStartChanged(start_hour) => {
+                if let Some(quiet_hours) = &mut self.ui_configuration.quiet_hours {
+                    quiet_hours.start_hour = start_hour;
+                }
+            }
+            ConfigurationInput::QuietHoursEndChanged(end_hour) => {
+                if let Some(quiet_hours) = &mut self.ui_configuration.quiet_hours {
+                    quiet_hours.end_hour = end_hour;
+                }
+            }
+            ConfigurationInput::FarmingDeadlineWarningMarginChanged(margin) => {
+                self.ui_configuration.farming_deadline_warning_margin = NonZeroU16::new(margin);
+            }
+            ConfigurationInput::PlottingStallTimeoutChanged(timeout) => {
+                self.ui_configuration.plotting_stall_timeout = NonZeroU16::new(timeout);
+            }
+            ConfigurationInput::EncryptConfigChanged(encrypt_config) => {
+                self.encrypt_config = encrypt_config;
+            }
+            ConfigurationInput::ConfigPassphraseChanged(config_passphrase) => {
+                self.config_passphrase = Zeroizing::new(config_passphrase);
+            }
+            ConfigurationInput::NodeExtraArgsChanged(extra_args) => {
+                let result = split_args(&extra_args).and_then(|args| {
+                    parse_extra_args::<NodeExtraArgs>("node", &args)
+                        .map(drop)
+                        .map_err(|error| error.to_string())
+                });
+                match result {
+                    Ok(()) => {
+                        self.node_extra_args = MaybeValid::Valid(extra_args);
+                        self.node_extra_args_error.take();
+                    }
+                    Err(error) => {
+                        self.node_extra_args = MaybeValid::Invalid(extra_args);
+                        self.node_extra_args_error.replace(error);
+                    }
+                }
+            }
+            ConfigurationInput::FarmerExtraArgsChanged(extra_args) => {
+                let result = split_args(&extra_args).and_then(|args| {
+                    parse_extra_args::<FarmerExtraArgs>("farmer", &args)
+                        .map(drop)
+                        .map_err(|error| error.to_string())
+                });
+                match result {
+                    Ok(()) => {
+                        self.farmer_extra_args = MaybeValid::Valid(extra_args);
+                        self.farmer_extra_args_error.take();
+                    }
+                    Err(error) => {
+                        self.farmer_extra_args = MaybeValid::Invalid(extra_args);
+                        self.farmer_extra_args_error.replace(error);
+                    }
+                }
+            }
+            ConfigurationInput::MoveUp(index) => {
+                let index = index.current_index();
+                if index > 0 {
+                    let mut farms = self.farms.guard();
+                    farms.swap(index, index - 1);
+                    rerender_farms(&mut farms);
+                }
+            }
+            ConfigurationInput::MoveDown(index) => {
+                let index = index.current_index();
+                if index + 1 < self.farms.len() {
+                    let mut farms = self.farms.guard();
+                    farms.swap(index, index + 1);
+                    rerender_farms(&mut farms);
+                }
+            }
             ConfigurationInput::Delete(index) => {
                 let mut farms = self.farms.guard();
                 farms.remove(index.current_index());
-                // Force re-rendering of all farms
-                farms.iter_mut().for_each(|_| {
-                    // Nothing
-                });
+                rerender_farms(&mut farms);
             }
             ConfigurationInput::RewardAddressChanged(new_reward_address) => {
                 let new_reward_address = new_reward_address.trim();
@@ -547,6 +2177,16 @@ impl ConfigurationView {
                 } else {
                     MaybeValid::Invalid(new_reward_address.to_string())
                 };
+                // Signature is only valid for the address it was created with
+                self.update_reward_address_signature(self.reward_address_signature.to_string());
+            }
+            ConfigurationInput::VerifyRewardAddressOwnershipChanged(
+                verify_reward_address_ownership,
+            ) => {
+                self.verify_reward_address_ownership = verify_reward_address_ownership;
+            }
+            ConfigurationInput::RewardAddressSignatureChanged(signature) => {
+                self.update_reward_address_signature(signature);
             }
             ConfigurationInput::Reconfigure(raw_config) => {
                 // `Unknown` is a hack to make it actually render the first time
@@ -560,14 +2200,57 @@ impl ConfigurationView {
                             path: MaybeValid::Valid(farm.path.clone()),
                             // `Unknown` is a hack to make it actually render the first time
                             size: MaybeValid::Unknown(farm.size.clone()),
+                            plotting_concurrency: farm.plotting_concurrency,
+                            max_disk_temperature: farm.max_disk_temperature,
+                            max_plotted_sectors: farm.max_plotted_sectors,
+                            network_filesystem: farm.network_filesystem,
+                            numa_node: farm.numa_node,
+                            enabled: farm.enabled,
                         });
                     }
                 }
                 self.network_configuration =
                     NetworkConfigurationWrapper::from(raw_config.network());
+                self.farmer_configuration = raw_config.farmer();
+                self.encrypt_config = config_passphrase_set();
+                match raw_config.piece_cache() {
+                    Some(piece_cache) => {
+                        self.piece_cache_path = MaybeValid::Valid(piece_cache.path.clone());
+                        // `Unknown` is a hack to make it actually render the first time
+                        self.piece_cache_size = MaybeValid::Unknown(piece_cache.size.clone());
+                    }
+                    None => {
+                        self.piece_cache_path = MaybeValid::default();
+                        self.piece_cache_size = MaybeValid::default();
+                    }
+                }
+                self.node_snapshot = raw_config.node_snapshot().cloned();
+                self.event_commands = raw_config.event_commands().clone();
+                self.fiat = raw_config.fiat().cloned();
+                // `Unknown` is a hack to make it actually render the first time
+                self.node_extra_args =
+                    MaybeValid::Unknown(shell_words::join(&raw_config.extra_args().node));
+                self.farmer_extra_args =
+                    MaybeValid::Unknown(shell_words::join(&raw_config.extra_args().farmer));
+                self.node_extra_args_error.take();
+                self.farmer_extra_args_error.take();
                 self.reconfiguration = true;
+                self.wizard_step.take();
+                self.node_path_free_space.take();
+            }
+            ConfigurationInput::UiConfiguration(ui_configuration) => {
+                self.ui_configuration = ui_configuration;
             }
             ConfigurationInput::Start => {
+                self.apply_config_encryption();
+                self.send_ui_configuration(&sender);
+                if self.test_plotting_after_start
+                    && sender
+                        .output(ConfigurationOutput::TestPlottingAfterStart)
+                        .is_err()
+                {
+                    debug!("Failed to send ConfigurationOutput::TestPlottingAfterStart");
+                }
                 if sender
                     .output(ConfigurationOutput::StartWithNewConfig(
                         self.create_raw_config(),
@@ -588,6 +2271,8 @@ impl ConfigurationView {
                 }
             }
             ConfigurationInput::Save => {
+                self.apply_config_encryption();
+                self.send_ui_configuration(&sender);
                 if sender
                     .output(ConfigurationOutput::ConfigUpdate(self.create_raw_config()))
                     .is_err()
@@ -601,6 +2286,180 @@ impl ConfigurationView {
         }
     }
 
+    fn process_command(
+        &mut self,
+        command_output: ConfigurationCommandOutput,
+        sender: ComponentSender<Self>,
+    ) {
+        match command_output {
+            ConfigurationCommandOutput::FarmImported(result) => match result {
+                Ok(farm) => {
+                    let mut farms = self.farms.guard();
+                    // Replace the only empty farm that is present by default instead of adding
+                    // another one
+                    if farms.len() == 1
+                        && farms
+                            .get(0)
+                            .is_some_and(|farm| farm.farm().path.as_os_str().is_empty())
+                    {
+                        farms.clear();
+                    }
+                    farms.push_back(FarmWidgetInit {
+                        path: MaybeValid::Valid(farm.path),
+                        // `Unknown` is a hack to make it actually render the first time
+                        size: MaybeValid::Unknown(farm.size),
+                        plotting_concurrency: farm.plotting_concurrency,
+                        max_disk_temperature: farm.max_disk_temperature,
+                        max_plotted_sectors: farm.max_plotted_sectors,
+                        network_filesystem: farm.network_filesystem,
+                        numa_node: farm.numa_node,
+                        enabled: farm.enabled,
+                    });
+                }
+                Err(error) => {
+                    self.farm_import_error.replace(error.to_string());
+                }
+            },
+            ConfigurationCommandOutput::DisksDetected(detected_disks) => {
+                self.detecting_disks = false;
+                self.detected_disks.replace(detected_disks);
+                self.update_detected_disks_list(&sender);
+            }
+            ConfigurationCommandOutput::NodePathFreeSpace { path, result } => {
+                // Ignore stale results if different directory was selected in the meantime
+                if *self.node_path == path {
+                    self.node_path_free_space.replace(result);
+                }
+            }
+            ConfigurationCommandOutput::FarmFreeSpace {
+                index,
+                path,
+                result,
+            } => match result {
+                Ok(free_space) => {
+                    self.farms.send(
+                        index.current_index(),
+                        FarmWidgetInput::FreeSpaceDetected { path, free_space },
+                    );
+                }
+                Err(error) => {
+                    warn!(
+                        %error,
+                        path = %path.display(),
+                        "Failed to check free space of farm directory"
+                    );
+                }
+            },
+        }
+    }
+
+    fn update_detected_disks_list(&self, sender: &ComponentSender<Self>) {
+        while let Some(child) = self.detected_disks_list.first_child() {
+            self.detected_disks_list.remove(&child);
+        }
+
+        let Some(detected_disks) = &self.detected_disks else {
+            return;
+        };
+
+        for (index, disk) in detected_disks.iter().enumerate() {
+            let button = gtk::Button::builder()
+                .halign(gtk::Align::Start)
+                .label(format!(
+                    "{}: {} free of {}, farm at {}",
+                    disk.mount_point.display(),
+                    bytesize::to_string(disk.free_space, true),
+                    bytesize::to_string(disk.total_space, true),
+                    disk.suggested_farm_path(detected_disks).display(),
+                ))
+                .build();
+            button.connect_clicked({
+                let sender = sender.clone();
+
+                move |_| {
+                    sender.input(ConfigurationInput::AddDetectedDisk(index));
+                }
+            });
+            self.detected_disks_list.append(&button);
+        }
+    }
+
+    /// Reward address needs to be signed by its owner only when ownership verification is enabled
+    fn reward_address_accepted(&self) -> bool {
+        self.reward_address.valid()
+            && (!self.verify_reward_address_ownership || self.reward_address_signature.valid())
+    }
+
+    fn update_reward_address_signature(&mut self, signature: String) {
+        let signature = signature.trim().to_string();
+        self.reward_address_signature = if signature.is_empty() {
+            MaybeValid::Unknown(signature)
+        } else if let Ok(public_key) = parse_ss58_reward_address(&self.reward_address)
+            && verify_signature(&public_key, &self.reward_address_challenge, &signature)
+        {
+            MaybeValid::Valid(signature)
+        } else {
+            MaybeValid::Invalid(signature)
+        };
+    }
+
+    /// Piece cache size needs to be valid only when dedicated piece cache is used
+    fn piece_cache_valid(&self) -> bool {
+        self.piece_cache_path.as_os_str().is_empty()
+            || ByteSize::from_str(self.piece_cache_size.trim())
+                .map(|size| size.as_u64() > 0)
+                .unwrap_or_default()
+    }
+
+    fn extra_args_valid(&self) -> bool {
+        self.node_extra_args_error.is_none() && self.farmer_extra_args_error.is_none()
+    }
+
+    /// Encryption requires either new passphrase or the current one to be kept
+    fn config_encryption_valid(&self) -> bool {
+        !self.encrypt_config || !self.config_passphrase.is_empty() || config_passphrase_set()
+    }
+
+    /// Configuration file is written by backend with passphrase that is set at that time, new
+    /// passphrase is not kept in the view afterwards
+    fn apply_config_encryption(&mut self) {
+        if !self.encrypt_config {
+            self.config_passphrase = Zeroizing::default();
+            set_config_passphrase(None);
+        } else if !self.config_passphrase.is_empty() {
+            set_config_passphrase(Some(mem::take(&mut *self.config_passphrase)));
+        }
+    }
+
+    /// Farms are numbered among enabled farms only, the same way as farmer does it, such that
+    /// numbers shown here match logs, status bar and `--debug-farm`. The last farm is also updated
+    /// here, such that it can't be moved down.
+    fn update_farm_indices(&self) {
+        let mut next_farm_index = 0;
+        for (index, farm) in self.farms.iter().enumerate() {
+            let farm_index = farm.enabled().then_some(next_farm_index);
+            next_farm_index += usize::from(farm.enabled());
+            if farm.farm_index() != farm_index {
+                self.farms
+                    .send(index, FarmWidgetInput::FarmIndex(farm_index));
+            }
+
+            let last = index + 1 == self.farms.len();
+            if farm.last() != last {
+                self.farms.send(index, FarmWidgetInput::Last(last));
+            }
+        }
+    }
+
+    fn send_ui_configuration(&self, sender: &ComponentSender<Self>) {
+        if sender
+            .output(ConfigurationOutput::UiConfigUpdate(self.ui_configuration))
+            .is_err()
+        {
+            debug!("Failed to send ConfigurationOutput::UiConfigUpdate");
+        }
+    }
+
     /// Create raw config from own state
     fn create_raw_config(&self) -> RawConfig {
         RawConfig::V0 {
@@ -611,7 +2470,70 @@ impl ConfigurationView {
                 substrate_port: *self.network_configuration.substrate_port,
                 subspace_port: *self.network_configuration.subspace_port,
                 faster_networking: self.network_configuration.faster_networking,
+                ip_version: self.network_configuration.ip_version,
+                reserved_peers: self.network_configuration.reserved_peers.clone(),
+                blocked_peers: self.network_configuration.blocked_peers.clone(),
+            },
+            farmer: self.farmer_configuration,
+            ui: None,
+            piece_cache: (!self.piece_cache_path.as_os_str().is_empty()).then(|| PieceCache {
+                path: PathBuf::clone(&self.piece_cache_path),
+                size: self.piece_cache_size.trim().to_string(),
+            }),
+            node_snapshot: self.node_snapshot.clone(),
+            extra_args: ExtraArgs {
+                // Arguments are validated before configuration can be saved
+                node: split_args(&self.node_extra_args).unwrap_or_default(),
+                farmer: split_args(&self.farmer_extra_args).unwrap_or_default(),
             },
+            event_commands: self.event_commands.clone(),
+            fiat: self.fiat.clone(),
         }
     }
 }
+
+/// Force re-rendering of all farms, such that widgets that depend on position of the farm in the
+/// list are updated after farms were reordered or removed
+fn rerender_farms(farms: &mut FactoryVecDequeGuard<'_, FarmWidget>) {
+    farms.iter_mut().for_each(|_| {
+        // Nothing
+    });
+}
+
+/// Split extra arguments entered by user the way shell does, such that arguments with spaces can
+/// be quoted, arguments are joined back with [`shell_words::join`]
+fn split_args(args: &str) -> Result<Vec<String>, String> {
+    shell_words::split(args).map_err(|error| format!("Invalid quoting of arguments: {error}"))
+}
+
+/// Free space on the disk where directory is located
+async fn free_space(path: PathBuf) -> Result<ByteSize, String> {
+    match tokio::task::spawn_blocking(move || fs4::available_space(path)).await {
+        Ok(Ok(free_space)) => Ok(ByteSize::b(free_space)),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(error) => Err(format!("Free space check task panicked: {error}")),
+    }
+}
+
+/// Check that hex-encoded sr25519 `signature` of `message` was created by `public_key`. Wallets
+/// like polkadot{.js} wrap message into `<Bytes>..</Bytes>` before signing, so both forms are
+/// accepted
+fn verify_signature(public_key: &PublicKey, message: &str, signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature.trim_start_matches("0x")) else {
+        return false;
+    };
+    let Ok(signature) = schnorrkel::Signature::from_bytes(&signature) else {
+        return false;
+    };
+    let Ok(public_key) = schnorrkel::PublicKey::from_bytes(public_key.as_ref()) else {
+        return false;
+    };
+
+    [message.to_string(), format!("<Bytes>{message}</Bytes>")]
+        .iter()
+        .any(|message| {
+            public_key
+                .verify_simple(SUBSTRATE_SIGNING_CONTEXT, message.as_bytes(), &signature)
+                .is_ok()
+        })
+}
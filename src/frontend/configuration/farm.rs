@@ -1,32 +1,76 @@
-use crate::backend::config::Farm;
+use crate::backend::config::{max_plotting_concurrency, Farm};
+use crate::backend::farmer::numa::numa_node_count;
 use crate::frontend::configuration::MaybeValid;
 use bytesize::ByteSize;
 use gtk::prelude::*;
 use relm4::prelude::*;
 use relm4_icons::icon_name;
+use std::num::{NonZeroU16, NonZeroU8, NonZeroUsize};
 use std::path::PathBuf;
 use std::str::FromStr;
 use tracing::warn;
 
 // 2 GB
 const MIN_FARM_SIZE: u64 = 1000 * 1000 * 1000 * 2;
+/// Percentage of free space suggested as farm size, the rest is left for filesystem and farm
+/// metadata
+const SUGGESTED_FARM_SIZE_PERCENTAGE: u64 = 95;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub(super) struct FarmWidgetInit {
     pub(super) path: MaybeValid<PathBuf>,
     pub(super) size: MaybeValid<String>,
+    pub(super) plotting_concurrency: Option<NonZeroUsize>,
+    pub(super) max_disk_temperature: Option<NonZeroU8>,
+    pub(super) max_plotted_sectors: Option<NonZeroU16>,
+    pub(super) network_filesystem: bool,
+    pub(super) numa_node: Option<u16>,
+    pub(super) enabled: bool,
+}
+
+impl Default for FarmWidgetInit {
+    fn default() -> Self {
+        Self {
+            path: MaybeValid::default(),
+            size: MaybeValid::default(),
+            plotting_concurrency: None,
+            max_disk_temperature: None,
+            max_plotted_sectors: None,
+            network_filesystem: false,
+            numa_node: None,
+            // New farms are enabled
+            enabled: true,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub(super) enum FarmWidgetInput {
     DirectorySelected(PathBuf),
     FarmSizeChanged(String),
+    PlottingConcurrencyChanged(usize),
+    MaxDiskTemperatureChanged(u8),
+    MaxPlottedSectorsChanged(u16),
+    NetworkFilesystemChanged(bool),
+    NumaNodeChanged(Option<u16>),
+    EnabledChanged(bool),
+    /// Index of the farm among enabled farms was updated
+    FarmIndex(Option<usize>),
+    /// Whether farm is the last one in the list
+    Last(bool),
+    /// Free space detected on the disk where farm directory is located
+    FreeSpaceDetected {
+        path: PathBuf,
+        free_space: ByteSize,
+    },
 }
 
 #[derive(Debug)]
 pub(super) enum FarmWidgetOutput {
     OpenDirectory(DynamicIndex),
     ValidityUpdate,
+    MoveUp(DynamicIndex),
+    MoveDown(DynamicIndex),
     Delete(DynamicIndex),
 }
 
@@ -35,6 +79,19 @@ pub(super) struct FarmWidget {
     index: DynamicIndex,
     path: MaybeValid<PathBuf>,
     size: MaybeValid<String>,
+    plotting_concurrency: Option<NonZeroUsize>,
+    max_disk_temperature: Option<NonZeroU8>,
+    max_plotted_sectors: Option<NonZeroU16>,
+    network_filesystem: bool,
+    numa_node: Option<u16>,
+    enabled: bool,
+    /// Index of the farm among enabled farms (the one used in logs, status bar and with
+    /// `--debug-farm`), `None` for disabled farm
+    farm_index: Option<usize>,
+    /// Last farm in the list can't be moved down
+    last: bool,
+    /// Free space on the disk where farm directory is located, if known
+    free_space: Option<ByteSize>,
     valid: bool,
 }
 
@@ -61,74 +118,217 @@ impl FactoryComponent for FarmWidget {
                     add_css_class: "heading",
                     set_halign: gtk::Align::Start,
                     #[watch]
-                    set_label: &format!("Path to farm {} and its size", self.index.current_index()),
+                    set_label: &format!(
+                        "Path to {}, its size, plotting concurrency, max disk temperature, max sectors, network filesystem mode and NUMA node",
+                        match self.farm_index {
+                            Some(farm_index) => format!("farm {farm_index}"),
+                            None => "disabled farm".to_string(),
+                        }
+                    ),
                 },
 
                 gtk::Box {
                     set_spacing: 10,
 
+                    gtk::Switch {
+                        connect_state_set[sender] => move |_switch, state| {
+                            sender.input(FarmWidgetInput::EnabledChanged(state));
+
+                            gtk::glib::Propagation::Proceed
+                        },
+                        set_active: self.enabled,
+                        set_tooltip: "Disabled farm is neither plotted nor farmed, but stays in \
+                            configuration, such that it can be enabled again later",
+                        set_valign: gtk::Align::Center,
+                    },
+
                     gtk::Box {
-                        add_css_class: "linked",
+                        set_spacing: 10,
+                        #[watch]
+                        set_sensitive: self.enabled,
+
+                        gtk::Box {
+                            add_css_class: "linked",
+
+                            gtk::Entry {
+                                set_can_focus: false,
+                                set_editable: false,
+                                set_hexpand: true,
+                                set_placeholder_text: Some(
+                                    if cfg!(windows) {
+                                        "D:\\subspace-farm"
+                                    } else {
+                                        "/media/subspace-farm"
+                                    },
+                                ),
+                                set_primary_icon_name: Some(icon_name::SSD),
+                                set_primary_icon_activatable: false,
+                                set_primary_icon_sensitive: false,
+                                #[watch]
+                                set_secondary_icon_name: self.path.icon(),
+                                set_secondary_icon_activatable: false,
+                                set_secondary_icon_sensitive: false,
+                                #[watch]
+                                set_text: self.path.display().to_string().as_str(),
+                                set_tooltip_markup: Some(
+                                    "Absolute path where farm files will be stored, any \
+                                    SSD works, high endurance not necessary"
+                                ),
+                            },
+
+                            gtk::Button {
+                                connect_clicked[sender, index] => move |_| {
+                                    if sender.output(FarmWidgetOutput::OpenDirectory(index.clone())).is_err() {
+                                        warn!("Can't send open directory output");
+                                    }
+                                },
+                                set_label: "Select",
+                            },
+                        },
 
                         gtk::Entry {
-                            set_can_focus: false,
-                            set_editable: false,
-                            set_hexpand: true,
+                            connect_activate[sender] => move |entry| {
+                                sender.input(FarmWidgetInput::FarmSizeChanged(entry.text().into()));
+                            },
+                            connect_changed[sender] => move |entry| {
+                                sender.input(FarmWidgetInput::FarmSizeChanged(entry.text().into()));
+                            },
                             set_placeholder_text: Some(
-                                if cfg!(windows) {
-                                    "D:\\subspace-farm"
-                                } else {
-                                    "/media/subspace-farm"
-                                },
+                                "4T, 2.5TB, 500GiB, etc.",
                             ),
-                            set_primary_icon_name: Some(icon_name::SSD),
+                            set_primary_icon_name: Some(icon_name::SIZE_HORIZONTALLY),
                             set_primary_icon_activatable: false,
                             set_primary_icon_sensitive: false,
                             #[watch]
-                            set_secondary_icon_name: self.path.icon(),
+                            set_secondary_icon_name: self.size.icon(),
                             set_secondary_icon_activatable: false,
                             set_secondary_icon_sensitive: false,
-                            #[watch]
-                            set_text: self.path.display().to_string().as_str(),
+                            #[track = "self.size.unknown()"]
+                            set_text: self.size.as_str(),
                             set_tooltip_markup: Some(
-                                "Absolute path where farm files will be stored, any \
-                                SSD works, high endurance not necessary"
+                                "Size of the farm in whichever units you prefer, any \
+                                amount of space above 2 GB works"
+                            ),
+                        },
+
+                        gtk::SpinButton {
+                            connect_value_changed[sender] => move |entry| {
+                                sender.input(FarmWidgetInput::PlottingConcurrencyChanged(
+                                    entry.value().round() as usize
+                                ));
+                            },
+                            set_adjustment: &gtk::Adjustment::new(
+                                0.0,
+                                0.0,
+                                max_plotting_concurrency() as f64,
+                                1.0,
+                                0.0,
+                                0.0,
+                            ),
+                            set_tooltip: "Number of sectors plotted concurrently in this farm, \
+                                0 to share automatically determined limit with other farms",
+                            set_value: self.plotting_concurrency.map(NonZeroUsize::get).unwrap_or_default() as f64,
+                            set_width_chars: 2,
+                        },
+
+                        gtk::SpinButton {
+                            connect_value_changed[sender] => move |entry| {
+                                sender.input(FarmWidgetInput::MaxDiskTemperatureChanged(
+                                    entry.value().round() as u8
+                                ));
+                            },
+                            set_adjustment: &gtk::Adjustment::new(
+                                0.0,
+                                0.0,
+                                100.0,
+                                1.0,
+                                0.0,
+                                0.0,
+                            ),
+                            set_tooltip: "Disk temperature in °C above which plotting of this farm is \
+                                paused until disk cools down, 0 to not monitor temperature (only \
+                                supported on Linux for disks that expose temperature sensor)",
+                            set_value: self.max_disk_temperature.map(NonZeroU8::get).unwrap_or_default() as f64,
+                            set_width_chars: 3,
+                        },
+
+                        gtk::SpinButton {
+                            connect_value_changed[sender] => move |entry| {
+                                sender.input(FarmWidgetInput::MaxPlottedSectorsChanged(
+                                    entry.value().round() as u16
+                                ));
+                            },
+                            set_adjustment: &gtk::Adjustment::new(
+                                0.0,
+                                0.0,
+                                u16::MAX as f64,
+                                1.0,
+                                0.0,
+                                0.0,
                             ),
+                            set_tooltip: "Maximum number of sectors (~1 GiB each) plotted in this farm, \
+                                can be increased later to resume plotting, 0 to plot all allocated \
+                                space. Space for sectors above the limit is not reserved on disk \
+                                until the limit is increased. Decreasing the limit does not remove \
+                                already plotted sectors.",
+                            set_value: self.max_plotted_sectors.map(NonZeroU16::get).unwrap_or_default() as f64,
+                            set_width_chars: 5,
+                        },
+
+                        gtk::CheckButton {
+                            connect_toggled[sender] => move |check_button| {
+                                sender.input(FarmWidgetInput::NetworkFilesystemChanged(
+                                    check_button.is_active()
+                                ));
+                            },
+                            set_active: self.network_filesystem,
+                            set_label: Some("Network"),
+                            set_tooltip: "Farm is located on network filesystem (NFS, SMB, etc.), \
+                                disables farm locking, reduces plotting concurrency, retries \
+                                less aggressively after errors and waits longer before warning \
+                                about stalled plotting to tolerate higher latency",
                         },
 
+                        gtk::DropDown::new(Some(numa_node_options(self.numa_node)), None::<gtk::Expression>) {
+                            connect_selected_notify[sender] => move |drop_down| {
+                                sender.input(FarmWidgetInput::NumaNodeChanged(
+                                    drop_down.selected().checked_sub(1).map(|numa_node| numa_node as u16)
+                                ));
+                            },
+                            set_selected: self.numa_node.map(|numa_node| u32::from(numa_node) + 1).unwrap_or_default(),
+                            set_tooltip: "NUMA node to pin plotting threads of this farm to (Linux only), \
+                                memory used for plotting is allocated on the same node, Auto to \
+                                group CPU cores by L3 cache and share them with other farms",
+                            set_visible: numa_node_count() > 1 || self.numa_node.is_some(),
+                        },
+                    },
+
+                    gtk::Box {
+                        add_css_class: "linked",
+
                         gtk::Button {
                             connect_clicked[sender, index] => move |_| {
-                                if sender.output(FarmWidgetOutput::OpenDirectory(index.clone())).is_err() {
-                                    warn!("Can't send open directory output");
+                                if sender.output(FarmWidgetOutput::MoveUp(index.clone())).is_err() {
+                                    warn!("Can't send move up output");
                                 }
                             },
-                            set_label: "Select",
+                            set_icon_name: "pan-up-symbolic",
+                            #[watch]
+                            set_sensitive: self.index.current_index() > 0,
+                            set_tooltip: "Move this farm up, farm order determines farm index",
                         },
-                    },
 
-                    gtk::Entry {
-                        connect_activate[sender] => move |entry| {
-                            sender.input(FarmWidgetInput::FarmSizeChanged(entry.text().into()));
-                        },
-                        connect_changed[sender] => move |entry| {
-                            sender.input(FarmWidgetInput::FarmSizeChanged(entry.text().into()));
+                        gtk::Button {
+                            connect_clicked[sender, index] => move |_| {
+                                if sender.output(FarmWidgetOutput::MoveDown(index.clone())).is_err() {
+                                    warn!("Can't send move down output");
+                                }
+                            },
+                            set_icon_name: "pan-down-symbolic",
+                            #[watch]
+                            set_sensitive: !self.last,
+                            set_tooltip: "Move this farm down, farm order determines farm index",
                         },
-                        set_placeholder_text: Some(
-                            "4T, 2.5TB, 500GiB, etc.",
-                        ),
-                        set_primary_icon_name: Some(icon_name::SIZE_HORIZONTALLY),
-                        set_primary_icon_activatable: false,
-                        set_primary_icon_sensitive: false,
-                        #[watch]
-                        set_secondary_icon_name: self.size.icon(),
-                        set_secondary_icon_activatable: false,
-                        set_secondary_icon_sensitive: false,
-                        #[track = "self.size.unknown()"]
-                        set_text: self.size.as_str(),
-                        set_tooltip_markup: Some(
-                            "Size of the farm in whichever units you prefer, any \
-                            amount of space above 2 GB works"
-                        ),
                     },
 
                     gtk::Button {
@@ -141,6 +341,33 @@ impl FactoryComponent for FarmWidget {
                         set_tooltip: "Delete this farm",
                     },
                 },
+
+                gtk::Label {
+                    add_css_class: "dim-label",
+                    set_halign: gtk::Align::Start,
+                    #[watch]
+                    set_label: &self.free_space
+                        .map(|free_space| {
+                            format!(
+                                "{} of free space available on this disk",
+                                free_space.to_string_as(true)
+                            )
+                        })
+                        .unwrap_or_default(),
+                    #[watch]
+                    set_visible: self.free_space.is_some(),
+                },
+
+                gtk::Label {
+                    add_css_class: "warning-label",
+                    set_halign: gtk::Align::Start,
+                    set_label: "Network filesystem mode: farm must not be used by more than one \
+                        farmer at a time since locking is disabled, farming over network is less \
+                        reliable and may miss rewards due to latency",
+                    set_wrap: true,
+                    #[watch]
+                    set_visible: self.network_filesystem,
+                },
             },
         }
     }
@@ -150,6 +377,16 @@ impl FactoryComponent for FarmWidget {
             index: index.clone(),
             path: value.path,
             size: value.size,
+            plotting_concurrency: value.plotting_concurrency,
+            max_disk_temperature: value.max_disk_temperature,
+            max_plotted_sectors: value.max_plotted_sectors,
+            network_filesystem: value.network_filesystem,
+            numa_node: value.numa_node,
+            enabled: value.enabled,
+            // Updated by parent view once all farms are known
+            farm_index: None,
+            last: false,
+            free_space: None,
             valid: false,
         }
     }
@@ -158,6 +395,7 @@ impl FactoryComponent for FarmWidget {
         match input {
             FarmWidgetInput::DirectorySelected(path) => {
                 self.path = MaybeValid::Valid(path);
+                self.free_space.take();
             }
             FarmWidgetInput::FarmSizeChanged(size) => {
                 let size = if ByteSize::from_str(&size)
@@ -170,6 +408,47 @@ impl FactoryComponent for FarmWidget {
                 };
                 self.size = size;
             }
+            FarmWidgetInput::PlottingConcurrencyChanged(plotting_concurrency) => {
+                self.plotting_concurrency = NonZeroUsize::new(plotting_concurrency);
+            }
+            FarmWidgetInput::MaxDiskTemperatureChanged(max_disk_temperature) => {
+                self.max_disk_temperature = NonZeroU8::new(max_disk_temperature);
+            }
+            FarmWidgetInput::MaxPlottedSectorsChanged(max_plotted_sectors) => {
+                self.max_plotted_sectors = NonZeroU16::new(max_plotted_sectors);
+            }
+            FarmWidgetInput::NetworkFilesystemChanged(network_filesystem) => {
+                self.network_filesystem = network_filesystem;
+            }
+            FarmWidgetInput::NumaNodeChanged(numa_node) => {
+                self.numa_node = numa_node;
+            }
+            FarmWidgetInput::EnabledChanged(enabled) => {
+                self.enabled = enabled;
+
+                // Parent view needs to re-render, at least one farm must be enabled
+                if sender.output(FarmWidgetOutput::ValidityUpdate).is_err() {
+                    warn!("Can't send validity update output");
+                }
+            }
+            FarmWidgetInput::FarmIndex(farm_index) => {
+                self.farm_index = farm_index;
+            }
+            FarmWidgetInput::Last(last) => {
+                self.last = last;
+            }
+            FarmWidgetInput::FreeSpaceDetected { path, free_space } => {
+                // Ignore stale results if different directory was selected in the meantime
+                if *self.path == path {
+                    self.free_space.replace(free_space);
+
+                    // Suggest using most of free space if size was not specified yet
+                    if self.size.is_empty() {
+                        // `Unknown` is a hack to make it actually render
+                        self.size = MaybeValid::Unknown(suggested_farm_size(free_space.as_u64()));
+                    }
+                }
+            }
         }
 
         let valid = self.valid();
@@ -185,15 +464,53 @@ impl FactoryComponent for FarmWidget {
     }
 }
 
+/// Options of NUMA node selection: automatic followed by all NUMA nodes, including configured one
+/// even if it is not detected
+fn numa_node_options(numa_node: Option<u16>) -> gtk::StringList {
+    let numa_node_count = numa_node_count().max(numa_node.map_or(0, |numa_node| numa_node + 1));
+    let options = gtk::StringList::new(&["Auto"]);
+    for numa_node in 0..numa_node_count {
+        options.append(&format!("NUMA {numa_node}"));
+    }
+
+    options
+}
+
+/// Farm size that uses most of free space, rounded down to whole gigabytes
+pub(super) fn suggested_farm_size(free_space: u64) -> String {
+    format!(
+        "{}GB",
+        free_space / 100 * SUGGESTED_FARM_SIZE_PERCENTAGE / (1000 * 1000 * 1000)
+    )
+}
+
 impl FarmWidget {
     pub(super) fn valid(&self) -> bool {
         self.path.valid() && self.size.valid()
     }
 
+    pub(super) fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(super) fn farm_index(&self) -> Option<usize> {
+        self.farm_index
+    }
+
+    pub(super) fn last(&self) -> bool {
+        self.last
+    }
+
     pub(super) fn farm(&self) -> Farm {
         Farm {
             path: PathBuf::clone(&self.path),
             size: String::clone(&self.size),
+            plotting_concurrency: self.plotting_concurrency,
+            max_disk_temperature: self.max_disk_temperature,
+            max_plotted_sectors: self.max_plotted_sectors,
+            network_filesystem: self.network_filesystem,
+            numa_node: self.numa_node,
+            enabled: self.enabled,
         }
     }
 }
@@ -1,22 +1,34 @@
 use crate::backend::LoadingStep;
 use gtk::prelude::*;
 use relm4::prelude::*;
+use tracing::debug;
 
 #[derive(Debug)]
 pub enum LoadingInput {
     BackendLoading(LoadingStep),
+    CancelStartup,
+}
+
+#[derive(Debug)]
+pub enum LoadingOutput {
+    /// User requested to abort startup while waiting for farm directories or while already plotted
+    /// sectors are being scanned
+    CancelStartup,
 }
 
 #[derive(Debug)]
 pub struct LoadingView {
     message: String,
+    /// Startup can be cancelled at the current step
+    cancellable: bool,
+    cancelling: bool,
 }
 
 #[relm4::component(pub)]
 impl Component for LoadingView {
     type Init = ();
     type Input = LoadingInput;
-    type Output = ();
+    type Output = LoadingOutput;
     type CommandOutput = ();
 
     view! {
@@ -36,6 +48,19 @@ impl Component for LoadingView {
                 #[watch]
                 set_label: &model.message,
             },
+
+            gtk::Button {
+                connect_clicked => LoadingInput::CancelStartup,
+                set_halign: gtk::Align::Center,
+                set_label: "Cancel",
+                set_margin_top: 10,
+                #[watch]
+                set_sensitive: !model.cancelling,
+                set_tooltip: "Abort startup, nothing is lost and startup will start over on next \
+                    launch",
+                #[watch]
+                set_visible: model.cancellable,
+            },
         }
     }
 
@@ -46,6 +71,8 @@ impl Component for LoadingView {
     ) -> ComponentParts<Self> {
         let model = Self {
             message: String::new(),
+            cancellable: false,
+            cancelling: false,
         };
 
         let widgets = view_output!();
@@ -53,15 +80,24 @@ impl Component for LoadingView {
         ComponentParts { model, widgets }
     }
 
-    fn update(&mut self, input: Self::Input, _sender: ComponentSender<Self>, _root: &Self::Root) {
-        self.process_input(input);
+    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        self.process_input(input, &sender);
     }
 }
 
 impl LoadingView {
-    fn process_input(&mut self, input: LoadingInput) {
+    fn process_input(&mut self, input: LoadingInput, sender: &ComponentSender<Self>) {
         match input {
             LoadingInput::BackendLoading(step) => {
+                self.cancellable = matches!(
+                    step,
+                    LoadingStep::WaitingForFarmDirectory { .. }
+                        | LoadingStep::ScanningPlottedSectors { .. }
+                );
+                if self.cancelling {
+                    // Scan is being aborted, keep showing that instead of stale progress
+                    return;
+                }
                 self.message = match step {
                     LoadingStep::LoadingConfiguration => "Loading configuration...".to_string(),
                     LoadingStep::ReadingConfiguration => "Reading configuration...".to_string(),
@@ -70,15 +106,44 @@ impl LoadingView {
                     }
                     LoadingStep::CheckingConfiguration => "Checking configuration...".to_string(),
                     LoadingStep::ConfigurationIsValid => "Configuration is valid".to_string(),
+                    LoadingStep::WaitingForFarmDirectory { farm_index, path } => {
+                        format!(
+                            "Waiting for farm {farm_index} directory {} to become available, \
+                            connect or mount its disk...",
+                            path.display()
+                        )
+                    }
                     LoadingStep::DecodingChainSpecification => {
                         "Decoding chain specification...".to_string()
                     }
                     LoadingStep::DecodedChainSpecificationSuccessfully => {
                         "Decoded chain specification successfully".to_string()
                     }
+                    LoadingStep::CheckingInternetConnection => {
+                        "Checking internet connection...".to_string()
+                    }
                     LoadingStep::CheckingNodePath => "Checking node path...".to_string(),
                     LoadingStep::CreatingNodePath => "Creating node path...".to_string(),
                     LoadingStep::NodePathReady => "Node path ready".to_string(),
+                    LoadingStep::DownloadingNodeSnapshot { downloaded, total } => match total {
+                        Some(total) => format!(
+                            "Downloading node snapshot: {}/{}...",
+                            bytesize::to_string(downloaded, true),
+                            bytesize::to_string(total, true)
+                        ),
+                        None => format!(
+                            "Downloading node snapshot: {}...",
+                            bytesize::to_string(downloaded, true)
+                        ),
+                    },
+                    LoadingStep::VerifyingNodeSnapshot => "Verifying node snapshot...".to_string(),
+                    LoadingStep::ImportingNodeSnapshot => "Importing node snapshot...".to_string(),
+                    LoadingStep::NodeSnapshotImported => {
+                        "Node snapshot imported successfully".to_string()
+                    }
+                    LoadingStep::NodeSnapshotImportFailed { error } => {
+                        format!("Node snapshot import failed, syncing normally: {error}")
+                    }
                     LoadingStep::PreparingNetworkingStack => {
                         "Preparing networking stack...".to_string()
                     }
@@ -99,18 +164,69 @@ impl LoadingView {
                     LoadingStep::ConsensusNodeCreatedSuccessfully => {
                         "Consensus node created successfully".to_string()
                     }
+                    LoadingStep::CheckingDisks { path } => {
+                        format!("Checking disk performance of {}...", path.display())
+                    }
                     LoadingStep::CreatingFarmer => "Creating farmer...".to_string(),
+                    LoadingStep::ScanningPlottedSectors {
+                        scanned_sectors,
+                        total_sectors,
+                        farm_index,
+                        farm_scanned_sectors,
+                        farm_total_sectors,
+                    } => {
+                        format!(
+                            "Resuming from where we left off: scanning {scanned_sectors}/{total_sectors} \
+                            already plotted sectors (farm {farm_index}: \
+                            {farm_scanned_sectors}/{farm_total_sectors}), nothing is being \
+                            replotted...",
+                        )
+                    }
+                    LoadingStep::PreallocatingFarm {
+                        farm_index,
+                        preallocated,
+                        total,
+                    } => {
+                        format!(
+                            "Pre-allocating farm {farm_index}: {}/{}...",
+                            bytesize::to_string(preallocated, true),
+                            bytesize::to_string(total, true),
+                        )
+                    }
                     LoadingStep::FarmerCreatedSuccessfully => {
                         "Farmer created successfully".to_string()
                     }
+                    LoadingStep::ResumingUpgrade => {
+                        "Previous upgrade was interrupted, resuming it...".to_string()
+                    }
                     LoadingStep::WipingFarm { farm_index, path } => {
                         format!("Wiping farm {farm_index} at {}...", path.display())
                     }
                     LoadingStep::WipingNode { path } => {
                         format!("Wiping node at {}...", path.display())
                     }
+                    LoadingStep::WipingCompleted {
+                        wiped_directories,
+                        freed_bytes,
+                    } => {
+                        format!(
+                            "Wiped {wiped_directories} directories, freed {}, restarting...",
+                            bytesize::to_string(freed_bytes, true)
+                        )
+                    }
                 };
             }
+            LoadingInput::CancelStartup => {
+                if !self.cancellable || self.cancelling {
+                    return;
+                }
+
+                self.cancelling = true;
+                self.message = "Cancelling startup...".to_string();
+                if sender.output(LoadingOutput::CancelStartup).is_err() {
+                    debug!("Failed to send LoadingOutput::CancelStartup");
+                }
+            }
         }
     }
 }
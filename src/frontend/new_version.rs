@@ -1,92 +1,201 @@
+use crate::backend::http::http_client;
 use gtk::prelude::*;
 use relm4::prelude::*;
 use relm4::{Sender, ShutdownReceiver};
-use reqwest::Client;
+use reqwest::Url;
 use semver::Version;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use tracing::{debug, warn};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info, warn};
 
 /// Check new release every hour
 const NEW_VERSION_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
 /// Retry failed check every 5 minutes
 const NEW_VERSION_CHECK_RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
 
+#[derive(Debug, Clone, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+    /// Digest in `algorithm:hex` format, for example `sha256:...`
+    #[serde(default)]
+    digest: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct LatestRelease {
     tag_name: String,
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum UpdateError {
+    #[error("Failed to download update: {0}")]
+    Download(#[from] reqwest::Error),
+    #[error("Update I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Release doesn't provide SHA-256 checksum of {asset}, update manually instead")]
+    MissingChecksum { asset: String },
+    #[error("Checksum mismatch of {asset}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        asset: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Downloaded file is not an executable")]
+    NotExecutable,
+}
+
+#[derive(Debug, Default)]
+enum UpdateState {
+    #[default]
+    Idle,
+    Installing,
+    Failed(String),
+}
+
+#[derive(Debug)]
+pub struct NewVersionInit {
+    /// Proxy to use for new version check
+    pub proxy: Option<Url>,
+}
+
+#[derive(Debug)]
+pub enum NewVersionInput {
+    /// Download and install update, only used when running as AppImage
+    InstallUpdate,
+}
+
+#[derive(Debug)]
+pub enum NewVersionOutput {
+    /// Update was installed, application needs to be restarted to use it
+    Restart,
 }
 
 #[derive(Debug)]
 pub enum NewVersionCommandOutput {
-    NewVersion(Version),
+    NewVersion {
+        version: Version,
+        /// AppImage of the new version for current architecture, if available
+        appimage: Option<ReleaseAsset>,
+    },
+    UpdateInstalled(Result<(), String>),
 }
 
 #[derive(Debug)]
 pub struct NewVersion {
     new_version: Option<Version>,
+    /// Path to AppImage application is running from (Linux only), it can be updated in-app, while
+    /// other packages (like Flatpak) are updated by system tools
+    appimage_path: Option<PathBuf>,
+    update_asset: Option<ReleaseAsset>,
+    update_state: UpdateState,
+    proxy: Option<Url>,
 }
 
 #[relm4::component(pub)]
 impl Component for NewVersion {
-    type Init = ();
-    type Input = ();
-    type Output = ();
+    type Init = NewVersionInit;
+    type Input = NewVersionInput;
+    type Output = NewVersionOutput;
     type CommandOutput = NewVersionCommandOutput;
 
     view! {
         #[root]
-        gtk::LinkButton {
-            add_css_class: "suggested-action",
-            remove_css_class: "flat",
-            remove_css_class: "link",
-            remove_css_class: "text-button",
-            #[watch]
-            set_label: &format!(
-                "Version {} available 🎉",
-                model.new_version.as_ref().map(Version::to_string).unwrap_or_default()
-            ),
-            set_tooltip: "Open releases page",
-            set_uri: &{
-                let repository = env!("CARGO_PKG_REPOSITORY");
-
-                if repository.starts_with("https://github.com") {
-                    // Turn:
-                    // https://github.com/subspace/space-acres
-                    // Into:
-                    // https://github.com/subspace/space-acres/releases
-                    format!("{}/releases", env!("CARGO_PKG_REPOSITORY"))
-                } else {
-                    repository.to_string()
-                }
-            },
-            set_use_underline: false,
+        gtk::Box {
+            add_css_class: "linked",
             #[watch]
             set_visible: model.new_version.is_some(),
+
+            gtk::LinkButton {
+                add_css_class: "suggested-action",
+                remove_css_class: "flat",
+                remove_css_class: "link",
+                remove_css_class: "text-button",
+                #[watch]
+                set_label: &format!(
+                    "Version {} available 🎉",
+                    model.new_version.as_ref().map(Version::to_string).unwrap_or_default()
+                ),
+                set_tooltip: "Open releases page",
+                set_uri: &{
+                    let repository = env!("CARGO_PKG_REPOSITORY");
+
+                    if repository.starts_with("https://github.com") {
+                        // Turn:
+                        // https://github.com/subspace/space-acres
+                        // Into:
+                        // https://github.com/subspace/space-acres/releases
+                        format!("{}/releases", env!("CARGO_PKG_REPOSITORY"))
+                    } else {
+                        repository.to_string()
+                    }
+                },
+                set_use_underline: false,
+            },
+
+            gtk::Button {
+                connect_clicked => NewVersionInput::InstallUpdate,
+                #[watch]
+                set_label: match model.update_state {
+                    UpdateState::Idle => "Install and restart",
+                    UpdateState::Installing => "Installing...",
+                    UpdateState::Failed(_) => "Retry installation",
+                },
+                #[watch]
+                set_sensitive: !matches!(model.update_state, UpdateState::Installing),
+                #[watch]
+                set_tooltip: &match &model.update_state {
+                    UpdateState::Failed(error) => format!("Installation failed: {error}"),
+                    _ => "Download new version, verify its checksum, replace current AppImage \
+                        with it and restart, this will interrupt plotting"
+                        .to_string(),
+                },
+                #[watch]
+                set_visible: model.appimage_path.is_some() && model.update_asset.is_some(),
+            },
         }
     }
 
     fn init(
-        _init: Self::Init,
+        init: Self::Init,
         _root: Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
-        let model = Self { new_version: None };
+        let model = Self {
+            new_version: None,
+            appimage_path: appimage_path(),
+            update_asset: None,
+            update_state: UpdateState::default(),
+            proxy: init.proxy.clone(),
+        };
 
         let widgets = view_output!();
 
-        sender.command(Self::check_new_version);
+        sender.command(move |sender, shutdown_receiver| {
+            Self::check_new_version(sender, shutdown_receiver, init.proxy)
+        });
 
         ComponentParts { model, widgets }
     }
 
+    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        self.process_input(input, sender);
+    }
+
     fn update_cmd(
         &mut self,
         input: Self::CommandOutput,
-        _sender: ComponentSender<Self>,
+        sender: ComponentSender<Self>,
         _root: &Self::Root,
     ) {
-        self.process_command(input);
+        self.process_command(input, sender);
     }
 }
 
@@ -94,6 +203,7 @@ impl NewVersion {
     async fn check_new_version(
         sender: Sender<NewVersionCommandOutput>,
         shutdown_receiver: ShutdownReceiver,
+        proxy: Option<Url>,
     ) {
         let url = env!("CARGO_PKG_REPOSITORY");
 
@@ -116,11 +226,18 @@ impl NewVersion {
                 return;
             }
         };
-        let user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        let user_agent = user_agent();
+
+        let client = match http_client(proxy.as_ref()) {
+            Ok(client) => client,
+            Err(error) => {
+                warn!(%error, "Failed to create HTTP client, not checking for new version");
+                return;
+            }
+        };
 
         shutdown_receiver
             .register(async move {
-                let client = Client::new();
                 loop {
                     let response: reqwest::Result<LatestRelease> = try {
                         client
@@ -132,8 +249,8 @@ impl NewVersion {
                             .await?
                     };
 
-                    let tag_name = match response {
-                        Ok(latest_release) => latest_release.tag_name,
+                    let (tag_name, assets) = match response {
+                        Ok(latest_release) => (latest_release.tag_name, latest_release.assets),
                         Err(error) => {
                             warn!(%error, %url, "Failed to check new release");
                             tokio::time::sleep(NEW_VERSION_CHECK_RETRY_INTERVAL).await;
@@ -152,7 +269,13 @@ impl NewVersion {
 
                     if new_version > current_version
                         && sender
-                            .send(NewVersionCommandOutput::NewVersion(new_version))
+                            .send(NewVersionCommandOutput::NewVersion {
+                                version: new_version,
+                                appimage: assets.into_iter().find(|asset| {
+                                    asset.name.ends_with(".AppImage")
+                                        && asset.name.contains(env::consts::ARCH)
+                                }),
+                            })
                             .is_err()
                     {
                         break;
@@ -165,11 +288,164 @@ impl NewVersion {
             .await
     }
 
-    fn process_command(&mut self, command_output: NewVersionCommandOutput) {
+    fn process_input(&mut self, input: NewVersionInput, sender: ComponentSender<Self>) {
+        match input {
+            NewVersionInput::InstallUpdate => {
+                let (Some(appimage_path), Some(asset)) =
+                    (self.appimage_path.clone(), self.update_asset.clone())
+                else {
+                    return;
+                };
+                if matches!(self.update_state, UpdateState::Installing) {
+                    return;
+                }
+                self.update_state = UpdateState::Installing;
+
+                let proxy = self.proxy.clone();
+                sender.oneshot_command(async move {
+                    NewVersionCommandOutput::UpdateInstalled(
+                        install_appimage_update(&appimage_path, &asset, proxy.as_ref())
+                            .await
+                            .map_err(|error| error.to_string()),
+                    )
+                });
+            }
+        }
+    }
+
+    fn process_command(
+        &mut self,
+        command_output: NewVersionCommandOutput,
+        sender: ComponentSender<Self>,
+    ) {
         match command_output {
-            NewVersionCommandOutput::NewVersion(version) => {
+            NewVersionCommandOutput::NewVersion { version, appimage } => {
                 self.new_version.replace(version);
+                // Don't replace asset while it is being installed
+                if !matches!(self.update_state, UpdateState::Installing) {
+                    self.update_asset = appimage;
+                }
+            }
+            NewVersionCommandOutput::UpdateInstalled(result) => match result {
+                Ok(()) => {
+                    self.update_state = UpdateState::Idle;
+                    if sender.output(NewVersionOutput::Restart).is_err() {
+                        debug!("Failed to send NewVersionOutput::Restart");
+                    }
+                }
+                Err(error) => {
+                    warn!(%error, "Failed to install update");
+                    self.update_state = UpdateState::Failed(error);
+                }
+            },
+        }
+    }
+}
+
+pub(super) fn user_agent() -> String {
+    format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+}
+
+/// Path to AppImage that application is running from, set by AppImage runtime
+fn appimage_path() -> Option<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    env::var_os("APPIMAGE")
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+}
+
+/// Download AppImage of the new version next to the current one, verify it and atomically replace
+/// current AppImage with it, supervisor will start the new AppImage on restart
+async fn install_appimage_update(
+    appimage_path: &Path,
+    asset: &ReleaseAsset,
+    proxy: Option<&Url>,
+) -> Result<(), UpdateError> {
+    let expected_checksum = asset
+        .digest
+        .as_deref()
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+        .ok_or_else(|| UpdateError::MissingChecksum {
+            asset: asset.name.clone(),
+        })?
+        .to_ascii_lowercase();
+
+    let mut download_path = appimage_path.as_os_str().to_os_string();
+    download_path.push(".update");
+    let download_path = PathBuf::from(download_path);
+
+    info!(
+        url = %asset.browser_download_url,
+        path = %download_path.display(),
+        "Downloading update"
+    );
+
+    let result = async {
+        let mut response = http_client(proxy)?
+            .get(&asset.browser_download_url)
+            .header("User-Agent", user_agent())
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let mut file = fs::File::create(&download_path).await?;
+        let mut hasher = Sha256::new();
+        let mut header = Vec::with_capacity(4);
+
+        while let Some(chunk) = response.chunk().await? {
+            if header.len() < 4 {
+                header.extend(chunk.iter().take(4 - header.len()));
             }
+            hasher.update(&chunk);
+            file.write_all(&chunk).await?;
+        }
+        file.sync_all().await?;
+        drop(file);
+
+        let actual_checksum = hex::encode(hasher.finalize());
+        if actual_checksum != expected_checksum {
+            return Err(UpdateError::ChecksumMismatch {
+                asset: asset.name.clone(),
+                expected: expected_checksum,
+                actual: actual_checksum,
+            });
         }
+        // AppImage is an ELF executable
+        if header != b"\x7fELF" {
+            return Err(UpdateError::NotExecutable);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(&download_path, std::fs::Permissions::from_mode(0o755)).await?;
+        }
+
+        // Rename is atomic, such that AppImage is never partially written
+        fs::rename(&download_path, appimage_path).await?;
+
+        Ok(())
     }
+    .await;
+
+    if result.is_err()
+        && let Err(error) = fs::remove_file(&download_path).await
+        && error.kind() != std::io::ErrorKind::NotFound
+    {
+        warn!(
+            %error,
+            path = %download_path.display(),
+            "Failed to remove downloaded update"
+        );
+    }
+
+    if result.is_ok() {
+        info!(path = %appimage_path.display(), "Update installed");
+    }
+
+    result
 }
@@ -1,23 +1,52 @@
 mod farm;
+mod fiat_price;
 mod node;
 
-use crate::backend::config::RawConfig;
-use crate::backend::farmer::{FarmerNotification, InitialFarmState};
-use crate::backend::node::ChainInfo;
-use crate::backend::NodeNotification;
-use crate::frontend::running::farm::{FarmWidget, FarmWidgetInit, FarmWidgetInput};
+use crate::backend::config::{
+    FiatConfiguration, RawConfig, UiConfiguration, MAX_PLOTTING_RATE_LIMIT,
+};
+use crate::backend::farmer::{
+    FarmerNotification, InitialFarmState, NETWORK_FILESYSTEM_TIMEOUT_MULTIPLIER,
+};
+use crate::backend::http::http_client;
+use crate::backend::node::{ChainInfo, SpacePledged};
+use crate::backend::{NodeNotification, PieceGetterStats};
+use crate::frontend::running::farm::{
+    FarmWidget, FarmWidgetInit, FarmWidgetInput, FarmWidgetOutput,
+};
+use crate::frontend::running::fiat_price::{
+    fetch_fiat_price, FiatPrice, FIAT_PRICE_CHECK_INTERVAL, FIAT_PRICE_RETRY_INTERVAL,
+    FIAT_PRICE_UPDATE_INTERVAL,
+};
 use crate::frontend::running::node::{NodeInput, NodeView};
 use gtk::prelude::*;
+use parking_lot::Mutex;
 use relm4::factory::FactoryHashMap;
 use relm4::prelude::*;
+use relm4::{Sender, ShutdownReceiver};
 use relm4_icons::icon_name;
-use subspace_core_primitives::BlockNumber;
+use reqwest::Url;
+use std::collections::{HashMap, VecDeque};
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use subspace_core_primitives::{BlockNumber, SectorIndex};
+use subspace_farmer::farm::{SectorPlottingDetails, SectorUpdate};
 use subspace_runtime_primitives::{Balance, SSC};
-use tracing::debug;
+use tracing::{debug, warn};
+
+/// Number of most recent events shown in event log
+const EVENT_LOG_CAPACITY: usize = 100;
 
 #[derive(Debug)]
 pub struct RunningInit {
     pub plotting_paused: bool,
+    /// Show details of each farm
+    pub farm_details: bool,
+    pub ui_configuration: UiConfiguration,
+    /// Proxy to use for fiat price updates
+    pub proxy: Option<Url>,
 }
 
 #[derive(Debug)]
@@ -27,18 +56,78 @@ pub enum RunningInput {
         reward_address_balance: Balance,
         initial_farm_states: Vec<InitialFarmState>,
         farm_during_initial_plotting: bool,
+        farm_only_when_synced: bool,
         raw_config: RawConfig,
         chain_info: ChainInfo,
     },
-    NodeNotification(NodeNotification),
-    FarmerNotification(FarmerNotification),
+    NodeNotification {
+        notification: NodeNotification,
+        /// Time at which notification was emitted
+        timestamp: Instant,
+    },
+    FarmerNotification {
+        notification: FarmerNotification,
+        /// Time at which notification was emitted
+        timestamp: Instant,
+    },
+    PieceGetterStats(PieceGetterStats),
     ToggleFarmDetails,
     TogglePausePlotting,
+    /// Plotting rate limit in sectors per minute, `0` for unlimited
+    PlottingRateLimitChanged(u32),
+    ToggleHideRewardAddress,
+    /// UI configuration in application settings was changed
+    UiConfiguration(UiConfiguration),
+    WipeFarm {
+        farm_index: u8,
+        path: PathBuf,
+    },
+    FarmingDeadlineWarning {
+        farm_index: u8,
+        activity: &'static str,
+        margin: Duration,
+    },
+    PlottingStalled {
+        farm_index: u8,
+        stage: &'static str,
+        stalled_for: Duration,
+    },
 }
 
 #[derive(Debug)]
 pub enum RunningOutput {
     PausePlotting(bool),
+    PlottingRateLimit(Option<NonZeroU32>),
+    HideRewardAddress(bool),
+    /// Details of each farm were shown (`true`) or hidden (`false`)
+    FarmDetails(bool),
+    /// User requested farm to be wiped, needs to be confirmed
+    WipeFarm {
+        farm_index: u8,
+        path: PathBuf,
+    },
+    /// Average auditing or proving time of the farm got too close to the deadline
+    FarmingDeadlineWarning {
+        farm_index: u8,
+        /// `auditing` or `proving`
+        activity: &'static str,
+        margin: Duration,
+    },
+    /// Farm made no plotting progress for longer than configured timeout while not paused
+    PlottingStalled {
+        farm_index: u8,
+        /// Stage the sector with the lowest index among sectors being plotted is stuck at
+        stage: &'static str,
+        stalled_for: Duration,
+    },
+}
+
+#[derive(Debug)]
+pub enum RunningCommandOutput {
+    FiatPrice {
+        fiat: FiatConfiguration,
+        price: Result<f64, String>,
+    },
 }
 
 #[derive(Debug, Default)]
@@ -46,17 +135,66 @@ struct FarmerState {
     initial_reward_address_balance: Balance,
     reward_address_balance: Balance,
     piece_cache_sync_progress: f32,
+    /// Share of piece cache in % that was already stored when sync started
+    piece_cache_sync_resumed_from: Option<f32>,
+    piece_getter_stats: PieceGetterStats,
+    reward_address: String,
     reward_address_url: String,
     token_symbol: String,
+    /// Number of sectors plotted within the last minute
+    sectors_per_minute: u32,
+    plotting_rate_limit: Option<NonZeroU32>,
+    /// Sectors plotted across all farms, excluding replotting
+    plotted_sectors: u64,
+    /// Total sectors across all farms
+    total_sectors: u64,
+    /// Farmer lost connection to the node and is trying to reconnect
+    node_reconnecting: bool,
+    /// Connected and required number of DSN peers while plotting start is held until enough peers
+    /// are connected
+    waiting_for_peers: Option<(u32, u32)>,
+    /// Space pledged to the network, `None` until node is synced and estimate is available
+    space_pledged: Option<SpacePledged>,
+}
+
+impl FarmerState {
+    /// Plotting progress of all farms combined in %, farms are weighted by their size
+    fn plotting_progress(&self) -> f32 {
+        self.plotted_sectors.min(self.total_sectors) as f32 / self.total_sectors.max(1) as f32
+            * 100.0
+    }
+
+    /// Share of space pledged to the network by local farms in %
+    fn space_pledged_share(&self, space_pledged: &SpacePledged) -> f64 {
+        let local_space_pledged = self.plotted_sectors as f64 * space_pledged.sector_size as f64;
+
+        local_space_pledged / space_pledged.total_space_pledged.max(1) as f64 * 100.0
+    }
 }
 
 #[derive(Debug)]
 pub struct RunningView {
     node_view: Controller<NodeView>,
     node_synced: bool,
+    /// Farming is held back until node is synced
+    farm_only_when_synced: bool,
     farmer_state: FarmerState,
     farms: FactoryHashMap<u8, FarmWidget>,
     plotting_paused: bool,
+    farm_details: bool,
+    ui_configuration: UiConfiguration,
+    /// Sectors that remain to be plotted before initial plotting of the farm is finished
+    remaining_initial_sectors: HashMap<u8, SectorIndex>,
+    /// Recent significant events with their local time, newest first
+    event_log: VecDeque<(String, String)>,
+    /// Event log was updated and needs to be re-rendered
+    event_log_updated: bool,
+    /// Conversion of balance into fiat currency, shared with price updates
+    fiat: Arc<Mutex<Option<FiatConfiguration>>>,
+    /// The last successfully fetched price, kept while price source is unavailable
+    fiat_price: Option<FiatPrice>,
+    /// Error of the last price update
+    fiat_price_error: Option<String>,
 }
 
 #[relm4::component(pub)]
@@ -64,7 +202,7 @@ impl Component for RunningView {
     type Init = RunningInit;
     type Input = RunningInput;
     type Output = RunningOutput;
-    type CommandOutput = ();
+    type CommandOutput = RunningCommandOutput;
 
     view! {
         #[root]
@@ -77,6 +215,33 @@ impl Component for RunningView {
                 set_margin_all: 10,
             },
 
+            gtk::Box {
+                set_margin_bottom: 10,
+                set_margin_start: 10,
+                set_margin_end: 10,
+                set_spacing: 10,
+                set_tooltip: "Node needs to be synced with the network before farmer can farm, \
+                    plotting may still progress in the meantime",
+                #[watch]
+                set_visible: !model.node_synced,
+
+                gtk::Spinner {
+                    start: (),
+                },
+
+                gtk::Label {
+                    add_css_class: "heading",
+                    set_halign: gtk::Align::Start,
+                    #[watch]
+                    set_label: if model.farm_only_when_synced {
+                        "Node is syncing, farming is held back until it is synced as configured, plotting continues"
+                    } else {
+                        "Node is syncing, farming will start automatically once it is synced"
+                    },
+                    set_wrap: true,
+                },
+            },
+
             gtk::Box {
                 set_orientation: gtk::Orientation::Vertical,
                 set_spacing: 10,
@@ -89,6 +254,21 @@ impl Component for RunningView {
                         set_halign: gtk::Align::Start,
                         set_label: "Farmer",
                     },
+                    gtk::Label {
+                        add_css_class: "heading",
+                        #[watch]
+                        set_label: &format!("{:.2}% plotted", model.farmer_state.plotting_progress()),
+                        set_tooltip: "Plotting progress of all farms combined",
+                        #[watch]
+                        set_visible: model.farmer_state.plotted_sectors < model.farmer_state.total_sectors,
+                    },
+                    gtk::Label {
+                        add_css_class: "error-label",
+                        set_label: "Node reconnecting...",
+                        set_tooltip: "Connection to the node was lost, farmer will resume automatically once it is back",
+                        #[watch]
+                        set_visible: model.farmer_state.node_reconnecting,
+                    },
                     gtk::Box {
                         gtk::ToggleButton {
                             connect_clicked => RunningInput::ToggleFarmDetails,
@@ -104,9 +284,123 @@ impl Component for RunningView {
                             set_tooltip: "Pause plotting/replotting, note that currently encoding sectors will not be interrupted",
                         },
                     },
+                    gtk::Box {
+                        set_spacing: 5,
+
+                        gtk::Label {
+                            add_css_class: "dim-label",
+                            #[watch]
+                            set_label: &match model.farmer_state.plotting_rate_limit {
+                                Some(limit) => format!(
+                                    "{}/{limit} sectors/min",
+                                    model.farmer_state.sectors_per_minute
+                                ),
+                                None => format!(
+                                    "{} sectors/min",
+                                    model.farmer_state.sectors_per_minute
+                                ),
+                            },
+                            set_tooltip: "Sectors plotted within the last minute (and plotting rate limit if set)",
+                        },
+                        gtk::SpinButton {
+                            connect_value_changed[sender] => move |entry| {
+                                sender.input(RunningInput::PlottingRateLimitChanged(
+                                    entry.value().round() as u32
+                                ));
+                            },
+                            set_adjustment: &gtk::Adjustment::new(
+                                0.0,
+                                0.0,
+                                MAX_PLOTTING_RATE_LIMIT as f64,
+                                1.0,
+                                0.0,
+                                0.0,
+                            ),
+                            set_tooltip: "Maximum number of sectors plotted per minute across all farms, 0 for unlimited. \
+                                Applies immediately until restart, default can be changed in configuration. \
+                                Note that currently encoding sectors will not be interrupted, so the limit may be slightly exceeded.",
+                            #[watch]
+                            set_value: model.farmer_state.plotting_rate_limit.map(NonZeroU32::get).unwrap_or_default() as f64,
+                            set_width_chars: 3,
+                        },
+                    },
                     gtk::Box {
                         set_halign: gtk::Align::End,
                         set_hexpand: true,
+                        set_spacing: 10,
+
+                        gtk::Label {
+                            add_css_class: "dim-label",
+                            #[watch]
+                            set_label: &match &model.farmer_state.space_pledged {
+                                Some(space_pledged) => format!(
+                                    "Network: {}, your share {:.4}%",
+                                    bytesize::to_string(space_pledged.total_space_pledged, true),
+                                    model.farmer_state.space_pledged_share(space_pledged),
+                                ),
+                                None => "Network: unknown".to_string(),
+                            },
+                            set_tooltip: "Total space pledged to the network (estimated from current \
+                                solution range) and share of it plotted by your farms, chance of \
+                                winning rewards is roughly proportional to the share. Becomes \
+                                available once node is synced.",
+                        },
+
+                        gtk::Label {
+                            add_css_class: "dim-label",
+                            #[watch]
+                            set_label: &{
+                                let stats = model.farmer_state.piece_getter_stats;
+                                let total = stats.cache_hits + stats.network_fetches;
+                                let cache_hit_rate = stats.cache_hits as f32 / total.max(1) as f32 * 100.0;
+
+                                format!("Pieces: {cache_hit_rate:.0}% from cache")
+                            },
+                            #[watch]
+                            set_tooltip: &{
+                                let stats = model.farmer_state.piece_getter_stats;
+
+                                let mut tooltip = format!(
+                                    "Pieces retrieved from local cache: {}\n\
+                                    Pieces retrieved from the network: {}\n\
+                                    Average network retrieval time: {:.2}s",
+                                    stats.cache_hits,
+                                    stats.network_fetches,
+                                    stats.average_fetch_latency.as_secs_f32(),
+                                );
+                                if stats.verification_failures > 0 {
+                                    tooltip.push_str(&format!(
+                                        "\nPieces that failed verification and were re-fetched: {}",
+                                        stats.verification_failures,
+                                    ));
+                                }
+
+                                tooltip
+                            },
+                            #[watch]
+                            set_visible: model.farmer_state.piece_getter_stats != PieceGetterStats::default(),
+                        },
+
+                        gtk::Label {
+                            add_css_class: "dim-label",
+                            set_selectable: true,
+                            #[watch]
+                            set_label: &if model.ui_configuration.hide_reward_address {
+                                mask_reward_address(&model.farmer_state.reward_address)
+                            } else {
+                                model.farmer_state.reward_address.clone()
+                            },
+                            set_tooltip: "Reward address",
+                        },
+
+                        gtk::ToggleButton {
+                            connect_clicked => RunningInput::ToggleHideRewardAddress,
+                            set_has_frame: false,
+                            set_label: "Hide",
+                            #[watch]
+                            set_active: model.ui_configuration.hide_reward_address,
+                            set_tooltip: "Hide reward address (and balance if enabled in configuration), for example during screen sharing",
+                        },
 
                         gtk::LinkButton {
                             remove_css_class: "link",
@@ -117,7 +411,9 @@ impl Component for RunningView {
 
                             gtk::Label {
                                 #[watch]
-                                set_label: &{
+                                set_label: &if model.ui_configuration.hide_reward_address && model.ui_configuration.hide_balance {
+                                    format!("*** {}", model.farmer_state.token_symbol)
+                                } else {
                                     let current_balance = model.farmer_state.reward_address_balance;
                                     let balance_increase = model.farmer_state.reward_address_balance - model.farmer_state.initial_reward_address_balance;
                                     let current_balance = (current_balance / (SSC / 100)) as f32 / 100.0;
@@ -130,7 +426,18 @@ impl Component for RunningView {
                                 },
                                 set_use_markup: true,
                             },
-                        }
+                        },
+
+                        gtk::Label {
+                            add_css_class: "dim-label",
+                            #[watch]
+                            set_label: &model.fiat_balance().map(|(balance, _)| balance).unwrap_or_default(),
+                            #[watch]
+                            set_tooltip: &model.fiat_balance().map(|(_, details)| details).unwrap_or_default(),
+                            #[watch]
+                            set_visible: model.fiat_balance().is_some()
+                                && !(model.ui_configuration.hide_reward_address && model.ui_configuration.hide_balance),
+                        },
                     },
                 },
 
@@ -156,10 +463,17 @@ impl Component for RunningView {
                                     set_halign: gtk::Align::Start,
 
                                     #[watch]
-                                    set_label: &format!(
-                                        "Piece cache sync {:.2}%",
-                                        model.farmer_state.piece_cache_sync_progress
-                                    ),
+                                    set_label: &match model.farmer_state.piece_cache_sync_resumed_from {
+                                        Some(resumed_from) => format!(
+                                            "Piece cache sync {:.2}% (resumed from {:.2}%)",
+                                            model.farmer_state.piece_cache_sync_progress,
+                                            resumed_from,
+                                        ),
+                                        None => format!(
+                                            "Piece cache sync {:.2}%",
+                                            model.farmer_state.piece_cache_sync_progress
+                                        ),
+                                    },
                                 },
 
                                 gtk::Spinner {
@@ -173,6 +487,29 @@ impl Component for RunningView {
                             },
                         },
 
+                        gtk::Box {
+                            set_spacing: 5,
+                            #[watch]
+                            set_visible: model.farmer_state.waiting_for_peers.is_some(),
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+
+                                #[watch]
+                                set_label: &{
+                                    let (connected, required) =
+                                        model.farmer_state.waiting_for_peers.unwrap_or_default();
+                                    format!(
+                                        "Waiting for peers ({connected}/{required}) before starting plotting"
+                                    )
+                                },
+                            },
+
+                            gtk::Spinner {
+                                start: (),
+                            },
+                        },
+
                         #[local_ref]
                         farms_box -> gtk::Box {
                             set_orientation: gtk::Orientation::Vertical,
@@ -180,6 +517,38 @@ impl Component for RunningView {
                         },
                     },
                 },
+
+                gtk::Expander {
+                    set_label: Some("Events"),
+                    set_margin_bottom: 10,
+                    set_margin_start: 10,
+                    set_margin_end: 10,
+                    set_tooltip: "Recent significant events since application started, see logs for details",
+
+                    gtk::ScrolledWindow {
+                        set_max_content_height: 150,
+                        set_propagate_natural_height: true,
+
+                        gtk::Label {
+                            add_css_class: "dim-label",
+                            set_halign: gtk::Align::Start,
+                            set_selectable: true,
+                            set_valign: gtk::Align::Start,
+                            set_wrap: true,
+                            set_xalign: 0.0,
+                            #[track = "model.event_log_updated"]
+                            set_label: &if model.event_log.is_empty() {
+                                "No events yet".to_string()
+                            } else {
+                                model.event_log
+                                    .iter()
+                                    .map(|(time, message)| format!("{time}  {message}"))
+                                    .collect::<Vec<_>>()
+                                    .join("\n")
+                            },
+                        },
+                    },
+                },
             },
         }
     }
@@ -187,49 +556,110 @@ impl Component for RunningView {
     fn init(
         init: Self::Init,
         _root: Self::Root,
-        _sender: ComponentSender<Self>,
+        sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
         let node_view = NodeView::builder().launch(()).detach();
         let farms = FactoryHashMap::builder()
             .launch(gtk::Box::default())
-            .detach();
+            .forward(sender.input_sender(), |output| match output {
+                FarmWidgetOutput::Wipe { farm_index, path } => {
+                    RunningInput::WipeFarm { farm_index, path }
+                }
+                FarmWidgetOutput::FarmingDeadlineWarning {
+                    farm_index,
+                    activity,
+                    margin,
+                } => RunningInput::FarmingDeadlineWarning {
+                    farm_index,
+                    activity,
+                    margin,
+                },
+                FarmWidgetOutput::PlottingStalled {
+                    farm_index,
+                    stage,
+                    stalled_for,
+                } => RunningInput::PlottingStalled {
+                    farm_index,
+                    stage,
+                    stalled_for,
+                },
+            });
 
         let model = Self {
             node_view,
             node_synced: false,
+            farm_only_when_synced: false,
             farmer_state: FarmerState::default(),
             farms,
             plotting_paused: init.plotting_paused,
+            farm_details: init.farm_details,
+            ui_configuration: init.ui_configuration,
+            remaining_initial_sectors: HashMap::new(),
+            event_log: VecDeque::with_capacity(EVENT_LOG_CAPACITY),
+            event_log_updated: false,
+            fiat: Arc::default(),
+            fiat_price: None,
+            fiat_price_error: None,
         };
 
         let farms_box = model.farms.widget();
         let widgets = view_output!();
 
+        let fiat = Arc::clone(&model.fiat);
+        sender.command(move |sender, shutdown_receiver| async move {
+            Self::update_fiat_price(sender, shutdown_receiver, fiat, init.proxy).await;
+        });
+
         ComponentParts { model, widgets }
     }
 
     fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
         self.process_input(input, sender);
     }
+
+    fn update_cmd(
+        &mut self,
+        input: Self::CommandOutput,
+        _sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        self.process_command(input);
+    }
 }
 
 impl RunningView {
     fn process_input(&mut self, input: RunningInput, sender: ComponentSender<Self>) {
+        self.event_log_updated = false;
+
         match input {
             RunningInput::Initialize {
                 best_block_number,
                 reward_address_balance,
                 initial_farm_states,
                 farm_during_initial_plotting,
+                farm_only_when_synced,
                 raw_config,
                 chain_info,
             } => {
+                self.farm_only_when_synced = farm_only_when_synced;
                 for (farm_index, (initial_farm_state, farm)) in initial_farm_states
                     .iter()
                     .copied()
-                    .zip(raw_config.farms().iter().cloned())
+                    // Disabled farms are not created by farmer
+                    .zip(
+                        raw_config
+                            .farms()
+                            .iter()
+                            .filter(|farm| farm.enabled)
+                            .cloned(),
+                    )
                     .enumerate()
                 {
+                    let plotting_stall_timeout_multiplier = if farm.network_filesystem {
+                        NETWORK_FILESYSTEM_TIMEOUT_MULTIPLIER
+                    } else {
+                        1
+                    };
                     self.farms.insert(
                         u8::try_from(farm_index).expect(
                             "More than 256 plots are not supported, this is checked on \
@@ -241,14 +671,53 @@ impl RunningView {
                             plotted_total_sectors: initial_farm_state.plotted_sectors_count,
                             farm_during_initial_plotting,
                             plotting_paused: self.plotting_paused,
+                            farm_details: self.farm_details,
+                            deadline_warning_margin: self
+                                .ui_configuration
+                                .farming_deadline_warning_margin
+                                .map(|margin| Duration::from_millis(margin.get().into())),
+                            plotting_stall_timeout: self
+                                .ui_configuration
+                                .plotting_stall_timeout
+                                .map(|timeout| {
+                                    Duration::from_secs(u64::from(timeout.get()) * 60)
+                                        * plotting_stall_timeout_multiplier
+                                }),
                         },
                     );
                 }
 
+                self.remaining_initial_sectors = initial_farm_states
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(farm_index, farm_state)| {
+                        let remaining_sectors = farm_state
+                            .total_sectors_count
+                            .saturating_sub(farm_state.plotted_sectors_count);
+
+                        Some((u8::try_from(farm_index).ok()?, remaining_sectors))
+                    })
+                    .collect();
+                self.log_event(format!(
+                    "Farmer started, number of farms: {}",
+                    initial_farm_states.len()
+                ));
+
+                {
+                    let mut fiat = self.fiat.lock();
+                    if fiat.as_ref() != raw_config.fiat() {
+                        *fiat = raw_config.fiat().cloned();
+                        self.fiat_price.take();
+                        self.fiat_price_error.take();
+                    }
+                }
                 self.farmer_state = FarmerState {
                     initial_reward_address_balance: reward_address_balance,
                     reward_address_balance,
                     piece_cache_sync_progress: 0.0,
+                    piece_cache_sync_resumed_from: None,
+                    piece_getter_stats: PieceGetterStats::default(),
+                    reward_address: raw_config.reward_address().to_string(),
                     // TODO: Would be great to have `gemini-3h` in chain spec, but it is
                     //  not available in there in clean form
                     reward_address_url: format!(
@@ -260,6 +729,20 @@ impl RunningView {
                         raw_config.reward_address()
                     ),
                     token_symbol: chain_info.token_symbol.clone(),
+                    sectors_per_minute: 0,
+                    plotting_rate_limit: raw_config.farmer().plotting_rate_limit,
+                    plotted_sectors: initial_farm_states
+                        .iter()
+                        .map(|farm_state| u64::from(farm_state.plotted_sectors_count))
+                        .sum(),
+                    total_sectors: initial_farm_states
+                        .iter()
+                        .map(|farm_state| u64::from(farm_state.total_sectors_count))
+                        .sum(),
+                    node_reconnecting: false,
+                    waiting_for_peers: None,
+                    // Node notifications may arrive before farmer is initialized
+                    space_pledged: self.farmer_state.space_pledged.take(),
                 };
                 self.node_view.emit(NodeInput::Initialize {
                     best_block_number,
@@ -267,18 +750,32 @@ impl RunningView {
                     node_path: raw_config.node_path().clone(),
                 });
             }
-            RunningInput::NodeNotification(node_notification) => {
-                self.node_view
-                    .emit(NodeInput::NodeNotification(node_notification.clone()));
+            RunningInput::NodeNotification {
+                notification,
+                timestamp,
+            } => {
+                self.node_view.emit(NodeInput::NodeNotification {
+                    notification: notification.clone(),
+                    timestamp,
+                });
 
-                match node_notification {
+                match notification {
                     NodeNotification::SyncStateUpdate(sync_state) => {
                         let new_synced = sync_state.is_synced();
                         if self.node_synced != new_synced {
                             self.farms
                                 .broadcast(FarmWidgetInput::NodeSynced(new_synced));
+                            self.log_event(if new_synced {
+                                "Node synced".to_string()
+                            } else {
+                                "Node is syncing".to_string()
+                            });
                         }
                         self.node_synced = new_synced;
+                        if !new_synced {
+                            // Estimate becomes stale and is only updated again once synced
+                            self.farmer_state.space_pledged.take();
+                        }
                     }
                     NodeNotification::BlockImported(imported_block) => {
                         if !self.node_synced {
@@ -301,19 +798,56 @@ impl RunningView {
                         self.farmer_state.reward_address_balance =
                             imported_block.reward_address_balance;
                     }
+                    NodeNotification::RewardReceived { amount, .. } => {
+                        // Balance is already updated on block import
+                        self.log_event(format!(
+                            "Received reward of {:.4} {}",
+                            (amount / (SSC / 10_000)) as f64 / 10_000.0,
+                            self.farmer_state.token_symbol
+                        ));
+                    }
+                    NodeNotification::SpacePledged(space_pledged) => {
+                        self.farmer_state.space_pledged.replace(space_pledged);
+                    }
+                    NodeNotification::ConnectedPeersUpdate(_) => {
+                        // Not shown on running view
+                    }
                 }
             }
-            RunningInput::FarmerNotification(farmer_notification) => match farmer_notification {
+            RunningInput::FarmerNotification {
+                notification,
+                timestamp,
+            } => match notification {
                 FarmerNotification::SectorUpdate {
                     farm_index,
                     sector_index,
                     update,
                 } => {
+                    if let SectorUpdate::Plotting(SectorPlottingDetails::Finished {
+                        old_plotted_sector: None,
+                        ..
+                    }) = &update
+                    {
+                        self.farmer_state.plotted_sectors += 1;
+
+                        if let Some(remaining_sectors) =
+                            self.remaining_initial_sectors.get_mut(&farm_index)
+                            && *remaining_sectors > 0
+                        {
+                            *remaining_sectors -= 1;
+                            if *remaining_sectors == 0 {
+                                self.log_event(format!(
+                                    "Farm {farm_index} finished initial plotting"
+                                ));
+                            }
+                        }
+                    }
                     self.farms.send(
                         &farm_index,
                         FarmWidgetInput::SectorUpdate {
                             sector_index,
                             update,
+                            timestamp,
                         },
                     );
                 }
@@ -326,16 +860,203 @@ impl RunningView {
                         FarmWidgetInput::FarmingNotification(notification),
                     );
                 }
-                FarmerNotification::FarmerCacheSyncProgress { progress } => {
+                FarmerNotification::FarmerCacheSyncProgress {
+                    progress,
+                    resumed_from,
+                } => {
                     self.farmer_state.piece_cache_sync_progress = progress;
+                    self.farmer_state.piece_cache_sync_resumed_from = resumed_from;
+                }
+                FarmerNotification::ExpiringSectors {
+                    farm_index,
+                    about_to_expire_sectors_count,
+                    expired_sectors_count,
+                } => {
+                    self.farms.send(
+                        &farm_index,
+                        FarmWidgetInput::ExpiringSectors {
+                            about_to_expire_sectors_count,
+                            expired_sectors_count,
+                        },
+                    );
                 }
                 FarmerNotification::FarmError { farm_index, error } => {
+                    self.log_event(format!("Farm {farm_index} crashed: {error}"));
                     self.farms
                         .send(&farm_index, FarmWidgetInput::Error { error });
                 }
+                FarmerNotification::FarmRecoveryAttempt {
+                    farm_index,
+                    attempt,
+                } => {
+                    self.farms
+                        .send(&farm_index, FarmWidgetInput::RecoveryAttempt { attempt });
+                }
+                FarmerNotification::FarmRecovered { farm_index } => {
+                    self.log_event(format!("Farm {farm_index} recovered"));
+                    self.farms.send(&farm_index, FarmWidgetInput::Recovered);
+                }
+                FarmerNotification::PlottingRate {
+                    sectors_per_minute,
+                    limit,
+                } => {
+                    self.farmer_state.sectors_per_minute = sectors_per_minute;
+                    self.farmer_state.plotting_rate_limit = limit;
+                }
+                FarmerNotification::NodeReconnecting(node_reconnecting) => {
+                    if self.farmer_state.node_reconnecting != node_reconnecting {
+                        self.log_event(if node_reconnecting {
+                            "Farmer lost connection to the node, reconnecting".to_string()
+                        } else {
+                            "Farmer reconnected to the node".to_string()
+                        });
+                    }
+                    self.farmer_state.node_reconnecting = node_reconnecting;
+                }
+                FarmerNotification::WaitingForPeers {
+                    connected,
+                    required,
+                    waiting,
+                } => {
+                    if waiting {
+                        if self.farmer_state.waiting_for_peers.is_none() {
+                            self.log_event(format!(
+                                "Waiting for {required} peers before starting plotting"
+                            ));
+                        }
+                        self.farmer_state
+                            .waiting_for_peers
+                            .replace((connected, required));
+                    } else {
+                        self.log_event(if connected >= required {
+                            format!("{connected} peers connected, starting plotting")
+                        } else {
+                            format!(
+                                "Only {connected}/{required} peers connected after timeout, \
+                                starting plotting anyway"
+                            )
+                        });
+                        self.farmer_state.waiting_for_peers.take();
+                    }
+                }
+                FarmerNotification::PlottedSectorsScanProgress(_) => {
+                    // Shown in status bar
+                }
+                FarmerNotification::FarmsRescanned {
+                    farm_indices,
+                    error,
+                } => {
+                    for farm_index in &farm_indices {
+                        self.farms.send(farm_index, FarmWidgetInput::Rescanned);
+                    }
+                    self.log_event(match error {
+                        Some(error) => format!("Farms {farm_indices:?} rescan failed: {error}"),
+                        None => format!("Farms {farm_indices:?} rescanned"),
+                    });
+                }
+                FarmerNotification::FarmWiping { farm_index } => {
+                    self.farms.send(&farm_index, FarmWidgetInput::Wiping);
+                }
+                FarmerNotification::FarmWiped {
+                    farm_index,
+                    freed_bytes,
+                    ..
+                } => {
+                    self.log_event(format!(
+                        "Farm {farm_index} wiped, {} freed",
+                        bytesize::to_string(freed_bytes, true)
+                    ));
+                    self.farms
+                        .send(&farm_index, FarmWidgetInput::Wiped { freed_bytes });
+                }
+                FarmerNotification::FarmWipeFailed { farm_index, error } => {
+                    self.log_event(format!("Failed to wipe farm {farm_index}: {error}"));
+                    self.farms
+                        .send(&farm_index, FarmWidgetInput::WipeFailed { error });
+                }
+                FarmerNotification::DiskTemperaturePause {
+                    farm_index,
+                    temperature,
+                    paused,
+                } => {
+                    self.log_event(if paused {
+                        format!(
+                            "Plotting of farm {farm_index} paused, disk is too hot \
+                            ({temperature:.0} °C)"
+                        )
+                    } else {
+                        format!(
+                            "Plotting of farm {farm_index} resumed, disk cooled down \
+                            ({temperature:.0} °C)"
+                        )
+                    });
+                    self.farms.send(
+                        &farm_index,
+                        FarmWidgetInput::DiskTemperaturePause {
+                            temperature,
+                            paused,
+                        },
+                    );
+                }
+                FarmerNotification::FarmReadBenchmark { farm_index, result } => {
+                    self.log_event(match result {
+                        Ok(result) => format!(
+                            "Farm {farm_index} piece read benchmark: {:.0} ms average and {:.0} ms \
+                            max per piece, {}/s{}",
+                            result.average_read_time.as_secs_f32() * 1_000.0,
+                            result.max_read_time.as_secs_f32() * 1_000.0,
+                            bytesize::to_string(result.throughput, true),
+                            if result.too_slow() {
+                                ", too slow to reliably produce proofs in time"
+                            } else {
+                                ""
+                            }
+                        ),
+                        Err(error) => {
+                            format!("Farm {farm_index} piece read benchmark failed: {error}")
+                        }
+                    });
+                }
+                FarmerNotification::PlottingSelfTest { farm_index, result } => {
+                    self.log_event(match result {
+                        Ok(result) => format!(
+                            "Farm {farm_index} plotting self-test succeeded: sector plotted in \
+                            {:.1} s, {:.0} ms average per piece read back",
+                            result.plotting_time.as_secs_f32(),
+                            result.average_read_time.as_secs_f32() * 1_000.0,
+                        ),
+                        Err(error) => {
+                            format!("Farm {farm_index} plotting self-test failed: {error}")
+                        }
+                    });
+                }
+                FarmerNotification::FarmerCacheOccupancy { result } => {
+                    self.log_event(match result {
+                        Ok(occupancy) => format!(
+                            "Farmer cache occupancy: {}/{} pieces stored ({:.1}%)",
+                            occupancy.stored_pieces,
+                            occupancy.capacity,
+                            occupancy.stored_pieces as f64 * 100.0
+                                / occupancy.capacity.max(1) as f64
+                        ),
+                        Err(error) => {
+                            format!("Failed to collect farmer cache occupancy: {error}")
+                        }
+                    });
+                }
             },
+            RunningInput::PieceGetterStats(stats) => {
+                self.farmer_state.piece_getter_stats = stats;
+            }
             RunningInput::ToggleFarmDetails => {
+                self.farm_details = !self.farm_details;
                 self.farms.broadcast(FarmWidgetInput::ToggleFarmDetails);
+                if sender
+                    .output(RunningOutput::FarmDetails(self.farm_details))
+                    .is_err()
+                {
+                    debug!("Failed to send RunningOutput::FarmDetails");
+                }
             }
             RunningInput::TogglePausePlotting => {
                 self.plotting_paused = !self.plotting_paused;
@@ -348,6 +1069,221 @@ impl RunningView {
                     debug!("Failed to send RunningOutput::TogglePausePlotting");
                 }
             }
+            RunningInput::PlottingRateLimitChanged(plotting_rate_limit) => {
+                let plotting_rate_limit = NonZeroU32::new(plotting_rate_limit);
+                if self.farmer_state.plotting_rate_limit == plotting_rate_limit {
+                    return;
+                }
+                self.farmer_state.plotting_rate_limit = plotting_rate_limit;
+                if sender
+                    .output(RunningOutput::PlottingRateLimit(plotting_rate_limit))
+                    .is_err()
+                {
+                    debug!("Failed to send RunningOutput::PlottingRateLimit");
+                }
+            }
+            RunningInput::ToggleHideRewardAddress => {
+                self.ui_configuration.hide_reward_address =
+                    !self.ui_configuration.hide_reward_address;
+                if sender
+                    .output(RunningOutput::HideRewardAddress(
+                        self.ui_configuration.hide_reward_address,
+                    ))
+                    .is_err()
+                {
+                    debug!("Failed to send RunningOutput::HideRewardAddress");
+                }
+            }
+            RunningInput::UiConfiguration(ui_configuration) => {
+                self.ui_configuration = ui_configuration;
+            }
+            RunningInput::WipeFarm { farm_index, path } => {
+                if sender
+                    .output(RunningOutput::WipeFarm { farm_index, path })
+                    .is_err()
+                {
+                    debug!("Failed to send RunningOutput::WipeFarm");
+                }
+            }
+            RunningInput::FarmingDeadlineWarning {
+                farm_index,
+                activity,
+                margin,
+            } => {
+                self.log_event(format!(
+                    "Farm {farm_index} is only {} ms away from {activity} deadline",
+                    margin.as_millis()
+                ));
+                if sender
+                    .output(RunningOutput::FarmingDeadlineWarning {
+                        farm_index,
+                        activity,
+                        margin,
+                    })
+                    .is_err()
+                {
+                    debug!("Failed to send RunningOutput::FarmingDeadlineWarning");
+                }
+            }
+            RunningInput::PlottingStalled {
+                farm_index,
+                stage,
+                stalled_for,
+            } => {
+                self.log_event(format!(
+                    "Farm {farm_index} made no plotting progress for {} minutes ({stage})",
+                    stalled_for.as_secs() / 60
+                ));
+                if sender
+                    .output(RunningOutput::PlottingStalled {
+                        farm_index,
+                        stage,
+                        stalled_for,
+                    })
+                    .is_err()
+                {
+                    debug!("Failed to send RunningOutput::PlottingStalled");
+                }
+            }
         }
     }
+
+    fn process_command(&mut self, command_output: RunningCommandOutput) {
+        match command_output {
+            RunningCommandOutput::FiatPrice { fiat, price } => {
+                // Configuration might have changed while price was being fetched
+                if self.fiat.lock().as_ref() != Some(&fiat) {
+                    return;
+                }
+
+                match price {
+                    Ok(price) => {
+                        self.fiat_price.replace(FiatPrice::new(fiat, price));
+                        self.fiat_price_error.take();
+                    }
+                    Err(error) => {
+                        self.fiat_price_error.replace(error);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn update_fiat_price(
+        sender: Sender<RunningCommandOutput>,
+        shutdown_receiver: ShutdownReceiver,
+        fiat: Arc<Mutex<Option<FiatConfiguration>>>,
+        proxy: Option<Url>,
+    ) {
+        let client = match http_client(proxy.as_ref()) {
+            Ok(client) => client,
+            Err(error) => {
+                warn!(%error, "Failed to create HTTP client, fiat price will not be available");
+                return;
+            }
+        };
+
+        shutdown_receiver
+            .register(async move {
+                // Configuration and time of the last update attempt, and whether it succeeded
+                let mut last_update = None::<(FiatConfiguration, Instant, bool)>;
+
+                loop {
+                    let maybe_fiat = fiat.lock().clone();
+
+                    if let Some(fiat) = maybe_fiat {
+                        let update_due = match &last_update {
+                            Some((last_fiat, updated_at, succeeded)) if last_fiat == &fiat => {
+                                updated_at.elapsed()
+                                    >= if *succeeded {
+                                        FIAT_PRICE_UPDATE_INTERVAL
+                                    } else {
+                                        FIAT_PRICE_RETRY_INTERVAL
+                                    }
+                            }
+                            _ => true,
+                        };
+
+                        if update_due {
+                            let price = fetch_fiat_price(&client, &fiat).await;
+                            if let Err(error) = &price {
+                                warn!(
+                                    %error,
+                                    currency = %fiat.currency,
+                                    price_source = %fiat.price_source,
+                                    "Failed to update fiat price"
+                                );
+                            }
+                            last_update.replace((fiat.clone(), Instant::now(), price.is_ok()));
+
+                            if sender
+                                .send(RunningCommandOutput::FiatPrice {
+                                    fiat,
+                                    price: price.map_err(|error| error.to_string()),
+                                })
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                    }
+
+                    tokio::time::sleep(FIAT_PRICE_CHECK_INTERVAL).await;
+                }
+            })
+            .drop_on_shutdown()
+            .await
+    }
+
+    /// Approximate value of reward address balance in fiat currency and details of the price it
+    /// is based on, `None` if price is not configured or not known (or outdated)
+    fn fiat_balance(&self) -> Option<(String, String)> {
+        let fiat_price = self.fiat_price.as_ref().filter(|price| price.is_fresh())?;
+        let currency = &fiat_price.fiat.currency;
+        let balance = self.farmer_state.reward_address_balance as f64 / SSC as f64;
+        let value = balance * fiat_price.price;
+
+        let mut details = format!(
+            "Approximate value, price of 1 {} is {:.4} {currency} according to {} as of {}",
+            self.farmer_state.token_symbol,
+            fiat_price.price,
+            fiat_price.source_host(),
+            fiat_price.fetched_at_local,
+        );
+        if let Some(error) = &self.fiat_price_error {
+            details.push_str(&format!(", price update failed: {error}"));
+        }
+
+        Some((format!("≈ {value:.2} {currency}"), details))
+    }
+
+    /// Add event to the event log with current local time, oldest events are dropped once
+    /// capacity is reached
+    fn log_event(&mut self, message: String) {
+        let time = gtk::glib::DateTime::now_local()
+            .and_then(|now| now.format("%H:%M:%S"))
+            .map(|time| time.to_string())
+            .unwrap_or_default();
+
+        if self.event_log.len() == EVENT_LOG_CAPACITY {
+            self.event_log.pop_back();
+        }
+        self.event_log.push_front((time, message));
+        self.event_log_updated = true;
+    }
+}
+
+/// Mask reward address such that only a few characters at the beginning and end remain visible,
+/// which is enough to recognize it, but not to reveal it
+fn mask_reward_address(reward_address: &str) -> String {
+    let chars = reward_address.chars().collect::<Vec<_>>();
+    if chars.len() <= 7 {
+        return "*".repeat(chars.len());
+    }
+
+    format!(
+        "{}...{}",
+        chars[..4].iter().collect::<String>(),
+        chars[chars.len() - 3..].iter().collect::<String>()
+    )
 }
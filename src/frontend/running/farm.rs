@@ -1,18 +1,19 @@
 use crate::backend::config::Farm;
 use gtk::prelude::*;
 use relm4::prelude::*;
+use relm4::{Sender, ShutdownReceiver};
 use relm4_icons::icon_name;
 use simple_moving_average::{SingleSumSMA, SMA};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use subspace_core_primitives::SectorIndex;
 use subspace_farmer::farm::{
     FarmingNotification, SectorExpirationDetails, SectorPlottingDetails, SectorUpdate,
 };
 use subspace_farmer::single_disk_farm::FarmingError;
-use tracing::error;
+use tracing::{debug, error};
 
 /// Experimentally found number that is good for default window size to not have horizontal scroll
 const SECTORS_PER_ROW: usize = 108;
@@ -30,6 +31,16 @@ const BLOCK_AUTHORING_DELAY: Duration = Duration::from_secs(4);
 const EXCELLENT_PROVING_TIME: Duration = Duration::from_millis(1800);
 /// Number of samples over which to track sector plotting time
 const SECTOR_PLOTTING_TIME_TRACKING_WINDOW: usize = 10;
+/// Plotting speed history is tracked in buckets of this duration
+const PLOTTING_HISTORY_BUCKET_DURATION: Duration = Duration::from_secs(5 * 60);
+/// Number of buckets in plotting speed history, 1 hour in total
+const PLOTTING_HISTORY_BUCKETS: usize = 12;
+/// Minimal number of auditing/proving samples before farming deadline margin is checked
+const MIN_FARMING_DEADLINE_SAMPLES: usize = 3;
+/// For how long auditing/proving indicator is highlighted after corresponding farming notification
+const FARM_ACTIVITY_HIGHLIGHT_DURATION: Duration = Duration::from_millis(400);
+/// How often plotting is checked for being stalled (if enabled)
+const PLOTTING_STALL_CHECK_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum PlottingKind {
@@ -47,6 +58,32 @@ enum PlottingState {
     Idle,
 }
 
+/// Stage of the sector that is currently being plotted
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum SectorPlottingStage {
+    Starting,
+    Downloading,
+    Downloaded,
+    Encoding,
+    Encoded,
+    Writing,
+    Written,
+}
+
+impl SectorPlottingStage {
+    fn description(&self) -> &'static str {
+        match self {
+            Self::Starting => "starting",
+            Self::Downloading => "downloading",
+            Self::Downloaded => "waiting for encoding",
+            Self::Encoding => "encoding",
+            Self::Encoded => "waiting for writing",
+            Self::Writing => "writing",
+            Self::Written => "finishing",
+        }
+    }
+}
+
 #[derive(Debug)]
 enum SectorState {
     Plotted,
@@ -70,6 +107,45 @@ impl SectorState {
     }
 }
 
+/// Number of sectors plotted within each of the last few fixed-size time buckets, oldest first,
+/// the last bucket is the current one
+#[derive(Debug)]
+struct PlottingHistory {
+    buckets: VecDeque<u32>,
+}
+
+impl Default for PlottingHistory {
+    fn default() -> Self {
+        let mut buckets = VecDeque::with_capacity(PLOTTING_HISTORY_BUCKETS);
+        buckets.push_back(0);
+
+        Self { buckets }
+    }
+}
+
+impl PlottingHistory {
+    fn record_sector(&mut self) {
+        if let Some(current_bucket) = self.buckets.back_mut() {
+            *current_bucket += 1;
+        }
+    }
+
+    fn next_bucket(&mut self) {
+        if self.buckets.len() == PLOTTING_HISTORY_BUCKETS {
+            self.buckets.pop_front();
+        }
+        self.buckets.push_back(0);
+    }
+
+    fn total_sectors(&self) -> u32 {
+        self.buckets.iter().sum()
+    }
+
+    fn max_sectors(&self) -> u32 {
+        self.buckets.iter().copied().max().unwrap_or_default()
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct FarmWidgetInit {
     pub(super) farm: Farm,
@@ -77,6 +153,12 @@ pub(super) struct FarmWidgetInit {
     pub(super) plotted_total_sectors: SectorIndex,
     pub(super) farm_during_initial_plotting: bool,
     pub(super) plotting_paused: bool,
+    /// Show farm details initially
+    pub(super) farm_details: bool,
+    /// Warn when auditing or proving gets this close to the deadline
+    pub(super) deadline_warning_margin: Option<Duration>,
+    /// Warn when plotting makes no progress for this long while not paused
+    pub(super) plotting_stall_timeout: Option<Duration>,
 }
 
 #[derive(Debug, Clone)]
@@ -84,26 +166,110 @@ pub(super) enum FarmWidgetInput {
     SectorUpdate {
         sector_index: SectorIndex,
         update: SectorUpdate,
+        /// Time at which update was emitted by farmer
+        timestamp: Instant,
     },
     FarmingNotification(FarmingNotification),
+    ExpiringSectors {
+        about_to_expire_sectors_count: SectorIndex,
+        expired_sectors_count: SectorIndex,
+    },
     PausePlotting(bool),
+    /// Plotting of this farm was paused or resumed due to disk temperature
+    DiskTemperaturePause {
+        temperature: f32,
+        paused: bool,
+    },
     OpenFarmFolder,
     NodeSynced(bool),
     ToggleFarmDetails,
     Error {
         error: Arc<anyhow::Error>,
     },
+    RecoveryAttempt {
+        attempt: usize,
+    },
+    Recovered,
+    /// Farm was stopped and opened again after rescan
+    Rescanned,
+    RequestWipe,
+    Wiping,
+    Wiped {
+        freed_bytes: u64,
+    },
+    WipeFailed {
+        error: Arc<anyhow::Error>,
+    },
+}
+
+#[derive(Debug)]
+pub(super) enum FarmWidgetOutput {
+    /// User requested farm to be wiped, needs to be confirmed
+    Wipe { farm_index: u8, path: PathBuf },
+    /// Average auditing or proving time got too close to the deadline
+    FarmingDeadlineWarning {
+        farm_index: u8,
+        /// `auditing` or `proving`
+        activity: &'static str,
+        margin: Duration,
+    },
+    /// Sectors are being plotted, but there was no progress for longer than configured timeout
+    PlottingStalled {
+        farm_index: u8,
+        /// Description of the stage the sector with the lowest index among sectors being plotted
+        /// is stuck at
+        stage: &'static str,
+        stalled_for: Duration,
+    },
+}
+
+#[derive(Debug)]
+enum FarmWipeState {
+    Wiping,
+    Wiped { freed_bytes: u64 },
+    Failed { error: Arc<anyhow::Error> },
+}
+
+#[derive(Debug)]
+pub(super) enum FarmWidgetCommandOutput {
+    /// Time to start the next plotting history bucket
+    PlottingHistoryTick,
+    AuditingHighlightEnded,
+    ProvingHighlightEnded,
+    /// Time to check whether plotting is stalled
+    PlottingStallCheck,
 }
 
 #[derive(Debug)]
 pub(super) struct FarmWidget {
+    farm_index: u8,
     path: PathBuf,
     size: String,
     auditing_time: SingleSumSMA<Duration, u32, AUDITING_TIME_TRACKING_WINDOW>,
     proving_time: SingleSumSMA<Duration, u32, PROVING_TIME_TRACKING_WINDOW>,
+    deadline_warning_margin: Option<Duration>,
+    /// Whether warning about small deadline margin was already issued, reset once margin recovers
+    deadline_warning_issued: bool,
+    plotting_stall_timeout: Option<Duration>,
+    /// Last time plotting of any sector made progress or plotting was paused
+    last_plotting_progress: Instant,
+    /// Whether warning about stalled plotting was already issued, reset once plotting progresses
+    plotting_stall_warning_issued: bool,
+    /// Number of recent audits for which auditing indicator is still highlighted
+    auditing_highlights: usize,
+    /// Number of recent proofs for which proving indicator is still highlighted
+    proving_highlights: usize,
     sector_plotting_time: SingleSumSMA<Duration, u32, SECTOR_PLOTTING_TIME_TRACKING_WINDOW>,
+    plotting_history: PlottingHistory,
+    plotting_history_chart: gtk::DrawingArea,
     last_sector_plotted: Option<SectorIndex>,
+    about_to_expire_sectors_count: SectorIndex,
+    expired_sectors_count: SectorIndex,
     plotting_state: PlottingState,
+    /// Sectors that were already plotted when farm was started, these are kept as is
+    initially_plotted_sectors: SectorIndex,
+    /// Sectors that are currently being plotted
+    plotting_sectors: BTreeMap<SectorIndex, SectorPlottingStage>,
     is_node_synced: bool,
     farm_during_initial_plotting: bool,
     sector_rows: gtk::Box,
@@ -112,15 +278,20 @@ pub(super) struct FarmWidget {
     farm_details: bool,
     encoding_sectors: usize,
     plotting_paused: bool,
+    /// Disk temperature (in °C) if plotting of this farm is paused because disk is too hot
+    disk_overheated: Option<f32>,
     error: Option<Arc<anyhow::Error>>,
+    /// Last attempt to recover farm after error
+    recovery_attempt: Option<usize>,
+    wipe_state: Option<FarmWipeState>,
 }
 
 #[relm4::factory(pub(super))]
 impl FactoryComponent for FarmWidget {
     type Init = FarmWidgetInit;
     type Input = FarmWidgetInput;
-    type Output = ();
-    type CommandOutput = ();
+    type Output = FarmWidgetOutput;
+    type CommandOutput = FarmWidgetCommandOutput;
     type ParentWidget = gtk::Box;
     type Index = u8;
 
@@ -165,6 +336,37 @@ impl FactoryComponent for FarmWidget {
                             set_margin_top: 5,
                             set_spacing: 10,
 
+                            gtk::Label {
+                                add_css_class: "warning-label",
+                                #[watch]
+                                set_label: &format!(
+                                    "{} sectors to replot",
+                                    self.about_to_expire_sectors_count + self.expired_sectors_count
+                                ),
+                                #[watch]
+                                set_tooltip: &format!(
+                                    "Significant number of sectors needs to be replotted soon: {} about to expire, {} already expired",
+                                    self.about_to_expire_sectors_count,
+                                    self.expired_sectors_count
+                                ),
+                                #[watch]
+                                set_visible: self.about_to_expire_sectors_count + self.expired_sectors_count > 0,
+                            },
+
+                            gtk::Box {
+                                #[watch]
+                                set_tooltip: &format!(
+                                    "Plotting speed over the last hour: {} sectors plotted, up to {} sectors per {} minutes",
+                                    self.plotting_history.total_sectors(),
+                                    self.plotting_history.max_sectors(),
+                                    PLOTTING_HISTORY_BUCKET_DURATION.as_secs() / 60,
+                                ),
+                                #[watch]
+                                set_visible: self.plotting_history.total_sectors() > 0,
+
+                                self.plotting_history_chart.clone(),
+                            },
+
                             gtk::Box {
                                 set_spacing: 5,
                                 #[watch]
@@ -177,6 +379,12 @@ impl FactoryComponent for FarmWidget {
                                 set_visible: self.auditing_time.get_num_samples() > 0,
 
                                 gtk::Image {
+                                    #[watch]
+                                    set_css_classes: if self.auditing_highlights > 0 {
+                                        &["farm-activity", "highlighted"]
+                                    } else {
+                                        &["farm-activity"]
+                                    },
                                     set_icon_name: Some(icon_name::PUZZLE_PIECE),
                                 },
 
@@ -205,6 +413,12 @@ impl FactoryComponent for FarmWidget {
                                 set_visible: self.proving_time.get_num_samples() > 0,
 
                                 gtk::Image {
+                                    #[watch]
+                                    set_css_classes: if self.proving_highlights > 0 {
+                                        &["farm-activity", "highlighted"]
+                                    } else {
+                                        &["farm-activity"]
+                                    },
                                     set_icon_name: Some(icon_name::PROCESSOR),
                                 },
 
@@ -236,19 +450,60 @@ impl FactoryComponent for FarmWidget {
                         }
                     },
                 },
+
+                gtk::Button {
+                    connect_clicked => FarmWidgetInput::RequestWipe,
+                    set_has_frame: false,
+                    set_icon_name: icon_name::CROSS,
+                    set_tooltip: "Wipe this farm: stop it, delete its files and remove it from configuration",
+                    set_valign: gtk::Align::Center,
+                    #[watch]
+                    set_visible: self.wipe_state.is_none(),
+                },
             },
 
             #[transition = "SlideUpDown"]
-            match (&self.error, self.plotting_state) {
-                (Some(error), _) => gtk::Box {
+            match (&self.wipe_state, &self.error, self.plotting_state) {
+                (Some(FarmWipeState::Wiping), _, _) => gtk::Box {
+                    set_spacing: 5,
+
+                    gtk::Label {
+                        set_label: "Wiping farm, deleting its files...",
+                    },
+
+                    gtk::Spinner {
+                        start: (),
+                    },
+                },
+                (Some(FarmWipeState::Wiped { freed_bytes }), _, _) => gtk::Box {
+                    gtk::Label {
+                        #[watch]
+                        set_label: &format!(
+                            "Farm wiped, {} freed",
+                            bytesize::to_string(*freed_bytes, true)
+                        ),
+                    },
+                },
+                (Some(FarmWipeState::Failed { error }), _, _) => gtk::Box {
                     gtk::Label {
                         add_css_class: "farm-error",
                         set_halign: gtk::Align::Start,
                         #[watch]
-                        set_label: &format!("Farm crashed: {error}"),
+                        set_label: &format!("Failed to wipe farm: {error}"),
+                    },
+                },
+                (None, Some(error), _) => gtk::Box {
+                    gtk::Label {
+                        add_css_class: "farm-error",
+                        set_halign: gtk::Align::Start,
+                        #[watch]
+                        set_label: &match self.recovery_attempt {
+                            Some(attempt) => format!("Farm crashed: {error} (recovery attempt {attempt})"),
+                            None => format!("Farm crashed: {error}"),
+                        },
                     }
                 },
-                (_, PlottingState::Plotting { kind, progress }) => gtk::Box {
+                (None, None, PlottingState::Plotting { kind, progress }) => gtk::Box {
                     set_orientation: gtk::Orientation::Vertical,
                     set_spacing: 10,
 
@@ -277,7 +532,7 @@ impl FactoryComponent for FarmWidget {
 
                                 match kind {
                                     PlottingKind::Initial => {
-                                        let initial_plotting = if self.plotting_paused {
+                                        let initial_plotting = if self.plotting_paused || self.disk_overheated.is_some() {
                                             if self.encoding_sectors > 0 {
                                                 "Pausing initial plotting"
                                             } else {
@@ -300,7 +555,7 @@ impl FactoryComponent for FarmWidget {
                                         )
                                     },
                                     PlottingKind::Replotting => {
-                                        let replotting = if self.plotting_paused {
+                                        let replotting = if self.plotting_paused || self.disk_overheated.is_some() {
                                             if self.encoding_sectors > 0 {
                                                 "Pausing replotting"
                                             } else {
@@ -335,8 +590,38 @@ impl FactoryComponent for FarmWidget {
                         #[watch]
                         set_fraction: progress as f64 / 100.0,
                     },
+
+                    gtk::Label {
+                        add_css_class: "farm-error",
+                        set_halign: gtk::Align::Start,
+                        #[watch]
+                        set_label: &self.disk_overheated
+                            .map(|temperature| {
+                                format!(
+                                    "Plotting of this farm is paused automatically, disk is too \
+                                    hot ({temperature:.0} °C), it will resume once disk cools down"
+                                )
+                            })
+                            .unwrap_or_default(),
+                        set_wrap: true,
+                        #[watch]
+                        set_visible: self.disk_overheated.is_some(),
+                    },
+
+                    gtk::Label {
+                        add_css_class: "dim-label",
+                        set_halign: gtk::Align::Start,
+                        set_label: &format!(
+                            "Resumed from where it left off, {} sectors plotted earlier are kept and \
+                            only remaining sectors are being plotted",
+                            self.initially_plotted_sectors
+                        ),
+                        set_wrap: true,
+                        #[watch]
+                        set_visible: kind == PlottingKind::Initial && self.initially_plotted_sectors > 0,
+                    },
                 },
-                (_, PlottingState::Idle) => gtk::Box {
+                (None, None, PlottingState::Idle) => gtk::Box {
                     gtk::Label {
                         #[watch]
                         set_label: if self.is_node_synced {
@@ -349,15 +634,26 @@ impl FactoryComponent for FarmWidget {
             },
 
             gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 5,
                 #[watch]
-                set_visible: self.farm_details && self.error.is_none(),
+                set_visible: self.farm_details && self.error.is_none() && self.wipe_state.is_none(),
+
+                gtk::Label {
+                    add_css_class: "dim-label",
+                    set_halign: gtk::Align::Start,
+                    #[watch]
+                    set_label: &self.plotting_now(),
+                    #[watch]
+                    set_visible: !self.plotting_sectors.is_empty(),
+                },
 
                 self.sector_rows.clone(),
             },
         },
     }
 
-    fn init_model(init: Self::Init, _index: &Self::Index, _sender: FactorySender<Self>) -> Self {
+    fn init_model(init: Self::Init, index: &Self::Index, sender: FactorySender<Self>) -> Self {
         let mut sectors = Vec::with_capacity(usize::from(init.total_sectors));
         for sector_index in 0..init.total_sectors {
             let sector = gtk::Box::builder()
@@ -380,88 +676,148 @@ impl FactoryComponent for FarmWidget {
             }
         });
 
+        let plotting_history_chart = gtk::DrawingArea::builder()
+            .content_width(70)
+            .content_height(16)
+            .build();
+
+        sender.command(|sender, shutdown_receiver| async move {
+            Self::plotting_history_ticks(sender, shutdown_receiver).await;
+        });
+        if init.plotting_stall_timeout.is_some() {
+            sender.command(|sender, shutdown_receiver| async move {
+                Self::plotting_stall_checks(sender, shutdown_receiver).await;
+            });
+        }
+
         Self {
+            farm_index: *index,
             path: init.farm.path,
             size: init.farm.size,
             auditing_time: SingleSumSMA::from_zero(Duration::ZERO),
             proving_time: SingleSumSMA::from_zero(Duration::ZERO),
+            deadline_warning_margin: init.deadline_warning_margin,
+            deadline_warning_issued: false,
+            plotting_stall_timeout: init.plotting_stall_timeout,
+            last_plotting_progress: Instant::now(),
+            plotting_stall_warning_issued: false,
+            auditing_highlights: 0,
+            proving_highlights: 0,
             sector_plotting_time: SingleSumSMA::from_zero(Duration::ZERO),
+            plotting_history: PlottingHistory::default(),
+            plotting_history_chart,
             last_sector_plotted: None,
+            about_to_expire_sectors_count: 0,
+            expired_sectors_count: 0,
             plotting_state: PlottingState::Idle,
+            initially_plotted_sectors: init.plotted_total_sectors,
+            plotting_sectors: BTreeMap::new(),
             is_node_synced: false,
             farm_during_initial_plotting: init.farm_during_initial_plotting,
             sector_rows,
             sectors: HashMap::from_iter((SectorIndex::MIN..).zip(sectors)),
             non_fatal_farming_error: None,
-            farm_details: false,
+            farm_details: init.farm_details,
             encoding_sectors: 0,
             plotting_paused: init.plotting_paused,
+            disk_overheated: None,
             error: None,
+            recovery_attempt: None,
+            wipe_state: None,
         }
     }
 
-    fn update(&mut self, input: Self::Input, _sender: FactorySender<Self>) {
-        self.process_input(input);
+    fn update(&mut self, input: Self::Input, sender: FactorySender<Self>) {
+        self.process_input(input, sender);
+    }
+
+    fn update_cmd(&mut self, input: Self::CommandOutput, sender: FactorySender<Self>) {
+        self.process_command(input, &sender);
     }
 }
 
 impl FarmWidget {
-    fn process_input(&mut self, input: FarmWidgetInput) {
+    fn process_input(&mut self, input: FarmWidgetInput, sender: FactorySender<Self>) {
         match input {
             FarmWidgetInput::SectorUpdate {
                 sector_index,
                 update,
+                timestamp,
             } => match update {
-                SectorUpdate::Plotting(plotting_update) => match plotting_update {
-                    SectorPlottingDetails::Starting {
-                        progress,
-                        replotting,
-                        last_queued,
-                    } => {
-                        self.plotting_state = PlottingState::Plotting {
-                            kind: if replotting {
-                                PlottingKind::Replotting
-                            } else {
-                                PlottingKind::Initial
-                            },
+                SectorUpdate::Plotting(plotting_update) => {
+                    self.last_plotting_progress = timestamp;
+                    self.plotting_stall_warning_issued = false;
+
+                    match plotting_update {
+                        SectorPlottingDetails::Starting {
                             progress,
-                        };
+                            replotting,
+                            last_queued,
+                        } => {
+                            self.plotting_state = PlottingState::Plotting {
+                                kind: if replotting {
+                                    PlottingKind::Replotting
+                                } else {
+                                    PlottingKind::Initial
+                                },
+                                progress,
+                            };
 
-                        if last_queued {
-                            self.last_sector_plotted.replace(sector_index);
-                        }
-                    }
-                    SectorPlottingDetails::Downloading => {
-                        self.update_sector_state(sector_index, SectorState::Downloading);
-                    }
-                    SectorPlottingDetails::Downloaded(_) => {
-                        self.remove_sector_state(sector_index, SectorState::Downloading);
-                    }
-                    SectorPlottingDetails::Encoding => {
-                        self.encoding_sectors += 1;
-                        self.update_sector_state(sector_index, SectorState::Encoding);
-                    }
-                    SectorPlottingDetails::Encoded(_) => {
-                        self.encoding_sectors -= 1;
-                        self.remove_sector_state(sector_index, SectorState::Encoding);
-                    }
-                    SectorPlottingDetails::Writing => {
-                        self.update_sector_state(sector_index, SectorState::Writing);
-                    }
-                    SectorPlottingDetails::Written(_) => {
-                        self.remove_sector_state(sector_index, SectorState::Writing);
-                    }
-                    SectorPlottingDetails::Finished { time, .. } => {
-                        if self.last_sector_plotted == Some(sector_index) {
-                            self.last_sector_plotted.take();
+                            if last_queued {
+                                self.last_sector_plotted.replace(sector_index);
+                            }
 
-                            self.plotting_state = PlottingState::Idle;
+                            self.plotting_sectors
+                                .insert(sector_index, SectorPlottingStage::Starting);
+                        }
+                        SectorPlottingDetails::Downloading => {
+                            self.plotting_sectors
+                                .insert(sector_index, SectorPlottingStage::Downloading);
+                            self.update_sector_state(sector_index, SectorState::Downloading);
                         }
+                        SectorPlottingDetails::Downloaded(_) => {
+                            self.plotting_sectors
+                                .insert(sector_index, SectorPlottingStage::Downloaded);
+                            self.remove_sector_state(sector_index, SectorState::Downloading);
+                        }
+                        SectorPlottingDetails::Encoding => {
+                            self.encoding_sectors += 1;
+                            self.plotting_sectors
+                                .insert(sector_index, SectorPlottingStage::Encoding);
+                            self.update_sector_state(sector_index, SectorState::Encoding);
+                        }
+                        SectorPlottingDetails::Encoded(_) => {
+                            self.encoding_sectors -= 1;
+                            self.plotting_sectors
+                                .insert(sector_index, SectorPlottingStage::Encoded);
+                            self.remove_sector_state(sector_index, SectorState::Encoding);
+                        }
+                        SectorPlottingDetails::Writing => {
+                            self.plotting_sectors
+                                .insert(sector_index, SectorPlottingStage::Writing);
+                            self.update_sector_state(sector_index, SectorState::Writing);
+                        }
+                        SectorPlottingDetails::Written(_) => {
+                            self.plotting_sectors
+                                .insert(sector_index, SectorPlottingStage::Written);
+                            self.remove_sector_state(sector_index, SectorState::Writing);
+                        }
+                        SectorPlottingDetails::Finished { time, .. } => {
+                            self.plotting_sectors.remove(&sector_index);
+
+                            if self.last_sector_plotted == Some(sector_index) {
+                                self.last_sector_plotted.take();
 
-                        self.update_sector_state(sector_index, SectorState::Plotted);
-                        self.sector_plotting_time.add_sample(time);
+                                self.plotting_state = PlottingState::Idle;
+                            }
+
+                            self.update_sector_state(sector_index, SectorState::Plotted);
+                            self.sector_plotting_time.add_sample(time);
+                            self.plotting_history.record_sector();
+                            self.update_plotting_history_chart();
+                        }
                     }
-                },
+                }
                 SectorUpdate::Expiration(expiration_update) => match expiration_update {
                     SectorExpirationDetails::Determined { .. } => {
                         // TODO: Track segments to mark sector as about to expire/expired even if
@@ -478,17 +834,42 @@ impl FarmWidget {
             FarmWidgetInput::FarmingNotification(notification) => match notification {
                 FarmingNotification::Auditing(auditing_details) => {
                     self.auditing_time.add_sample(auditing_details.time);
+                    self.check_deadline_margin(&sender);
+                    self.auditing_highlights += 1;
+                    sender.oneshot_command(async {
+                        tokio::time::sleep(FARM_ACTIVITY_HIGHLIGHT_DURATION).await;
+                        FarmWidgetCommandOutput::AuditingHighlightEnded
+                    });
                 }
                 FarmingNotification::Proving(proving_details) => {
                     self.proving_time.add_sample(proving_details.time);
+                    self.check_deadline_margin(&sender);
+                    self.proving_highlights += 1;
+                    sender.oneshot_command(async {
+                        tokio::time::sleep(FARM_ACTIVITY_HIGHLIGHT_DURATION).await;
+                        FarmWidgetCommandOutput::ProvingHighlightEnded
+                    });
                 }
                 FarmingNotification::NonFatalError(error) => {
                     self.non_fatal_farming_error.replace(error);
                 }
             },
+            FarmWidgetInput::ExpiringSectors {
+                about_to_expire_sectors_count,
+                expired_sectors_count,
+            } => {
+                self.about_to_expire_sectors_count = about_to_expire_sectors_count;
+                self.expired_sectors_count = expired_sectors_count;
+            }
             FarmWidgetInput::PausePlotting(plotting_paused) => {
                 self.plotting_paused = plotting_paused;
             }
+            FarmWidgetInput::DiskTemperaturePause {
+                temperature,
+                paused,
+            } => {
+                self.disk_overheated = paused.then_some(temperature);
+            }
             FarmWidgetInput::OpenFarmFolder => {
                 if let Err(error) = open::that_detached(&self.path) {
                     error!(%error, path = %self.path.display(), "Failed to open farm folder");
@@ -502,8 +883,243 @@ impl FarmWidget {
             }
             FarmWidgetInput::Error { error } => {
                 self.error.replace(error);
+                self.recovery_attempt = None;
+                // Plotting was interrupted by the error
+                self.plotting_sectors.clear();
+            }
+            FarmWidgetInput::RecoveryAttempt { attempt } => {
+                self.recovery_attempt.replace(attempt);
+            }
+            FarmWidgetInput::Recovered => {
+                self.error = None;
+                self.recovery_attempt = None;
+            }
+            FarmWidgetInput::Rescanned => {
+                // Plotting was interrupted when farm was stopped for rescan
+                self.plotting_sectors.clear();
+            }
+            FarmWidgetInput::RequestWipe => {
+                if sender
+                    .output(FarmWidgetOutput::Wipe {
+                        farm_index: self.farm_index,
+                        path: self.path.clone(),
+                    })
+                    .is_err()
+                {
+                    debug!("Failed to send FarmWidgetOutput::Wipe");
+                }
             }
+            FarmWidgetInput::Wiping => {
+                self.wipe_state.replace(FarmWipeState::Wiping);
+                self.plotting_sectors.clear();
+            }
+            FarmWidgetInput::Wiped { freed_bytes } => {
+                self.wipe_state
+                    .replace(FarmWipeState::Wiped { freed_bytes });
+            }
+            FarmWidgetInput::WipeFailed { error } => {
+                self.wipe_state.replace(FarmWipeState::Failed { error });
+            }
+        }
+    }
+
+    /// Issue a warning once average auditing or proving time gets closer to the deadline than
+    /// configured margin, warning is issued again only after margin recovers to twice that
+    fn check_deadline_margin(&mut self, sender: &FactorySender<Self>) {
+        let Some(deadline_warning_margin) = self.deadline_warning_margin else {
+            return;
+        };
+
+        let margins = [
+            (
+                "auditing",
+                self.auditing_time.get_num_samples(),
+                MAX_AUDITING_TIME.saturating_sub(self.auditing_time.get_average()),
+            ),
+            (
+                "proving",
+                self.proving_time.get_num_samples(),
+                BLOCK_AUTHORING_DELAY.saturating_sub(self.proving_time.get_average()),
+            ),
+        ];
+        let Some((activity, margin)) = margins
+            .into_iter()
+            .filter(|(_activity, num_samples, _margin)| {
+                *num_samples >= MIN_FARMING_DEADLINE_SAMPLES
+            })
+            .map(|(activity, _num_samples, margin)| (activity, margin))
+            .min_by_key(|(_activity, margin)| *margin)
+        else {
+            return;
+        };
+
+        if self.deadline_warning_issued {
+            if margin >= deadline_warning_margin * 2 {
+                self.deadline_warning_issued = false;
+            }
+        } else if margin < deadline_warning_margin {
+            self.deadline_warning_issued = true;
+
+            if sender
+                .output(FarmWidgetOutput::FarmingDeadlineWarning {
+                    farm_index: self.farm_index,
+                    activity,
+                    margin,
+                })
+                .is_err()
+            {
+                debug!("Failed to send FarmWidgetOutput::FarmingDeadlineWarning");
+            }
+        }
+    }
+
+    /// Issue a warning once sectors are being plotted, but there was no progress for longer than
+    /// configured timeout, intentional pauses (by user or due to disk temperature) don't count
+    fn check_plotting_stall(&mut self, sender: &FactorySender<Self>) {
+        let Some(plotting_stall_timeout) = self.plotting_stall_timeout else {
+            return;
+        };
+
+        if self.plotting_paused
+            || self.disk_overheated.is_some()
+            || self.error.is_some()
+            || self.wipe_state.is_some()
+        {
+            // Time spent paused is not a stall, start counting from scratch once resumed
+            self.last_plotting_progress = Instant::now();
+            return;
+        }
+
+        // Sectors are keyed by index rather than start time, so stage of the sector with the lowest
+        // index is reported, which is typically the one that was started first
+        let Some(stage) = self.plotting_sectors.values().next() else {
+            // Nothing is being plotted, so there is nothing to stall
+            self.last_plotting_progress = Instant::now();
+            return;
+        };
+
+        let stalled_for = self.last_plotting_progress.elapsed();
+        if self.plotting_stall_warning_issued || stalled_for < plotting_stall_timeout {
+            return;
         }
+        self.plotting_stall_warning_issued = true;
+
+        if sender
+            .output(FarmWidgetOutput::PlottingStalled {
+                farm_index: self.farm_index,
+                stage: stage.description(),
+                stalled_for,
+            })
+            .is_err()
+        {
+            debug!("Failed to send FarmWidgetOutput::PlottingStalled");
+        }
+    }
+
+    fn process_command(
+        &mut self,
+        command_output: FarmWidgetCommandOutput,
+        sender: &FactorySender<Self>,
+    ) {
+        match command_output {
+            FarmWidgetCommandOutput::PlottingHistoryTick => {
+                self.plotting_history.next_bucket();
+                self.update_plotting_history_chart();
+            }
+            FarmWidgetCommandOutput::AuditingHighlightEnded => {
+                self.auditing_highlights = self.auditing_highlights.saturating_sub(1);
+            }
+            FarmWidgetCommandOutput::ProvingHighlightEnded => {
+                self.proving_highlights = self.proving_highlights.saturating_sub(1);
+            }
+            FarmWidgetCommandOutput::PlottingStallCheck => {
+                self.check_plotting_stall(sender);
+            }
+        }
+    }
+
+    async fn plotting_stall_checks(
+        sender: Sender<FarmWidgetCommandOutput>,
+        shutdown_receiver: ShutdownReceiver,
+    ) {
+        shutdown_receiver
+            .register(async move {
+                loop {
+                    tokio::time::sleep(PLOTTING_STALL_CHECK_INTERVAL).await;
+
+                    if sender
+                        .send(FarmWidgetCommandOutput::PlottingStallCheck)
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            })
+            .drop_on_shutdown()
+            .await
+    }
+
+    async fn plotting_history_ticks(
+        sender: Sender<FarmWidgetCommandOutput>,
+        shutdown_receiver: ShutdownReceiver,
+    ) {
+        shutdown_receiver
+            .register(async move {
+                loop {
+                    tokio::time::sleep(PLOTTING_HISTORY_BUCKET_DURATION).await;
+
+                    if sender
+                        .send(FarmWidgetCommandOutput::PlottingHistoryTick)
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            })
+            .drop_on_shutdown()
+            .await
+    }
+
+    /// Render plotting history as a line chart, the latest bucket is at the right edge
+    fn update_plotting_history_chart(&self) {
+        let buckets = self.plotting_history.buckets.clone();
+        let max_sectors = f64::from(self.plotting_history.max_sectors().max(1));
+
+        self.plotting_history_chart
+            .set_draw_func(move |_drawing_area, context, width, height| {
+                let (width, height) = (f64::from(width), f64::from(height));
+                let step = width / (PLOTTING_HISTORY_BUCKETS - 1) as f64;
+                let offset = PLOTTING_HISTORY_BUCKETS - buckets.len();
+
+                // Same color as plotted sectors
+                context.set_source_rgb(0.624, 0.769, 1.0);
+                context.set_line_width(1.5);
+                for (index, &sectors) in buckets.iter().enumerate() {
+                    let x = (offset + index) as f64 * step;
+                    let y = height - 1.0 - f64::from(sectors) / max_sectors * (height - 2.0);
+                    if index == 0 {
+                        context.move_to(x, y);
+                    } else {
+                        context.line_to(x, y);
+                    }
+                }
+                if let Err(error) = context.stroke() {
+                    error!(%error, "Failed to draw plotting history chart");
+                }
+            });
+        self.plotting_history_chart.queue_draw();
+    }
+
+    /// Human-readable description of sectors that are currently being plotted
+    fn plotting_now(&self) -> String {
+        let sectors = self
+            .plotting_sectors
+            .iter()
+            .map(|(sector_index, stage)| format!("sector {sector_index} ({})", stage.description()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("Plotting now: {sectors}")
     }
 
     fn update_sector_state(&self, sector_index: SectorIndex, sector_state: SectorState) {
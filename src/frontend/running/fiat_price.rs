@@ -0,0 +1,104 @@
+//! Price of the token in fiat currency, used to show approximate value of reward address balance
+
+use crate::backend::config::FiatConfiguration;
+use crate::frontend::new_version::user_agent;
+use reqwest::{Client, Url};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+/// How frequently to check whether price needs to be updated, such that configuration changes are
+/// picked up quickly
+pub(super) const FIAT_PRICE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// Update price every 15 minutes
+pub(super) const FIAT_PRICE_UPDATE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Retry failed price update every 5 minutes
+pub(super) const FIAT_PRICE_RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Cached price is no longer shown once it is older than this, for example when price source is
+/// unavailable for a long time
+const FIAT_PRICE_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, thiserror::Error)]
+pub(super) enum FiatPriceError {
+    #[error("Failed to fetch price: {0}")]
+    Fetch(#[from] reqwest::Error),
+    #[error("Price not found at \"{pointer}\" in response")]
+    NotFound { pointer: String },
+    #[error("Invalid price {price}")]
+    InvalidPrice { price: String },
+}
+
+/// Price of one token that was fetched from price source
+#[derive(Debug, Clone)]
+pub(super) struct FiatPrice {
+    pub(super) fiat: FiatConfiguration,
+    pub(super) price: f64,
+    fetched_at: Instant,
+    /// Local time at which price was fetched, for display purposes
+    pub(super) fetched_at_local: String,
+}
+
+impl FiatPrice {
+    pub(super) fn new(fiat: FiatConfiguration, price: f64) -> Self {
+        let fetched_at_local = gtk::glib::DateTime::now_local()
+            .and_then(|now| now.format("%H:%M"))
+            .map(|time| time.to_string())
+            .unwrap_or_default();
+
+        Self {
+            fiat,
+            price,
+            fetched_at: Instant::now(),
+            fetched_at_local,
+        }
+    }
+
+    /// Price is recent enough to be shown
+    pub(super) fn is_fresh(&self) -> bool {
+        self.fetched_at.elapsed() < FIAT_PRICE_MAX_AGE
+    }
+
+    /// Host of the price source for display purposes, the whole source if it can't be parsed
+    pub(super) fn source_host(&self) -> String {
+        Url::parse(&self.fiat.price_source)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| self.fiat.price_source.clone())
+    }
+}
+
+/// Fetch price of one token in fiat currency from configured price source
+pub(super) async fn fetch_fiat_price(
+    client: &Client,
+    fiat: &FiatConfiguration,
+) -> Result<f64, FiatPriceError> {
+    let currency = fiat.currency.to_lowercase();
+    let url = fiat.price_source.replace("{currency}", &currency);
+    let pointer = fiat.price_pointer.replace("{currency}", &currency);
+
+    let response = client
+        .get(&url)
+        .header("User-Agent", user_agent())
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<Value>()
+        .await?;
+
+    let price = response
+        .pointer(&pointer)
+        .ok_or_else(|| FiatPriceError::NotFound {
+            pointer: pointer.clone(),
+        })?;
+    // Some APIs return price as a string to avoid loss of precision
+    let price_value = match price {
+        Value::Number(price) => price.as_f64(),
+        Value::String(price) => price.parse::<f64>().ok(),
+        _ => None,
+    };
+
+    price_value
+        .filter(|price| price.is_finite() && *price >= 0.0)
+        .ok_or_else(|| FiatPriceError::InvalidPrice {
+            price: price.to_string(),
+        })
+}
@@ -1,4 +1,4 @@
-use crate::backend::node::{ChainInfo, SyncKind, SyncState};
+use crate::backend::node::{ChainInfo, SyncKind, SyncState, WarpSyncStage};
 use crate::backend::NodeNotification;
 use bytesize::ByteSize;
 use gtk::prelude::*;
@@ -30,7 +30,11 @@ pub enum NodeInput {
         chain_info: ChainInfo,
         node_path: PathBuf,
     },
-    NodeNotification(NodeNotification),
+    NodeNotification {
+        notification: NodeNotification,
+        /// Time at which notification was emitted
+        timestamp: Instant,
+    },
     OpenNodeFolder,
 }
 
@@ -138,10 +142,15 @@ impl Component for NodeView {
                             set_halign: gtk::Align::Start,
 
                             #[watch]
-                            set_label: &{
-                                let kind = match kind {
-                                    SyncKind::Dsn => "Syncing from DSN",
-                                    SyncKind::Regular => "Regular sync",
+                            set_label: &if let SyncKind::Warp(stage) = kind {
+                                // Block numbers jump during warp sync, so block-based speed and
+                                // remaining time would be misleading
+                                warp_sync_status(stage, target)
+                            } else {
+                                let kind = if matches!(kind, SyncKind::Dsn) {
+                                    "Syncing from DSN"
+                                } else {
+                                    "Regular sync"
                                 };
                                 let sync_speed = if model.block_import_time.get_num_samples() > 0 {
                                      let mut sync_speed = format!(
@@ -191,7 +200,18 @@ impl Component for NodeView {
 
                     gtk::ProgressBar {
                         #[watch]
-                        set_fraction: model.best_block_number as f64 / target as f64,
+                        set_fraction: match kind {
+                            SyncKind::Warp(WarpSyncStage::DownloadingState { percentage, .. }) => {
+                                f64::from(percentage) / 100.0
+                            }
+                            SyncKind::Warp(WarpSyncStage::Preparing) => 0.0,
+                            SyncKind::Warp(
+                                WarpSyncStage::ImportingState | WarpSyncStage::DownloadingBlocks
+                            ) => 1.0,
+                            SyncKind::Dsn | SyncKind::Regular => {
+                                model.best_block_number as f64 / target as f64
+                            }
+                        },
                     },
                 },
                 SyncState::Idle => gtk::Box {
@@ -261,7 +281,10 @@ impl NodeView {
                 );
                 *self.node_path.lock() = node_path;
             }
-            NodeInput::NodeNotification(node_notification) => match node_notification {
+            NodeInput::NodeNotification {
+                notification,
+                timestamp,
+            } => match notification {
                 NodeNotification::SyncStateUpdate(mut new_sync_state) => {
                     if let SyncState::Syncing {
                         target: new_target, ..
@@ -284,8 +307,25 @@ impl NodeView {
                             }
                         }
                     }
-                    // Reset block import time on transition to sync
-                    if self.sync_state.is_synced() && !new_sync_state.is_synced() {
+                    let was_warp_syncing = matches!(
+                        self.sync_state,
+                        SyncState::Syncing {
+                            kind: SyncKind::Warp(_),
+                            ..
+                        }
+                    );
+                    let is_warp_syncing = matches!(
+                        new_sync_state,
+                        SyncState::Syncing {
+                            kind: SyncKind::Warp(_),
+                            ..
+                        }
+                    );
+                    // Reset block import time on transition to sync and after warp sync, during
+                    // which block numbers jump
+                    if (self.sync_state.is_synced() && !new_sync_state.is_synced())
+                        || (was_warp_syncing && !is_warp_syncing)
+                    {
                         self.block_import_time = SingleSumSMA::from_zero(Duration::ZERO);
                         self.last_block_import_time.take();
                     }
@@ -299,12 +339,17 @@ impl NodeView {
                     }
 
                     if let Some(last_block_import_time) =
-                        self.last_block_import_time.replace(Instant::now())
+                        self.last_block_import_time.replace(timestamp)
                     {
                         self.block_import_time
-                            .add_sample(last_block_import_time.elapsed());
+                            .add_sample(timestamp.duration_since(last_block_import_time));
                     }
                 }
+                NodeNotification::ConnectedPeersUpdate(_)
+                | NodeNotification::RewardReceived { .. }
+                | NodeNotification::SpacePledged(_) => {
+                    // Not relevant for node view
+                }
             },
             NodeInput::OpenNodeFolder => {
                 let node_path = self.node_path.lock().clone();
@@ -365,3 +410,22 @@ impl NodeView {
             .await
     }
 }
+
+fn warp_sync_status(stage: WarpSyncStage, target: BlockNumber) -> String {
+    match stage {
+        WarpSyncStage::Preparing => {
+            format!("Warp syncing, looking for peers and proofs of block #{target}")
+        }
+        WarpSyncStage::DownloadingState {
+            percentage,
+            downloaded,
+        } => format!(
+            "Warp syncing, downloading state {percentage}% ({})",
+            ByteSize::b(downloaded)
+        ),
+        WarpSyncStage::ImportingState => "Warp syncing, importing downloaded state".to_string(),
+        WarpSyncStage::DownloadingBlocks => {
+            "Warp syncing, downloading older blocks in the background".to_string()
+        }
+    }
+}
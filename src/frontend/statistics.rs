@@ -0,0 +1,148 @@
+use crate::backend::stats::PlottingStats;
+use gtk::prelude::*;
+use relm4::prelude::*;
+use tracing::debug;
+
+#[derive(Debug)]
+pub enum StatisticsInput {
+    PlottingStats(PlottingStats),
+    Close,
+}
+
+#[derive(Debug)]
+pub enum StatisticsOutput {
+    Close,
+}
+
+#[derive(Debug)]
+pub struct StatisticsView {
+    plotting_stats: PlottingStats,
+}
+
+#[relm4::component(pub)]
+impl Component for StatisticsView {
+    type Init = ();
+    type Input = StatisticsInput;
+    type Output = StatisticsOutput;
+    type CommandOutput = ();
+
+    view! {
+        #[root]
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 20,
+
+            gtk::Label {
+                add_css_class: "heading",
+                set_halign: gtk::Align::Start,
+                set_label: "Plotting statistics (all time)",
+            },
+
+            gtk::Grid {
+                set_column_spacing: 20,
+                set_row_spacing: 10,
+
+                attach[0, 0, 1, 1] = &gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    set_label: "Sectors plotted:",
+                },
+                attach[1, 0, 1, 1] = &gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    #[watch]
+                    set_label: &model.plotting_stats.sectors_plotted.to_string(),
+                },
+
+                attach[0, 1, 1, 1] = &gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    set_label: "Sectors replotted:",
+                },
+                attach[1, 1, 1, 1] = &gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    #[watch]
+                    set_label: &model.plotting_stats.sectors_replotted.to_string(),
+                },
+
+                attach[0, 2, 1, 1] = &gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    set_label: "Total plotting time:",
+                },
+                attach[1, 2, 1, 1] = &gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    #[watch]
+                    set_label: &{
+                        let plotting_time = model.plotting_stats.plotting_time.as_secs();
+
+                        format!("{}h {}m", plotting_time / 3600, plotting_time % 3600 / 60)
+                    },
+                },
+
+                attach[0, 3, 1, 1] = &gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    set_label: "Average sector plotting time:",
+                },
+                attach[1, 3, 1, 1] = &gtk::Label {
+                    set_halign: gtk::Align::Start,
+                    #[watch]
+                    set_label: &if model.plotting_stats.sectors_plotted > 0 {
+                        format!(
+                            "{:.2}m",
+                            model.plotting_stats.plotting_time.as_secs_f32()
+                                / model.plotting_stats.sectors_plotted as f32
+                                / 60.0
+                        )
+                    } else {
+                        "-".to_string()
+                    },
+                },
+            },
+
+            gtk::Box {
+                set_halign: gtk::Align::End,
+                set_valign: gtk::Align::End,
+                set_vexpand: true,
+
+                gtk::Button {
+                    connect_clicked => StatisticsInput::Close,
+
+                    gtk::Label {
+                        set_label: "Close",
+                        set_margin_all: 10,
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        _init: Self::Init,
+        _root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = Self {
+            plotting_stats: PlottingStats::default(),
+        };
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, input: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
+        self.process_input(input, sender);
+    }
+}
+
+impl StatisticsView {
+    fn process_input(&mut self, input: StatisticsInput, sender: ComponentSender<Self>) {
+        match input {
+            StatisticsInput::PlottingStats(plotting_stats) => {
+                self.plotting_stats = plotting_stats;
+            }
+            StatisticsInput::Close => {
+                if sender.output(StatisticsOutput::Close).is_err() {
+                    debug!("Failed to send StatisticsOutput::Close");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Message catalogs for user-facing strings.
+//!
+//! Catalogs are JSON files in `res/translations` that map message IDs to text, arguments are
+//! written as `{name}`. English catalog is complete and is used as a fallback for messages that
+//! are missing in the catalog of the selected language.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::{Display, Write};
+use std::sync::OnceLock;
+use tracing::error;
+
+const FALLBACK_LANGUAGE: &str = "en";
+/// Language code and corresponding catalog, new translations are added here
+const CATALOGS: &[(&str, &str)] = &[("en", include_str!("../../res/translations/en.json"))];
+
+struct Catalogs {
+    selected: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+fn catalogs() -> &'static Catalogs {
+    static CATALOGS_CACHE: OnceLock<Catalogs> = OnceLock::new();
+
+    CATALOGS_CACHE.get_or_init(|| {
+        let load = |language: &str| {
+            CATALOGS
+                .iter()
+                .find(|(catalog_language, _)| *catalog_language == language)
+                .and_then(|(_, catalog)| match serde_json::from_str::<HashMap<String, String>>(catalog) {
+                    Ok(catalog) => Some(catalog),
+                    Err(error) => {
+                        error!(%language, %error, "Failed to parse message catalog, ignoring it");
+                        None
+                    }
+                })
+        };
+
+        // Message IDs are shown as is if even fallback catalog is not available
+        Catalogs {
+            selected: detect_language()
+                .and_then(|language| load(&language))
+                .unwrap_or_default(),
+            fallback: load(FALLBACK_LANGUAGE).unwrap_or_default(),
+        }
+    })
+}
+
+// TODO: Detect language on Windows and macOS, where environment variables are typically not set
+/// Language code like `en` from environment variables, `None` if not set
+fn detect_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .find_map(|variable| env::var(variable).ok().filter(|locale| !locale.is_empty()))
+        .and_then(|locale| {
+            locale
+                .split(['_', '-', '.', '@'])
+                .next()
+                .map(str::to_lowercase)
+        })
+}
+
+/// Translated message, message ID itself is returned if it is missing in all catalogs
+pub fn tr(id: &'static str) -> &'static str {
+    let catalogs = catalogs();
+
+    catalogs
+        .selected
+        .get(id)
+        .or_else(|| catalogs.fallback.get(id))
+        .map(String::as_str)
+        .unwrap_or(id)
+}
+
+/// Replace `{name}` placeholders in `message` with corresponding arguments in a single pass, such
+/// that placeholders that happen to be in argument values are left as is. Unknown placeholders are
+/// kept verbatim.
+pub(crate) fn format_message(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut formatted = String::with_capacity(message.len());
+    let mut remaining = message;
+
+    while let Some(start) = remaining.find('{') {
+        formatted.push_str(&remaining[..start]);
+        remaining = &remaining[start..];
+
+        let Some(end) = remaining.find('}') else {
+            break;
+        };
+        let name = &remaining[1..end];
+        match args.iter().find(|(arg_name, _)| *arg_name == name) {
+            Some((_, value)) => {
+                let _ = write!(formatted, "{value}");
+            }
+            None => {
+                formatted.push_str(&remaining[..=end]);
+            }
+        }
+        remaining = &remaining[end + 1..];
+    }
+    formatted.push_str(remaining);
+
+    formatted
+}
+
+/// Translated message with `{name}` placeholders replaced with corresponding arguments, for
+/// example `tr_args!("upgrade-button", chain_name = chain_name)`
+macro_rules! tr_args {
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::frontend::translations::format_message(
+            $crate::frontend::translations::tr($id),
+            &[$((stringify!($name), &($value) as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+pub(crate) use tr_args;
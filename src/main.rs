@@ -4,52 +4,117 @@
 mod backend;
 mod frontend;
 
-use crate::backend::config::RawConfig;
-use crate::backend::farmer::FarmerAction;
-use crate::backend::{wipe, BackendAction, BackendNotification};
+use crate::backend::config::encryption::{set_config_passphrase, CONFIG_PASSPHRASE_ENV};
+use crate::backend::config::{
+    Config, ProcessPriority, RawConfig, RawConfigError, Theme, UiConfiguration,
+};
+use crate::backend::disk_check::check_disk;
+use crate::backend::farmer::{FarmerAction, FarmerNotification, FarmerResources};
+use crate::backend::http::{redacted_proxy, SUPPORTED_PROXY_SCHEMES};
+use crate::backend::node::SyncState;
+use crate::backend::readiness::serve_readiness_endpoint;
+use crate::backend::{
+    wipe, BackendAction, BackendNotification, BackendNotificationSender, NodeNotification,
+    TimestampedBackendNotification,
+};
+use crate::frontend::app_settings::AppSettings;
 use crate::frontend::configuration::{ConfigurationInput, ConfigurationOutput, ConfigurationView};
-use crate::frontend::loading::{LoadingInput, LoadingView};
-use crate::frontend::new_version::NewVersion;
+use crate::frontend::loading::{LoadingInput, LoadingOutput, LoadingView};
+use crate::frontend::new_version::{NewVersion, NewVersionInit, NewVersionOutput};
 use crate::frontend::running::{RunningInit, RunningInput, RunningOutput, RunningView};
-use clap::Parser;
+use crate::frontend::statistics::{StatisticsInput, StatisticsOutput, StatisticsView};
+use crate::frontend::translations::{tr, tr_args};
+use bytesize::ByteSize;
+use clap::{Parser, ValueEnum};
 use duct::cmd;
 use file_rotate::compression::Compression;
 use file_rotate::suffix::AppendCount;
 use file_rotate::{ContentLimit, FileRotate};
 use futures::channel::mpsc;
-use futures::{select, FutureExt, SinkExt, StreamExt};
+use futures::{select, FutureExt, SinkExt, Stream, StreamExt};
 use gtk::prelude::*;
 use parking_lot::Mutex;
 use relm4::prelude::*;
 use relm4::{Sender, ShutdownReceiver, RELM_THREADS};
 use relm4_icons::icon_name;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::future::Future;
 use std::io::{Read, Write};
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::process::{ExitCode, Termination};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::thread::available_parallelism;
-use std::{env, fs, io, process};
+use std::thread::{self, available_parallelism};
+use std::time::{Duration, Instant};
+use std::{env, fmt, fs, io, mem, panic, process};
+use subspace_core_primitives::SectorIndex;
+use subspace_farmer::farm::{SectorPlottingDetails, SectorUpdate};
 use subspace_farmer::utils::{run_future_in_dedicated_thread, AsyncJoinOnDrop};
+use subspace_farmer_components::sector::sector_size;
 use subspace_proof_of_space::chia::ChiaTable;
-use tracing::{error, info, warn};
+use subspace_runtime_primitives::{Balance, SSC};
+use tracing::{debug, error, info, warn};
 use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::prelude::*;
-use tracing_subscriber::EnvFilter;
-
-/// Number of log files to keep
-const LOG_FILE_LIMIT_COUNT: usize = 5;
-/// Size of one log file
-const LOG_FILE_LIMIT_SIZE: usize = 1024 * 1024 * 10;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+/// Maximum size of one log file, smaller files are used when total log size is too small to fit
+/// at least two files of this size
+const LOG_FILE_LIMIT_SIZE: u64 = 1024 * 1024 * 10;
+/// Default total size of current and rotated log files
+const DEFAULT_MAX_LOG_SIZE: &str = "60 MiB";
+/// Total log size can't be smaller than this
+const MIN_MAX_LOG_SIZE: u64 = 1024 * 1024;
+/// File in app data directory where child process stores the reason it exited for the supervisor
+/// and the next run, instances supervised together use separate files with instance tag appended
+const EXIT_REASON_FILE_NAME: &str = "exit-reason.json";
 const LOG_READ_BUFFER: usize = 1024 * 1024;
 /// If `true`, this means supervisor will not be able to capture logs from child application and logger needs to be in
 /// the child process itself, while supervisor will not attempt to read stdout/stderr at all
 const WINDOWS_SUBSYSTEM_WINDOWS: bool = cfg!(all(windows, not(debug_assertions)));
-
-#[derive(Debug, Copy, Clone)]
+/// Identical errors within this window are collapsed into a single status bar notification
+const ERROR_DEDUPLICATION_WINDOW: Duration = Duration::from_secs(5 * 60);
+/// Node connected to fewer peers than this is considered to be in degraded health
+const HEALTH_MIN_CONNECTED_PEERS: u32 = 5;
+/// How long to show wiping summary before restarting the application
+const WIPE_SUMMARY_DISPLAY_DURATION: Duration = Duration::from_secs(3);
+/// How long to show received reward in status bar
+const REWARD_NOTIFICATION_DISPLAY_DURATION: Duration = Duration::from_secs(10);
+/// How often to check whether application executable was updated
+const EXECUTABLE_UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// How often to check whether stylesheets were changed on disk during theme development
+#[cfg(debug_assertions)]
+const CSS_RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// How long to wait for connection to proxy when checking whether it is reachable on startup
+const PROXY_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Child process that exits with restart status code sooner than this after start is considered
+/// to be restarting in a loop, which increases restart delay
+const QUICK_RESTART_THRESHOLD: Duration = Duration::from_secs(60);
+/// Upper limit for restart delay growing due to repeated quick restarts
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+/// Maximum number of pieces in sector of supported chain, used for memory estimate when node isn't
+/// running, the actual value comes from the node
+const EXPECTED_MAX_PIECES_IN_SECTOR: u16 = 1000;
+/// Log filter used when `RUST_LOG` is not set, silences noisy `quinn_udp` warnings.
+///
+/// TODO: This is a hack to work around https://github.com/quinn-rs/quinn/issues/1750, should be
+///  removed once fixed upstream
+const QUINN_LOG_WORKAROUND_FILTER: &str = "info,quinn_udp=error";
+
+/// Status code of the child process.
+///
+/// Custom codes start at `100`, skipping `101` that Rust uses for panics, such that panicking child
+/// is restarted rather than mistaken for a custom exit reason.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum AppStatusCode {
     Exit,
     Restart,
+    /// GTK failed to initialize, typically because there is no display available
+    GtkInitializationFailed,
     Unknown(i32),
 }
 
@@ -58,6 +123,7 @@ impl AppStatusCode {
         match status_code {
             0 => Self::Exit,
             100 => Self::Restart,
+            102 => Self::GtkInitializationFailed,
             code => Self::Unknown(code),
         }
     }
@@ -66,45 +132,175 @@ impl AppStatusCode {
         match self {
             AppStatusCode::Exit => 0,
             AppStatusCode::Restart => 100,
+            AppStatusCode::GtkInitializationFailed => 102,
             AppStatusCode::Unknown(code) => code,
         }
     }
 }
 
+/// Why application exited, complements [`AppStatusCode`] with details that don't fit into status
+/// code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "camelCase")]
+enum ExitReason {
+    /// Application was closed normally
+    Exit,
+    /// Restart was requested by user or after update
+    Restart,
+    GtkInitializationFailed {
+        error: String,
+    },
+    /// Application was closed after irrecoverable error
+    Error {
+        message: String,
+    },
+    Panic {
+        message: String,
+    },
+}
+
+impl fmt::Display for ExitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exit => write!(f, "closed normally"),
+            Self::Restart => write!(f, "restart requested"),
+            Self::GtkInitializationFailed { error } => {
+                write!(f, "failed to initialize GTK: {error}")
+            }
+            Self::Error { message } => write!(f, "closed after irrecoverable error: {message}"),
+            Self::Panic { message } => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+impl ExitReason {
+    fn path(app_data_dir: &Path, instance_tag: Option<&str>) -> PathBuf {
+        match instance_tag {
+            Some(instance_tag) => app_data_dir.join(format!("exit-reason-{instance_tag}.json")),
+            None => app_data_dir.join(EXIT_REASON_FILE_NAME),
+        }
+    }
+
+    fn read(app_data_dir: &Path, instance_tag: Option<&str>) -> Option<Self> {
+        let contents = fs::read(Self::path(app_data_dir, instance_tag)).ok()?;
+        serde_json::from_slice(&contents).ok()
+    }
+
+    /// Read exit reason left by previous run and remove it, such that it is not reported twice
+    fn take(app_data_dir: &Path, instance_tag: Option<&str>) -> Option<Self> {
+        let exit_reason = Self::read(app_data_dir, instance_tag);
+        let _ = fs::remove_file(Self::path(app_data_dir, instance_tag));
+        exit_reason
+    }
+
+    fn write(&self, app_data_dir: &Path, instance_tag: Option<&str>) {
+        let path = Self::path(app_data_dir, instance_tag);
+        let contents = serde_json::to_vec(self).expect("Serialization doesn't fail; qed");
+
+        if let Err(error) = fs::write(&path, contents) {
+            warn!(%error, path = %path.display(), "Failed to write exit reason");
+        }
+    }
+}
+
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 const GLOBAL_CSS: &str = include_str!("../res/app.css");
+#[cfg(debug_assertions)]
+const GLOBAL_CSS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/res/app.css");
+const HIGH_CONTRAST_CSS: &str = include_str!("../res/app-high-contrast.css");
+#[cfg(debug_assertions)]
+const HIGH_CONTRAST_CSS_PATH: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/res/app-high-contrast.css");
 const ABOUT_IMAGE: &[u8] = include_bytes!("../res/about.png");
 
 type PosTable = ChiaTable;
 
 #[derive(Debug)]
 enum AppInput {
-    BackendNotification(BackendNotification),
+    BackendNotification(TimestampedBackendNotification),
+    Loading(LoadingOutput),
     Configuration(ConfigurationOutput),
     Running(RunningOutput),
+    Statistics(StatisticsOutput),
     OpenLogFolder,
+    /// New log filter in `RUST_LOG` format
+    LogFilterChanged(String),
     OpenReconfiguration,
+    OpenStatistics,
+    RescanFarms,
+    BenchmarkFarms,
+    TestPlotting,
+    ReportFarmerCacheOccupancy,
+    ClearFarmerCache,
+    /// Clearing of farmer cache was confirmed by user
+    ClearFarmerCacheConfirmed,
+    /// Wiping of the farm was confirmed by user
+    WipeFarm {
+        farm_index: u8,
+    },
+    /// Farm was wiped and needs to be removed from configuration
+    RemoveWipedFarm {
+        directory: PathBuf,
+    },
     ShowAboutDialog,
+    /// Window close was requested while plotting is in progress
+    ConfirmClose,
     InitialConfiguration,
+    /// Passphrase for encrypted configuration was entered
+    ConfigPassphraseEntered(String),
+    /// Load again after internet connection check failed, with `skip` set to `true` connection is
+    /// not checked anymore
+    RetryInternetConnectionCheck {
+        skip: bool,
+    },
     StartUpgrade,
+    /// Remove farm that belongs to unsupported chain from configuration and restart
+    RemoveUnsupportedFarm,
     Restart,
 }
 
 #[derive(Debug)]
 enum AppCommandOutput {
-    BackendNotification(BackendNotification),
+    BackendNotification(TimestampedBackendNotification),
+    ProxyUnreachable {
+        proxy: Url,
+        error: io::Error,
+    },
+    /// Application executable was replaced, likely new version was installed
+    ExecutableUpdated,
+    /// Stylesheet was changed on disk, only used during theme development
+    #[cfg(debug_assertions)]
+    CssUpdated,
     Restart,
+    /// Informational status bar notification with this message should be hidden
+    InfoNotificationExpired(String),
 }
 
 enum View {
     Welcome,
-    Upgrade { chain_name: String },
+    Upgrade {
+        chain_name: String,
+    },
+    UnsupportedFarmChain {
+        farm_directory: PathBuf,
+        farm_genesis_hash: String,
+        supported_chain: String,
+    },
     Loading,
+    /// Configuration is encrypted and passphrase needs to be entered
+    ConfigPassphrase {
+        wrong_passphrase: bool,
+    },
+    /// None of bootstrap nodes are reachable on startup
+    NoInternetConnection {
+        error: String,
+    },
     Configuration,
     Reconfiguration,
     Running,
+    Statistics,
     Stopped(Option<anyhow::Error>),
     Error(anyhow::Error),
 }
@@ -114,10 +310,14 @@ impl View {
         match self {
             Self::Welcome => "Welcome",
             Self::Upgrade { .. } => "Upgrade",
+            Self::UnsupportedFarmChain { .. } => "Unsupported chain",
             Self::Loading => "Loading",
+            Self::ConfigPassphrase { .. } => "Encrypted configuration",
+            Self::NoInternetConnection { .. } => "No internet connection",
             Self::Configuration => "Configuration",
             Self::Reconfiguration => "Reconfiguration",
             Self::Running => "Running",
+            Self::Statistics => "Statistics",
             Self::Stopped(_) => "Stopped",
             Self::Error(_) => "Error",
         }
@@ -128,6 +328,8 @@ impl View {
 enum StatusBarNotification {
     #[default]
     None,
+    /// Short-lived notification, for example about received reward
+    Info(String),
     Warning {
         message: String,
         /// Whether to show restart button
@@ -147,7 +349,7 @@ impl StatusBarNotification {
 
     fn css_class(&self) -> &'static str {
         match self {
-            Self::None => "label",
+            Self::None | Self::Info(_) => "label",
             Self::Warning { .. } => "warning-label",
             Self::Error(_) => "error-label",
         }
@@ -156,7 +358,9 @@ impl StatusBarNotification {
     fn message(&self) -> &str {
         match self {
             Self::None => "",
-            Self::Warning { message, .. } | Self::Error(message) => message.as_str(),
+            Self::Info(message) | Self::Warning { message, .. } | Self::Error(message) => {
+                message.as_str()
+            }
         }
     }
 
@@ -168,10 +372,138 @@ impl StatusBarNotification {
     }
 }
 
+/// Aggregate health of the application derived from backend notifications
+#[derive(Debug, Default)]
+struct Health {
+    sync_state: SyncState,
+    /// Number of peers node is connected to
+    connected_peers: u32,
+    errored_farms: BTreeSet<u8>,
+    /// Farms that made no plotting progress for longer than configured timeout
+    stalled_farms: BTreeSet<u8>,
+    /// Plotting didn't start yet because not enough DSN peers are connected
+    waiting_for_peers: bool,
+    /// Node and farms were initialized and are running
+    running: bool,
+}
+
+impl Health {
+    /// Whether application is ready from the point of view of external orchestration
+    fn is_ready(&self) -> bool {
+        self.running && self.sync_state.is_synced() && self.errored_farms.is_empty()
+    }
+
+    fn css_classes() -> &'static [&'static str] {
+        &["health-good", "health-degraded", "health-bad"]
+    }
+
+    fn css_class(&self) -> &'static str {
+        if !self.errored_farms.is_empty() {
+            "health-bad"
+        } else if !self.sync_state.is_synced()
+            || self.connected_peers < HEALTH_MIN_CONNECTED_PEERS
+            || !self.stalled_farms.is_empty()
+            || self.waiting_for_peers
+        {
+            "health-degraded"
+        } else {
+            "health-good"
+        }
+    }
+
+    /// Human-readable list of factors contributing to health status
+    fn tooltip(&self) -> String {
+        let mut factors = Vec::new();
+
+        for farm_index in &self.errored_farms {
+            factors.push(format!("Farm {farm_index} crashed"));
+        }
+        for farm_index in &self.stalled_farms {
+            factors.push(format!("Plotting in farm {farm_index} is stalled"));
+        }
+        if self.waiting_for_peers {
+            factors.push("Plotting is waiting for enough peers to start".to_string());
+        }
+        if self.connected_peers == 0 {
+            factors.push("Node is not connected to any peers".to_string());
+        } else if self.connected_peers < HEALTH_MIN_CONNECTED_PEERS {
+            factors.push(format!(
+                "Node is connected to only {} peers",
+                self.connected_peers
+            ));
+        }
+        match self.sync_state {
+            SyncState::Unknown => {
+                factors.push("Node sync state is unknown".to_string());
+            }
+            SyncState::Syncing { .. } => {
+                factors.push("Node is syncing".to_string());
+            }
+            SyncState::Idle => {}
+        }
+
+        if factors.is_empty() {
+            "Everything is working fine".to_string()
+        } else {
+            factors.join("\n")
+        }
+    }
+}
+
+/// Tracks recent occurrences of identical errors, such that repeated errors can be collapsed into a
+/// single status bar notification with a count
+#[derive(Debug, Default)]
+struct RecentErrors {
+    occurrences: HashMap<String, VecDeque<Instant>>,
+}
+
+impl RecentErrors {
+    /// Record occurrence of an error, returns number of identical errors within
+    /// [`ERROR_DEDUPLICATION_WINDOW`] (including this one)
+    fn record(&mut self, message: &str) -> usize {
+        let now = Instant::now();
+
+        self.occurrences.retain(|_message, occurrences| {
+            while let Some(occurrence) = occurrences.front()
+                && now.duration_since(*occurrence) > ERROR_DEDUPLICATION_WINDOW
+            {
+                occurrences.pop_front();
+            }
+
+            !occurrences.is_empty()
+        });
+
+        let occurrences = self.occurrences.entry(message.to_string()).or_default();
+        occurrences.push_back(now);
+        occurrences.len()
+    }
+}
+
 struct AppInit {
     app_data_dir: Option<PathBuf>,
+    /// Runtime preferences loaded from application data directory
+    app_settings: AppSettings,
+    /// File runtime preferences are stored in, `None` if application data directory is unknown
+    app_settings_path: Option<PathBuf>,
     exit_status_code: Arc<Mutex<AppStatusCode>>,
+    /// Irrecoverable error application stopped with, if any
+    irrecoverable_error: Arc<Mutex<Option<String>>>,
     minimize_on_start: bool,
+    /// Theme override, takes precedence over theme from app settings
+    theme: Option<Theme>,
+    safe_mode: bool,
+    /// Custom config file path, default is used if not specified
+    maybe_config_file_path: Option<PathBuf>,
+    /// Proxy for HTTP requests made by the application
+    proxy: Option<Url>,
+    /// Export plot map to this file after startup scan and exit
+    export_plot_map: Option<PathBuf>,
+    /// Serve readiness probe on this address
+    readiness_endpoint: Option<SocketAddr>,
+    /// Log filter that was applied on start
+    log_filter: String,
+    /// Allows changing log filter at runtime
+    log_filter_reload_handle: reload::Handle<EnvFilter, Registry>,
 }
 
 // TODO: Efficient updates with tracker
@@ -179,15 +511,44 @@ struct App {
     current_view: View,
     current_raw_config: Option<RawConfig>,
     status_bar_notification: StatusBarNotification,
+    recent_errors: RecentErrors,
+    health: Health,
+    /// Token symbol of the chain node is running, used for received reward notifications
+    token_symbol: String,
     backend_action_sender: mpsc::Sender<BackendAction>,
     new_version: Controller<NewVersion>,
     loading_view: Controller<LoadingView>,
     configuration_view: Controller<ConfigurationView>,
     running_view: Controller<RunningView>,
+    statistics_view: Controller<StatisticsView>,
     menu_popover: gtk::Popover,
     about_dialog: gtk::AboutDialog,
+    /// Application stylesheet, replaced at runtime when high contrast mode is toggled
+    css_provider: gtk::CssProvider,
+    high_contrast: bool,
     app_data_dir: Option<PathBuf>,
+    /// Custom config file path, default is used if not specified
+    maybe_config_file_path: Option<PathBuf>,
+    /// Runtime preferences stored in application data directory
+    app_settings: AppSettings,
+    app_settings_path: Option<PathBuf>,
+    log_filter: String,
+    log_filter_reload_handle: reload::Handle<EnvFilter, Registry>,
     exit_status_code: Arc<Mutex<AppStatusCode>>,
+    irrecoverable_error: Arc<Mutex<Option<String>>>,
+    /// Neither node nor farmer are running in safe mode, only configuration can be changed
+    safe_mode: bool,
+    /// Sectors that are currently being plotted across all farms
+    plotting_sectors: HashSet<(u8, SectorIndex)>,
+    plotting_paused: bool,
+    /// Plotting self-test was requested in configuration and will run once farmer is started
+    test_plotting_after_start: bool,
+    /// Whether closing the window needs to be confirmed by user, shared with close request handler
+    close_needs_confirmation: Arc<AtomicBool>,
+    /// Set to abort startup while already plotted sectors are being scanned, shared with backend
+    startup_cancelled: Arc<AtomicBool>,
+    /// Reflects [`Health::is_ready()`], shared with readiness endpoint
+    ready: Arc<AtomicBool>,
     // Stored here so `Drop` is called on this future as well, preventing exit until everything shuts down gracefully
     _background_tasks: Box<dyn Future<Output = ()>>,
 }
@@ -214,6 +575,23 @@ impl AsyncComponent for App {
                     pack_end = &gtk::Box {
                         set_spacing: 10,
 
+                        #[name(health_label)]
+                        gtk::Label {
+                            #[track = "!health_label.has_css_class(model.health.css_class())"]
+                            add_css_class: {
+                                for css_class in Health::css_classes() {
+                                    health_label.remove_css_class(css_class);
+                                }
+
+                                model.health.css_class()
+                            },
+                            set_label: "●",
+                            #[watch]
+                            set_tooltip: &model.health.tooltip(),
+                            #[watch]
+                            set_visible: matches!(model.current_view, View::Running),
+                        },
+
                         model.new_version.widget().clone(),
 
                         gtk::MenuButton {
@@ -234,6 +612,16 @@ impl AsyncComponent for App {
                                         set_visible: model.app_data_dir.is_some(),
                                     },
 
+                                    gtk::Entry {
+                                        connect_activate[sender] => move |entry| {
+                                            sender.input(AppInput::LogFilterChanged(entry.text().to_string()));
+                                        },
+                                        set_placeholder_text: Some("info,subspace_farmer=debug"),
+                                        set_text: &model.log_filter,
+                                        set_tooltip: "Log filter in RUST_LOG format, press Enter to apply it immediately, \
+                                            it will be reset back on application restart",
+                                    },
+
                                     gtk::Button {
                                         connect_clicked => AppInput::OpenReconfiguration,
                                         set_label: "Update configuration",
@@ -241,6 +629,53 @@ impl AsyncComponent for App {
                                         set_visible: model.current_raw_config.is_some(),
                                     },
 
+                                    gtk::Button {
+                                        connect_clicked => AppInput::OpenStatistics,
+                                        set_label: "Statistics",
+                                        #[watch]
+                                        set_visible: !model.safe_mode && model.current_raw_config.is_some(),
+                                    },
+
+                                    gtk::Button {
+                                        connect_clicked => AppInput::RescanFarms,
+                                        set_label: "Rescan farms",
+                                        set_tooltip: "Collect plotted pieces from farms again, for example after farm files were changed by external tools",
+                                        #[watch]
+                                        set_visible: matches!(model.current_view, View::Running) && !model.safe_mode,
+                                    },
+
+                                    gtk::Button {
+                                        connect_clicked => AppInput::BenchmarkFarms,
+                                        set_label: "Benchmark farms piece reads",
+                                        set_tooltip: "Measure how fast pieces can be read and decoded from farms (this is slower than reads done for proving), results are shown in events",
+                                        #[watch]
+                                        set_visible: matches!(model.current_view, View::Running) && !model.safe_mode,
+                                    },
+
+                                    gtk::Button {
+                                        connect_clicked => AppInput::TestPlotting,
+                                        set_label: "Test plotting",
+                                        set_tooltip: "Plot a single sector into a temporary directory inside of each farm, one farm at a time, and read it back to check that plotting works on its disk, results are shown in events (can take a while and won't complete while plotting is paused)",
+                                        #[watch]
+                                        set_visible: matches!(model.current_view, View::Running) && !model.safe_mode,
+                                    },
+
+                                    gtk::Button {
+                                        connect_clicked => AppInput::ReportFarmerCacheOccupancy,
+                                        set_label: "Farmer cache occupancy",
+                                        set_tooltip: "Count pieces stored in farmer cache, results are shown in events",
+                                        #[watch]
+                                        set_visible: matches!(model.current_view, View::Running) && !model.safe_mode,
+                                    },
+
+                                    gtk::Button {
+                                        connect_clicked => AppInput::ClearFarmerCache,
+                                        set_label: "Clear farmer cache",
+                                        set_tooltip: "Delete farmer cache such that it is synced from scratch on next start, only available in safe mode",
+                                        #[watch]
+                                        set_visible: model.safe_mode && model.current_raw_config.is_some(),
+                                    },
+
                                     gtk::Button {
                                         connect_clicked => AppInput::ShowAboutDialog,
                                         set_label: "About",
@@ -272,14 +707,7 @@ impl AsyncComponent for App {
                             },
 
                             gtk::Label {
-                                set_label: indoc::indoc! {"
-                                    Space Acres is an opinionated GUI application for farming on Subspace Network.
-
-                                    Before continuing you need 3 things:
-                                    ✔ Wallet address where you'll receive rewards (use Subwallet, polkadot{.js} extension or any other wallet compatible with Substrate chain)
-                                    ✔ 100G of space on a good quality SSD to store node data
-                                    ✔ any SSDs (or multiple) with as much space as you can afford for farming purposes, this is what will generate rewards"
-                                },
+                                set_label: tr("welcome-text"),
                                 set_wrap: true,
                             },
 
@@ -292,7 +720,7 @@ impl AsyncComponent for App {
                                     connect_clicked => AppInput::InitialConfiguration,
 
                                     gtk::Label {
-                                        set_label: "Continue",
+                                        set_label: tr("welcome-continue"),
                                         set_margin_all: 10,
                                     },
                                 },
@@ -312,13 +740,7 @@ impl AsyncComponent for App {
                             },
 
                             gtk::Label {
-                                set_label: indoc::indoc! {"
-                                    Thanks for choosing Space Acres again!
-
-                                    The chain you were running before upgrade is no longer compatible with this release of Space Acres, likely because you were participating in the previous version of Subspace Network.
-
-                                    But fear not, you can upgrade to currently supported network with a single click of a button!"
-                                },
+                                set_label: tr("upgrade-text"),
                                 set_wrap: true,
                             },
 
@@ -332,15 +754,160 @@ impl AsyncComponent for App {
 
                                     gtk::Label {
                                         #[watch]
-                                        set_label: &format!("Upgrade to {chain_name}"),
+                                        set_label: &tr_args!("upgrade-button", chain_name = chain_name),
+                                        set_margin_all: 10,
+                                    },
+                                },
+                            },
+                        },
+                        View::UnsupportedFarmChain { farm_directory, farm_genesis_hash, supported_chain } => gtk::Box {
+                            set_margin_all: 10,
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 20,
+
+                            gtk::Label {
+                                add_css_class: "heading",
+                                set_halign: gtk::Align::Start,
+                                set_label: "Farm belongs to unsupported chain",
+                            },
+
+                            gtk::Label {
+                                #[watch]
+                                set_label: &format!(
+                                    indoc::indoc! {"
+                                        Farm at {} was created for a chain with genesis hash 0x{}, but this release of Space Acres only supports {}.
+
+                                        The chain this farm belongs to is no longer supported, likely because it was created for the previous version of Subspace Network. Plotted data can't be reused, please remove this farm from configuration, it can be added again afterwards with a different directory."
+                                    },
+                                    farm_directory.display(),
+                                    farm_genesis_hash,
+                                    supported_chain,
+                                ),
+                                set_wrap: true,
+                            },
+
+                            gtk::Box {
+                                set_halign: gtk::Align::End,
+
+                                gtk::Button {
+                                    add_css_class: "destructive-action",
+                                    connect_clicked => AppInput::RemoveUnsupportedFarm,
+
+                                    gtk::Label {
+                                        set_label: "Remove farm and restart",
                                         set_margin_all: 10,
                                     },
                                 },
                             },
                         },
                         View::Loading => model.loading_view.widget().clone(),
+                        View::ConfigPassphrase { wrong_passphrase } => gtk::Box {
+                            set_margin_all: 10,
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 20,
+
+                            gtk::Label {
+                                add_css_class: "heading",
+                                set_halign: gtk::Align::Start,
+                                set_label: "Configuration is encrypted",
+                            },
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                #[watch]
+                                set_label: if *wrong_passphrase {
+                                    "Passphrase is wrong or configuration file is damaged, please try again"
+                                } else {
+                                    "Enter passphrase to decrypt configuration"
+                                },
+                                set_wrap: true,
+                            },
+
+                            #[name(config_passphrase_entry)]
+                            gtk::PasswordEntry {
+                                connect_activate[sender] => move |entry| {
+                                    sender.input(AppInput::ConfigPassphraseEntered(entry.text().into()));
+                                    entry.set_text("");
+                                },
+                                set_show_peek_icon: true,
+                            },
+
+                            gtk::Box {
+                                set_halign: gtk::Align::End,
+
+                                gtk::Button {
+                                    add_css_class: "suggested-action",
+                                    connect_clicked[sender, config_passphrase_entry] => move |_| {
+                                        sender.input(AppInput::ConfigPassphraseEntered(
+                                            config_passphrase_entry.text().into()
+                                        ));
+                                        config_passphrase_entry.set_text("");
+                                    },
+
+                                    gtk::Label {
+                                        set_label: "Unlock",
+                                        set_margin_all: 10,
+                                    },
+                                },
+                            },
+                        },
+                        View::NoInternetConnection { error } => gtk::Box {
+                            set_margin_all: 10,
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 20,
+
+                            gtk::Label {
+                                add_css_class: "heading",
+                                set_halign: gtk::Align::Start,
+                                set_label: "Internet connection is required",
+                            },
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+                                set_label: "Space Acres needs internet connection to sync the node and download pieces for plotting, but none of the bootstrap nodes are reachable. Check your network connection and firewall settings, then try again.",
+                                set_wrap: true,
+                            },
+
+                            gtk::Label {
+                                add_css_class: "dim-label",
+                                set_halign: gtk::Align::Start,
+                                #[watch]
+                                set_label: error,
+                                set_selectable: true,
+                                set_wrap: true,
+                            },
+
+                            gtk::Box {
+                                set_halign: gtk::Align::End,
+                                set_spacing: 10,
+
+                                gtk::Button {
+                                    connect_clicked => AppInput::RetryInternetConnectionCheck {
+                                        skip: true,
+                                    },
+
+                                    gtk::Label {
+                                        set_label: "Continue anyway",
+                                        set_margin_all: 10,
+                                    },
+                                },
+
+                                gtk::Button {
+                                    add_css_class: "suggested-action",
+                                    connect_clicked => AppInput::RetryInternetConnectionCheck {
+                                        skip: false,
+                                    },
+
+                                    gtk::Label {
+                                        set_label: "Retry",
+                                        set_margin_all: 10,
+                                    },
+                                },
+                            },
+                        },
                         View::Configuration | View::Reconfiguration => model.configuration_view.widget().clone(),
                         View::Running=> model.running_view.widget().clone(),
+                        View::Statistics => model.statistics_view.widget().clone(),
                         View::Stopped(Some(error)) => {
                             // TODO: Better error handling
                             gtk::Label {
@@ -385,7 +952,7 @@ impl AsyncComponent for App {
                         gtk::Button {
                             add_css_class: "suggested-action",
                             connect_clicked => AppInput::Restart,
-                            set_label: "Restart",
+                            set_label: tr("status-bar-restart"),
                             #[watch]
                             set_visible: model.status_bar_notification.restart_button(),
                         },
@@ -402,10 +969,29 @@ impl AsyncComponent for App {
     ) -> AsyncComponentParts<Self> {
         let (backend_action_sender, backend_action_receiver) = mpsc::channel(1);
         let (backend_notification_sender, mut backend_notification_receiver) = mpsc::channel(100);
+        let safe_mode = init.safe_mode;
+        let maybe_config_file_path = init.maybe_config_file_path.clone();
+        let export_plot_map = init.export_plot_map.clone();
+        let proxy = init.proxy.clone();
+        let startup_cancelled = Arc::<AtomicBool>::default();
 
         // Create and run backend in dedicated thread
         let backend_fut = run_future_in_dedicated_thread(
-            move || backend::create(backend_action_receiver, backend_notification_sender),
+            {
+                let startup_cancelled = Arc::clone(&startup_cancelled);
+
+                move || {
+                    backend::create(
+                        backend_action_receiver,
+                        BackendNotificationSender::new(backend_notification_sender),
+                        safe_mode,
+                        maybe_config_file_path,
+                        export_plot_map,
+                        proxy,
+                        startup_cancelled,
+                    )
+                }
+            },
             "backend".to_string(),
         )
         .expect("Must be able to spawn a thread");
@@ -413,34 +999,53 @@ impl AsyncComponent for App {
         // Forward backend notifications as application inputs
         let message_forwarder_fut = AsyncJoinOnDrop::new(
             tokio::spawn({
-                let sender = sender.clone();
+                let input_sender = sender.input_sender().clone();
 
                 async move {
-                    while let Some(notification) = backend_notification_receiver.next().await {
-                        // TODO: This panics on shutdown because component is already shut down, this should be handled
-                        //  more gracefully
-                        sender.input(AppInput::BackendNotification(notification));
-                    }
+                    forward_notifications(
+                        backend_notification_receiver,
+                        &input_sender,
+                        AppInput::BackendNotification,
+                    )
+                    .await;
                 }
             }),
             true,
         );
 
-        let new_version = NewVersion::builder().launch(()).detach();
+        let new_version = NewVersion::builder()
+            .launch(NewVersionInit {
+                proxy: init.proxy.clone(),
+            })
+            .forward(sender.input_sender(), |output| match output {
+                NewVersionOutput::Restart => AppInput::Restart,
+            });
 
-        let loading_view = LoadingView::builder().launch(()).detach();
+        let loading_view = LoadingView::builder()
+            .launch(())
+            .forward(sender.input_sender(), AppInput::Loading);
 
         let configuration_view = ConfigurationView::builder()
             .launch(root.clone())
             .forward(sender.input_sender(), AppInput::Configuration);
 
+        let app_settings = init.app_settings;
+        configuration_view.emit(ConfigurationInput::UiConfiguration(app_settings.ui));
+
         let running_view = RunningView::builder()
             .launch(RunningInit {
                 // Not paused on start
                 plotting_paused: false,
+                farm_details: app_settings.farm_details,
+                ui_configuration: app_settings.ui,
+                proxy: init.proxy.clone(),
             })
             .forward(sender.input_sender(), AppInput::Running);
 
+        let statistics_view = StatisticsView::builder()
+            .launch(())
+            .forward(sender.input_sender(), AppInput::Statistics);
+
         let about_dialog = gtk::AboutDialog::builder()
             .title("About")
             .program_name("Space Acres")
@@ -456,26 +1061,28 @@ impl AsyncComponent for App {
                     .expect("Statically correct image; qed"),
             ))
             .system_information({
-                let config_directory = dirs::config_local_dir()
-                    .map(|config_local_dir| {
-                        config_local_dir
-                            .join(env!("CARGO_PKG_NAME"))
-                            .display()
-                            .to_string()
+                let config_directory = init
+                    .maybe_config_file_path
+                    .as_deref()
+                    .and_then(Path::parent)
+                    .map(Path::to_path_buf)
+                    .or_else(|| {
+                        dirs::config_local_dir()
+                            .map(|config_local_dir| config_local_dir.join(env!("CARGO_PKG_NAME")))
                     })
+                    .map(|config_directory| config_directory.display().to_string())
                     .unwrap_or_else(|| "Unknown".to_string());
-                let data_directory = dirs::data_local_dir()
-                    .map(|data_local_dir| {
-                        data_local_dir
-                            .join(env!("CARGO_PKG_NAME"))
-                            .display()
-                            .to_string()
-                    })
+                let data_directory = init
+                    .app_data_dir
+                    .as_ref()
+                    .map(|app_data_dir| app_data_dir.display().to_string())
                     .unwrap_or_else(|| "Unknown".to_string());
 
                 format!(
-                    "Config directory: {config_directory}\n\
+                    "{}\n\
+                    Config directory: {config_directory}\n\
                     Data directory (including logs): {data_directory}",
+                    build_kind_description(),
                 )
             })
             .transient_for(&root)
@@ -489,19 +1096,38 @@ impl AsyncComponent for App {
             current_view: View::Loading,
             current_raw_config: None,
             status_bar_notification: StatusBarNotification::None,
+            recent_errors: RecentErrors::default(),
+            health: Health::default(),
+            token_symbol: String::new(),
             backend_action_sender,
             new_version,
             loading_view,
             configuration_view,
             running_view,
+            statistics_view,
             // Hack to initialize a field before this data structure is used
             menu_popover: gtk::Popover::default(),
             about_dialog,
+            css_provider: gtk::CssProvider::new(),
+            high_contrast: false,
             app_data_dir: init.app_data_dir,
+            maybe_config_file_path: init.maybe_config_file_path.clone(),
+            app_settings,
+            app_settings_path: init.app_settings_path,
+            log_filter: init.log_filter,
+            log_filter_reload_handle: init.log_filter_reload_handle,
             exit_status_code: init.exit_status_code,
+            irrecoverable_error: init.irrecoverable_error,
+            safe_mode,
+            plotting_sectors: HashSet::new(),
+            plotting_paused: false,
+            test_plotting_after_start: false,
+            close_needs_confirmation: Arc::default(),
+            startup_cancelled,
+            ready: Arc::default(),
             _background_tasks: Box::new(async move {
-                // Order is important here, if backend is dropped first, there will be an annoying panic in logs due to
-                // notification forwarder sending notification to the component that is already shut down
+                // Order is important here, if backend is dropped first, notification forwarder
+                // would be sending notifications to the component that is already shut down
                 select! {
                     _ = message_forwarder_fut.fuse() => {
                         warn!("Message forwarder exited");
@@ -517,10 +1143,74 @@ impl AsyncComponent for App {
 
         model.menu_popover = widgets.menu_popover.clone();
 
-        if init.minimize_on_start {
+        let ui_configuration = model.app_settings.ui;
+
+        Self::apply_theme(init.theme.unwrap_or(ui_configuration.theme));
+
+        if let Some(display) = gtk::gdk::Display::default() {
+            gtk::style_context_add_provider_for_display(
+                &display,
+                &model.css_provider,
+                gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+            );
+        }
+        model.apply_css(ui_configuration.high_contrast);
+
+        if init.minimize_on_start || ui_configuration.start_minimized {
             root.minimize();
         }
 
+        root.connect_close_request({
+            let close_needs_confirmation = Arc::clone(&model.close_needs_confirmation);
+            let sender = sender.clone();
+
+            move |_window| {
+                if close_needs_confirmation.load(Ordering::Relaxed) {
+                    sender.input(AppInput::ConfirmClose);
+                    gtk::glib::Propagation::Stop
+                } else {
+                    gtk::glib::Propagation::Proceed
+                }
+            }
+        });
+
+        sender.command(Self::watch_executable);
+        #[cfg(debug_assertions)]
+        sender.command(Self::watch_css);
+
+        if let Some(address) = init.readiness_endpoint {
+            let ready = Arc::clone(&model.ready);
+            sender.command(move |_sender, shutdown_receiver| async move {
+                shutdown_receiver
+                    .register(async move {
+                        if let Err(error) = serve_readiness_endpoint(address, ready).await {
+                            error!(%address, %error, "Failed to start readiness endpoint");
+                        }
+                    })
+                    .drop_on_shutdown()
+                    .await
+            });
+        }
+
+        if let Some(proxy) = init.proxy {
+            sender.command(move |sender, shutdown_receiver| async move {
+                shutdown_receiver
+                    .register(async move {
+                        match Self::check_proxy(&proxy).await {
+                            Ok(()) => {
+                                info!(proxy = %redacted_proxy(&proxy), "Proxy is reachable");
+                            }
+                            Err(error) => {
+                                let _ = sender
+                                    .send(AppCommandOutput::ProxyUnreachable { proxy, error });
+                            }
+                        }
+                    })
+                    .drop_on_shutdown()
+                    .await
+            });
+        }
+
         AsyncComponentParts { model, widgets }
     }
 
@@ -528,47 +1218,218 @@ impl AsyncComponent for App {
         &mut self,
         input: Self::Input,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         match input {
             AppInput::OpenLogFolder => {
                 self.open_log_folder();
             }
             AppInput::BackendNotification(notification) => {
-                self.process_backend_notification(notification);
+                self.process_backend_notification(notification, &sender);
+            }
+            AppInput::Loading(loading_output) => {
+                self.process_loading_output(loading_output);
             }
             AppInput::Configuration(configuration_output) => {
                 self.process_configuration_output(configuration_output)
                     .await;
             }
             AppInput::Running(running_output) => {
-                self.process_running_output(running_output).await;
+                self.process_running_output(running_output, sender).await;
+            }
+            AppInput::Statistics(statistics_output) => {
+                self.process_statistics_output(statistics_output);
             }
             AppInput::OpenReconfiguration => {
                 self.menu_popover.hide();
                 if let Some(raw_config) = self.current_raw_config.clone() {
+                    // Discard unsaved changes from previous reconfiguration
+                    self.configuration_view
+                        .emit(ConfigurationInput::UiConfiguration(self.app_settings.ui));
                     self.configuration_view
                         .emit(ConfigurationInput::Reconfigure(raw_config));
                     self.current_view = View::Reconfiguration;
                 }
             }
-            AppInput::ShowAboutDialog => {
+            AppInput::OpenStatistics => {
                 self.menu_popover.hide();
-                self.about_dialog.show();
+                self.current_view = View::Statistics;
             }
-            AppInput::InitialConfiguration => {
-                self.current_view = View::Configuration;
+            AppInput::LogFilterChanged(log_filter) => {
+                self.menu_popover.hide();
+                self.change_log_filter(log_filter);
             }
-            AppInput::StartUpgrade => {
-                let raw_config = self
-                    .current_raw_config
-                    .clone()
-                    .expect("Must have raw config when corresponding button is clicked; qed");
-                sender.command(move |sender, shutdown_receiver| async move {
-                    Self::do_upgrade(sender, shutdown_receiver, raw_config).await;
+            AppInput::RemoveWipedFarm { directory } => {
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::RemoveFarm { path: directory })
+                    .await
+                {
+                    self.show_error(tr_args!(
+                        "status-bar-configuration-save-failed",
+                        error = error
+                    ));
+                }
+            }
+            AppInput::RescanFarms => {
+                self.menu_popover.hide();
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::RescanFarms)
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send farms rescan to backend: {error}"
+                    ));
+                }
+            }
+            AppInput::BenchmarkFarms => {
+                self.menu_popover.hide();
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::Farmer(FarmerAction::BenchmarkFarms))
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send farms piece read benchmark to backend: {error}"
+                    ));
+                }
+            }
+            AppInput::TestPlotting => {
+                self.menu_popover.hide();
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::Farmer(FarmerAction::TestPlotting))
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send plotting self-test to backend: {error}"
+                    ));
+                }
+            }
+            AppInput::ReportFarmerCacheOccupancy => {
+                self.menu_popover.hide();
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::Farmer(
+                        FarmerAction::ReportFarmerCacheOccupancy,
+                    ))
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send farmer cache occupancy request to backend: {error}"
+                    ));
+                }
+            }
+            AppInput::ClearFarmerCache => {
+                self.menu_popover.hide();
+                self.confirm_clear_farmer_cache(&sender);
+            }
+            AppInput::ClearFarmerCacheConfirmed => {
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::ClearFarmerCache)
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send farmer cache clearing to backend: {error}"
+                    ));
+                }
+            }
+            AppInput::WipeFarm { farm_index } => {
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::Farmer(FarmerAction::WipeFarm { farm_index }))
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send farm wipe to backend: {error}"
+                    ));
+                }
+            }
+            AppInput::ShowAboutDialog => {
+                self.menu_popover.hide();
+                self.about_dialog.show();
+            }
+            AppInput::ConfirmClose => {
+                self.confirm_close(root);
+            }
+            AppInput::InitialConfiguration => {
+                self.configuration_view
+                    .emit(ConfigurationInput::StartWizard);
+                self.current_view = View::Configuration;
+            }
+            AppInput::ConfigPassphraseEntered(passphrase) => {
+                set_config_passphrase(Some(passphrase));
+                self.current_view = View::Loading;
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::ConfigPassphraseEntered)
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send configuration passphrase to backend: {error}"
+                    ));
+                }
+            }
+            AppInput::RetryInternetConnectionCheck { skip } => {
+                self.current_view = View::Loading;
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::RetryInternetConnectionCheck { skip })
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send internet connection check retry to backend: {error}"
+                    ));
+                }
+            }
+            AppInput::StartUpgrade => {
+                let raw_config = self
+                    .current_raw_config
+                    .clone()
+                    .expect("Must have raw config when upgrade is started; qed");
+                let maybe_config_file_path = self.maybe_config_file_path.clone();
+                sender.command(move |sender, shutdown_receiver| async move {
+                    Self::do_upgrade(
+                        sender,
+                        shutdown_receiver,
+                        raw_config,
+                        maybe_config_file_path,
+                    )
+                    .await;
                 });
                 self.current_view = View::Loading;
             }
+            AppInput::RemoveUnsupportedFarm => {
+                let View::UnsupportedFarmChain { farm_directory, .. } = &self.current_view else {
+                    return;
+                };
+                let Some(mut raw_config) = self.current_raw_config.clone() else {
+                    return;
+                };
+                raw_config.remove_farm(farm_directory);
+
+                // Backend has already exited at this point, so configuration is written here
+                let result: anyhow::Result<()> = try {
+                    let config_file_path =
+                        RawConfig::path(self.maybe_config_file_path.as_deref()).await?;
+                    raw_config.write_to_path(&config_file_path).await?;
+                };
+
+                match result {
+                    Ok(()) => {
+                        *self.exit_status_code.lock() = AppStatusCode::Restart;
+                        relm4::main_application().quit();
+                    }
+                    Err(error) => {
+                        self.show_error(tr_args!(
+                            "status-bar-configuration-save-failed",
+                            error = error
+                        ));
+                    }
+                }
+            }
             AppInput::Restart => {
                 *self.exit_status_code.lock() = AppStatusCode::Restart;
                 relm4::main_application().quit();
@@ -579,14 +1440,133 @@ impl AsyncComponent for App {
     async fn update_cmd(
         &mut self,
         input: Self::CommandOutput,
-        _sender: AsyncComponentSender<Self>,
+        sender: AsyncComponentSender<Self>,
         _root: &Self::Root,
     ) {
-        self.process_command(input);
+        self.process_command(input, &sender);
     }
 }
 
 impl App {
+    /// Move UI configuration from configuration file to app settings if not done yet, returns
+    /// `true` if settings were changed and need to be saved.
+    ///
+    /// Configuration that can't be read yet (for example encrypted one) is migrated once loaded,
+    /// see [`Self::migrate_legacy_ui_configuration()`].
+    async fn migrate_ui_configuration(
+        app_settings: &mut AppSettings,
+        maybe_config_file_path: Option<&Path>,
+    ) -> bool {
+        let config_file_path = match RawConfig::path(maybe_config_file_path).await {
+            Ok(config_file_path) => config_file_path,
+            Err(error) => {
+                warn!(%error, "Failed to determine config file path");
+                return false;
+            }
+        };
+
+        match RawConfig::read_from_path(&config_file_path).await {
+            Ok(maybe_raw_config) => app_settings.migrate_legacy_ui(maybe_raw_config.as_ref()),
+            Err(error) => {
+                info!(
+                    %error,
+                    "Configuration can't be read yet, UI configuration will be migrated once it \
+                    is loaded"
+                );
+                false
+            }
+        }
+    }
+
+    /// Migrate UI configuration from configuration file that was loaded by backend if it wasn't
+    /// possible on startup
+    fn migrate_legacy_ui_configuration(&mut self, raw_config: &RawConfig) {
+        if self.app_settings.ui_migrated {
+            return;
+        }
+
+        self.app_settings.ui_migrated = true;
+        let ui_configuration = raw_config.legacy_ui().unwrap_or(self.app_settings.ui);
+        // Process priority and runtime threads are applied on start before configuration is
+        // decrypted, restart is needed for migrated values to take effect
+        if ui_configuration.process_priority != self.app_settings.ui.process_priority
+            || ui_configuration.runtime_threads != self.app_settings.ui.runtime_threads
+        {
+            info!(
+                process_priority = ?ui_configuration.process_priority,
+                runtime_threads = ?ui_configuration.runtime_threads,
+                "Process settings were migrated from encrypted configuration, restart is needed to \
+                apply them"
+            );
+            self.status_bar_notification = StatusBarNotification::Warning {
+                message: tr("status-bar-restart-needed").to_string(),
+                restart: true,
+            };
+        }
+        self.update_ui_configuration(ui_configuration);
+    }
+
+    /// Save UI configuration to application settings and apply it to views
+    fn update_ui_configuration(&mut self, ui_configuration: UiConfiguration) {
+        self.apply_css(ui_configuration.high_contrast);
+        self.configuration_view
+            .emit(ConfigurationInput::UiConfiguration(ui_configuration));
+        self.running_view
+            .emit(RunningInput::UiConfiguration(ui_configuration));
+        self.update_app_settings(|app_settings| {
+            app_settings.ui = ui_configuration;
+        });
+    }
+
+    fn apply_theme(theme: Theme) {
+        let Some(settings) = gtk::Settings::default() else {
+            return;
+        };
+
+        match theme {
+            Theme::System => {
+                // Prefer dark theme in cross-platform way if environment is configured that way
+                if matches!(dark_light::detect(), dark_light::Mode::Dark) {
+                    settings.set_gtk_application_prefer_dark_theme(true);
+                }
+            }
+            Theme::Light => {
+                settings.set_gtk_application_prefer_dark_theme(false);
+            }
+            Theme::Dark => {
+                settings.set_gtk_application_prefer_dark_theme(true);
+            }
+        }
+    }
+
+    fn apply_css(&mut self, high_contrast: bool) {
+        self.high_contrast = high_contrast;
+
+        let css = if high_contrast {
+            HIGH_CONTRAST_CSS
+        } else {
+            GLOBAL_CSS
+        };
+
+        // Stylesheets are read from disk in debug builds, such that themes can be developed
+        // without recompiling the application
+        #[cfg(debug_assertions)]
+        let css = &{
+            let path = if high_contrast {
+                HIGH_CONTRAST_CSS_PATH
+            } else {
+                GLOBAL_CSS_PATH
+            };
+
+            fs::read_to_string(path).unwrap_or_else(|error| {
+                warn!(%error, path, "Failed to read stylesheet, using built-in one");
+                css.to_string()
+            })
+        };
+
+        self.css_provider.load_from_data(css);
+    }
+
     fn open_log_folder(&mut self) {
         let Some(app_data_dir) = &self.app_data_dir else {
             return;
@@ -595,10 +1575,74 @@ impl App {
             error!(%error, path = %app_data_dir.display(), "Failed to open logs folder");
         }
     }
-    fn process_backend_notification(&mut self, notification: BackendNotification) {
+
+    fn change_log_filter(&mut self, log_filter: String) {
+        let filter = match EnvFilter::builder()
+            .with_default_directive(LevelFilter::INFO.into())
+            .parse(&log_filter)
+        {
+            Ok(filter) => filter,
+            Err(error) => {
+                self.show_error(format!("Invalid log filter \"{log_filter}\": {error}"));
+                return;
+            }
+        };
+
+        if let Err(error) = self.log_filter_reload_handle.reload(filter) {
+            self.show_error(format!("Failed to change log filter: {error}"));
+            return;
+        }
+
+        info!(%log_filter, "Log filter changed");
+        self.log_filter = log_filter;
+    }
+
+    /// Show error in status bar, identical errors are collapsed into one with a count
+    fn show_error(&mut self, message: String) {
+        let count = self.recent_errors.record(&message);
+
+        self.status_bar_notification = if count > 1 {
+            StatusBarNotification::Error(tr_args!(
+                "status-bar-repeated-error",
+                message = message,
+                count = count,
+                minutes = ERROR_DEDUPLICATION_WINDOW.as_secs() / 60,
+            ))
+        } else {
+            StatusBarNotification::Error(message)
+        };
+    }
+
+    /// Show warning in status bar, error that is already displayed is more important and is not
+    /// replaced
+    fn show_warning(&mut self, message: String) {
+        if matches!(
+            self.status_bar_notification,
+            StatusBarNotification::Error(_)
+        ) {
+            return;
+        }
+
+        self.status_bar_notification = StatusBarNotification::Warning {
+            message,
+            restart: false,
+        };
+    }
+
+    fn process_backend_notification(
+        &mut self,
+        notification: TimestampedBackendNotification,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let TimestampedBackendNotification {
+            timestamp,
+            notification,
+        } = notification;
+
         match notification {
             // TODO: Render progress
             BackendNotification::Loading { step, progress: _ } => {
+                self.health.running = false;
                 self.current_view = View::Loading;
                 self.status_bar_notification = StatusBarNotification::None;
                 self.loading_view.emit(LoadingInput::BackendLoading(step));
@@ -612,28 +1656,78 @@ impl App {
                     chain_name: compatible_chain,
                 };
             }
+            BackendNotification::InterruptedUpgrade { raw_config } => {
+                self.current_raw_config.replace(raw_config);
+                // Upgrade was already confirmed by user before it was interrupted
+                sender.input(AppInput::StartUpgrade);
+            }
+            BackendNotification::UnsupportedFarmChain {
+                raw_config,
+                farm_directory,
+                farm_genesis_hash,
+                supported_chain,
+            } => {
+                self.current_raw_config.replace(raw_config);
+                self.current_view = View::UnsupportedFarmChain {
+                    farm_directory,
+                    farm_genesis_hash,
+                    supported_chain,
+                };
+            }
             BackendNotification::NotConfigured => {
                 self.current_view = View::Welcome;
             }
+            BackendNotification::ConfigurationPassphraseRequired { wrong_passphrase } => {
+                self.current_view = View::ConfigPassphrase { wrong_passphrase };
+            }
+            BackendNotification::NoInternetConnection { error } => {
+                self.current_view = View::NoInternetConnection { error };
+            }
+            BackendNotification::ConfigurationIsCorrupt { backup_path, error } => {
+                self.show_corrupt_configuration_dialog(backup_path, &error);
+            }
             BackendNotification::ConfigurationIsInvalid { error, .. } => {
-                self.status_bar_notification =
-                    StatusBarNotification::Error(format!("Configuration is invalid: {error}"));
+                self.show_error(tr_args!("status-bar-configuration-invalid", error = error));
             }
             BackendNotification::ConfigSaveResult(result) => match result {
                 Ok(()) => {
                     self.status_bar_notification = StatusBarNotification::Warning {
-                        message:
-                            "Application restart is needed for configuration changes to take effect"
-                                .to_string(),
+                        message: tr("status-bar-restart-needed").to_string(),
                         restart: true,
                     };
                 }
                 Err(error) => {
-                    self.status_bar_notification = StatusBarNotification::Error(format!(
-                        "Failed to save configuration changes: {error}"
+                    self.show_error(tr_args!(
+                        "status-bar-configuration-save-failed",
+                        error = error
                     ));
                 }
             },
+            BackendNotification::FarmerCacheClearResult(result) => match result {
+                Ok(()) => {
+                    self.status_bar_notification = StatusBarNotification::Warning {
+                        message: tr("status-bar-farmer-cache-cleared").to_string(),
+                        restart: true,
+                    };
+                }
+                Err(error) => {
+                    self.show_error(tr_args!(
+                        "status-bar-farmer-cache-clear-failed",
+                        error = error
+                    ));
+                }
+            },
+            BackendNotification::SafeMode { raw_config } => {
+                self.migrate_legacy_ui_configuration(&raw_config);
+                self.current_raw_config.replace(raw_config.clone());
+                self.configuration_view
+                    .emit(ConfigurationInput::Reconfigure(raw_config));
+                self.current_view = View::Reconfiguration;
+                self.status_bar_notification = StatusBarNotification::Warning {
+                    message: tr("status-bar-safe-mode").to_string(),
+                    restart: true,
+                };
+            }
             BackendNotification::Running {
                 config: _,
                 raw_config,
@@ -641,38 +1735,208 @@ impl App {
                 reward_address_balance,
                 initial_farm_states,
                 farm_during_initial_plotting,
+                farm_only_when_synced,
+                plot_cache_enabled,
                 chain_info,
             } => {
+                self.migrate_legacy_ui_configuration(&raw_config);
                 self.current_raw_config.replace(raw_config.clone());
                 self.current_view = View::Running;
+                if mem::take(&mut self.test_plotting_after_start) {
+                    sender.input(AppInput::TestPlotting);
+                }
+                self.health = Health {
+                    running: true,
+                    ..Health::default()
+                };
+                self.token_symbol = chain_info.token_symbol.clone();
+                // Plot cache that was explicitly disabled doesn't need a warning
+                if !plot_cache_enabled && !raw_config.farmer().disable_plot_cache {
+                    self.status_bar_notification = StatusBarNotification::Warning {
+                        message: tr("status-bar-plot-cache-disabled").to_string(),
+                        restart: false,
+                    };
+                }
                 self.running_view.emit(RunningInput::Initialize {
                     best_block_number,
                     reward_address_balance,
                     initial_farm_states,
                     farm_during_initial_plotting,
+                    farm_only_when_synced,
                     raw_config,
                     chain_info,
                 });
             }
             BackendNotification::Node(node_notification) => {
-                self.running_view
-                    .emit(RunningInput::NodeNotification(node_notification));
+                match &node_notification {
+                    NodeNotification::SyncStateUpdate(sync_state) => {
+                        self.health.sync_state = *sync_state;
+                    }
+                    NodeNotification::ConnectedPeersUpdate(connected_peers) => {
+                        self.health.connected_peers = *connected_peers;
+                    }
+                    NodeNotification::BlockImported(_) | NodeNotification::SpacePledged(_) => {}
+                    NodeNotification::RewardReceived {
+                        amount,
+                        reward_address_balance,
+                    } => {
+                        self.notify_reward_received(*amount, *reward_address_balance, sender);
+                    }
+                }
+                self.running_view.emit(RunningInput::NodeNotification {
+                    notification: node_notification,
+                    timestamp,
+                });
             }
             BackendNotification::Farmer(farmer_notification) => {
+                match &farmer_notification {
+                    FarmerNotification::SectorUpdate {
+                        farm_index,
+                        sector_index,
+                        update: SectorUpdate::Plotting(plotting_details),
+                    } => {
+                        // Any progress means plotting is no longer stalled
+                        self.health.stalled_farms.remove(farm_index);
+                        match plotting_details {
+                            SectorPlottingDetails::Starting { .. } => {
+                                self.plotting_sectors.insert((*farm_index, *sector_index));
+                                self.update_close_needs_confirmation();
+                            }
+                            SectorPlottingDetails::Finished { .. } => {
+                                self.plotting_sectors.remove(&(*farm_index, *sector_index));
+                                self.update_close_needs_confirmation();
+                            }
+                            _ => {}
+                        }
+                    }
+                    FarmerNotification::FarmError { farm_index, error } => {
+                        self.plotting_sectors
+                            .retain(|(index, _sector_index)| index != farm_index);
+                        self.update_close_needs_confirmation();
+                        self.health.errored_farms.insert(*farm_index);
+                        self.health.stalled_farms.remove(farm_index);
+                        let message = format!("Farm {farm_index} crashed: {error}");
+                        self.notify_error(&message);
+                        self.show_error(message);
+                    }
+                    FarmerNotification::FarmRecovered { farm_index } => {
+                        self.health.errored_farms.remove(farm_index);
+                    }
+                    FarmerNotification::FarmWiped {
+                        farm_index,
+                        directory,
+                        freed_bytes,
+                    } => {
+                        self.plotting_sectors
+                            .retain(|(index, _sector_index)| index != farm_index);
+                        self.update_close_needs_confirmation();
+                        self.health.errored_farms.remove(farm_index);
+                        self.health.stalled_farms.remove(farm_index);
+                        self.status_bar_notification = StatusBarNotification::Warning {
+                            message: tr_args!(
+                                "status-bar-farm-wiped",
+                                path = directory.display(),
+                                freed = bytesize::to_string(*freed_bytes, true)
+                            ),
+                            restart: false,
+                        };
+
+                        if let Some(raw_config) = &mut self.current_raw_config {
+                            raw_config.remove_farm(directory);
+                        }
+                        sender.input(AppInput::RemoveWipedFarm {
+                            directory: directory.clone(),
+                        });
+                    }
+                    FarmerNotification::FarmWipeFailed { farm_index, error } => {
+                        self.show_error(tr_args!(
+                            "status-bar-farm-wipe-failed",
+                            farm_index = farm_index,
+                            error = error
+                        ));
+                    }
+                    FarmerNotification::PlottedSectorsScanProgress(scan_progress) => {
+                        self.status_bar_notification =
+                            if scan_progress.scanned_sectors < scan_progress.total_sectors {
+                                StatusBarNotification::Warning {
+                                    message: tr_args!(
+                                        "status-bar-rescanning-farms",
+                                        progress = scan_progress.scanned_sectors * 100
+                                            / scan_progress.total_sectors
+                                    ),
+                                    restart: false,
+                                }
+                            } else {
+                                StatusBarNotification::None
+                            };
+                    }
+                    FarmerNotification::FarmsRescanned {
+                        farm_indices,
+                        error,
+                    } => {
+                        // Plotting of sectors was interrupted when farms were stopped for rescan
+                        self.plotting_sectors.retain(|(farm_index, _sector_index)| {
+                            !farm_indices.contains(farm_index)
+                        });
+                        self.health
+                            .stalled_farms
+                            .retain(|farm_index| !farm_indices.contains(farm_index));
+                        self.update_close_needs_confirmation();
+                        if let Some(error) = error {
+                            self.show_error(tr_args!(
+                                "status-bar-farms-rescan-failed",
+                                error = error
+                            ));
+                        }
+                    }
+                    FarmerNotification::WaitingForPeers { waiting, .. } => {
+                        self.health.waiting_for_peers = *waiting;
+                    }
+                    _ => {}
+                }
+                self.running_view.emit(RunningInput::FarmerNotification {
+                    notification: farmer_notification,
+                    timestamp,
+                });
+            }
+            BackendNotification::PieceGetterStats(stats) => {
                 self.running_view
-                    .emit(RunningInput::FarmerNotification(farmer_notification));
+                    .emit(RunningInput::PieceGetterStats(stats));
+            }
+            BackendNotification::PlottingStats(plotting_stats) => {
+                self.statistics_view
+                    .emit(StatisticsInput::PlottingStats(plotting_stats));
             }
             BackendNotification::Stopped { error } => {
+                self.health.running = false;
+                if let Some(error) = &error {
+                    self.irrecoverable_error.lock().replace(error.to_string());
+                }
                 self.current_view = View::Stopped(error);
             }
             BackendNotification::IrrecoverableError { error } => {
+                self.health.running = false;
+                self.notify_error(&error.to_string());
+                self.irrecoverable_error.lock().replace(error.to_string());
                 self.current_view = View::Error(error);
             }
+            BackendNotification::PlotMapExported { path } => {
+                info!(path = %path.display(), "Plot map exported, exiting");
+                relm4::main_application().quit();
+            }
         }
+
+        self.ready.store(self.health.is_ready(), Ordering::Release);
     }
 
     async fn process_configuration_output(&mut self, configuration_output: ConfigurationOutput) {
         match configuration_output {
+            ConfigurationOutput::UiConfigUpdate(ui_configuration) => {
+                self.update_ui_configuration(ui_configuration);
+            }
+            ConfigurationOutput::TestPlottingAfterStart => {
+                self.test_plotting_after_start = true;
+            }
             ConfigurationOutput::StartWithNewConfig(raw_config) => {
                 if let Err(error) = self
                     .backend_action_sender
@@ -685,8 +1949,11 @@ impl App {
             }
             ConfigurationOutput::ConfigUpdate(raw_config) => {
                 self.current_raw_config.replace(raw_config.clone());
-                // Config is updated when application is already running, switch to corresponding screen
-                self.current_view = View::Running;
+                // Config is updated when application is already running, switch to corresponding
+                // screen, in safe mode there is nothing running, so stay on configuration screen
+                if !self.safe_mode {
+                    self.current_view = View::Running;
+                }
                 if let Err(error) = self
                     .backend_action_sender
                     .send(BackendAction::NewConfig { raw_config })
@@ -702,14 +1969,23 @@ impl App {
             }
             ConfigurationOutput::Close => {
                 // Configuration view is closed when application is already running, switch to corresponding screen
-                self.current_view = View::Running;
+                if !self.safe_mode {
+                    self.current_view = View::Running;
+                }
             }
         }
     }
 
-    async fn process_running_output(&mut self, running_output: RunningOutput) {
+    async fn process_running_output(
+        &mut self,
+        running_output: RunningOutput,
+        sender: AsyncComponentSender<Self>,
+    ) {
         match running_output {
             RunningOutput::PausePlotting(pause_plotting) => {
+                self.plotting_paused = pause_plotting;
+                self.update_close_needs_confirmation();
+
                 if let Err(error) = self
                     .backend_action_sender
                     .send(BackendAction::Farmer(FarmerAction::PausePlotting(
@@ -721,31 +1997,478 @@ impl App {
                         "Failed to send pause plotting to backend: {error}"
                     ));
                 }
-            }
-        }
+            }
+            RunningOutput::PlottingRateLimit(plotting_rate_limit) => {
+                if let Err(error) = self
+                    .backend_action_sender
+                    .send(BackendAction::Farmer(FarmerAction::SetPlottingRateLimit(
+                        plotting_rate_limit,
+                    )))
+                    .await
+                {
+                    self.current_view = View::Error(anyhow::anyhow!(
+                        "Failed to send plotting rate limit to backend: {error}"
+                    ));
+                }
+            }
+            RunningOutput::FarmDetails(farm_details) => {
+                self.update_app_settings(|app_settings| {
+                    app_settings.farm_details = farm_details;
+                });
+            }
+            RunningOutput::HideRewardAddress(hide_reward_address) => {
+                let mut ui_configuration = self.app_settings.ui;
+                ui_configuration.hide_reward_address = hide_reward_address;
+                self.update_ui_configuration(ui_configuration);
+            }
+            RunningOutput::FarmingDeadlineWarning {
+                farm_index,
+                activity,
+                margin,
+            } => {
+                warn!(
+                    %farm_index,
+                    %activity,
+                    ?margin,
+                    "Farming is close to the deadline, disk may be too slow"
+                );
+                self.show_warning(tr_args!(
+                    "status-bar-farming-deadline-warning",
+                    farm_index = farm_index,
+                    activity = activity,
+                    margin = margin.as_millis()
+                ));
+            }
+            RunningOutput::PlottingStalled {
+                farm_index,
+                stage,
+                stalled_for,
+            } => {
+                warn!(
+                    %farm_index,
+                    %stage,
+                    ?stalled_for,
+                    "Plotting made no progress for a long time, it may be stalled"
+                );
+                self.health.stalled_farms.insert(farm_index);
+                self.show_warning(tr_args!(
+                    "status-bar-plotting-stalled",
+                    farm_index = farm_index,
+                    stage = stage,
+                    minutes = stalled_for.as_secs() / 60
+                ));
+            }
+            RunningOutput::WipeFarm { farm_index, path } => {
+                let farms_count = self
+                    .current_raw_config
+                    .as_ref()
+                    .map(|raw_config| {
+                        raw_config
+                            .farms()
+                            .iter()
+                            .filter(|farm| farm.enabled)
+                            .count()
+                    })
+                    .unwrap_or_default();
+                if farms_count <= 1 {
+                    self.show_error(tr("status-bar-farm-wipe-last-farm").to_string());
+                    return;
+                }
+
+                let dialog = gtk::MessageDialog::builder()
+                    .modal(true)
+                    .message_type(gtk::MessageType::Warning)
+                    .text(tr_args!(
+                        "farm-wipe-confirmation-title",
+                        path = path.display()
+                    ))
+                    .secondary_text(tr("farm-wipe-confirmation-text"))
+                    .build();
+                dialog.set_transient_for(relm4::main_application().active_window().as_ref());
+                dialog.add_button(
+                    tr("farm-wipe-confirmation-cancel"),
+                    gtk::ResponseType::Cancel,
+                );
+                dialog
+                    .add_button(tr("farm-wipe-confirmation-wipe"), gtk::ResponseType::Accept)
+                    .add_css_class("destructive-action");
+                dialog.set_default_response(gtk::ResponseType::Cancel);
+                dialog.connect_response(move |dialog, response| {
+                    if response == gtk::ResponseType::Accept {
+                        sender.input(AppInput::WipeFarm { farm_index });
+                    }
+                    dialog.close();
+                });
+                dialog.present();
+            }
+        }
+    }
+
+    fn confirm_clear_farmer_cache(&self, sender: &AsyncComponentSender<Self>) {
+        let dialog = gtk::MessageDialog::builder()
+            .modal(true)
+            .message_type(gtk::MessageType::Warning)
+            .text(tr("farmer-cache-clear-confirmation-title"))
+            .secondary_text(tr("farmer-cache-clear-confirmation-text"))
+            .build();
+        dialog.set_transient_for(relm4::main_application().active_window().as_ref());
+        dialog.add_button(
+            tr("farmer-cache-clear-confirmation-cancel"),
+            gtk::ResponseType::Cancel,
+        );
+        dialog
+            .add_button(
+                tr("farmer-cache-clear-confirmation-clear"),
+                gtk::ResponseType::Accept,
+            )
+            .add_css_class("destructive-action");
+        dialog.set_default_response(gtk::ResponseType::Cancel);
+        let sender = sender.clone();
+        dialog.connect_response(move |dialog, response| {
+            if response == gtk::ResponseType::Accept {
+                sender.input(AppInput::ClearFarmerCacheConfirmed);
+            }
+            dialog.close();
+        });
+        dialog.present();
+    }
+
+    /// Show received reward in status bar and as desktop notification if enabled
+    fn notify_reward_received(
+        &mut self,
+        amount: Balance,
+        reward_address_balance: Balance,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let amount = format!("{:.4}", (amount / (SSC / 10_000)) as f64 / 10_000.0);
+        let balance = format!(
+            "{:.2}",
+            (reward_address_balance / (SSC / 100)) as f64 / 100.0
+        );
+
+        // Warnings and errors are more important than received reward
+        if matches!(
+            self.status_bar_notification,
+            StatusBarNotification::None | StatusBarNotification::Info(_)
+        ) {
+            let message = tr_args!(
+                "status-bar-reward-received",
+                amount = amount,
+                token_symbol = self.token_symbol
+            );
+            self.status_bar_notification = StatusBarNotification::Info(message.clone());
+
+            sender.oneshot_command(async move {
+                tokio::time::sleep(REWARD_NOTIFICATION_DISPLAY_DURATION).await;
+                AppCommandOutput::InfoNotificationExpired(message)
+            });
+        }
+
+        if self.app_settings.ui.reward_notifications {
+            let notification = gtk::gio::Notification::new(tr("reward-notification-title"));
+            notification.set_body(Some(&tr_args!(
+                "reward-notification-body",
+                amount = amount,
+                balance = balance,
+                token_symbol = self.token_symbol
+            )));
+            self.send_desktop_notification("reward-received", &notification, false);
+        }
+    }
+
+    /// Show desktop notification about an error if enabled
+    fn notify_error(&self, body: &str) {
+        if self.app_settings.ui.error_notifications {
+            let notification = gtk::gio::Notification::new(tr("error-notification-title"));
+            notification.set_body(Some(body));
+            notification.set_priority(gtk::gio::NotificationPriority::Urgent);
+            self.send_desktop_notification("error", &notification, true);
+        }
+    }
+
+    /// Send desktop notification, non-critical notifications are suppressed during quiet hours
+    fn send_desktop_notification(
+        &self,
+        id: &str,
+        notification: &gtk::gio::Notification,
+        critical: bool,
+    ) {
+        if !critical
+            && let Some(quiet_hours) = self.app_settings.ui.quiet_hours
+            && let Ok(now) = gtk::glib::DateTime::now_local()
+            && quiet_hours.contains(now.hour() as u8)
+        {
+            debug!(%id, "Desktop notification suppressed during quiet hours");
+            return;
+        }
+
+        relm4::main_application().send_notification(Some(id), notification);
+    }
+
+    /// Apply changes to runtime preferences and save them to application data directory
+    fn update_app_settings(&mut self, update: impl FnOnce(&mut AppSettings)) {
+        update(&mut self.app_settings);
+
+        let Some(app_settings_path) = &self.app_settings_path else {
+            return;
+        };
+        if let Err(error) = self.app_settings.save(app_settings_path) {
+            self.show_error(tr_args!("status-bar-settings-save-failed", error = error));
+        }
+    }
+
+    fn update_close_needs_confirmation(&self) {
+        self.close_needs_confirmation.store(
+            !self.plotting_paused && !self.plotting_sectors.is_empty(),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Explain that corrupt configuration was backed up, offering to open the backup for manual
+    /// repair, otherwise application continues with initial configuration from scratch
+    fn show_corrupt_configuration_dialog(&self, backup_path: PathBuf, error: &RawConfigError) {
+        let dialog = gtk::MessageDialog::builder()
+            .modal(true)
+            .message_type(gtk::MessageType::Warning)
+            .text(tr("corrupt-configuration-title"))
+            .secondary_text(tr_args!(
+                "corrupt-configuration-text",
+                path = backup_path.display(),
+                error = error
+            ))
+            .build();
+        dialog.set_transient_for(relm4::main_application().active_window().as_ref());
+        dialog.add_button(
+            tr("corrupt-configuration-open-backup"),
+            gtk::ResponseType::Apply,
+        );
+        dialog.add_button(
+            tr("corrupt-configuration-start-fresh"),
+            gtk::ResponseType::Accept,
+        );
+        dialog.set_default_response(gtk::ResponseType::Accept);
+        dialog.connect_response(move |dialog, response| {
+            if response == gtk::ResponseType::Apply
+                && let Err(error) = open::that_detached(&backup_path)
+            {
+                error!(
+                    %error,
+                    path = %backup_path.display(),
+                    "Failed to open configuration backup"
+                );
+            }
+            dialog.close();
+        });
+        dialog.present();
+    }
+
+    /// Ask user whether to quit while plotting is in progress, offering to minimize the window
+    /// instead
+    fn confirm_close(&self, root: &gtk::Window) {
+        let dialog = gtk::MessageDialog::builder()
+            .modal(true)
+            .message_type(gtk::MessageType::Question)
+            .text(tr("quit-confirmation-title"))
+            .secondary_text(tr("quit-confirmation-text"))
+            .transient_for(root)
+            .build();
+        dialog.add_button(tr("quit-confirmation-cancel"), gtk::ResponseType::Cancel);
+        dialog.add_button(tr("quit-confirmation-minimize"), gtk::ResponseType::Apply);
+        dialog
+            .add_button(tr("quit-confirmation-quit"), gtk::ResponseType::Accept)
+            .add_css_class("destructive-action");
+        dialog.set_default_response(gtk::ResponseType::Apply);
+        dialog.connect_response({
+            let root = root.clone();
+            let close_needs_confirmation = Arc::clone(&self.close_needs_confirmation);
+
+            move |dialog, response| {
+                dialog.close();
+                match response {
+                    gtk::ResponseType::Apply => {
+                        root.minimize();
+                    }
+                    gtk::ResponseType::Accept => {
+                        close_needs_confirmation.store(false, Ordering::Relaxed);
+                        root.close();
+                    }
+                    _ => {}
+                }
+            }
+        });
+        dialog.present();
+    }
+
+    fn process_loading_output(&mut self, loading_output: LoadingOutput) {
+        match loading_output {
+            LoadingOutput::CancelStartup => {
+                info!("Cancelling startup");
+                self.startup_cancelled.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn process_statistics_output(&mut self, statistics_output: StatisticsOutput) {
+        match statistics_output {
+            StatisticsOutput::Close => {
+                self.current_view = View::Running;
+            }
+        }
+    }
+
+    fn process_command(&mut self, input: AppCommandOutput, sender: &AsyncComponentSender<Self>) {
+        match input {
+            AppCommandOutput::BackendNotification(notification) => {
+                self.process_backend_notification(notification, sender);
+            }
+            AppCommandOutput::InfoNotificationExpired(message) => {
+                // Only hide if it wasn't replaced by another notification in the meantime
+                if matches!(
+                    &self.status_bar_notification,
+                    StatusBarNotification::Info(current_message) if *current_message == message
+                ) {
+                    self.status_bar_notification = StatusBarNotification::None;
+                }
+            }
+            AppCommandOutput::ProxyUnreachable { proxy, error } => {
+                let proxy = redacted_proxy(&proxy);
+                error!(%proxy, %error, "Proxy is unreachable");
+                self.show_error(format!("Proxy {proxy} is unreachable: {error}"));
+            }
+            #[cfg(debug_assertions)]
+            AppCommandOutput::CssUpdated => {
+                info!("Stylesheet changed on disk, reloading");
+                self.apply_css(self.high_contrast);
+            }
+            AppCommandOutput::ExecutableUpdated => {
+                if self.app_settings.ui.restart_on_update {
+                    info!("Application was updated, restarting");
+                    *self.exit_status_code.lock() = AppStatusCode::Restart;
+                    relm4::main_application().quit();
+                } else {
+                    self.status_bar_notification = StatusBarNotification::Warning {
+                        message: tr("status-bar-new-version-installed").to_string(),
+                        restart: true,
+                    };
+                }
+            }
+            AppCommandOutput::Restart => {
+                *self.exit_status_code.lock() = AppStatusCode::Restart;
+                relm4::main_application().quit();
+            }
+        }
+    }
+
+    /// Periodically check modification time of application executable to detect when new version
+    /// is installed
+    async fn watch_executable(
+        sender: Sender<AppCommandOutput>,
+        shutdown_receiver: ShutdownReceiver,
+    ) {
+        let executable_modified =
+            |executable: &Path| fs::metadata(executable).and_then(|metadata| metadata.modified());
+
+        let executable = match env::current_exe() {
+            Ok(executable) => executable,
+            Err(error) => {
+                warn!(%error, "Failed to determine application executable, not watching for updates");
+                return;
+            }
+        };
+        let initial_modified = match executable_modified(&executable) {
+            Ok(modified) => modified,
+            Err(error) => {
+                warn!(
+                    %error,
+                    executable = %executable.display(),
+                    "Failed to check application executable, not watching for updates"
+                );
+                return;
+            }
+        };
+
+        shutdown_receiver
+            .register(async move {
+                loop {
+                    tokio::time::sleep(EXECUTABLE_UPDATE_CHECK_INTERVAL).await;
+
+                    // Executable may be temporarily missing while it is being replaced
+                    if let Ok(modified) = executable_modified(&executable)
+                        && modified != initial_modified
+                    {
+                        info!(executable = %executable.display(), "Application executable was updated");
+                        let _ = sender.send(AppCommandOutput::ExecutableUpdated);
+                        return;
+                    }
+                }
+            })
+            .drop_on_shutdown()
+            .await
+    }
+
+    /// Watch stylesheets in source tree and reload them on change, only used during theme
+    /// development
+    #[cfg(debug_assertions)]
+    async fn watch_css(sender: Sender<AppCommandOutput>, shutdown_receiver: ShutdownReceiver) {
+        let css_modified = || {
+            [GLOBAL_CSS_PATH, HIGH_CONTRAST_CSS_PATH].map(|path| {
+                fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+        };
+
+        shutdown_receiver
+            .register(async move {
+                let mut last_modified = css_modified();
+
+                loop {
+                    tokio::time::sleep(CSS_RELOAD_CHECK_INTERVAL).await;
+
+                    let modified = css_modified();
+                    if modified != last_modified {
+                        last_modified = modified;
+                        if sender.send(AppCommandOutput::CssUpdated).is_err() {
+                            return;
+                        }
+                    }
+                }
+            })
+            .drop_on_shutdown()
+            .await
     }
 
-    fn process_command(&mut self, input: AppCommandOutput) {
-        match input {
-            AppCommandOutput::BackendNotification(notification) => {
-                self.process_backend_notification(notification);
-            }
-            AppCommandOutput::Restart => {
-                *self.exit_status_code.lock() = AppStatusCode::Restart;
-                relm4::main_application().quit();
-            }
-        }
+    /// Check that TCP connection to the proxy can be established
+    async fn check_proxy(proxy: &Url) -> io::Result<()> {
+        let (Some(host), Some(port)) = (proxy.host_str(), proxy.port_or_known_default()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Proxy URL must contain host and port",
+            ));
+        };
+
+        tokio::time::timeout(
+            PROXY_CHECK_TIMEOUT,
+            tokio::net::TcpStream::connect((host, port)),
+        )
+        .await
+        .map_err(|_elapsed| io::Error::from(io::ErrorKind::TimedOut))??;
+
+        Ok(())
     }
 
     async fn do_upgrade(
         sender: Sender<AppCommandOutput>,
         shutdown_receiver: ShutdownReceiver,
         raw_config: RawConfig,
+        maybe_config_file_path: Option<PathBuf>,
     ) {
         shutdown_receiver
             .register(async move {
-                let (mut backend_notification_sender, mut backend_notification_receiver) =
+                let (backend_notification_sender, mut backend_notification_receiver) =
                     mpsc::channel(100);
+                let mut backend_notification_sender =
+                    BackendNotificationSender::new(backend_notification_sender);
 
                 tokio::spawn({
                     let sender = sender.clone();
@@ -762,10 +2485,30 @@ impl App {
                     }
                 });
 
-                if let Err(error) = wipe(&raw_config, &mut backend_notification_sender).await {
+                let result: anyhow::Result<()> = try {
+                    let config_file_path =
+                        RawConfig::path(maybe_config_file_path.as_deref()).await?;
+                    wipe(
+                        &raw_config,
+                        &config_file_path,
+                        &mut backend_notification_sender,
+                    )
+                    .await?;
+                };
+
+                if let Err(error) = result {
                     error!(%error, "Wiping error");
+                    // Not restarting automatically, such that user can see the error, upgrade will
+                    // be resumed once application is restarted
+                    let _ = backend_notification_sender
+                        .send(BackendNotification::IrrecoverableError { error })
+                        .await;
+                    return;
                 }
 
+                // Give user a chance to see wiping summary before restart
+                tokio::time::sleep(WIPE_SUMMARY_DISPLAY_DURATION).await;
+
                 let _ = sender.send(AppCommandOutput::Restart);
             })
             .drop_on_shutdown()
@@ -773,7 +2516,17 @@ impl App {
     }
 }
 
-#[derive(Debug, Parser)]
+/// Where application logs are written
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+enum LogTarget {
+    /// Rotated log file in app data directory (and stderr)
+    File,
+    /// systemd journal, log levels are mapped to journal priorities
+    #[cfg(target_os = "linux")]
+    Journald,
+}
+
+#[derive(Debug, Clone, Parser)]
 #[clap(about, version)]
 struct Cli {
     /// Used for startup to minimize the window
@@ -782,6 +2535,101 @@ struct Cli {
     /// Used by child process such that supervisor parent process can control it
     #[arg(long)]
     child_process: bool,
+    /// Run application directly without built-in supervisor, for use with external supervisors
+    /// like systemd or Docker.
+    ///
+    /// Application exits with status code 0 on success, 100 when it needs to be restarted (for
+    /// example after upgrade) and other non-zero status codes on failure. Log file is not written
+    /// (logs are printed to stderr instead) and one-shot options like `--safe-mode` are not reset
+    /// on restart.
+    #[arg(long)]
+    no_supervisor: bool,
+    /// Theme to use instead of the one from configuration, `system` detects it from system
+    /// settings
+    #[arg(long, value_enum)]
+    theme: Option<Theme>,
+    /// Start without node and farms, only allowing to change configuration (for example to remove
+    /// farm that causes application to crash on startup), normal mode is used after restart
+    #[arg(long)]
+    safe_mode: bool,
+    /// Path to config file to use instead of the default one, allows running multiple isolated
+    /// instances. Files with `.toml` extension use TOML format, JSON is used otherwise
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Directory for all application data (configuration, logs and other state) instead of
+    /// default system-specific locations, useful for portable installations. `--config` still
+    /// takes precedence for configuration file if specified
+    #[arg(long)]
+    data_dir: Option<PathBuf>,
+    /// HTTP(S) or SOCKS5 proxy for all HTTP requests made by the application (like new version
+    /// check or node snapshot download), for example `http://127.0.0.1:3128` or
+    /// `socks5://127.0.0.1:1080`, P2P traffic of the node and farmer is not proxied
+    #[arg(long, value_parser = parse_proxy)]
+    proxy: Option<Url>,
+    /// Delay in seconds before supervisor restarts the application, fractions like `0.5` are
+    /// allowed
+    #[arg(long, default_value = "1", value_parser = parse_seconds)]
+    restart_delay: Duration,
+    /// Don't double restart delay (up to a minute) when application keeps restarting shortly
+    /// after start, for example due to persistent failure
+    #[arg(long)]
+    no_restart_backoff: bool,
+    /// Where to write logs, log file is not created and supervisor doesn't capture output of the
+    /// application when `journald` is used
+    #[arg(long, value_enum, default_value_t = LogTarget::File)]
+    log_target: LogTarget,
+    /// Maximum total size of current and rotated log files (of each instance when multiple
+    /// instances are supervised), for example `100 MiB`. Log files are rotated and the oldest
+    /// files are removed to stay within this size
+    #[arg(long, default_value = DEFAULT_MAX_LOG_SIZE)]
+    max_log_size: ByteSize,
+    /// Export indices of plotted sectors of each farm as JSON to this file once plotted sectors
+    /// are scanned on startup, then exit
+    #[arg(long)]
+    export_plot_map: Option<PathBuf>,
+    /// Benchmark farm and piece cache directories from configuration, print results and exit
+    /// without starting the application. Useful to find slow or failing disks before plotting
+    #[arg(long)]
+    disk_check: bool,
+    /// Print CPU, thread pool, concurrency and memory allocation that current configuration results
+    /// in on this machine and exit without starting the application
+    #[arg(long)]
+    explain_resources: bool,
+    /// Serve HTTP readiness probe for orchestration (like Kubernetes or Docker) on this address,
+    /// for example `127.0.0.1:9616`. Responds with `200` once node is synced, farms are
+    /// initialized and none of them is in error state, and with `503` otherwise. With `--instance`
+    /// it is specified once per instance in the same order, such that each instance gets its own
+    /// address
+    #[arg(long = "readiness-endpoint")]
+    readiness_endpoints: Vec<SocketAddr>,
+    /// Don't silence `quinn_udp` logs when `RUST_LOG` is not set, by default they are limited to
+    /// errors to work around https://github.com/quinn-rs/quinn/issues/1750, useful for debugging
+    /// networking issues
+    #[arg(long)]
+    no_quinn_log_workaround: bool,
+    /// Application set `RUST_LOG` for quinn logging workaround itself, passed by supervisor to
+    /// child process that inherits the environment variable
+    #[arg(long, hide = true)]
+    quinn_log_workaround_applied: bool,
+    /// Number of worker threads of UI and async runtime instead of the one from configuration, at
+    /// least 1, number of CPU cores is used by default
+    #[arg(long)]
+    runtime_threads: Option<NonZeroUsize>,
+    /// Log everything at debug level for farm with this index (0-based, in order of enabled farms
+    /// in configuration, disabled farms are not counted) while keeping other farms at the default
+    /// level, useful for debugging a single problematic farm among many
+    #[arg(long)]
+    debug_farm: Option<u8>,
+    /// Supervise an independent application instance for each of these config files (can be
+    /// specified multiple times). Each instance is restarted independently and its logs are tagged
+    /// with config file name, `--config` is ignored in this mode
+    #[arg(long = "instance")]
+    instances: Vec<PathBuf>,
+    /// Tag of the instance supervised together with other instances, used in log file name and to
+    /// prefix log lines
+    #[arg(long)]
+    #[doc(hidden)]
+    instance_tag: Option<String>,
     /// Show uninstall dialog to delete configuration and logs, typically called from installer
     /// during package uninstallation
     #[arg(long)]
@@ -793,7 +2641,20 @@ struct Cli {
 }
 
 impl Cli {
-    fn run(self) -> ExitCode {
+    fn run(mut self) -> ExitCode {
+        if self.config.is_none()
+            && let Some(data_dir) = &self.data_dir
+        {
+            self.config = Some(RawConfig::path_in_data_dir(data_dir));
+        }
+
+        if let Some(passphrase) = env::var(CONFIG_PASSPHRASE_ENV)
+            .ok()
+            .filter(|passphrase| !passphrase.is_empty())
+        {
+            set_config_passphrase(Some(passphrase));
+        }
+
         if self.uninstall {
             #[cfg(windows)]
             {
@@ -834,17 +2695,202 @@ impl Cli {
             }
 
             ExitCode::SUCCESS
-        } else if self.child_process {
+        } else if self.disk_check {
+            self.disk_check()
+        } else if self.explain_resources {
+            self.explain_resources()
+        } else if self.child_process || self.no_supervisor {
             ExitCode::from(self.app().into_status_code() as u8)
+        } else if !self.instances.is_empty() {
+            self.supervise_instances().report()
         } else {
             self.supervisor().report()
         }
     }
 
-    fn app(self) -> AppStatusCode {
-        let maybe_app_data_dir = Self::app_data_dir();
+    fn disk_check(self) -> ExitCode {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(error) => {
+                eprintln!("Failed to create runtime: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+        let raw_config = runtime.block_on(async {
+            let config_file_path = RawConfig::path(self.config.as_deref()).await?;
+            RawConfig::read_from_path(&config_file_path).await
+        });
+        let raw_config = match raw_config {
+            Ok(Some(raw_config)) => raw_config,
+            Ok(None) => {
+                eprintln!("Configuration not found, start application and configure it first");
+                return ExitCode::FAILURE;
+            }
+            Err(error) => {
+                eprintln!("Failed to read configuration: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let directories = raw_config
+            .farms()
+            .iter()
+            .map(|farm| farm.path.clone())
+            .chain(
+                raw_config
+                    .piece_cache()
+                    .map(|piece_cache| piece_cache.path.clone()),
+            );
+        let mut all_passed = true;
+        for directory in directories {
+            println!("Checking {}...", directory.display());
+
+            match check_disk(&directory) {
+                Ok(result) => {
+                    println!(
+                        "  Write: {:.0} MiB/s, {:.2?} latency",
+                        result.write_throughput, result.write_latency
+                    );
+                    println!(
+                        "  Read: {:.0} MiB/s, {:.2?} latency",
+                        result.read_throughput, result.read_latency
+                    );
+
+                    let warnings = result.warnings();
+                    if warnings.is_empty() {
+                        println!("  OK");
+                    } else {
+                        all_passed = false;
+                        for warning in warnings {
+                            println!("  Warning: {warning}");
+                        }
+                    }
+                }
+                Err(error) => {
+                    all_passed = false;
+                    println!("  Failed: {error}");
+                }
+            }
+        }
 
+        if all_passed {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        }
+    }
+
+    fn explain_resources(self) -> ExitCode {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(error) => {
+                eprintln!("Failed to create runtime: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+        let config = runtime.block_on(async {
+            let config_file_path = RawConfig::path(self.config.as_deref())
+                .await
+                .map_err(|error| anyhow::anyhow!("Failed to read configuration: {error}"))?;
+            let Some(raw_config) = RawConfig::read_from_path(&config_file_path)
+                .await
+                .map_err(|error| anyhow::anyhow!("Failed to read configuration: {error}"))?
+            else {
+                return Err(anyhow::anyhow!(
+                    "Configuration not found, start application and configure it first"
+                ));
+            };
+
+            Config::try_from_raw_config(&raw_config)
+                .await
+                .map_err(|error| anyhow::anyhow!("Configuration is invalid: {error}"))
+        });
+        let config = match config {
+            Ok(config) => config,
+            Err(error) => {
+                eprintln!("{error}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let resources = FarmerResources::derive(
+            &config.farms,
+            &config.farmer_extra_args,
+            config.farmer.disable_plot_cache,
+        );
+
+        println!("Farms: {}", config.farms.len());
+        println!(
+            "Plotting thread pools: {}",
+            resources.plotting_thread_pool_core_indices.len()
+        );
+        for (index, (plotting_cores, replotting_cores)) in resources
+            .plotting_thread_pool_core_indices
+            .iter()
+            .zip(&resources.replotting_thread_pool_core_indices)
+            .enumerate()
         {
+            println!(
+                "  Pool {index}: {} plotting threads, {} replotting threads",
+                plotting_cores.cpu_cores().len(),
+                replotting_cores.cpu_cores().len()
+            );
+        }
+        println!(
+            "Sector downloading concurrency: {}",
+            resources.sector_downloading_concurrency
+        );
+        for (farm_index, disk_farm) in config.farms.iter().enumerate() {
+            if let Some(plotting_concurrency) = disk_farm.plotting_concurrency() {
+                println!("  Farm {farm_index}: {plotting_concurrency} (own limit)");
+            }
+        }
+        println!(
+            "Record encoding concurrency: {}",
+            resources.record_encoding_concurrency
+        );
+        println!(
+            "Farming threads per farm: {}",
+            resources.farming_thread_pool_size
+        );
+        println!(
+            "Farming during initial plotting: {}",
+            if resources.farm_during_initial_plotting {
+                "yes"
+            } else {
+                "no"
+            }
+        );
+        println!(
+            "Plot cache: {}",
+            if resources.plot_cache {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        let max_sectors_in_memory = resources.max_sectors_in_memory(&config.farms);
+        println!(
+            "Sectors in memory during plotting: up to {max_sectors_in_memory} (~{} of RAM)",
+            bytesize::to_string(
+                (max_sectors_in_memory * sector_size(EXPECTED_MAX_PIECES_IN_SECTOR)) as u64,
+                true
+            )
+        );
+
+        ExitCode::SUCCESS
+    }
+
+    fn app(self) -> AppStatusCode {
+        let maybe_app_data_dir = self.app_data_dir();
+
+        let (log_filter, log_filter_reload_handle) = {
             let layer = tracing_subscriber::fmt::layer()
                 // TODO: Workaround for https://github.com/tokio-rs/tracing/issues/2214, also on
                 //  Windows terminal doesn't support the same colors as bash does
@@ -853,12 +2899,53 @@ impl Cli {
                 } else {
                     supports_color::on(supports_color::Stream::Stderr).is_some()
                 });
-            let filter = EnvFilter::builder()
+            let mut filter = EnvFilter::builder()
                 .with_default_directive(LevelFilter::INFO.into())
                 .from_env_lossy();
-            if WINDOWS_SUBSYSTEM_WINDOWS {
+            if let Some(farm_index) = self.debug_farm {
+                // Farm spans record `farm_index` field, events inside them (including plotting
+                // and farming done by the farm itself) are enabled at debug level
+                match format!("[{{farm_index={farm_index}}}]=debug").parse() {
+                    Ok(directive) => {
+                        filter = filter.add_directive(directive);
+                    }
+                    Err(error) => {
+                        eprintln!("Failed to create log filter for farm {farm_index}: {error}");
+                    }
+                }
+            }
+            let log_filter = filter.to_string();
+            let (filter, log_filter_reload_handle) = reload::Layer::new(filter);
+            #[cfg(target_os = "linux")]
+            let maybe_journald_layer = if self.log_target == LogTarget::Journald {
+                match tracing_journald::layer() {
+                    Ok(layer) => Some(layer.with_syslog_identifier(match &self.instance_tag {
+                        Some(instance_tag) => format!("{}-{instance_tag}", env!("CARGO_PKG_NAME")),
+                        None => env!("CARGO_PKG_NAME").to_string(),
+                    })),
+                    Err(error) => {
+                        eprintln!(
+                            "Failed to connect to journald, logging to stderr instead: {error}"
+                        );
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            #[cfg(not(target_os = "linux"))]
+            let maybe_journald_layer = None::<tracing_subscriber::layer::Identity>;
+            if let Some(journald_layer) = maybe_journald_layer {
+                tracing_subscriber::registry()
+                    .with(journald_layer.with_filter(filter))
+                    .init();
+            } else if WINDOWS_SUBSYSTEM_WINDOWS {
                 if let Some(app_data_dir) = &maybe_app_data_dir {
-                    let logger = std::sync::Mutex::new(Self::new_logger(app_data_dir));
+                    let logger = std::sync::Mutex::new(Self::new_logger(
+                        app_data_dir,
+                        self.instance_tag.as_deref(),
+                        self.max_log_size,
+                    ));
                     let layer = layer.with_writer(logger);
 
                     tracing_subscriber::registry()
@@ -874,24 +2961,122 @@ impl Cli {
                     .with(layer.with_filter(filter))
                     .init();
             }
-        }
+
+            (log_filter, log_filter_reload_handle)
+        };
 
         info!(
             "Starting {} {}",
             env!("CARGO_PKG_NAME"),
             env!("CARGO_PKG_VERSION")
         );
+        info!("{}", build_kind_description());
+        if self.quinn_log_workaround_applied {
+            info!(
+                log_filter = %QUINN_LOG_WORKAROUND_FILTER,
+                "Applied quinn logging workaround, use `--no-quinn-log-workaround` or set \
+                `RUST_LOG` to see all `quinn_udp` logs"
+            );
+        }
+
+        if let Some(farm_index) = self.debug_farm {
+            info!(%farm_index, "Debug logging enabled for farm");
+        }
+
+        if let Some(app_data_dir) = &maybe_app_data_dir {
+            if let Some(previous_exit_reason) =
+                ExitReason::take(app_data_dir, self.instance_tag.as_deref())
+            {
+                info!(%previous_exit_reason, "Previous run exit reason");
+            }
+
+            let app_data_dir = app_data_dir.clone();
+            let instance_tag = self.instance_tag.clone();
+            let default_panic_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |panic_info| {
+                ExitReason::Panic {
+                    message: panic_info.to_string(),
+                }
+                .write(&app_data_dir, instance_tag.as_deref());
+                default_panic_hook(panic_info);
+            }));
+        }
+
+        let maybe_app_settings_path = maybe_app_data_dir
+            .as_deref()
+            .map(|app_data_dir| AppSettings::path(app_data_dir, self.instance_tag.as_deref()));
+        let mut app_settings = maybe_app_settings_path
+            .as_deref()
+            .map(AppSettings::load)
+            .unwrap_or_default();
+        if !app_settings.ui_migrated {
+            match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => {
+                    if runtime.block_on(App::migrate_ui_configuration(
+                        &mut app_settings,
+                        self.config.as_deref(),
+                    )) && let Some(app_settings_path) = &maybe_app_settings_path
+                        && let Err(error) = app_settings.save(app_settings_path)
+                    {
+                        warn!(%error, "Failed to save migrated app settings");
+                    }
+                }
+                Err(error) => {
+                    warn!(%error, "Failed to create runtime to migrate UI configuration");
+                }
+            }
+        }
 
-        // The default in `relm4` is `1`, set this back to Tokio's default
+        // Applied before other threads are started, such that they inherit it
+        set_process_priority(app_settings.ui.process_priority);
+
+        let runtime_threads = self.runtime_threads.or(app_settings.ui.runtime_threads);
+        if let Some(runtime_threads) = runtime_threads {
+            info!(%runtime_threads, "Using custom number of runtime threads");
+        }
+        // The default in `relm4` is `1`, set this back to Tokio's default unless customized
         RELM_THREADS
-            .set(
+            .set(runtime_threads.map(NonZeroUsize::get).unwrap_or_else(|| {
                 available_parallelism()
                     .map(|cores| cores.get())
-                    .unwrap_or(1),
-            )
+                    .unwrap_or(1)
+            }))
             .expect("The first thing in the app, is not set; qed");
 
-        let app = RelmApp::new("network.subspace.space_acres");
+        // `RelmApp::new()` panics if GTK fails to initialize, check it upfront to print a helpful
+        // message instead
+        if let Err(error) = gtk::init() {
+            error!(%error, "Failed to initialize GTK");
+            eprintln!(
+                "Failed to initialize GTK: {error}\n\
+                No display found, make sure application is started in a graphical session (when \
+                running over SSH, set `DISPLAY`/`WAYLAND_DISPLAY` environment variable or enable \
+                X11 forwarding)"
+            );
+            if let Some(app_data_dir) = &maybe_app_data_dir {
+                ExitReason::GtkInitializationFailed {
+                    error: error.to_string(),
+                }
+                .write(app_data_dir, self.instance_tag.as_deref());
+            }
+            return AppStatusCode::GtkInitializationFailed;
+        }
+
+        let app = if self.instance_tag.is_some() {
+            // Instances supervised together share application ID, without this the second and
+            // later instances would just activate the first one and exit
+            RelmApp::from_app(
+                gtk::Application::builder()
+                    .application_id("network.subspace.space_acres")
+                    .flags(gtk::gio::ApplicationFlags::NON_UNIQUE)
+                    .build(),
+            )
+        } else {
+            RelmApp::new("network.subspace.space_acres")
+        };
         let app = app.with_args({
             let mut args = self.gtk_arguments;
             // Application itself is expected as the first argument
@@ -899,40 +3084,125 @@ impl Cli {
             args
         });
 
-        app.set_global_css(GLOBAL_CSS);
         relm4_icons::initialize_icons();
 
-        // Prefer dark theme in cross-platform way if environment is configured that way
-        if let Some(settings) = gtk::Settings::default() {
-            if matches!(dark_light::detect(), dark_light::Mode::Dark) {
-                settings.set_gtk_application_prefer_dark_theme(true);
-            }
-        }
-
         let exit_status_code = Arc::new(Mutex::new(AppStatusCode::Exit));
+        let irrecoverable_error = Arc::default();
+        let instance_tag = self.instance_tag.clone();
 
         app.run_async::<App>(AppInit {
-            app_data_dir: maybe_app_data_dir,
+            app_data_dir: maybe_app_data_dir.clone(),
+            app_settings,
+            app_settings_path: maybe_app_settings_path,
             exit_status_code: Arc::clone(&exit_status_code),
+            irrecoverable_error: Arc::clone(&irrecoverable_error),
             minimize_on_start: self.startup,
+            theme: self.theme,
+            safe_mode: self.safe_mode,
+            maybe_config_file_path: self.config,
+            proxy: self.proxy,
+            export_plot_map: self.export_plot_map,
+            readiness_endpoint: self.readiness_endpoints.first().copied(),
+            log_filter,
+            log_filter_reload_handle,
         });
 
         let exit_status_code = *exit_status_code.lock();
+        let exit_reason = match (exit_status_code, irrecoverable_error.lock().take()) {
+            (AppStatusCode::Restart, _) => ExitReason::Restart,
+            (_, Some(message)) => ExitReason::Error { message },
+            (_, None) => ExitReason::Exit,
+        };
         info!(
             ?exit_status_code,
+            %exit_reason,
             "Exiting {} {}",
             env!("CARGO_PKG_NAME"),
             env!("CARGO_PKG_VERSION")
         );
+        if let Some(app_data_dir) = &maybe_app_data_dir {
+            exit_reason.write(app_data_dir, instance_tag.as_deref());
+        }
         exit_status_code
     }
 
+    /// Run supervisor for each instance in a dedicated thread and wait for all of them to exit
+    fn supervise_instances(mut self) -> io::Result<()> {
+        if !self.readiness_endpoints.is_empty()
+            && self.readiness_endpoints.len() != self.instances.len()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--readiness-endpoint must be specified once for each --instance",
+            ));
+        }
+
+        let mut instance_tags = HashSet::new();
+        let mut readiness_endpoints = mem::take(&mut self.readiness_endpoints).into_iter();
+        let supervisors = mem::take(&mut self.instances)
+            .into_iter()
+            .enumerate()
+            .map(|(index, config)| {
+                let mut instance_tag = config
+                    .file_stem()
+                    .map(|file_stem| file_stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                // Tags must be unique, otherwise instances would write to the same log file
+                if !instance_tags.insert(instance_tag.clone()) {
+                    instance_tag = format!("{instance_tag}-{index}");
+                    instance_tags.insert(instance_tag.clone());
+                }
+
+                let mut cli = self.clone();
+                cli.config = Some(config);
+                cli.instance_tag = Some(instance_tag.clone());
+                cli.readiness_endpoints = readiness_endpoints.next().into_iter().collect();
+
+                thread::Builder::new()
+                    .name(format!("supervisor-{instance_tag}"))
+                    .spawn(move || (instance_tag, cli.supervisor()))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut result = Ok(());
+        for supervisor in supervisors {
+            let (instance_tag, supervisor_result) = supervisor.join().map_err(|_error| {
+                io::Error::new(io::ErrorKind::Other, "Supervisor thread panicked")
+            })?;
+            if let Err(error) = supervisor_result {
+                eprintln!("[{instance_tag}] Supervisor exited with error: {error}");
+                result = Err(error);
+            }
+        }
+
+        result
+    }
+
     fn supervisor(mut self) -> io::Result<()> {
-        let maybe_app_data_dir = Self::app_data_dir();
+        if self.readiness_endpoints.len() > 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--readiness-endpoint can only be specified multiple times together with \
+                --instance",
+            ));
+        }
 
-        let program = Self::child_program()?;
+        let maybe_app_data_dir = self.app_data_dir();
+        // Messages of instances supervised together are prefixed with instance tag
+        let tag = self
+            .instance_tag
+            .as_deref()
+            .map(|instance_tag| format!("[{instance_tag}] "))
+            .unwrap_or_default();
+
+        let mut program = Self::child_program()?;
+        let mut restart_delay = self.restart_delay;
+        // AppImage can be updated in-app, in which case new AppImage is started on restart
+        #[cfg(target_os = "linux")]
+        let mut initial_appimage = appimage_modified();
 
         loop {
+            let child_started = Instant::now();
             let mut args = vec!["--child-process".to_string()];
             if self.startup {
                 // In case of restart we no longer want to minimize the app
@@ -940,10 +3210,76 @@ impl Cli {
 
                 args.push("--startup".to_string());
             }
+            if self.safe_mode {
+                // In case of restart we want to start normally
+                self.safe_mode = false;
+
+                args.push("--safe-mode".to_string());
+            }
+            if let Some(config) = &self.config {
+                args.push("--config".to_string());
+                args.push(config.display().to_string());
+            }
+            if let Some(data_dir) = &self.data_dir {
+                args.push("--data-dir".to_string());
+                args.push(data_dir.display().to_string());
+            }
+            if let Some(proxy) = &self.proxy {
+                args.push("--proxy".to_string());
+                args.push(proxy.to_string());
+            }
+            for readiness_endpoint in &self.readiness_endpoints {
+                args.push("--readiness-endpoint".to_string());
+                args.push(readiness_endpoint.to_string());
+            }
+            if self.no_quinn_log_workaround {
+                args.push("--no-quinn-log-workaround".to_string());
+            }
+            if self.quinn_log_workaround_applied {
+                args.push("--quinn-log-workaround-applied".to_string());
+            }
+            if let Some(runtime_threads) = self.runtime_threads {
+                args.push("--runtime-threads".to_string());
+                args.push(runtime_threads.to_string());
+            }
+            if let Some(debug_farm) = self.debug_farm {
+                args.push("--debug-farm".to_string());
+                args.push(debug_farm.to_string());
+            }
+            if let Some(instance_tag) = &self.instance_tag {
+                args.push("--instance-tag".to_string());
+                args.push(instance_tag.clone());
+            }
+            if let Some(export_plot_map) = self.export_plot_map.take() {
+                // In case of restart we want to start normally
+                args.push("--export-plot-map".to_string());
+                args.push(export_plot_map.display().to_string());
+            }
+            if let Some(theme) = self.theme {
+                args.push("--theme".to_string());
+                args.push(
+                    theme
+                        .to_possible_value()
+                        .expect("No skipped variants; qed")
+                        .get_name()
+                        .to_string(),
+                );
+            }
+            args.push("--max-log-size".to_string());
+            args.push(self.max_log_size.as_u64().to_string());
+            args.push("--log-target".to_string());
+            args.push(
+                self.log_target
+                    .to_possible_value()
+                    .expect("No skipped variants; qed")
+                    .get_name()
+                    .to_string(),
+            );
             args.push("--".to_string());
             args.extend_from_slice(&self.gtk_arguments);
 
-            let exit_status = if let Some(app_data_dir) = (!WINDOWS_SUBSYSTEM_WINDOWS)
+            let exit_status = if let Some(app_data_dir) = (!WINDOWS_SUBSYSTEM_WINDOWS
+                && self.log_target == LogTarget::File)
                 .then_some(maybe_app_data_dir.as_ref())
                 .flatten()
             {
@@ -953,11 +3289,16 @@ impl Cli {
                     .unchecked()
                     .reader()?;
 
-                let mut logger = Self::new_logger(app_data_dir);
+                let mut logger = Self::new_logger(
+                    app_data_dir,
+                    self.instance_tag.as_deref(),
+                    self.max_log_size,
+                );
 
                 let mut log_read_buffer = vec![0u8; LOG_READ_BUFFER];
 
                 let mut stdout = io::stdout();
+                let mut at_line_start = true;
                 loop {
                     match expression.read(&mut log_read_buffer) {
                         Ok(bytes_count) => {
@@ -966,12 +3307,19 @@ impl Cli {
                             }
 
                             let write_result: io::Result<()> = try {
-                                stdout.write_all(&log_read_buffer[..bytes_count])?;
+                                write_tagged(
+                                    &mut stdout,
+                                    &tag,
+                                    &mut at_line_start,
+                                    &log_read_buffer[..bytes_count],
+                                )?;
                                 logger.write_all(&log_read_buffer[..bytes_count])?;
                             };
 
                             if let Err(error) = write_result {
-                                eprintln!("Error while writing output of child process: {error}");
+                                eprintln!(
+                                    "{tag}Error while writing output of child process: {error}"
+                                );
                                 break;
                             }
                         }
@@ -980,7 +3328,7 @@ impl Cli {
                                 // Try again
                                 continue;
                             }
-                            eprintln!("Error while reading output of child process: {error}");
+                            eprintln!("{tag}Error while reading output of child process: {error}");
                             break;
                         }
                     }
@@ -988,7 +3336,7 @@ impl Cli {
 
                 stdout.flush()?;
                 if let Err(error) = logger.flush() {
-                    eprintln!("Error while flushing logs: {error}");
+                    eprintln!("{tag}Error while flushing logs: {error}");
                 }
 
                 match expression.try_wait()? {
@@ -1010,7 +3358,9 @@ impl Cli {
                     .run()?
                     .status
             } else {
-                eprintln!("App data directory doesn't exist, not creating log file");
+                if self.log_target == LogTarget::File {
+                    eprintln!("{tag}App data directory doesn't exist, not creating log file");
+                }
                 cmd(&program, args)
                     // We use non-zero status codes and they don't mean error necessarily
                     .unchecked()
@@ -1018,23 +3368,94 @@ impl Cli {
                     .status
             };
 
+            // Left in place for the next run to log it on startup
+            let exit_reason = maybe_app_data_dir
+                .as_deref()
+                .and_then(|app_data_dir| {
+                    ExitReason::read(app_data_dir, self.instance_tag.as_deref())
+                })
+                .map(|exit_reason| format!(" ({exit_reason})"))
+                .unwrap_or_default();
+
             match exit_status.code() {
                 Some(status_code) => match AppStatusCode::from_status_code(status_code) {
                     AppStatusCode::Exit => {
-                        eprintln!("Application exited gracefully");
+                        eprintln!("{tag}Application exited gracefully{exit_reason}");
                         break;
                     }
                     AppStatusCode::Restart => {
-                        eprintln!("Restarting application");
+                        let quick_restart = child_started.elapsed() < QUICK_RESTART_THRESHOLD;
+                        if !quick_restart || self.no_restart_backoff {
+                            restart_delay = self.restart_delay;
+                        }
+
+                        eprintln!(
+                            "{tag}Restarting application in {:.1}s{exit_reason}",
+                            restart_delay.as_secs_f32()
+                        );
+                        thread::sleep(restart_delay);
+
+                        #[cfg(target_os = "linux")]
+                        if let Some((appimage, initial_modified)) = &mut initial_appimage
+                            && let Some((_appimage, modified)) = appimage_modified()
+                            && modified != *initial_modified
+                        {
+                            use std::os::unix::process::CommandExt;
+
+                            if self.instance_tag.is_some() {
+                                // Supervisor is shared with other instances and can't be replaced,
+                                // start new AppImage as a child process instead
+                                eprintln!("{tag}AppImage was updated, restarting with new version");
+                                program = appimage.clone();
+                                *initial_modified = modified;
+                                restart_delay = self.restart_delay;
+                                continue;
+                            }
+
+                            eprintln!("{tag}AppImage was updated, starting new version");
+                            // Only returns on error
+                            let error = process::Command::new(appimage)
+                                .args(env::args_os().skip(1).filter(|arg| {
+                                    // Same as for regular restart
+                                    arg != "--startup" && arg != "--safe-mode"
+                                }))
+                                .exec();
+                            eprintln!(
+                                "{tag}Failed to start updated AppImage, restarting current version: \
+                                {error}"
+                            );
+                        }
+
+                        // Grows further if the next restart is also a quick one
+                        restart_delay = restart_delay
+                            .saturating_mul(2)
+                            .min(MAX_RESTART_DELAY.max(self.restart_delay));
                         continue;
                     }
+                    AppStatusCode::GtkInitializationFailed => {
+                        eprintln!(
+                            "{tag}Application failed to initialize GTK, not restarting{exit_reason}"
+                        );
+                        if self.instance_tag.is_some() {
+                            // Other instances continue running
+                            break;
+                        }
+                        process::exit(status_code);
+                    }
                     AppStatusCode::Unknown(status_code) => {
-                        eprintln!("Application exited with unexpected status code {status_code}");
+                        eprintln!(
+                            "{tag}Application exited with unexpected status code \
+                            {status_code}{exit_reason}"
+                        );
+                        if self.instance_tag.is_some() {
+                            // Other instances continue running
+                            break;
+                        }
                         process::exit(status_code);
                     }
                 },
                 None => {
-                    eprintln!("Application terminated by signal");
+                    eprintln!("{tag}Application terminated by signal{exit_reason}");
                     break;
                 }
             }
@@ -1043,9 +3464,13 @@ impl Cli {
         Ok(())
     }
 
-    fn app_data_dir() -> Option<PathBuf> {
-        dirs::data_local_dir()
-            .map(|data_local_dir| data_local_dir.join(env!("CARGO_PKG_NAME")))
+    fn app_data_dir(&self) -> Option<PathBuf> {
+        self.data_dir
+            .clone()
+            .or_else(|| {
+                dirs::data_local_dir()
+                    .map(|data_local_dir| data_local_dir.join(env!("CARGO_PKG_NAME")))
+            })
             .and_then(|app_data_dir| {
                 if !app_data_dir.exists() {
                     if let Err(error) = fs::create_dir_all(&app_data_dir) {
@@ -1062,11 +3487,21 @@ impl Cli {
             })
     }
 
-    fn new_logger(app_data_dir: &Path) -> FileRotate<AppendCount> {
+    /// Instances supervised together write to separate log files, each within `max_log_size`
+    fn new_logger(
+        app_data_dir: &Path,
+        instance_tag: Option<&str>,
+        max_log_size: ByteSize,
+    ) -> FileRotate<AppendCount> {
+        let log_file_name = match instance_tag {
+            Some(instance_tag) => format!("space-acres-{instance_tag}.log"),
+            None => "space-acres.log".to_string(),
+        };
+        let (rotated_files, file_size) = log_retention(max_log_size.as_u64());
         FileRotate::new(
-            app_data_dir.join("space-acres.log"),
-            AppendCount::new(LOG_FILE_LIMIT_COUNT),
-            ContentLimit::Bytes(LOG_FILE_LIMIT_SIZE),
+            app_data_dir.join(log_file_name),
+            AppendCount::new(rotated_files),
+            ContentLimit::Bytes(file_size as usize),
             Compression::OnRotate(0),
             #[cfg(unix)]
             Some(0o600),
@@ -1118,11 +3553,234 @@ impl Cli {
     }
 }
 
+/// Only HTTP(S) and SOCKS5 proxies are supported by HTTP client used in the application
+fn parse_proxy(proxy: &str) -> Result<Url, String> {
+    let proxy = Url::parse(proxy).map_err(|error| error.to_string())?;
+
+    if !SUPPORTED_PROXY_SCHEMES.contains(&proxy.scheme()) {
+        return Err(format!(
+            "Unsupported proxy scheme \"{}\", supported schemes are: {}",
+            proxy.scheme(),
+            SUPPORTED_PROXY_SCHEMES.join(", ")
+        ));
+    }
+    // There is no default port for SOCKS5 in URLs
+    if proxy.port_or_known_default().is_none() {
+        return Err("Proxy port must be specified".to_string());
+    }
+
+    Ok(proxy)
+}
+
+/// Number of rotated log files to keep and size of each file, such that current file together
+/// with rotated files doesn't exceed `max_log_size` (rotated files are compressed, so they usually
+/// take even less space)
+fn log_retention(max_log_size: u64) -> (usize, u64) {
+    let max_log_size = max_log_size.max(MIN_MAX_LOG_SIZE);
+    let files = max_log_size / LOG_FILE_LIMIT_SIZE;
+
+    if files >= 2 {
+        ((files - 1) as usize, LOG_FILE_LIMIT_SIZE)
+    } else {
+        // Keep at least one rotated file, such that logs are not lost right after rotation
+        (1, max_log_size / 2)
+    }
+}
+
+fn parse_seconds(seconds: &str) -> Result<Duration, String> {
+    Duration::try_from_secs_f64(seconds.parse::<f64>().map_err(|error| error.to_string())?)
+        .map_err(|error| error.to_string())
+}
+
+/// Write output of child process, prefixing every line with `tag` (if not empty)
+fn write_tagged(
+    output: &mut impl Write,
+    tag: &str,
+    at_line_start: &mut bool,
+    bytes: &[u8],
+) -> io::Result<()> {
+    if tag.is_empty() {
+        return output.write_all(bytes);
+    }
+
+    for line in bytes.split_inclusive(|&byte| byte == b'\n') {
+        if *at_line_start {
+            output.write_all(tag.as_bytes())?;
+        }
+        output.write_all(line)?;
+        *at_line_start = line.ends_with(b"\n");
+    }
+
+    Ok(())
+}
+
+/// Set OS scheduling priority of the current process, on Linux it only applies to the current
+/// thread and threads created by it afterward
+fn set_process_priority(process_priority: ProcessPriority) {
+    if process_priority == ProcessPriority::Normal {
+        return;
+    }
+
+    #[cfg(unix)]
+    {
+        let nice = match process_priority {
+            ProcessPriority::Normal => 0,
+            ProcessPriority::BelowNormal => 10,
+            ProcessPriority::Idle => 19,
+        };
+        // SAFETY: Only changes priority of the current process
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) } != 0 {
+            warn!(
+                error = %io::Error::last_os_error(),
+                ?process_priority,
+                "Failed to set process priority"
+            );
+            return;
+        }
+    }
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::System::Threading::{
+            GetCurrentProcess, SetPriorityClass, BELOW_NORMAL_PRIORITY_CLASS, IDLE_PRIORITY_CLASS,
+            NORMAL_PRIORITY_CLASS,
+        };
+
+        let priority_class = match process_priority {
+            ProcessPriority::Normal => NORMAL_PRIORITY_CLASS,
+            ProcessPriority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+            ProcessPriority::Idle => IDLE_PRIORITY_CLASS,
+        };
+        // SAFETY: Pseudo handle of the current process is always valid
+        if unsafe { SetPriorityClass(GetCurrentProcess(), priority_class) } == 0 {
+            warn!(
+                error = %io::Error::last_os_error(),
+                ?process_priority,
+                "Failed to set process priority"
+            );
+            return;
+        }
+    }
+
+    info!(?process_priority, "Process priority set");
+}
+
+/// Path to AppImage application was started from along with its modification time
+#[cfg(target_os = "linux")]
+fn appimage_modified() -> Option<(PathBuf, std::time::SystemTime)> {
+    let appimage = PathBuf::from(env::var_os("APPIMAGE")?);
+    let modified = fs::metadata(&appimage)
+        .and_then(|metadata| metadata.modified())
+        .ok()?;
+
+    Some((appimage, modified))
+}
+
+/// Whether currently running executable is the optimized build picked by [`Cli::child_program()`]
+fn is_modern_build() -> bool {
+    env::current_exe()
+        .ok()
+        .and_then(|program| {
+            let file_name = program.file_name()?.to_string_lossy().to_string();
+            Some(file_name.ends_with("-modern") || file_name.ends_with("-modern.exe"))
+        })
+        .unwrap_or_default()
+}
+
+fn build_kind_description() -> &'static str {
+    if is_modern_build() {
+        "Using optimized build"
+    } else {
+        "Using baseline build"
+    }
+}
+
 fn main() -> ExitCode {
-    // TODO: This is a hack to work around https://github.com/quinn-rs/quinn/issues/1750, should be
-    //  removed once fixed upstream
-    if env::var("RUST_LOG").is_err() {
-        env::set_var("RUST_LOG", "info,quinn_udp=error");
+    let mut cli = Cli::parse();
+    if !cli.no_quinn_log_workaround && env::var("RUST_LOG").is_err() {
+        env::set_var("RUST_LOG", QUINN_LOG_WORKAROUND_FILTER);
+        cli.quinn_log_workaround_applied = true;
+    }
+    cli.run()
+}
+
+/// Forward notifications as component inputs until notifications stream ends. Component might be
+/// already shut down during application exit, in which case there is nobody to forward
+/// notifications to anymore and forwarding stops early.
+async fn forward_notifications<Notification, Input>(
+    mut notifications: impl Stream<Item = Notification> + Unpin,
+    input_sender: &relm4::Sender<Input>,
+    into_input: impl Fn(Notification) -> Input,
+) {
+    while let Some(notification) = notifications.next().await {
+        if input_sender.send(into_input(notification)).is_err() {
+            debug!("Component is shut down, stopping notification forwarder");
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{forward_notifications, AppStatusCode};
+    use futures::channel::mpsc;
+    use futures::executor::block_on;
+
+    /// Status code Rust exits with when process panics
+    const PANIC_STATUS_CODE: i32 = 101;
+
+    #[test]
+    fn app_status_code_round_trip() {
+        for status_code in [
+            AppStatusCode::Exit,
+            AppStatusCode::Restart,
+            AppStatusCode::GtkInitializationFailed,
+        ] {
+            let code = status_code.into_status_code();
+            assert_ne!(
+                code, PANIC_STATUS_CODE,
+                "{status_code:?} collides with panic"
+            );
+            assert_eq!(AppStatusCode::from_status_code(code), status_code);
+        }
+
+        assert_eq!(
+            AppStatusCode::from_status_code(PANIC_STATUS_CODE),
+            AppStatusCode::Unknown(PANIC_STATUS_CODE)
+        );
+    }
+
+    #[test]
+    fn notifications_are_forwarded() {
+        let (input_sender, input_receiver) = relm4::channel::<u32>();
+        let (mut notification_sender, notification_receiver) = mpsc::channel(10);
+        notification_sender.try_send(1).unwrap();
+        notification_sender.try_send(2).unwrap();
+        drop(notification_sender);
+
+        block_on(forward_notifications(
+            notification_receiver,
+            &input_sender,
+            |notification| notification * 10,
+        ));
+
+        assert_eq!(block_on(input_receiver.recv()), Some(10));
+        assert_eq!(block_on(input_receiver.recv()), Some(20));
+    }
+
+    #[test]
+    fn notification_forwarder_stops_after_component_shutdown() {
+        let (input_sender, input_receiver) = relm4::channel::<u32>();
+        let (mut notification_sender, notification_receiver) = mpsc::channel(10);
+        notification_sender.try_send(1).unwrap();
+        // Component is shut down while backend is still running and sending notifications
+        drop(input_receiver);
+
+        // Returns without panicking even though notifications are still being sent
+        block_on(forward_notifications(
+            notification_receiver,
+            &input_sender,
+            |notification| notification,
+        ));
+        assert!(notification_sender.try_send(2).is_err());
     }
-    Cli::parse().run()
 }